@@ -0,0 +1,6 @@
+//! Checks the consistency of `FKSMap` - see [`o1_fuzz`] for the input format.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| o1_fuzz::run(data));
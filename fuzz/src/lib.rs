@@ -0,0 +1,338 @@
+//! Consistency checks of [`FKSMap`] driven by the fuzzer.
+//!
+//! Builds a map from the entries decoded from the input, applies the decoded operations to it
+//! and to a [`std::collections::HashMap`] serving as the model, and asserts that both agree.
+//! The inputs the map can't be built from with the given seed are skipped.
+//! The values count their live instances, so a double-drop or a leak of the entries fails the
+//! checks even without a sanitizer.
+//!
+//! Run with `cargo +nightly fuzz run fks_map` from the root of the repository - ASan (the
+//! default sanitizer of `cargo fuzz`) reports the double-frees and the reads of freed memory.
+//! The corpus can be replayed under Miri with
+//! `cargo +nightly miri test --manifest-path fuzz/Cargo.toml`, which also reports the reads of
+//! uninitialized slots.
+//!
+//! # Input format
+//!
+//! - The first byte is the seed of the map.
+//! - Then the entries up to the first `0` byte - one per line, the key optionally followed by a
+//!   tab and the value. The keys are decoded as UTF-8 lossily, the duplicates are ignored.
+//! - The rest of the input is the operations - pairs of bytes, the first one selects the
+//!   operation and the second one is its argument, see [`Op`].
+use o1::fks::FKSMap;
+use o1::hashing::hashers::msp::MSPHasher;
+use o1_core::HashMap;
+use o1_core::O1Error::{BudgetExhausted, UnableToFindHashFunction};
+use std::collections::HashMap as StdHashMap;
+use std::sync::atomic::{AtomicIsize, Ordering};
+
+/// The number of the live instances of [`Value`].
+static NUM_VALUES: AtomicIsize = AtomicIsize::new(0);
+
+/// Value of the map counting its live instances in [`NUM_VALUES`].
+#[derive(Debug, PartialEq, Eq)]
+struct Value(Vec<u8>);
+
+impl Value {
+    fn new(bytes: &[u8]) -> Self {
+        NUM_VALUES.fetch_add(1, Ordering::Relaxed);
+        Self(bytes.to_vec())
+    }
+}
+
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        Self::new(&self.0)
+    }
+}
+
+impl Drop for Value {
+    fn drop(&mut self) {
+        let num_values = NUM_VALUES.fetch_sub(1, Ordering::Relaxed);
+        assert!(num_values > 0, "Value {:?} dropped twice", self.0);
+    }
+}
+
+type Map = FKSMap<'static, String, Value, MSPHasher<String>>;
+type Model = StdHashMap<String, Vec<u8>>;
+
+/// An operation applied to the map and to the model.
+///
+/// The `u8` arguments select a key of the model by its index modulo the number of keys, the
+/// operations rebuilding the map also use them as the seed.
+#[derive(Debug)]
+enum Op {
+    /// Look up a present key with [`HashMap::get`] and [`HashMap::get_key_value`].
+    Get(u8),
+    /// Look up a missing key with [`HashMap::get`] and [`FKSMap::try_get`].
+    GetMissing(u8),
+    /// Append the argument to the value of a key through [`FKSMap::get_many_mut`].
+    GetMut(u8),
+    /// Borrow the values of the key and of the next one mutably at once.
+    GetManyMut(u8),
+    /// Look up all keys and a missing one with [`FKSMap::get_batch`].
+    GetBatch,
+    /// Look up the key, the next one and a missing one with [`FKSMap::get_disjoint`].
+    GetDisjoint(u8),
+    /// Check the slots of the key, the next one and a missing one with [`FKSMap::raw_slot`].
+    RawSlot(u8),
+    /// Compare all entries yielded by [`FKSMap::iter`].
+    Iter,
+    /// Append the argument to all values through [`FKSMap::iter_mut`].
+    IterMut(u8),
+    /// Compare a clone of the map and drop it.
+    Clone,
+    /// Turn the map into a minimal perfect hash table with [`FKSMap::compact`].
+    Compact,
+    /// Rebuild the map without a part of the entries with [`FKSMap::retain`].
+    Retain(u8),
+    /// Rebuild the map overriding the value of the key and adding a new key with
+    /// [`FKSMap::extended`].
+    Extended(u8),
+    /// Move the given number of the entries out of the map and drop the rest with it.
+    IntoIterPartial(u8),
+    /// Move all entries out of the map with [`FKSMap::into_entries`].
+    IntoEntries,
+}
+
+impl Op {
+    const NUM_OPS: u8 = 15;
+
+    fn decode(op: u8, arg: u8) -> Self {
+        match op % Self::NUM_OPS {
+            0 => Op::Get(arg),
+            1 => Op::GetMissing(arg),
+            2 => Op::GetMut(arg),
+            3 => Op::GetManyMut(arg),
+            4 => Op::GetBatch,
+            5 => Op::GetDisjoint(arg),
+            6 => Op::RawSlot(arg),
+            7 => Op::Iter,
+            8 => Op::IterMut(arg),
+            9 => Op::Clone,
+            10 => Op::Compact,
+            11 => Op::Retain(arg),
+            12 => Op::Extended(arg),
+            13 => Op::IntoIterPartial(arg),
+            _ => Op::IntoEntries,
+        }
+    }
+}
+
+/// Decodes the entries - see the [module documentation](self).
+fn decode_entries(data: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut entries: Vec<(String, Vec<u8>)> = Vec::new();
+    for line in data.split(|&b| b == b'\n').filter(|line| !line.is_empty()) {
+        let (key, value) = match line.iter().position(|&b| b == b'\t') {
+            Some(pos) => (&line[..pos], &line[pos + 1..]),
+            None => (line, &[][..]),
+        };
+        let key = String::from_utf8_lossy(key).into_owned();
+        if !entries.iter().any(|(k, _)| *k == key) {
+            entries.push((key, value.to_vec()));
+        }
+    }
+    entries
+}
+
+/// Asserts that the map contains exactly the entries of the model.
+fn check_entries(map: &Map, model: &Model) {
+    let mut num_entries = 0;
+    for (key, value) in map.iter() {
+        assert_eq!(model.get(key), Some(&value.0), "Key: {key:?}");
+        num_entries += 1;
+    }
+    assert_eq!(num_entries, model.len());
+    for (key, value) in model.iter() {
+        assert_eq!(map.get(key).map(|v| &v.0), Some(value), "Key: {key:?}");
+    }
+}
+
+/// Asserts that the moved-out entries are the entries of the model.
+fn check_moved_entries(entries: Vec<(String, Value)>, model: &Model) {
+    assert_eq!(entries.len(), model.len());
+    for (key, value) in entries.iter() {
+        assert_eq!(model.get(key), Some(&value.0), "Key: {key:?}");
+    }
+}
+
+/// Keeps the map built by a rebuilding operation or drops it if the map can't be built with the
+/// given seed.
+fn rebuilt(result: Result<Map, o1_core::O1Error>) -> Option<Map> {
+    match result {
+        Ok(map) => {
+            assert!(map.verify());
+            Some(map)
+        }
+        Err(UnableToFindHashFunction | BudgetExhausted { .. }) => None,
+        Err(err) => panic!("Unable to rebuild the map: {err}"),
+    }
+}
+
+/// Runs the checks for the given fuzzer input - see the [module documentation](self).
+pub fn run(data: &[u8]) {
+    let Some((&seed, data)) = data.split_first() else {
+        return;
+    };
+    let (entries, ops) = match data.iter().position(|&b| b == 0) {
+        Some(pos) => (&data[..pos], &data[pos + 1..]),
+        None => (data, &[][..]),
+    };
+    let entries = decode_entries(entries);
+    let mut keys: Vec<String> = entries.iter().map(|(k, _)| k.clone()).collect();
+    let mut model: Model = entries.iter().cloned().collect();
+
+    let data: Box<[(String, Value)]> = entries
+        .iter()
+        .map(|(k, v)| (k.clone(), Value::new(v)))
+        .collect();
+    let mut map = match Map::new(data, seed as u64, 0.75) {
+        Ok(map) => Some(map),
+        Err(UnableToFindHashFunction | BudgetExhausted { .. }) => {
+            assert_eq!(NUM_VALUES.load(Ordering::Relaxed), 0);
+            return;
+        }
+        Err(err) => panic!("Unable to build the map: {err}"),
+    };
+    assert!(map.as_ref().unwrap().verify());
+    check_entries(map.as_ref().unwrap(), &model);
+
+    for op in ops.chunks_exact(2).map(|op| Op::decode(op[0], op[1])) {
+        let Some(current) = map.as_mut() else {
+            break;
+        };
+        let key = |arg: u8| (!keys.is_empty()).then(|| keys[arg as usize % keys.len()].clone());
+        let missing = |arg: u8| match key(arg) {
+            Some(key) => format!("{key}\0"),
+            None => String::from("\0"),
+        };
+
+        match op {
+            Op::Get(arg) => {
+                if let Some(key) = key(arg) {
+                    assert_eq!(current.get(&key).map(|v| &v.0), model.get(&key));
+                    let (k, _) = current.get_key_value(&key).unwrap();
+                    assert_eq!(*k, key);
+                }
+            }
+            Op::GetMissing(arg) => {
+                let missing = missing(arg);
+                assert!(current.get(&missing).is_none());
+                assert!(current.try_get(&missing).is_err());
+            }
+            Op::GetMut(arg) => {
+                if let Some(key) = key(arg) {
+                    let [value] = current.get_many_mut([&key]).unwrap();
+                    value.0.push(arg);
+                    model.get_mut(&key).unwrap().push(arg);
+                    assert_eq!(current.get(&key).map(|v| &v.0), model.get(&key));
+                }
+            }
+            Op::GetManyMut(arg) => {
+                if let (Some(first), Some(second)) = (key(arg), key(arg.wrapping_add(1))) {
+                    let values = current.get_many_mut([&first, &second]);
+                    if first == second {
+                        assert!(values.is_none());
+                    } else {
+                        let [first_value, second_value] = values.unwrap();
+                        core::mem::swap(first_value, second_value);
+                        let first_value = model.remove(&first).unwrap();
+                        let second_value = model.insert(second.clone(), first_value).unwrap();
+                        model.insert(first, second_value);
+                    }
+                }
+            }
+            Op::GetBatch => {
+                let mut batch = keys.clone();
+                batch.push(missing(0));
+                let mut values = Vec::new();
+                current.get_batch(&batch, &mut values);
+                assert_eq!(values.len(), batch.len());
+                for (key, value) in batch.iter().zip(values) {
+                    assert_eq!(value.map(|v| &v.0), model.get(key), "Key: {key:?}");
+                }
+            }
+            Op::GetDisjoint(arg) => {
+                if let (Some(first), Some(second)) = (key(arg), key(arg.wrapping_add(1))) {
+                    let missing = missing(arg);
+                    let [first_value, second_value, missing_value] =
+                        current.get_disjoint([&first, &second, &missing]);
+                    assert_eq!(first_value.map(|v| &v.0), model.get(&first));
+                    assert_eq!(second_value.map(|v| &v.0), model.get(&second));
+                    assert!(missing_value.is_none());
+                }
+            }
+            Op::RawSlot(arg) => {
+                assert!(current.raw_slot(&missing(arg)).is_none());
+                if let (Some(first), Some(second)) = (key(arg), key(arg.wrapping_add(1))) {
+                    let first_slot = current.raw_slot(&first).unwrap();
+                    let second_slot = current.raw_slot(&second).unwrap();
+                    assert!(first_slot < current.capacity());
+                    assert!(second_slot < current.capacity());
+                    assert_eq!(first_slot == second_slot, first == second);
+                }
+            }
+            Op::Iter => check_entries(current, &model),
+            Op::IterMut(arg) => {
+                for (_, value) in current.iter_mut() {
+                    value.0.push(arg);
+                }
+                model.values_mut().for_each(|value| value.push(arg));
+                check_entries(current, &model);
+            }
+            Op::Clone => {
+                let clone = current.clone();
+                check_entries(&clone, &model);
+                assert!(clone == *current);
+            }
+            Op::Compact => {
+                current.compact();
+                assert!(current.verify());
+                check_entries(current, &model);
+            }
+            Op::Retain(arg) => {
+                let keep = |key: &String, value: &[u8]| {
+                    !(key.len() + value.len() + arg as usize).is_multiple_of(3)
+                };
+                map = rebuilt(map.take().unwrap().retain(|k, v| keep(k, &v.0), arg as u64));
+                model.retain(|k, v| keep(k, v));
+                keys.retain(|k| model.contains_key(k));
+                if let Some(current) = map.as_ref() {
+                    check_entries(current, &model);
+                }
+            }
+            Op::Extended(arg) => {
+                let mut extra = Vec::new();
+                let added = match key(arg) {
+                    Some(key) => {
+                        extra.push((key.clone(), Value::new(&[arg])));
+                        model.insert(key.clone(), vec![arg]);
+                        format!("{key}\0{arg}")
+                    }
+                    None => format!("\0{arg}"),
+                };
+                if !model.contains_key(&added) {
+                    extra.push((added.clone(), Value::new(&[arg, arg])));
+                    model.insert(added.clone(), vec![arg, arg]);
+                    keys.push(added);
+                }
+                map = rebuilt(map.take().unwrap().extended(extra, arg as u64));
+                if let Some(current) = map.as_ref() {
+                    check_entries(current, &model);
+                }
+            }
+            Op::IntoIterPartial(arg) => {
+                let mut iter = map.take().unwrap().into_iter();
+                for (key, value) in iter.by_ref().take(arg as usize) {
+                    assert_eq!(model.get(&key), Some(&value.0), "Key: {key:?}");
+                }
+            }
+            Op::IntoEntries => {
+                check_moved_entries(map.take().unwrap().into_entries(), &model);
+            }
+        }
+    }
+    drop(map);
+
+    assert_eq!(NUM_VALUES.load(Ordering::Relaxed), 0);
+}
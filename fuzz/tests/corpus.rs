@@ -0,0 +1,15 @@
+//! Replays the corpus of the `fks_map` fuzz target - e.g. under Miri.
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_corpus() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus/fks_map");
+    let mut num_inputs = 0;
+    for entry in fs::read_dir(corpus).unwrap() {
+        let path = entry.unwrap().path();
+        o1_fuzz::run(&fs::read(&path).unwrap());
+        num_inputs += 1;
+    }
+    assert!(num_inputs > 0);
+}
@@ -0,0 +1,54 @@
+//! Compares hashing of `[u8; 16]` keys (e.g. UUIDs) with the general vector path of the
+//! multiply-shift hashing and with [`MSPHasher`], which loads such keys directly into integers.
+//!
+//! Run with `cargo bench -p o1 --bench byte_arrays`.
+#![allow(clippy::print_stdout)]
+
+mod common;
+
+use common::measure;
+use o1::hashing::hashers::msp::MSPHasher;
+use o1::hashing::multiply_shift::pair_multiply_shift_vector_u8;
+use o1_core::Hasher;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::hint::black_box;
+
+const NUM_KEYS: usize = 1 << 16;
+const NUM_ROUNDS: usize = 20;
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let keys: Vec<[u8; 16]> = (0..NUM_KEYS).map(|_| rng.random()).collect();
+
+    let seed: u64 = rng.random();
+    let value_seed: [u64; 4] = rng.random();
+    let vector = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(pair_multiply_shift_vector_u8(
+                black_box(key),
+                16,
+                seed,
+                &value_seed,
+            ));
+        }
+    });
+
+    let hasher = MSPHasher::<[u8; 16]>::from_seed(42, 1 << 16);
+    let fixed = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(hasher.hash(black_box(key)));
+        }
+    });
+
+    println!(
+        "vector path: {:?} ({:?} per key)",
+        vector,
+        vector / NUM_KEYS as u32
+    );
+    println!(
+        "fixed path:  {:?} ({:?} per key)",
+        fixed,
+        fixed / NUM_KEYS as u32
+    );
+}
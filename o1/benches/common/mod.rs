@@ -0,0 +1,15 @@
+//! Helpers shared by the benchmarks.
+use std::time::{Duration, Instant};
+
+/// Runs `f` `num_rounds` times and returns the shortest of the durations - the least disturbed
+/// by the rest of the system.
+pub fn measure(num_rounds: usize, mut f: impl FnMut()) -> Duration {
+    (0..num_rounds)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
@@ -0,0 +1,56 @@
+//! Compares lookups of many keys with [`FKSMap::get`] and [`FKSMap::get_batch`].
+//!
+//! The map holds millions of entries and the keys are looked up in random order, so that the
+//! lookups miss the CPU cache - which is what the prefetching of `get_batch` hides.
+//!
+//! Run with `cargo bench -p o1 --bench get_batch`.
+#![allow(clippy::print_stdout)]
+
+mod common;
+
+use common::measure;
+use o1::fks::FKSMap;
+use o1::hashing::hashers::msp::MSPHasher;
+use o1_core::HashMap;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::hint::black_box;
+
+const NUM_KEYS: usize = 4_000_000;
+const NUM_LOOKUPS: usize = 1 << 20;
+const NUM_ROUNDS: usize = 10;
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    // Unlike an arithmetic progression, random keys overflow the L1 buckets of a map this large.
+    let data: Box<[(u64, u64)]> = (0..NUM_KEYS as u64).map(|i| (i * 7919, i)).collect();
+    let keys: Vec<u64> = (0..NUM_LOOKUPS)
+        .map(|_| data[rng.random_range(0..data.len())].0)
+        .collect();
+    // `FKSMap::new` needs memory quadratic in the number of entries.
+    let map = FKSMap::<u64, u64, MSPHasher<u64>>::new_streaming(data, 42, 0.75).unwrap();
+
+    let get = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(map.get(black_box(key)));
+        }
+    });
+
+    let mut out = Vec::with_capacity(keys.len());
+    let get_batch = measure(NUM_ROUNDS, || {
+        map.get_batch(black_box(&keys), &mut out);
+        black_box(&out);
+    });
+
+    println!("Map size:  {} MiB", map.total_memory_bytes() / (1 << 20));
+    println!(
+        "get:       {:?} ({:?} per key)",
+        get,
+        get / NUM_LOOKUPS as u32
+    );
+    println!(
+        "get_batch: {:?} ({:?} per key)",
+        get_batch,
+        get_batch / NUM_LOOKUPS as u32
+    );
+}
@@ -0,0 +1,50 @@
+//! Compares hashing of 64-byte keys with [`GxHasher`] and with [`XXH3Hasher`].
+//!
+//! Run with `cargo bench -p o1 --features gxhash,xxh3 --bench gxhash`.
+#![allow(clippy::print_stdout)]
+
+mod common;
+
+use common::measure;
+use o1::hashing::hashers::gxhash::GxHasher;
+use o1::hashing::hashers::xxh3::XXH3Hasher;
+use o1_core::Hasher;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::hint::black_box;
+
+const KEY_LEN: usize = 64;
+const NUM_KEYS: usize = 1 << 12;
+const NUM_ROUNDS: usize = 20;
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let keys: Vec<Vec<u8>> = (0..NUM_KEYS)
+        .map(|_| (0..KEY_LEN).map(|_| rng.random()).collect())
+        .collect();
+
+    let hasher = XXH3Hasher::<&[u8]>::from_seed(42, 1 << 16);
+    let xxh3 = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(hasher.hash(&black_box(key.as_slice())));
+        }
+    });
+
+    let hasher = GxHasher::<&[u8]>::from_seed(42, 1 << 16);
+    let gxhash = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(hasher.hash(&black_box(key.as_slice())));
+        }
+    });
+
+    println!(
+        "XXH3Hasher: {:?} ({:?} per key)",
+        xxh3,
+        xxh3 / NUM_KEYS as u32
+    );
+    println!(
+        "GxHasher:   {:?} ({:?} per key)",
+        gxhash,
+        gxhash / NUM_KEYS as u32
+    );
+}
@@ -0,0 +1,82 @@
+//! Compares lookups with [`FKSMap::get`] and [`InlineFKSMap::get`] on a map dominated by buckets
+//! with at most one key.
+//!
+//! Measured on two maps - one fitting into the CPU cache and one whose working set is several
+//! times larger than the last level cache (105 MiB on the machine the numbers below are from),
+//! so that every lookup misses the cache and the saved memory access shows. On an x86_64 machine
+//! it took:
+//!
+//! | keys      | map size | inline entries | `FKSMap`  | `InlineFKSMap` |
+//! |-----------|----------|----------------|-----------|----------------|
+//! | 20 000    | 1 MiB    | 53.9%          | 9ns/key   | 12.5ns/key     |
+//! | 8 000 000 | 583 MiB  | 63.8%          | 79ns/key  | 67ns/key       |
+//!
+//! [`InlineFKSMap`] pays off only for the maps that don't fit into the cache - for the others
+//! its larger buckets and the branch on the kind of the bucket make the lookups slower.
+//!
+//! Run with `cargo bench -p o1 --bench inline_buckets`.
+#![allow(clippy::print_stdout)]
+
+mod common;
+
+use common::measure;
+use o1::fks::{FKSMap, InlineFKSMap};
+use o1::hashing::hashers::msp::MSPHasher;
+use o1_core::HashMap;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::hint::black_box;
+
+const NUM_KEYS_SMALL: usize = 20_000;
+const NUM_KEYS_LARGE: usize = 8_000_000;
+const NUM_LOOKUPS: usize = 1 << 20;
+const NUM_ROUNDS: usize = 10;
+
+fn bench(rng: &mut ChaCha20Rng, data: Box<[(u64, u64)]>) {
+    let num_keys = data.len();
+    let mut keys: Vec<u64> = (0..NUM_LOOKUPS).map(|i| data[i % data.len()].0).collect();
+    keys.shuffle(rng);
+    // `FKSMap::new` needs memory quadratic in the number of entries.
+    let map = FKSMap::<u64, u64, MSPHasher<u64>>::new_streaming(data, 42, 0.75).unwrap();
+    let map_size = map.total_memory_bytes();
+    let inline_map = InlineFKSMap::from_map(map.clone());
+
+    let get = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(map.get(black_box(key)));
+        }
+    });
+    let get_inline = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(inline_map.get(black_box(key)));
+        }
+    });
+
+    println!("{num_keys} keys, FKSMap size: {} MiB", map_size / (1 << 20));
+    println!(
+        "inline entries: {:.1}%",
+        inline_map.num_inline() as f64 / inline_map.len() as f64 * 100.0
+    );
+    println!(
+        "FKSMap:       {:?} ({:?} per key)",
+        get,
+        get / NUM_LOOKUPS as u32
+    );
+    println!(
+        "InlineFKSMap: {:?} ({:?} per key)",
+        get_inline,
+        get_inline / NUM_LOOKUPS as u32
+    );
+}
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let small: Box<[(u64, u64)]> = (0..NUM_KEYS_SMALL as u64)
+        .map(|i| (rng.random(), i))
+        .collect();
+    bench(&mut rng, small);
+    // Unlike an arithmetic progression, random keys overflow the L1 buckets of a map this large.
+    let large: Box<[(u64, u64)]> = (0..NUM_KEYS_LARGE as u64).map(|i| (i * 7919, i)).collect();
+    bench(&mut rng, large);
+}
@@ -0,0 +1,51 @@
+//! Compares hashing of 1 KiB keys with [`HighwayHasher`] and with [`MSPHasher`], which hashes
+//! long keys with the polynomial hashing.
+//!
+//! Run with `cargo bench -p o1 --features highway --bench long_keys`.
+#![allow(clippy::print_stdout)]
+
+mod common;
+
+use common::measure;
+use o1::hashing::hashers::highway::HighwayHasher;
+use o1::hashing::hashers::msp::MSPHasher;
+use o1_core::Hasher;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::hint::black_box;
+
+const KEY_LEN: usize = 1024;
+const NUM_KEYS: usize = 1 << 12;
+const NUM_ROUNDS: usize = 20;
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let keys: Vec<Vec<u8>> = (0..NUM_KEYS)
+        .map(|_| (0..KEY_LEN).map(|_| rng.random()).collect())
+        .collect();
+
+    let hasher = MSPHasher::<&[u8]>::from_seed(42, 1 << 16);
+    let msp = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(hasher.hash(&black_box(key.as_slice())));
+        }
+    });
+
+    let hasher = HighwayHasher::<&[u8]>::from_seed(42, 1 << 16);
+    let highway = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(hasher.hash(&black_box(key.as_slice())));
+        }
+    });
+
+    println!(
+        "MSPHasher:     {:?} ({:?} per key)",
+        msp,
+        msp / NUM_KEYS as u32
+    );
+    println!(
+        "HighwayHasher: {:?} ({:?} per key)",
+        highway,
+        highway / NUM_KEYS as u32
+    );
+}
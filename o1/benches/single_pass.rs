@@ -0,0 +1,67 @@
+//! Compares lookups of 1 KiB keys with [`FKSMap::get`] and [`SinglePassFKSMap::get_single_pass`],
+//! which computes the polynomial digest of the key once for both levels of the map.
+//!
+//! Run with `cargo bench -p o1 --bench single_pass`.
+#![allow(clippy::print_stdout)]
+
+mod common;
+
+use common::measure;
+use o1::fks::{FKSMap, SinglePassFKSMap};
+use o1::hashing::hashers::msp::{MSPHasher, MSPHasherDigest};
+use o1_core::HashMap;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::hint::black_box;
+
+const KEY_LEN: usize = 1024;
+const NUM_KEYS: usize = 1 << 12;
+const NUM_LOOKUPS: usize = 1 << 14;
+const NUM_ROUNDS: usize = 10;
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let data: Box<[(Vec<u8>, u64)]> = (0..NUM_KEYS)
+        .map(|i| ((0..KEY_LEN).map(|_| rng.random()).collect(), i as u64))
+        .collect();
+    let data: Box<[(&[u8], u64)]> = data.iter().map(|(k, v)| (k.as_slice(), *v)).collect();
+
+    let mut keys: Vec<&[u8]> = (0..NUM_LOOKUPS).map(|i| data[i % data.len()].0).collect();
+    keys.shuffle(&mut rng);
+
+    let map = FKSMap::<&[u8], u64, MSPHasher<&[u8]>>::new(data.clone(), 42, 0.75).unwrap();
+    let msp = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(map.get(black_box(key)));
+        }
+    });
+
+    let map = SinglePassFKSMap::<&[u8], u64, MSPHasherDigest<&[u8]>>::new(data, 42, 0.75).unwrap();
+    let get = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(map.get(black_box(key)));
+        }
+    });
+    let get_single_pass = measure(NUM_ROUNDS, || {
+        for key in keys.iter() {
+            black_box(map.get_single_pass(black_box(key)));
+        }
+    });
+
+    println!(
+        "MSPHasher, get:                   {:?} ({:?} per key)",
+        msp,
+        msp / NUM_LOOKUPS as u32
+    );
+    println!(
+        "MSPHasherDigest, get:             {:?} ({:?} per key)",
+        get,
+        get / NUM_LOOKUPS as u32
+    );
+    println!(
+        "MSPHasherDigest, get_single_pass: {:?} ({:?} per key)",
+        get_single_pass,
+        get_single_pass / NUM_LOOKUPS as u32
+    );
+}
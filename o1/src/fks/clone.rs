@@ -0,0 +1,96 @@
+//! Implements [`Clone`] for [`FKSMap`].
+use crate::fks::{Bucket, FKSMap};
+use alloc::vec::Vec;
+use bitvec::prelude::*;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use o1_core::Hasher;
+
+impl<K: Eq, H: Hasher<K> + Clone> Clone for Bucket<K, H> {
+    fn clone(&self) -> Self {
+        Self {
+            offset: self.offset,
+            slots: self.slots,
+            num_slots: self.num_slots,
+            hasher: self.hasher.clone(),
+            key_type: PhantomData,
+        }
+    }
+}
+
+/// Makes an owned copy of the map cloning only the initialized slots.
+///
+/// Maps built by the compile-time constructor are cloned into owned memory as well.
+impl<K, V, H> Clone for FKSMap<'_, K, V, H>
+where
+    K: Eq + Clone,
+    V: Clone,
+    H: Hasher<K> + Clone,
+{
+    fn clone(&self) -> Self {
+        let buckets: Vec<Bucket<K, H>> = self.buckets.to_vec();
+        let mut slots: Vec<MaybeUninit<(K, V)>> = Vec::with_capacity(self.slots.len());
+        slots.resize_with(self.slots.len(), MaybeUninit::uninit);
+
+        for bucket in buckets.iter() {
+            for pos in bucket.slots.view_bits::<Lsb0>().iter_ones() {
+                let data_idx = bucket.slot_idx(pos, self.is_compact);
+                let (k, v) = unsafe { self.slots[data_idx].assume_init_ref() };
+                slots[data_idx].write((k.clone(), v.clone()));
+            }
+        }
+
+        Self {
+            l1_hasher: self.l1_hasher.clone(),
+            buckets: buckets.into(),
+            slots: slots.into(),
+            len: self.len,
+            is_compact: self.is_compact,
+            min_load_factor: self.min_load_factor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use crate::new_fks_map;
+    use o1_core::HashMap;
+
+    #[test]
+    fn test_clone_is_independent() {
+        let data: Vec<(u32, String)> = (0..1000_u32).map(|i| (i * 3, i.to_string())).collect();
+        let original =
+            FKSMap::<u32, String, MSPHasher<u32>>::new(data.clone().into(), 42, 0.75).unwrap();
+        let mut clone = original.clone();
+        assert_eq!(clone, original);
+
+        for value in clone.values_mut() {
+            value.push('!');
+        }
+
+        for (key, value) in data.iter() {
+            assert_eq!(original.get(key), Some(value));
+            assert_eq!(clone.get(key), Some(&format!("{value}!")));
+        }
+    }
+
+    const DATA: [(u32, u64); 4] = [(1, 10), (2, 20), (30, 300), (400, 4000)];
+    new_fks_map!(MAP, u32, u64, DATA, MSPHasher<u32>, 42, 0.75);
+
+    #[test]
+    fn test_clone_static_map() {
+        let mut clone = MAP.clone();
+        assert!(clone.buckets.is_owned() && clone.slots.is_owned());
+        assert_eq!(clone, MAP);
+
+        for value in clone.values_mut() {
+            *value += 1;
+        }
+        for (key, value) in DATA.iter() {
+            assert_eq!(MAP.get(key), Some(value));
+            assert_eq!(clone.get(key), Some(&(value + 1)));
+        }
+    }
+}
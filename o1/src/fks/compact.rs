@@ -0,0 +1,117 @@
+//! Implements compaction of [`FKSMap`] into a minimal perfect hash table.
+use crate::fks::FKSMap;
+use alloc::vec::Vec;
+use bitvec::prelude::*;
+use core::mem::MaybeUninit;
+use o1_core::Hasher;
+
+impl<K: Eq, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// Compacts the map so that it has exactly as many slots as entries.
+    ///
+    /// The L2 tables of the buckets usually have unoccupied positions, and the map allocates a
+    /// slot for each of them. Compaction moves the entries of every bucket next to each other
+    /// and re-targets the offsets of the buckets to the new positions - so the map becomes a
+    /// minimal perfect hash table. Lookups still take `O(1)` time: the index of a slot is the
+    /// offset of its bucket plus the number of the occupied positions preceding the key's
+    /// position in the L2 table. It takes `O(n)` time and reallocates the slots.
+    ///
+    /// Compacting a compact map does nothing.
+    ///
+    /// # Panics
+    ///
+    /// - If the map has been built by the compile-time constructor and hence does not own its memory.
+    pub fn compact(&mut self) {
+        assert!(
+            self.slots.is_owned() && self.buckets.is_owned(),
+            "Cannot compact a map that borrows its memory."
+        );
+        if self.is_compact {
+            return;
+        }
+
+        let num_entries = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.slots.count_ones() as usize)
+            .sum();
+        let mut slots: Vec<MaybeUninit<(K, V)>> = Vec::with_capacity(num_entries);
+
+        for bucket in self.buckets.iter_mut() {
+            let offset = slots.len();
+            for pos in bucket.slots.view_bits::<Lsb0>().iter_ones() {
+                // SAFETY: the slot is occupied and it's dropped together with the old slots
+                //         without being read again - `MaybeUninit` doesn't drop its contents.
+                let entry = unsafe { self.slots[bucket.offset + pos].assume_init_read() };
+                slots.push(MaybeUninit::new(entry));
+            }
+            bucket.offset = offset;
+        }
+
+        self.slots = slots.into();
+        self.is_compact = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_core::HashMap;
+    use o1_test::data::{STR_DATA, U64_DATA};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_compact() {
+        let mut map = FKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        assert!(map.slots.len() > U64_DATA.len());
+
+        map.compact();
+
+        assert_eq!(map.slots.len(), U64_DATA.len());
+        assert_eq!(map.len(), U64_DATA.len());
+        assert_eq!(map.slot_utilization(), 1.0);
+        assert!(map.verify());
+        for (key, val) in &U64_DATA {
+            assert_eq!(map.get(key), Some(val), "Key: {:?}", key);
+        }
+        for key in 0..1000_u64 {
+            let expected = U64_DATA.iter().find(|(k, _)| *k == key).map(|(_, v)| v);
+            assert_eq!(map.get(&key), expected, "Key: {:?}", key);
+        }
+    }
+
+    #[test]
+    fn test_compact_iteration() {
+        let mut map = FKSMap::<&str, u64, MSPHasher<&str>>::new(STR_DATA.into(), 42, 0.75).unwrap();
+        map.compact();
+        map.compact();
+
+        assert_eq!(map.slots.len(), STR_DATA.len());
+        for v in map.values_mut() {
+            *v += 1;
+        }
+        let mut entries: Vec<(&str, u64)> = map.clone().into_iter().collect();
+        entries.sort();
+        let mut expected: Vec<(&str, u64)> = STR_DATA.iter().map(|&(k, v)| (k, v + 1)).collect();
+        expected.sort();
+        assert_eq!(entries, expected);
+        assert_eq!(map.iter().count(), STR_DATA.len());
+        assert_eq!(
+            map.get_many_mut([&STR_DATA[0].0]),
+            Some([&mut (STR_DATA[0].1 + 1)])
+        );
+    }
+
+    #[test]
+    fn test_compact_drops_each_entry_once() {
+        let value = Rc::new(());
+        let data: Box<[(u32, Rc<()>)]> = (0..1000_u32).map(|i| (i, Rc::clone(&value))).collect();
+        let mut map = FKSMap::<u32, Rc<()>, MSPHasher<u32>>::new(data, 42, 0.75).unwrap();
+
+        map.compact();
+        assert_eq!(Rc::strong_count(&value), 1001);
+
+        drop(map);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
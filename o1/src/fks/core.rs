@@ -1,9 +1,9 @@
 //! Declares core types for [`FKSMap`].
 use crate::utils::maybe_owned_slice::MaybeOwnedSliceMut;
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
 use o1_core::Hasher;
-use std::fmt::{Debug, Formatter};
-use std::marker::PhantomData;
-use std::mem::MaybeUninit;
 
 /// Static hash-table based on the FKS scheme.
 ///
@@ -43,6 +43,15 @@ pub struct FKSMap<'a, K: Eq, V, H: Hasher<K>> {
     pub buckets: MaybeOwnedSliceMut<'a, Bucket<K, H>>,
     #[doc(hidden)]
     pub slots: MaybeOwnedSliceMut<'a, MaybeUninit<(K, V)>>,
+    /// The number of entries - unless the map is compact, some of the slots are unoccupied.
+    #[doc(hidden)]
+    pub len: usize,
+    /// Whether the map has been compacted by [`FKSMap::compact`].
+    #[doc(hidden)]
+    pub is_compact: bool,
+    /// The minimum load factor the map has been built with - reused when the map is rebuilt.
+    #[doc(hidden)]
+    pub min_load_factor: f32,
 }
 
 impl<K, V, H> Debug for FKSMap<'_, K, V, H>
@@ -51,11 +60,14 @@ where
     V: Debug,
     H: Hasher<K> + Debug,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("FKSMap")
             .field("l1_hasher", &self.l1_hasher)
             .field("buckets", &self.buckets)
             .field("slots", &self.slots)
+            .field("len", &self.len)
+            .field("is_compact", &self.is_compact)
+            .field("min_load_factor", &self.min_load_factor)
             .finish()
     }
 }
@@ -69,7 +81,7 @@ pub struct Bucket<K: Eq, H: Hasher<K>> {
     /// The offset of the first slot in the bucket.
     pub offset: usize,
     /// A bit-mask of the occupied slots in the bucket.
-    pub slots: u8,
+    pub slots: u16,
     /// A number of slots in the bucket.
     pub num_slots: u8,
     /// L2 hasher that contains parameters for the L2 hash function.
@@ -82,6 +94,33 @@ impl<K: Eq, H: Hasher<K>> Bucket<K, H> {
     pub fn num_slots(&self) -> usize {
         self.num_slots as usize
     }
+
+    /// Index of the data-slot corresponding to the position `pos` of the L2 table.
+    #[inline]
+    pub(crate) fn slot_idx(&self, pos: usize, is_compact: bool) -> usize {
+        slot_idx(self.offset, self.slots, pos, is_compact)
+    }
+}
+
+/// Index of the data-slot corresponding to the position `pos` of the L2 table of a bucket.
+///
+/// The data-slots of a bucket mirror its L2 table unless the map is compact - then they hold only
+/// the occupied positions, so a position is shifted back by the number of the unoccupied ones
+/// preceding it.
+///
+/// # Parameters
+///
+/// - `offset`: The offset of the bucket.
+/// - `slots`: The bit-mask of the occupied positions of the bucket.
+/// - `pos`: The position within the L2 table.
+/// - `is_compact`: Whether the map is compact.
+#[inline]
+pub(crate) const fn slot_idx(offset: usize, slots: u16, pos: usize, is_compact: bool) -> usize {
+    if is_compact {
+        offset + (slots & !(u16::MAX << pos)).count_ones() as usize
+    } else {
+        offset + pos
+    }
 }
 
 impl<K: Eq, H: Hasher<K>> Default for Bucket<K, H> {
@@ -0,0 +1,134 @@
+//! Implements construction of [`FKSMap`] searching for a seed the hash functions resolve with.
+use super::new::TrialBudget;
+use crate::fks::FKSMap;
+use alloc::boxed::Box;
+use core::fmt::Debug;
+use o1_core::Hasher;
+use o1_core::O1Error;
+use o1_core::O1Error::UnableToFindHashFunction;
+
+impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// The maximum number of seeds tried by [`FKSMap::build_deterministic`].
+    pub const MAX_SEED_TRIALS: usize = 100;
+
+    /// Creates a new [`FKSMap`] trying the seeds `start_seed`, `start_seed + 1`, ... until the
+    /// hash functions resolve and returns the map together with the winning seed.
+    ///
+    /// The hash functions are resolved on the borrowed `data`, which is moved into the map only
+    /// once a seed wins. Passing the returned seed and the same `min_load_factor` to
+    /// [`FKSMap::new`] reproduces the same map, so it can be pinned once found.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `start_seed`: The first seed to try.
+    /// - `min_load_factor`: The minimum load factor.
+    ///
+    /// # Errors
+    ///
+    /// - [`O1Error::UnableToFindHashFunction`] if none of [`FKSMap::MAX_SEED_TRIALS`] seeds
+    ///   resolved.
+    /// - The other errors of [`FKSMap::new`] are returned right away, since they don't depend on
+    ///   the seed.
+    ///
+    /// # Notes
+    ///
+    /// - The compile-time constructors use a different random number generator, so the seed
+    ///   isn't guaranteed to resolve with [`new_fks_map!`](crate::new_fks_map) - validate the
+    ///   pinned seed at compile time with [`check_perfect!`](crate::check_perfect), e.g.
+    ///   `const _: () = assert!(check_perfect!(DATA, K, V, H, SEED, MIN_LOAD_FACTOR));`.
+    pub fn build_deterministic(
+        data: Box<[(K, V)]>,
+        start_seed: u64,
+        min_load_factor: f32,
+    ) -> Result<(Self, u64), O1Error> {
+        let mut seed = start_seed;
+        for _ in 0..Self::MAX_SEED_TRIALS {
+            match Self::resolve_with_budget(&data, seed, min_load_factor, TrialBudget::unlimited())
+            {
+                Ok((l1_hasher, buckets)) => {
+                    let map = Self::from_resolved(data, l1_hasher, buckets, min_load_factor);
+                    return Ok((map, seed));
+                }
+                Err(UnableToFindHashFunction) => seed = seed.wrapping_add(1),
+                Err(error) => return Err(error),
+            }
+        }
+        Err(UnableToFindHashFunction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check_perfect;
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::*;
+    use alloc::boxed::Box;
+    use o1_core::HashMap;
+    use o1_core::O1Error::UnableToFindHashFunction;
+    use o1_test::data::U64_DATA;
+
+    #[test]
+    fn test_build_deterministic_seed_reproduces_map() {
+        let data: Box<[(u64, u64)]> = (0..10_000_u64).map(|i| (i * 7919, i)).collect();
+
+        let (map, seed) =
+            FKSMap::<u64, u64, MSPHasher<u64>>::build_deterministic(data.clone(), 42, 0.9).unwrap();
+        let pinned = FKSMap::<u64, u64, MSPHasher<u64>>::new(data, seed, 0.9).unwrap();
+
+        assert!(map.iter().eq(pinned.iter()));
+        assert_eq!(map.min_load_factor, 0.9);
+        assert_eq!(map.buckets.len(), pinned.buckets.len());
+        for (first, second) in map.buckets.iter().zip(pinned.buckets.iter()) {
+            assert_eq!(first.offset, second.offset);
+            assert_eq!(first.slots, second.slots);
+        }
+    }
+
+    #[test]
+    fn test_build_deterministic_non_clone_entries() {
+        #[derive(Debug, PartialEq)]
+        struct Value(u64);
+
+        let data: Box<[(u64, Value)]> = (0..1000_u64).map(|i| (i * 7919, Value(i))).collect();
+        let (map, _) =
+            FKSMap::<u64, Value, MSPHasher<u64>>::build_deterministic(data, 0, 0.75).unwrap();
+
+        assert_eq!(map.len(), 1000);
+        for i in 0..1000_u64 {
+            assert_eq!(map.get(&(i * 7919)), Some(&Value(i)));
+        }
+    }
+
+    /// The seed found by [`FKSMap::build_deterministic`] for [`U64_DATA`], pinned for the
+    /// compile-time constructors.
+    const PINNED_SEED: u64 = 42;
+
+    #[test]
+    fn test_build_deterministic_pinned_seed_resolves_const() {
+        let (_, seed) =
+            FKSMap::<u64, u64, MSPHasher<u64>>::build_deterministic(U64_DATA.into(), 42, 0.75)
+                .unwrap();
+        assert_eq!(seed, PINNED_SEED);
+
+        const {
+            assert!(check_perfect!(
+                U64_DATA,
+                u64,
+                u64,
+                MSPHasher<u64>,
+                PINNED_SEED,
+                0.75
+            ))
+        };
+    }
+
+    #[test]
+    fn test_build_deterministic_gives_up() {
+        let duplicates: Box<[(u32, u32)]> = [(1, 1), (1, 2)].into();
+        assert!(matches!(
+            FKSMap::<u32, u32, MSPHasher<u32>>::build_deterministic(duplicates, 0, 0.75),
+            Err(UnableToFindHashFunction)
+        ));
+    }
+}
@@ -0,0 +1,63 @@
+//! Implements conversion from [`std::collections::HashMap`] into [`FKSMap`].
+use crate::fks::FKSMap;
+use o1_core::Hasher;
+use o1_core::O1Error;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// Creates a new [`FKSMap`] from the entries of the given standard library's hash map.
+    ///
+    /// The default minimum load factor is used - [`FKSMap::DEFAULT_MIN_LOAD_FACTOR`].
+    ///
+    /// # Parameters
+    ///
+    /// - `map`: The map whose entries are moved into the new map.
+    /// - `seed`: The seed for the random number generator.
+    pub fn from_std_hashmap<S>(map: HashMap<K, V, S>, seed: u64) -> Result<Self, O1Error> {
+        Self::with_seed(map.into_iter().collect(), seed)
+    }
+}
+
+/// Converts a standard library's hash map using [`FKSMap::DEFAULT_SEED`].
+impl<K: Eq + Debug, V, H: Hasher<K>, S> TryFrom<HashMap<K, V, S>> for FKSMap<'_, K, V, H> {
+    type Error = O1Error;
+
+    fn try_from(map: HashMap<K, V, S>) -> Result<Self, Self::Error> {
+        Self::from_std_hashmap(map, Self::DEFAULT_SEED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_core::HashMap;
+
+    fn make_std_map() -> std::collections::HashMap<String, u32> {
+        (0..1000_u32).map(|i| (format!("key-{i}"), i)).collect()
+    }
+
+    #[test]
+    fn test_from_std_hashmap() {
+        let std_map = make_std_map();
+        let map = FKSMap::<String, u32, MSPHasher<String>>::from_std_hashmap(std_map.clone(), 42)
+            .unwrap();
+
+        assert_eq!(map.iter().count(), std_map.len());
+        for (key, value) in &std_map {
+            assert_eq!(map.get(key), Some(value), "Key: {:?}", key);
+        }
+        assert_eq!(map.get(&String::from("key-1000")), None);
+    }
+
+    #[test]
+    fn test_try_from_std_hashmap() {
+        let std_map = make_std_map();
+        let map: FKSMap<String, u32, MSPHasher<String>> = std_map.clone().try_into().unwrap();
+
+        for (key, value) in &std_map {
+            assert_eq!(map.get(key), Some(value), "Key: {:?}", key);
+        }
+    }
+}
@@ -1,2 +1,10 @@
+mod deterministic;
+#[cfg(feature = "std")]
+mod from_std;
 mod new;
 mod new_const;
+#[cfg(feature = "parallel")]
+mod new_par;
+mod new_single_pass;
+mod new_streaming;
+mod rebuild;
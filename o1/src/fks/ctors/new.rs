@@ -1,18 +1,159 @@
 //! Implements constructors for [`FKSMap`].
 use crate::fks::core::Bucket;
 use crate::fks::FKSMap;
+use crate::hashing::common::num_buckets_for_len;
+use crate::utils::const_hacks::ceil_f32;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
 use bitvec::prelude::*;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
 use o1_core::Hasher;
 use o1_core::O1Error;
-use o1_core::O1Error::UnableToFindHashFunction;
+use o1_core::O1Error::{BudgetExhausted, InvalidConfig, TooLarge, UnableToFindHashFunction};
 use rand::{RngCore, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
-use std::fmt::Debug;
-use std::marker::PhantomData;
-use std::mem::MaybeUninit;
+
+/// Checks that `min_load_factor` is within `(0.0, 1.0]`.
+const fn check_min_load_factor(min_load_factor: f32) -> Result<(), O1Error> {
+    // Written this way to also reject NaN.
+    if !(min_load_factor > 0.0 && min_load_factor <= 1.0) {
+        return Err(InvalidConfig {
+            parameter: "min_load_factor",
+            reason: r#""min_load_factor" must be within (0.0, 1.0]"#,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that `data_len` entries fit into at most `max_num_buckets` buckets under
+/// `min_load_factor`.
+///
+/// Calculated with `f64`, since `f32` can't represent every number of entries in the `u32`
+/// range and would let a number of buckets slightly above `max_num_buckets` through.
+const fn check_len(
+    data_len: usize,
+    min_load_factor: f32,
+    max_num_buckets: u32,
+) -> Result<(), O1Error> {
+    let max_len = (max_num_buckets as f64 * min_load_factor as f64) as usize;
+    if data_len > max_len {
+        return Err(TooLarge {
+            len: data_len,
+            max: max_len,
+        });
+    }
+    Ok(())
+}
+
+/// Counts the trials of resolving the hash functions against the total budget of a construction.
+pub(super) struct TrialBudget {
+    /// The maximum total number of the L1 and L2 trials.
+    max_trials: usize,
+    /// The number of trials spent so far.
+    num_trials: usize,
+}
+
+impl TrialBudget {
+    pub(super) const fn new(max_trials: usize) -> Self {
+        Self {
+            max_trials,
+            num_trials: 0,
+        }
+    }
+
+    /// A budget that is never exhausted - the number of trials is bounded only by the per-call
+    /// limits.
+    pub(super) const fn unlimited() -> Self {
+        Self::new(usize::MAX)
+    }
+
+    /// Spends a trial, fails with [`O1Error::BudgetExhausted`] if no trials are left.
+    #[inline]
+    fn spend(&mut self) -> Result<(), O1Error> {
+        if self.num_trials == self.max_trials {
+            return Err(BudgetExhausted {
+                max_trials: self.max_trials,
+            });
+        }
+        self.num_trials += 1;
+        Ok(())
+    }
+}
+
+impl<K: Eq, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// The minimum load factor used by the constructors that don't accept it explicitly.
+    pub const DEFAULT_MIN_LOAD_FACTOR: f32 = 0.75;
+    /// The seed used by the constructors that don't accept it explicitly.
+    pub const DEFAULT_SEED: u64 = 42;
+}
 
 impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
-    const MAX_KEYS_PER_BUCKET: u32 = 5;
+    pub(super) const MAX_KEYS_PER_BUCKET: u32 = 5;
+    /// The number of slots addressable by the bit-mask of occupied slots of a bucket.
+    const MAX_SLOTS_PER_BUCKET: u32 = u16::BITS;
+    /// The maximum number of entries of a map built by the runtime constructors.
+    ///
+    /// Hash values and hence the indices of the L1 buckets are `u32`, and a map needs at least
+    /// one bucket per entry. Under a minimum load factor lower than `1.0` the ceiling is
+    /// proportionally lower - `MAX_LEN * min_load_factor`.
+    pub const MAX_LEN: usize = u32::MAX as usize;
+
+    /// Validates the parameters of the runtime constructors.
+    ///
+    /// Rejects the parameters with which the hash functions could never be resolved, so that
+    /// the constructors fail before spending any trials.
+    pub(super) fn validate_config(data_len: usize, min_load_factor: f32) -> Result<(), O1Error> {
+        check_min_load_factor(min_load_factor)?;
+        Self::check_num_slots_per_bucket_const(
+            H::from_seed(0, Self::MAX_KEYS_PER_BUCKET).num_buckets(),
+        )?;
+
+        check_len(data_len, min_load_factor, u32::MAX)
+    }
+
+    /// Validates the parameters of the compile-time constructors and calculates the maximum
+    /// number of L1 buckets under the minimum load factor.
+    ///
+    /// Used by [`new_fks_map_seeded!`](crate::new_fks_map_seeded) which panics with the reason
+    /// of the error.
+    #[doc(hidden)]
+    pub const fn max_num_buckets_const(
+        data_len: usize,
+        min_load_factor: f32,
+    ) -> Result<usize, O1Error> {
+        if let Err(error) = check_min_load_factor(min_load_factor) {
+            return Err(error);
+        }
+        if let Err(error) = check_len(data_len, min_load_factor, u32::MAX) {
+            return Err(error);
+        }
+        Ok(num_buckets_for_len(data_len, min_load_factor) as usize)
+    }
+
+    /// Checks that a bucket can hold `num_slots` slots - the number of buckets of the L2 hasher
+    /// created for the maximum number of keys per bucket.
+    ///
+    /// The number of slots depends only on the hasher type and the maximum number of keys per
+    /// bucket, so the hasher either always fits or never does. Used by
+    /// [`new_fks_map_seeded!`](crate::new_fks_map_seeded) which panics with the reason of the
+    /// error.
+    #[doc(hidden)]
+    pub const fn check_num_slots_per_bucket_const(num_slots: u32) -> Result<(), O1Error> {
+        if num_slots > Self::MAX_SLOTS_PER_BUCKET {
+            return Err(InvalidConfig {
+                parameter: "hasher",
+                reason:
+                    "the L2 hasher allocates more than 16 slots for the maximum number of keys \
+                         of a bucket - lower \"max_keys_per_bucket\" or use a hasher that rounds \
+                         the number of buckets up to at most the next power of two, changing the \
+                         seed or \"min_load_factor\" won't help",
+            });
+        }
+        Ok(())
+    }
 
     /// Attempts to find the L1 hash function.
     ///
@@ -22,18 +163,21 @@ impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
     /// - `load_factor`: The desirable load factor.
     /// - `num_trials`: The maximum number of trials to find the hash function.
     /// - `data`: The data to be hashed.
+    /// - `budget`: The total budget of trials of the construction.
     fn try_resolve_l1(
         rng: &mut Xoshiro256PlusPlus,
         load_factor: f32,
         num_trials: usize,
         data: &[(K, V)],
+        budget: &mut TrialBudget,
     ) -> Result<(H, Vec<BitVec>), O1Error> {
         for _ in 0..num_trials {
+            budget.spend()?;
             let l1_hasher = H::from_seed(
                 rng.next_u64(),
                 // TODO: Is this correct? Shouldn't the number of buckets be lower than the size of
                 //       the data?
-                (data.len() as f32 / load_factor).ceil() as u32,
+                ceil_f32(data.len() as f32 / load_factor) as u32,
             );
             let num_buckets = l1_hasher.num_buckets();
 
@@ -77,28 +221,61 @@ impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
     /// - `bucket_to_keys`: A vec of bit-masks where each bit-mask is a bucket
     ///                     and each bit is a key in the input data.
     /// - `num_trials`: The maximum number of trials to find the hash function.
-    fn try_resolve_bucket(
+    /// - `budget`: The total budget of trials of the construction.
+    pub(super) fn try_resolve_bucket(
         rng: &mut Xoshiro256PlusPlus,
         bucket_idx: usize,
         current_offset: usize,
         data: &[(K, V)],
         bucket_to_keys: &[BitVec],
         num_trials: usize,
+        budget: &mut TrialBudget,
+    ) -> Result<Bucket<K, H>, O1Error> {
+        let keys = &bucket_to_keys[bucket_idx];
+        Self::try_resolve_bucket_keys(
+            &mut |num_keys| H::from_seed(rng.next_u64(), num_keys),
+            current_offset,
+            data,
+            keys.iter_ones(),
+            keys.count_ones(),
+            num_trials,
+            budget,
+        )
+    }
+
+    /// Attempt to find the L2 hash function for the bucket holding the given keys.
+    ///
+    /// # Parameters
+    ///
+    /// - `make_hasher`: Creates a candidate L2 hash function for the given number of keys.
+    /// - `current_offset`: The current global offset of the bucket.
+    /// - `data`: The complete input data.
+    /// - `keys`: The indices of the keys of the bucket in `data`.
+    /// - `num_keys`: The number of the keys of the bucket.
+    /// - `num_trials`: The maximum number of trials to find the hash function.
+    /// - `budget`: The total budget of trials of the construction.
+    pub(super) fn try_resolve_bucket_keys(
+        make_hasher: &mut impl FnMut(u32) -> H,
+        current_offset: usize,
+        data: &[(K, V)],
+        keys: impl Iterator<Item = usize> + Clone,
+        num_keys: usize,
+        num_trials: usize,
+        budget: &mut TrialBudget,
     ) -> Result<Bucket<K, H>, O1Error> {
         for _ in 0..num_trials {
-            let keys = &bucket_to_keys[bucket_idx];
-            let num_keys: usize = keys.count_ones();
             if num_keys == 0 {
                 // Unoccupied bucket
                 return Ok(Bucket::default());
             }
+            budget.spend()?;
 
-            let hasher = H::from_seed(rng.next_u64(), num_keys as u32);
+            let hasher = make_hasher(num_keys as u32);
             let num_slots = hasher.num_buckets();
 
-            let mut slots: u8 = 0;
+            let mut slots: u16 = 0;
 
-            for key_idx in keys.iter_ones() {
+            for key_idx in keys.clone() {
                 let key = &data[key_idx].0;
                 let hash = hasher.hash(key);
                 slots.view_bits_mut::<Lsb0>().set(hash as usize, true);
@@ -119,7 +296,7 @@ impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
     }
 
     /// Fills the hash table with data based on selected L1 and L2 hash functions.
-    fn fill_slots(
+    pub(super) fn fill_slots(
         data: Box<[(K, V)]>,
         buckets: &[Bucket<K, H>],
         slots: &mut [MaybeUninit<(K, V)>],
@@ -135,34 +312,30 @@ impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
         }
     }
 
-    const MAX_L1_TRIALS: usize = 999;
-    const MAX_L2_TRIALS: usize = 999;
+    pub(super) const MAX_L1_TRIALS: usize = 999;
+    pub(super) const MAX_L2_TRIALS: usize = 999;
 
-    /// Creates a new [`FKSMap`] with the given data, seed, and minimum load factor.
+    /// Resolves the L1 hash function gradually lowering the load factor after each failure.
     ///
     /// # Parameters
     ///
-    /// - `data`: The data to be hashed.
-    /// - `seed`: The seed for the random number generator.
+    /// - `rng`: A random number generator.
     /// - `min_load_factor`: The minimum load factor.
-    pub fn new(data: Box<[(K, V)]>, seed: u64, min_load_factor: f32) -> Result<Self, O1Error> {
-        debug_assert!(min_load_factor > 0.0 && min_load_factor <= 1.0);
-
-        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
-
+    /// - `data`: The data to be hashed.
+    /// - `budget`: The total budget of trials of the construction.
+    pub(super) fn resolve_l1(
+        rng: &mut Xoshiro256PlusPlus,
+        min_load_factor: f32,
+        data: &[(K, V)],
+        budget: &mut TrialBudget,
+    ) -> Result<(H, Vec<BitVec>), O1Error> {
         let mut load_factor = 1.0;
 
-        let l1_hasher: H;
-        let bucket_to_keys: Vec<BitVec>;
-
-        // Try to resolve the level-1 gradually lowering the load factor after each failure.
         loop {
-            if let Ok(l1_result) =
-                Self::try_resolve_l1(&mut rng, load_factor, Self::MAX_L1_TRIALS, &data)
-            {
-                l1_hasher = l1_result.0;
-                bucket_to_keys = l1_result.1;
-                break;
+            match Self::try_resolve_l1(rng, load_factor, Self::MAX_L1_TRIALS, data, budget) {
+                Ok(l1_result) => return Ok(l1_result),
+                Err(UnableToFindHashFunction) => (),
+                Err(error) => return Err(error),
             }
             load_factor -= 0.05;
 
@@ -170,6 +343,89 @@ impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
                 return Err(UnableToFindHashFunction);
             }
         }
+    }
+
+    /// Creates a new [`FKSMap`] with the given data, seed, and minimum load factor.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    /// - `min_load_factor`: The minimum load factor.
+    ///
+    /// # Errors
+    ///
+    /// - [`O1Error::InvalidConfig`] if `min_load_factor` isn't within `(0.0, 1.0]`.
+    /// - [`O1Error::TooLarge`] if the number of buckets required for `data` under
+    ///   `min_load_factor` exceeds `u32::MAX` - see [`FKSMap::MAX_LEN`].
+    /// - [`O1Error::UnableToFindHashFunction`] if the hash functions couldn't be resolved.
+    pub fn new(data: Box<[(K, V)]>, seed: u64, min_load_factor: f32) -> Result<Self, O1Error> {
+        Self::new_with_budget(data, seed, min_load_factor, TrialBudget::unlimited())
+    }
+
+    /// Creates a new [`FKSMap`] like [`FKSMap::new`], but gives up once resolving the hash
+    /// functions takes more than `max_trials` trials in total.
+    ///
+    /// Bounds the work of the construction - e.g. on adversarial inputs for which the hash
+    /// functions resolve only after a large number of trials or never. A trial is an attempt of
+    /// a seed for the L1 hash function or for the L2 hash function of a bucket, the empty buckets
+    /// cost no trials. The resulting map is identical to the one built by [`FKSMap::new`] with
+    /// the same `data` and `seed` if the budget suffices.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    /// - `min_load_factor`: The minimum load factor.
+    /// - `max_trials`: The maximum total number of the L1 and L2 trials.
+    ///
+    /// # Errors
+    ///
+    /// - [`O1Error::BudgetExhausted`] if the hash functions didn't resolve within `max_trials`
+    ///   trials.
+    /// - The other errors of [`FKSMap::new`].
+    pub fn with_max_trials(
+        data: Box<[(K, V)]>,
+        seed: u64,
+        min_load_factor: f32,
+        max_trials: usize,
+    ) -> Result<Self, O1Error> {
+        Self::new_with_budget(data, seed, min_load_factor, TrialBudget::new(max_trials))
+    }
+
+    /// Implements [`FKSMap::new`] and [`FKSMap::with_max_trials`].
+    fn new_with_budget(
+        data: Box<[(K, V)]>,
+        seed: u64,
+        min_load_factor: f32,
+        budget: TrialBudget,
+    ) -> Result<Self, O1Error> {
+        let (l1_hasher, buckets) = Self::resolve_with_budget(&data, seed, min_load_factor, budget)?;
+
+        Ok(Self::from_resolved(
+            data,
+            l1_hasher,
+            buckets,
+            min_load_factor,
+        ))
+    }
+
+    /// Resolves the L1 and the L2 hash functions for `data` like [`FKSMap::new`] without taking
+    /// the ownership of the data.
+    ///
+    /// Returns the L1 hasher and the buckets.
+    pub(super) fn resolve_with_budget(
+        data: &[(K, V)],
+        seed: u64,
+        min_load_factor: f32,
+        mut budget: TrialBudget,
+    ) -> Result<(H, Vec<Bucket<K, H>>), O1Error> {
+        Self::validate_config(data.len(), min_load_factor)?;
+
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+
+        let (l1_hasher, bucket_to_keys) =
+            Self::resolve_l1(&mut rng, min_load_factor, data, &mut budget)?;
 
         let l1_num_buckets: u32 = l1_hasher.num_buckets();
         let mut buckets = Vec::<Bucket<K, H>>::with_capacity(l1_num_buckets as usize);
@@ -181,25 +437,71 @@ impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
                 &mut rng,
                 bucket_idx as usize,
                 current_offset,
-                &data,
+                data,
                 &bucket_to_keys,
                 Self::MAX_L2_TRIALS,
+                &mut budget,
             )?;
 
             current_offset += resolved_bucket.num_slots();
             buckets.push(resolved_bucket);
         }
 
-        let mut slots = Vec::<MaybeUninit<(K, V)>>::with_capacity(current_offset);
+        Ok((l1_hasher, buckets))
+    }
+
+    /// Moves `data` into the slots of the buckets resolved by [`FKSMap::resolve_with_budget`].
+    pub(super) fn from_resolved(
+        data: Box<[(K, V)]>,
+        l1_hasher: H,
+        buckets: Vec<Bucket<K, H>>,
+        min_load_factor: f32,
+    ) -> Self {
+        let num_slots = buckets.iter().map(Bucket::num_slots).sum();
+        let mut slots = Vec::<MaybeUninit<(K, V)>>::with_capacity(num_slots);
         unsafe { slots.set_len(slots.capacity()) };
 
+        let len = data.len();
         Self::fill_slots(data, &buckets, &mut slots, &l1_hasher);
 
-        Ok(Self {
+        Self {
             l1_hasher,
             buckets: buckets.into(),
             slots: slots.into(),
-        })
+            len,
+            is_compact: false,
+            min_load_factor,
+        }
+    }
+
+    /// Creates a new [`FKSMap`] with the given data and seed and with the default minimum load
+    /// factor - [`FKSMap::DEFAULT_MIN_LOAD_FACTOR`].
+    ///
+    /// The resulting map is fully determined by `data` and `seed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    pub fn with_seed(data: Box<[(K, V)]>, seed: u64) -> Result<Self, O1Error> {
+        Self::new(data, seed, Self::DEFAULT_MIN_LOAD_FACTOR)
+    }
+
+    /// Creates a new [`FKSMap`] with the given data, seed, and minimum load factor.
+    ///
+    /// Equivalent to [`FKSMap::new`].
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    /// - `min_load_factor`: The minimum load factor.
+    pub fn with_seed_and_load_factor(
+        data: Box<[(K, V)]>,
+        seed: u64,
+        min_load_factor: f32,
+    ) -> Result<Self, O1Error> {
+        Self::new(data, seed, min_load_factor)
     }
 }
 
@@ -219,4 +521,269 @@ mod tests {
     }
 
     generate_map_tests!(FKSMap, MSPHasher, factory);
+
+    #[cfg(feature = "xxh3")]
+    mod xxh3 {
+        use super::factory;
+        use crate::fks::FKSMap;
+        use crate::hashing::hashers::xxh3::XXH3Hasher;
+        use o1_core::HashMap;
+        use o1_test::{generate_map_str_special_tests, generate_map_str_tests};
+
+        generate_map_str_tests!(FKSMap, XXH3Hasher, factory);
+        generate_map_str_special_tests!(FKSMap, XXH3Hasher, factory);
+    }
+
+    #[test]
+    fn test_build_get_map_vec_u8() {
+        use o1_test::*;
+        use rand::rngs::ThreadRng;
+
+        let mut rng = rand::rng();
+        let data = generate_map_data::<_, Vec<u8>, u128>(
+            &mut rng,
+            999,
+            &<Vec<u8> as Generate<ThreadRng>>::GenerateParams::default(),
+            &<u128 as Generate<ThreadRng>>::GenerateParams::default(),
+        );
+        let map = factory::<Vec<u8>, u128, MSPHasher<Vec<u8>>>(data.clone());
+        test_get(&mut rng, map, &data);
+    }
+
+    #[test]
+    fn test_build_get_map_cow_str() {
+        use std::borrow::Cow;
+
+        // Every other key is owned, the lookups use the opposite variant.
+        let strings: Vec<String> = (0..100_u32).map(|i| i.to_string()).collect();
+        let data: Box<[(Cow<str>, u32)]> = (0..100_u32)
+            .zip(strings.iter())
+            .map(|(i, string)| match i % 2 {
+                0 => (Cow::Owned(string.clone()), i),
+                _ => (Cow::Borrowed(string.as_str()), i),
+            })
+            .collect();
+        let map = factory::<Cow<str>, u32, MSPHasher<Cow<str>>>(data);
+        for (i, string) in (0..100_u32).zip(strings.iter()) {
+            let key: Cow<str> = match i % 2 {
+                0 => Cow::Borrowed(string.as_str()),
+                _ => Cow::Owned(string.clone()),
+            };
+            assert_eq!(map.get(&key), Some(&i), "Key: {:?}", key);
+        }
+        assert_eq!(map.get(&Cow::Borrowed("100")), None);
+    }
+
+    #[test]
+    fn test_new_rejects_load_factor_out_of_range() {
+        let data: Box<[(u32, u32)]> = (0..100_u32).map(|i| (i, i)).collect();
+        for min_load_factor in [1.5, 0.0, -0.5, f32::NAN] {
+            let result = FKSMap::<u32, u32, MSPHasher<u32>>::new(data.clone(), 42, min_load_factor);
+            assert!(
+                matches!(
+                    result,
+                    Err(InvalidConfig {
+                        parameter: "min_load_factor",
+                        ..
+                    })
+                ),
+                "min_load_factor: {min_load_factor}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_rejects_too_low_load_factor() {
+        let data: Box<[(u32, u32)]> = (0..1000_u32).map(|i| (i, i)).collect();
+        let result = FKSMap::<u32, u32, MSPHasher<u32>>::new(data, 42, 1e-7);
+        assert!(matches!(
+            result,
+            Err(TooLarge {
+                len: 1000,
+                max: 429
+            })
+        ));
+    }
+
+    #[test]
+    fn test_check_len() {
+        assert!(check_len(100, 0.5, 200).is_ok());
+        assert!(matches!(
+            check_len(101, 0.5, 200),
+            Err(TooLarge { len: 101, max: 100 })
+        ));
+        assert!(check_len(0, 1e-6, 0).is_ok());
+        assert!(matches!(
+            check_len(1, 1.0, 0),
+            Err(TooLarge { len: 1, max: 0 })
+        ));
+    }
+
+    /// The lengths are checked before the data is touched, so they don't have to be backed by
+    /// actual data.
+    #[test]
+    fn test_validate_config_rejects_lengths_beyond_u32() {
+        type Map = FKSMap<'static, u32, u32, MSPHasher<u32>>;
+
+        assert!(Map::validate_config(Map::MAX_LEN, 1.0).is_ok());
+        assert!(matches!(
+            Map::validate_config(Map::MAX_LEN + 1, 1.0),
+            Err(TooLarge {
+                max: Map::MAX_LEN,
+                ..
+            })
+        ));
+        // `f32` rounds both `u32::MAX` and the required number of buckets to `2 ** 32`.
+        assert!(matches!(
+            Map::validate_config(Map::MAX_LEN, 0.999_999_9),
+            Err(TooLarge { .. })
+        ));
+        assert!(matches!(
+            Map::validate_config(usize::MAX, 0.75),
+            Err(TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_max_num_buckets_const() {
+        type Map = FKSMap<'static, u32, u32, MSPHasher<u32>>;
+
+        assert!(matches!(Map::max_num_buckets_const(6, 0.75), Ok(8)));
+        assert!(matches!(Map::max_num_buckets_const(0, 1.0), Ok(0)));
+        assert!(matches!(
+            Map::max_num_buckets_const(4, 1.5),
+            Err(InvalidConfig {
+                parameter: "min_load_factor",
+                ..
+            })
+        ));
+        assert!(matches!(
+            Map::max_num_buckets_const(4, 0.0),
+            Err(InvalidConfig {
+                parameter: "min_load_factor",
+                ..
+            })
+        ));
+        assert!(matches!(
+            Map::max_num_buckets_const(4, 1e-7),
+            Ok(40_000_000)
+        ));
+        assert!(matches!(Map::max_num_buckets_const(5000, 0.75), Ok(6667)));
+        assert!(matches!(
+            Map::max_num_buckets_const(Map::MAX_LEN, 1.0),
+            Ok(Map::MAX_LEN)
+        ));
+        assert!(matches!(
+            Map::max_num_buckets_const(Map::MAX_LEN + 1, 1.0),
+            Err(TooLarge { .. })
+        ));
+        assert!(matches!(
+            Map::max_num_buckets_const(Map::MAX_LEN, 0.5),
+            Err(TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_with_seed_is_reproducible() {
+        let data: Box<[(u32, u32)]> = (0..1000_u32).map(|i| (i * 7, i)).collect();
+        let first = FKSMap::<u32, u32, MSPHasher<u32>>::with_seed(data.clone(), 42).unwrap();
+        let second = FKSMap::<u32, u32, MSPHasher<u32>>::with_seed(data.clone(), 42).unwrap();
+        assert!(first.iter().eq(second.iter()));
+
+        let third =
+            FKSMap::<u32, u32, MSPHasher<u32>>::with_seed_and_load_factor(data, 42, 0.75).unwrap();
+        assert!(first.iter().eq(third.iter()));
+    }
+
+    #[test]
+    fn test_with_max_trials() {
+        let data: Box<[(u32, u32)]> = (0..1000_u32).map(|i| (i * 7, i)).collect();
+
+        // Each of the hundreds of non-empty buckets takes at least one trial.
+        let result =
+            FKSMap::<u32, u32, MSPHasher<u32>>::with_max_trials(data.clone(), 42, 0.75, 10);
+        assert!(matches!(
+            result,
+            Err(O1Error::BudgetExhausted { max_trials: 10 })
+        ));
+
+        let map =
+            FKSMap::<u32, u32, MSPHasher<u32>>::with_max_trials(data.clone(), 42, 0.75, 100_000)
+                .unwrap();
+        let expected = FKSMap::<u32, u32, MSPHasher<u32>>::new(data, 42, 0.75).unwrap();
+        assert!(map.iter().eq(expected.iter()));
+    }
+
+    /// The duplicate keys never resolve, the budget stops the search early.
+    #[test]
+    fn test_with_max_trials_duplicate_keys() {
+        let data: Box<[(u32, u32)]> = [(1, 10), (2, 20), (1, 30)].into();
+        let result = FKSMap::<u32, u32, MSPHasher<u32>>::with_max_trials(data, 42, 0.75, 100);
+        assert!(matches!(
+            result,
+            Err(O1Error::BudgetExhausted { max_trials: 100 })
+        ));
+    }
+
+    /// Hasher that allocates four times as many buckets as requested - so its L2 tables don't fit
+    /// into the buckets.
+    #[derive(Default)]
+    struct SparseHasher(MSPHasher<u64>);
+
+    impl Hasher<u64> for SparseHasher {
+        type State = <MSPHasher<u64> as Hasher<u64>>::State;
+
+        fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+            MSPHasher::<u64>::make_state(seed, num_buckets.saturating_mul(4))
+        }
+        fn from_seed(seed: u64, num_buckets: u32) -> Self {
+            Self(MSPHasher::<u64>::from_seed(
+                seed,
+                num_buckets.saturating_mul(4),
+            ))
+        }
+        fn from_state(state: Self::State) -> Self {
+            Self(MSPHasher::<u64>::from_state(state))
+        }
+        fn state(&self) -> &Self::State {
+            self.0.state()
+        }
+        fn num_buckets(&self) -> u32 {
+            self.0.num_buckets()
+        }
+        fn hash(&self, value: &u64) -> u32 {
+            self.0.hash(value)
+        }
+    }
+
+    /// Used to panic on an out-of-bounds slot index for the first bucket with more than 2 keys.
+    #[test]
+    fn test_new_rejects_hasher_with_too_many_slots() {
+        let data: Box<[(u64, u64)]> = (0..1000_u64).map(|i| (i, i)).collect();
+        let result = FKSMap::<u64, u64, SparseHasher>::new(data, 42, 0.75);
+        assert!(matches!(
+            result,
+            Err(InvalidConfig {
+                parameter: "hasher",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_check_num_slots_per_bucket_const() {
+        type Map = FKSMap<'static, u32, u32, MSPHasher<u32>>;
+
+        assert!(Map::check_num_slots_per_bucket_const(16).is_ok());
+        assert!(matches!(
+            Map::check_num_slots_per_bucket_const(17),
+            Err(InvalidConfig {
+                parameter: "hasher",
+                ..
+            })
+        ));
+        let num_slots =
+            MSPHasher::<u32>::from_seed_const(1, Map::MAX_KEYS_PER_BUCKET).num_buckets_const();
+        assert!(Map::check_num_slots_per_bucket_const(num_slots).is_ok());
+    }
 }
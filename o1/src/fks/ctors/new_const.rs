@@ -7,6 +7,10 @@
 /// - `hasher_type`: Hasher type that should be used to hash the keys.
 /// - `seed`: The seed for the random number generator.
 /// - `min_load_factor`: The minimum load factor.
+/// - `max_keys_per_bucket`: Optional, the maximum number of keys hashing into one L1 bucket, `5`
+///                          by default. Raising it makes the L1 hash function easier to find at
+///                          the cost of larger L2 tables, the L2 table of a bucket can't have more
+///                          than 16 slots.
 ///
 /// # Examples
 ///
@@ -28,58 +32,156 @@
 /// assert_eq!(BOOK_RATINGS.get(&"The Great Gatsby"), Some(&5));
 /// assert_eq!(BOOK_RATINGS.get(&"War and Peace"), None);
 /// ```
+///
+/// # Panics
+///
+/// - Fails to compile if `min_load_factor` isn't within `(0.0, 1.0]`, naming the offending
+///   parameter.
+/// - Fails to compile if the number of buckets for `data` under `min_load_factor` exceeds the
+///   `u32` range, see [`FKSMap::MAX_LEN`].
+/// - Fails to compile if the L2 hasher allocates more than 16 slots for `max_keys_per_bucket`
+///   keys.
+///
+/// # Notes
+///
+/// - Large datasets and long keys may exceed the step limit of the const-evaluator, in that case
+///   the compiler emits the deny-by-default `long_running_const_eval` lint. It could be allowed
+///   for the module that invokes the macro with `#![allow(long_running_const_eval)]`.
 #[doc(hidden)]
 #[macro_export]
 macro_rules! new_fks_map {
     ($name:ident, $K:ty, $V:ty, $data:expr, $HasherType:ty, $seed:expr, $min_load_factor:expr$(,)?) => {
-        static $name: $crate::fks::FKSMap<'static, $K, $V, $HasherType> = {
+        $crate::new_fks_map!(
+            $name,
+            $K,
+            $V,
+            $data,
+            $HasherType,
+            $seed,
+            $min_load_factor,
+            5
+        );
+    };
+    (
+        $name:ident,
+        $K:ty,
+        $V:ty,
+        $data:expr,
+        $HasherType:ty,
+        $seed:expr,
+        $min_load_factor:expr,
+        $max_keys_per_bucket:expr$(,)?
+    ) => {
+        $crate::new_fks_map_seeded!(
+            $name,
+            $K,
+            $V,
+            $data,
+            $HasherType,
+            &[$seed],
+            $min_load_factor,
+            $max_keys_per_bucket
+        );
+    };
+}
+
+/// Alternative compile-time constructor of [`FKSMap`] that tries the candidate seeds in order.
+///
+/// It allows to pin a seed that is known to work for the data (e.g. found offline) and so to avoid
+/// exhaustive searching for the hash functions at compile-time. The first seed for which the hash
+/// functions are successfully resolved is used.
+///
+/// # Parameters
+///
+/// - `name`: The name of the resulting static variable.
+/// - `data`: The data to be hashed.
+/// - `hasher_type`: Hasher type that should be used to hash the keys.
+/// - `seeds`: A slice of the candidate seeds for the random number generator.
+/// - `min_load_factor`: The minimum load factor.
+/// - `max_keys_per_bucket`: Optional, the maximum number of keys hashing into one L1 bucket, `5`
+///                          by default. Raising it makes the L1 hash function easier to find at
+///                          the cost of larger L2 tables, the L2 table of a bucket can't have more
+///                          than 16 slots.
+///
+/// # Examples
+///
+/// ```rust
+/// use o1_core::HashMap;
+/// use o1::hashing::hashers::msp::MSPHasher;
+/// use o1::new_fks_map_seeded;
+///
+/// new_fks_map_seeded!(PRIMES, u32, &'static str, [
+///     (2, "two"),
+///     (3, "three"),
+///     (5, "five"),
+///     (7, "seven"),
+/// ], MSPHasher<u32>, &[7, 42], 0.75);
+///
+/// assert_eq!(PRIMES.get(&5), Some(&"five"));
+/// assert_eq!(PRIMES.get(&4), None);
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! new_fks_map_seeded {
+    ($name:ident, $K:ty, $V:ty, $data:expr, $HasherType:ty, $seeds:expr, $min_load_factor:expr$(,)?) => {
+        $crate::new_fks_map_seeded!(
+            $name,
+            $K,
+            $V,
+            $data,
+            $HasherType,
+            $seeds,
+            $min_load_factor,
+            5
+        );
+    };
+    (
+        $name:ident,
+        $K:ty,
+        $V:ty,
+        $data:expr,
+        $HasherType:ty,
+        $seeds:expr,
+        $min_load_factor:expr,
+        $max_keys_per_bucket:expr$(,)?
+    ) => {
+        static $name: $crate::fks::FKSMap<'static, $K, $V, $HasherType> = $crate::new_fks_map_seeded!(
+            @build static,
+            $K,
+            $V,
+            $data,
+            $HasherType,
+            $seeds,
+            $min_load_factor,
+            $max_keys_per_bucket
+        );
+    };
+    // Builds the map - the memory of a `static` map resides in `static mut` arrays, while the
+    // memory of a `const` map resides in `const` items.
+    (
+        @build $storage:tt,
+        $K:ty,
+        $V:ty,
+        $data:expr,
+        $HasherType:ty,
+        $seeds:expr,
+        $min_load_factor:expr,
+        $max_keys_per_bucket:expr
+    ) => {
+        {
             use core::marker::PhantomData;
-            use core::mem::{swap, transmute_copy, MaybeUninit};
-            use o1_core::Hasher;
-            use $crate::fks::{Bucket, FKSMap};
-            use $crate::utils::bit_array::{BitArray, Bits};
-            use $crate::utils::const_hacks::div_ceil_f32;
+            use core::mem::{swap, transmute_copy};
+            use $crate::fks::Bucket;
             use $crate::utils::maybe_owned_slice::MaybeOwnedSliceMut;
-            use $crate::utils::xorshift::XorShift;
-
-            const MAX_KEYS_PER_BUCKET: usize = 5;
-            const MAX_NUM_BUCKETS: usize =
-                div_ceil_f32($data.len() as f32, $min_load_factor as f32) as usize;
-            const DATA_LEN: usize = $data.len();
-            const DATA_REF: &[($K, $V); DATA_LEN] = &($data);
-            const KEY_BIT_ARRAY_LEN: usize = div_ceil_f32($data.len() as f32, 64 as f32) as usize;
-
-            /// A compile-time alternative bucket type of the hash table.
-            #[derive(Clone)]
-            #[doc(hidden)]
-            pub struct ConstBucket {
-                /// The offset of the first slot in the bucket.
-                pub offset: usize,
-                /// A bit-mask of the occupied slots in the bucket.
-                pub slots: u8,
-                /// A number of slots in the bucket.
-                pub num_slots: u8,
-                /// L2 hasher that contains parameters for the L2 hash function.
-                pub hasher: $HasherType,
-                pub hasher_state: <$HasherType as Hasher<$K>>::State,
-            }
 
-            /// Result of resolving L1 and L2 hash functions.
-            ///
-            /// It's an intermediate result of constructing the hash table. It contains everything
-            /// that is necessary to build the table, but the `l1_hasher` contains a const-version
-            /// of the hasher, `buckets` contains const-buckets and of unoptimal size.
-            #[doc(hidden)]
-            struct ResolveResult<const MAX_NUM_BUCKETS: usize> {
-                /// Total number of data-slots in the hash table.
-                num_slots: usize,
-                /// Total number of buckets in the hash table. Supposed to be less than or equal to
-                /// `MAX_NUM_BUCKETS`.
-                num_buckets: usize,
-                l1_hasher: $HasherType,
-                /// Buckets of the hash-table.
-                buckets: [MaybeUninit<ConstBucket>; MAX_NUM_BUCKETS],
-            }
+            $crate::new_fks_map_seeded!(
+                @resolve $K,
+                $V,
+                $data,
+                $HasherType,
+                $min_load_factor,
+                $max_keys_per_bucket
+            );
 
             /// Contains all the data required to instantiate the static [`FKSMap`].
             struct BuildResult<const NUM_BUCKETS: usize, const NUM_SLOTS: usize> {
@@ -91,231 +193,6 @@ macro_rules! new_fks_map {
                 slots: [MaybeUninit<($K, $V)>; NUM_SLOTS],
             }
 
-            /// Attempts to find a suitable level-1 hash function for the given input data.
-            ///
-            /// # Parameters
-            ///
-            /// - `rng`: The random number generator.
-            /// - `load_factor`: The desired load factor - it would determined the size of the output
-            ///                  space of the hash function.
-            /// - `num_trials`: The number of trials to attempt.
-            /// - `data`: The input dataset in form of a slice of key-value pairs as tuples.
-            /// - `DATA_LEN`: The length of the input dataset.
-            /// - `MAX_NUM_BUCKETS`: The maximum possible number of buckets under the minimum load
-            ///                      factor (not to confuse with the current load factor passed as
-            ///                      `load_factor`).
-            const fn try_resolve_l1<
-                const DATA_LEN: usize,
-                const MAX_NUM_BUCKETS: usize,
-                const KEY_BIT_ARRAY_LEN: usize,
-            >(
-                rng: &mut XorShift<u64>,
-                load_factor: f32,
-                num_trials: usize,
-                data: &[($K, $V); DATA_LEN],
-            ) -> Option<(
-                $HasherType,
-                [BitArray<u64, KEY_BIT_ARRAY_LEN>; MAX_NUM_BUCKETS],
-            )> {
-                let mut trial_idx = 0;
-                while trial_idx < num_trials {
-                    let num_buckets_raw = div_ceil_f32(DATA_LEN as f32, load_factor) as u32;
-                    let l1_hasher = <$HasherType>::from_seed_const(rng.next(), num_buckets_raw);
-                    let num_buckets = l1_hasher.num_buckets_const() as usize;
-
-                    if num_buckets > MAX_NUM_BUCKETS {
-                        break;
-                    }
-
-                    let mut bucket_to_keys: [BitArray<u64, KEY_BIT_ARRAY_LEN>; MAX_NUM_BUCKETS] =
-                        { [BitArray::<u64, KEY_BIT_ARRAY_LEN>::new(); MAX_NUM_BUCKETS] };
-
-                    let mut i = 0;
-                    while i < DATA_LEN {
-                        let hash = l1_hasher.hash_const(&data[i].0) as usize;
-                        bucket_to_keys[hash].set(i);
-                        i += 1;
-                    }
-
-                    let mut max_keys_per_bucket: usize = 0;
-                    let mut i = 0;
-                    while i < num_buckets {
-                        let num_keys = bucket_to_keys[i].count_ones();
-                        if num_keys > max_keys_per_bucket {
-                            max_keys_per_bucket = num_keys;
-                        }
-                        i += 1;
-                    }
-
-                    if max_keys_per_bucket <= MAX_KEYS_PER_BUCKET {
-                        return Some((l1_hasher, bucket_to_keys));
-                    }
-
-                    trial_idx += 1;
-                }
-
-                None
-            }
-
-            /// Attempt to find the L2 hash function for the given bucket.
-            ///
-            /// # Parameters
-            ///
-            /// - `rng`: A random number generator.
-            /// - `bucket_idx`: The global index of the bucket in the L1 table.
-            /// - `current_offset`: The current global offset of the bucket.
-            /// - `data`: The complete input data.
-            /// - `bucket_to_keys`: An array of bit-arrays where each bit-array is a bucket
-            ///                     and each bit is a key in the input data.
-            /// - `num_trials`: The maximum number of trials to find the hash function.
-            /// - `DATA_LEN`: The length of the input dataset.
-            /// - `MAX_NUM_BUCKETS`: The maximum possible number of buckets under the minimum load
-            ///                      factor.
-            const fn try_resolve_bucket<
-                const DATA_LEN: usize,
-                const MAX_NUM_BUCKETS: usize,
-                const KEY_BIT_ARRAY_LEN: usize,
-            >(
-                rng: &mut XorShift<u64>,
-                bucket_idx: usize,
-                current_offset: usize,
-                data: &[($K, $V); DATA_LEN],
-                bucket_to_keys: &[BitArray<u64, KEY_BIT_ARRAY_LEN>; MAX_NUM_BUCKETS],
-                num_trials: usize,
-            ) -> Option<ConstBucket> {
-                let keys = &bucket_to_keys[bucket_idx];
-                let num_keys: usize = keys.count_ones();
-
-                if num_keys == 0 {
-                    return Some(ConstBucket {
-                        offset: 0,
-                        slots: 0,
-                        num_slots: 0,
-                        hasher: <$HasherType>::from_seed_const(1, 1),
-                        hasher_state: <$HasherType>::make_state_const(1, 1),
-                    });
-                }
-
-                let mut trial_idx = 0;
-                while trial_idx < num_trials {
-                    let seed = rng.next();
-                    let l2_hasher = <$HasherType>::from_seed_const(seed, num_keys as u32);
-                    let num_slots = l2_hasher.num_buckets_const() as u8;
-
-                    if num_slots > u8::MAX {
-                        panic!("Number of slots exceeds u8::MAX");
-                    }
-
-                    let mut slots: Bits<u8> = Bits::<u8>::new();
-                    let mut is_collision = false;
-                    let mut iter = keys.iter_ones_const();
-
-                    while let Some(key_idx) = iter.next() {
-                        let hash = l2_hasher.hash_const(&data[key_idx].0) as usize;
-
-                        if slots.get(hash).unwrap() {
-                            is_collision = true;
-                            break;
-                        }
-                        slots.set(hash);
-                    }
-
-                    if !is_collision {
-                        return Some(ConstBucket {
-                            offset: current_offset,
-                            slots: slots.value(),
-                            num_slots,
-                            hasher: l2_hasher,
-                            hasher_state: <$HasherType>::make_state_const(seed, num_keys as u32),
-                        });
-                    }
-
-                    trial_idx += 1;
-                }
-
-                None
-            }
-
-            /// Attempts to resolve the level-1 hash function and the per-bucket level-2 hash functions.
-            const fn try_resolve<const MAX_NUM_BUCKETS: usize, const KEY_BIT_ARRAY_LEN: usize>(
-                data: &[($K, $V); DATA_LEN],
-                seed: u64,
-                min_load_factor: f32,
-            ) -> Option<ResolveResult<MAX_NUM_BUCKETS>> {
-                let mut rng = XorShift::<u64>::new(seed);
-
-                let mut load_factor = 1.0;
-                let mut l1_result = None;
-
-                const MAX_L1_TRIALS: usize = 999;
-
-                while load_factor >= min_load_factor {
-                    l1_result = try_resolve_l1::<DATA_LEN, MAX_NUM_BUCKETS, KEY_BIT_ARRAY_LEN>(
-                        &mut rng,
-                        load_factor,
-                        MAX_L1_TRIALS,
-                        data,
-                    );
-
-                    if l1_result.is_some() {
-                        break;
-                    }
-
-                    load_factor -= 0.05;
-                }
-
-                let (l1_hasher, bucket_to_keys) = match l1_result {
-                    Some(result) => result,
-                    None => return None,
-                };
-
-                let mut buckets: [MaybeUninit<ConstBucket>; MAX_NUM_BUCKETS] =
-                    { unsafe { MaybeUninit::uninit().assume_init() } };
-                let mut i = 0;
-                while i < MAX_NUM_BUCKETS {
-                    buckets[i] = MaybeUninit::new(ConstBucket {
-                        offset: 0,
-                        slots: 0,
-                        num_slots: 0,
-                        hasher: <$HasherType>::from_seed_const(1, 1),
-                        hasher_state: <$HasherType>::make_state_const(1, 1),
-                    });
-                    i += 1;
-                }
-
-                let num_buckets = l1_hasher.num_buckets_const() as usize;
-                let mut current_offset = 0;
-                let mut bucket_idx = 0;
-
-                const MAX_L2_TRIALS: usize = 999;
-
-                while bucket_idx < num_buckets {
-                    let bucket = try_resolve_bucket::<DATA_LEN, MAX_NUM_BUCKETS, KEY_BIT_ARRAY_LEN>(
-                        &mut rng,
-                        bucket_idx,
-                        current_offset,
-                        data,
-                        &bucket_to_keys,
-                        MAX_L2_TRIALS,
-                    );
-                    if bucket.is_none() {
-                        return None;
-                    }
-                    let bucket = bucket.unwrap();
-
-                    current_offset += bucket.num_slots as usize;
-                    buckets[bucket_idx] = MaybeUninit::new(bucket);
-                    bucket_idx += 1;
-                }
-
-                Some(ResolveResult {
-                    num_slots: current_offset,
-                    num_buckets,
-                    l1_hasher,
-                    buckets,
-                })
-            }
-
             // Builds [`BuildResult`] which contains everything to instantiate a static [`FKSMap`].
             const fn build<
                 const NUM_BUCKETS: usize,
@@ -372,15 +249,17 @@ macro_rules! new_fks_map {
                 }
             }
 
+            const MIN_LOAD_FACTOR: f32 = $min_load_factor;
+
             // It is necessary to save the result as a global const-constant because it should be const
             // to use it's fields as sizes of the final arrays.
             //
             // It's impossible to do that within a scope of a function - hence the intermediate step.
             const RESOLVE_RESULT: ResolveResult<MAX_NUM_BUCKETS> =
-                try_resolve::<MAX_NUM_BUCKETS, KEY_BIT_ARRAY_LEN>(
+                try_resolve_seeded::<MAX_NUM_BUCKETS, KEY_BIT_ARRAY_LEN>(
                     DATA_REF,
-                    $seed,
-                    $min_load_factor,
+                    $seeds,
+                    MIN_LOAD_FACTOR,
                 )
                 .expect("Unable to resolve the hash functions");
 
@@ -389,13 +268,351 @@ macro_rules! new_fks_map {
                 { RESOLVE_RESULT.num_buckets },
                 { RESOLVE_RESULT.num_slots },
             > = {
+                // Evaluates `data` anew instead of dereferencing `DATA_REF`, so that the keys and
+                // the values don't have to be `Copy`.
                 build::<{ RESOLVE_RESULT.num_buckets }, { RESOLVE_RESULT.num_slots }, DATA_LEN>(
-                    *DATA_REF,
+                    $data,
                     RESOLVE_RESULT.l1_hasher,
                     RESOLVE_RESULT.buckets,
                 )
             };
 
+            $crate::new_fks_map_seeded!(@storage $storage, $K, $V, $HasherType)
+        }
+    };
+    // Defines the items resolving the hash functions without building the map, shared by the
+    // constructors and `check_perfect!`.
+    (
+        @resolve $K:ty,
+        $V:ty,
+        $data:expr,
+        $HasherType:ty,
+        $min_load_factor:expr,
+        $max_keys_per_bucket:expr
+    ) => {
+        use core::mem::MaybeUninit;
+        use o1_core::{Hasher, O1Error};
+        use $crate::fks::FKSMap;
+        use $crate::hashing::common::num_buckets_for_len;
+        use $crate::utils::bit_array::{BitArray, Bits};
+        use $crate::utils::xorshift::XorShift;
+
+
+        const MAX_KEYS_PER_BUCKET: usize = $max_keys_per_bucket;
+        // Coercing to a slice allows `data` to be an empty array literal.
+        const DATA_LEN: usize = {
+            let data: &[($K, $V)] = &$data;
+            data.len()
+        };
+        const MAX_NUM_BUCKETS: usize = {
+            let max_num_buckets = match FKSMap::<$K, $V, $HasherType>::max_num_buckets_const(
+                DATA_LEN,
+                $min_load_factor as f32,
+            ) {
+                Ok(max_num_buckets) => max_num_buckets,
+                Err(O1Error::InvalidConfig { reason, .. }) => panic!("{}", reason),
+                Err(O1Error::TooLarge { .. }) => panic!(
+                    r#""data" is too large - the number of buckets under "min_load_factor" exceeds the u32 range"#
+                ),
+                Err(_) => unreachable!(),
+            };
+            // Hashers round the number of buckets up (e.g. to a power of two).
+            <$HasherType>::from_seed_const(1, max_num_buckets as u32).num_buckets_const()
+                as usize
+        };
+        // Ensures that the L2 tables fit into the buckets.
+        const _: () = match FKSMap::<$K, $V, $HasherType>::check_num_slots_per_bucket_const(
+            <$HasherType>::from_seed_const(1, MAX_KEYS_PER_BUCKET as u32).num_buckets_const(),
+        ) {
+            Ok(()) => (),
+            Err(O1Error::InvalidConfig { reason, .. }) => panic!("{}", reason),
+            Err(_) => unreachable!(),
+        };
+        const DATA_REF: &[($K, $V); DATA_LEN] = &($data);
+        const KEY_BIT_ARRAY_LEN: usize = DATA_LEN.div_ceil(64);
+
+        /// A compile-time alternative bucket type of the hash table.
+        #[derive(Clone)]
+        #[doc(hidden)]
+        // The fields are only read when the map is built, unlike by `check_perfect!`.
+        #[allow(dead_code)]
+        pub struct ConstBucket {
+            /// The offset of the first slot in the bucket.
+            pub offset: usize,
+            /// A bit-mask of the occupied slots in the bucket.
+            pub slots: u16,
+            /// A number of slots in the bucket.
+            pub num_slots: u8,
+            /// L2 hasher that contains parameters for the L2 hash function.
+            pub hasher: $HasherType,
+            pub hasher_state: <$HasherType as Hasher<$K>>::State,
+        }
+
+        /// Result of resolving L1 and L2 hash functions.
+        ///
+        /// It's an intermediate result of constructing the hash table. It contains everything
+        /// that is necessary to build the table, but the `l1_hasher` contains a const-version
+        /// of the hasher, `buckets` contains const-buckets and of unoptimal size.
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        struct ResolveResult<const MAX_NUM_BUCKETS: usize> {
+            /// Total number of data-slots in the hash table.
+            num_slots: usize,
+            /// Total number of buckets in the hash table. Supposed to be less than or equal to
+            /// `MAX_NUM_BUCKETS`.
+            num_buckets: usize,
+            l1_hasher: $HasherType,
+            /// Buckets of the hash-table, only the first `num_buckets` of them are initialized.
+            buckets: [MaybeUninit<ConstBucket>; MAX_NUM_BUCKETS],
+        }
+
+        /// The bucket without keys.
+        ///
+        /// Evaluated once - seeding a hasher may take many const-eval steps (e.g. the string
+        /// hashers generate more than a hundred of random numbers), so it must not be repeated for
+        /// every empty bucket.
+        const EMPTY_BUCKET: ConstBucket = ConstBucket {
+            offset: 0,
+            slots: 0,
+            num_slots: 0,
+            hasher: <$HasherType>::from_seed_const(1, 1),
+            hasher_state: <$HasherType>::make_state_const(1, 1),
+        };
+
+        /// Attempts to find a suitable level-1 hash function for the given input data.
+        ///
+        /// # Parameters
+        ///
+        /// - `rng`: The random number generator.
+        /// - `load_factor`: The desired load factor - it would determined the size of the output
+        ///                  space of the hash function.
+        /// - `num_trials`: The number of trials to attempt.
+        /// - `data`: The input dataset in form of a slice of key-value pairs as tuples.
+        /// - `DATA_LEN`: The length of the input dataset.
+        /// - `MAX_NUM_BUCKETS`: The maximum possible number of buckets under the minimum load
+        ///                      factor (not to confuse with the current load factor passed as
+        ///                      `load_factor`).
+        const fn try_resolve_l1<
+            const DATA_LEN: usize,
+            const MAX_NUM_BUCKETS: usize,
+            const KEY_BIT_ARRAY_LEN: usize,
+        >(
+            rng: &mut XorShift<u64>,
+            load_factor: f32,
+            num_trials: usize,
+            data: &[($K, $V); DATA_LEN],
+        ) -> Option<(
+            $HasherType,
+            [BitArray<u64, KEY_BIT_ARRAY_LEN>; MAX_NUM_BUCKETS],
+        )> {
+            let mut trial_idx = 0;
+            while trial_idx < num_trials {
+                let num_buckets_raw = num_buckets_for_len(DATA_LEN, load_factor);
+                let l1_hasher = <$HasherType>::from_seed_const(rng.next(), num_buckets_raw);
+                let num_buckets = l1_hasher.num_buckets_const() as usize;
+
+                if num_buckets > MAX_NUM_BUCKETS {
+                    break;
+                }
+
+                let mut bucket_to_keys: [BitArray<u64, KEY_BIT_ARRAY_LEN>; MAX_NUM_BUCKETS] =
+                    { [BitArray::<u64, KEY_BIT_ARRAY_LEN>::new(); MAX_NUM_BUCKETS] };
+
+                let mut i = 0;
+                while i < DATA_LEN {
+                    let hash = l1_hasher.hash_const(&data[i].0) as usize;
+                    bucket_to_keys[hash].set(i);
+                    i += 1;
+                }
+
+                let mut max_keys_per_bucket: usize = 0;
+                let mut i = 0;
+                while i < num_buckets {
+                    let num_keys = bucket_to_keys[i].count_ones();
+                    if num_keys > max_keys_per_bucket {
+                        max_keys_per_bucket = num_keys;
+                    }
+                    i += 1;
+                }
+
+                if max_keys_per_bucket <= MAX_KEYS_PER_BUCKET {
+                    return Some((l1_hasher, bucket_to_keys));
+                }
+
+                trial_idx += 1;
+            }
+
+            None
+        }
+
+        /// Attempt to find the L2 hash function for the given bucket.
+        ///
+        /// # Parameters
+        ///
+        /// - `rng`: A random number generator.
+        /// - `bucket_idx`: The global index of the bucket in the L1 table.
+        /// - `current_offset`: The current global offset of the bucket.
+        /// - `data`: The complete input data.
+        /// - `bucket_to_keys`: An array of bit-arrays where each bit-array is a bucket
+        ///                     and each bit is a key in the input data.
+        /// - `num_trials`: The maximum number of trials to find the hash function.
+        /// - `DATA_LEN`: The length of the input dataset.
+        /// - `MAX_NUM_BUCKETS`: The maximum possible number of buckets under the minimum load
+        ///                      factor.
+        const fn try_resolve_bucket<
+            const DATA_LEN: usize,
+            const MAX_NUM_BUCKETS: usize,
+            const KEY_BIT_ARRAY_LEN: usize,
+        >(
+            rng: &mut XorShift<u64>,
+            bucket_idx: usize,
+            current_offset: usize,
+            data: &[($K, $V); DATA_LEN],
+            bucket_to_keys: &[BitArray<u64, KEY_BIT_ARRAY_LEN>; MAX_NUM_BUCKETS],
+            num_trials: usize,
+        ) -> Option<ConstBucket> {
+            let keys = &bucket_to_keys[bucket_idx];
+            let num_keys: usize = keys.count_ones();
+
+            if num_keys == 0 {
+                return Some(EMPTY_BUCKET);
+            }
+
+            let mut trial_idx = 0;
+            while trial_idx < num_trials {
+                let seed = rng.next();
+                let l2_hasher = <$HasherType>::from_seed_const(seed, num_keys as u32);
+                // At most 16 - checked at compile time by `check_num_slots_per_bucket_const()`.
+                let num_slots = l2_hasher.num_buckets_const() as u8;
+
+                let mut slots: Bits<u16> = Bits::<u16>::new();
+                let mut is_collision = false;
+                let mut iter = keys.iter_ones_const();
+
+                while let Some(key_idx) = iter.next() {
+                    let hash = l2_hasher.hash_const(&data[key_idx].0) as usize;
+
+                    if slots.get(hash).unwrap() {
+                        is_collision = true;
+                        break;
+                    }
+                    slots.set(hash);
+                }
+
+                if !is_collision {
+                    return Some(ConstBucket {
+                        offset: current_offset,
+                        slots: slots.value(),
+                        num_slots,
+                        hasher: l2_hasher,
+                        hasher_state: <$HasherType>::make_state_const(seed, num_keys as u32),
+                    });
+                }
+
+                trial_idx += 1;
+            }
+
+            None
+        }
+
+        /// Attempts to resolve the level-1 hash function and the per-bucket level-2 hash functions.
+        const fn try_resolve<const MAX_NUM_BUCKETS: usize, const KEY_BIT_ARRAY_LEN: usize>(
+            data: &[($K, $V); DATA_LEN],
+            seed: u64,
+            min_load_factor: f32,
+        ) -> Option<ResolveResult<MAX_NUM_BUCKETS>> {
+            let mut rng = XorShift::<u64>::new(seed);
+
+            let mut load_factor = 1.0;
+            let mut l1_result = None;
+
+            const MAX_L1_TRIALS: usize = 999;
+
+            while load_factor >= min_load_factor {
+                l1_result = try_resolve_l1::<DATA_LEN, MAX_NUM_BUCKETS, KEY_BIT_ARRAY_LEN>(
+                    &mut rng,
+                    load_factor,
+                    MAX_L1_TRIALS,
+                    data,
+                );
+
+                if l1_result.is_some() {
+                    break;
+                }
+
+                load_factor -= 0.05;
+            }
+
+            let (l1_hasher, bucket_to_keys) = match l1_result {
+                Some(result) => result,
+                None => return None,
+            };
+
+            // Only the first `num_buckets` buckets are initialized by the loop below - the rest are
+            // never read.
+            let mut buckets: [MaybeUninit<ConstBucket>; MAX_NUM_BUCKETS] =
+                { unsafe { MaybeUninit::uninit().assume_init() } };
+
+            let num_buckets = l1_hasher.num_buckets_const() as usize;
+            let mut current_offset = 0;
+            let mut bucket_idx = 0;
+
+            const MAX_L2_TRIALS: usize = 999;
+
+            while bucket_idx < num_buckets {
+                let bucket = try_resolve_bucket::<DATA_LEN, MAX_NUM_BUCKETS, KEY_BIT_ARRAY_LEN>(
+                    &mut rng,
+                    bucket_idx,
+                    current_offset,
+                    data,
+                    &bucket_to_keys,
+                    MAX_L2_TRIALS,
+                );
+                if bucket.is_none() {
+                    return None;
+                }
+                let bucket = bucket.unwrap();
+
+                current_offset += bucket.num_slots as usize;
+                buckets[bucket_idx] = MaybeUninit::new(bucket);
+                bucket_idx += 1;
+            }
+
+            Some(ResolveResult {
+                num_slots: current_offset,
+                num_buckets,
+                l1_hasher,
+                buckets,
+            })
+        }
+
+        /// Attempts to resolve the hash functions with each of the seeds in order.
+        const fn try_resolve_seeded<
+            const MAX_NUM_BUCKETS: usize,
+            const KEY_BIT_ARRAY_LEN: usize,
+        >(
+            data: &[($K, $V); DATA_LEN],
+            seeds: &[u64],
+            min_load_factor: f32,
+        ) -> Option<ResolveResult<MAX_NUM_BUCKETS>> {
+            let mut i = 0;
+            while i < seeds.len() {
+                let result = try_resolve::<MAX_NUM_BUCKETS, KEY_BIT_ARRAY_LEN>(
+                    data,
+                    seeds[i],
+                    min_load_factor,
+                );
+                if result.is_some() {
+                    return result;
+                }
+                i += 1;
+            }
+
+            None
+        }
+    };
+    (@storage static, $K:ty, $V:ty, $HasherType:ty) => {
+        {
             static mut BUCKETS: [Bucket<$K, $HasherType>; BUILD_RESULT.buckets.len()] =
                 { BUILD_RESULT.buckets };
             static mut SLOTS: [MaybeUninit<($K, $V)>; BUILD_RESULT.slots.len()] =
@@ -406,19 +623,193 @@ macro_rules! new_fks_map {
                 l1_hasher: BUILD_RESULT.l1_hasher,
                 buckets: MaybeOwnedSliceMut::Borrowed(unsafe { &mut BUCKETS }),
                 slots: MaybeOwnedSliceMut::Borrowed(unsafe { &mut SLOTS }),
+                len: DATA_LEN,
+                is_compact: false,
+                min_load_factor: MIN_LOAD_FACTOR,
             }
-        };
+        }
+    };
+    (@storage const, $K:ty, $V:ty, $HasherType:ty) => {
+        {
+            const BUCKETS: &[Bucket<$K, $HasherType>] = &BUILD_RESULT.buckets;
+            const SLOTS: &[MaybeUninit<($K, $V)>] = &BUILD_RESULT.slots;
+
+            FKSMap::<'static, $K, $V, $HasherType> {
+                l1_hasher: BUILD_RESULT.l1_hasher,
+                buckets: MaybeOwnedSliceMut::Shared(BUCKETS),
+                slots: MaybeOwnedSliceMut::Shared(SLOTS),
+                len: DATA_LEN,
+                is_compact: false,
+                min_load_factor: MIN_LOAD_FACTOR,
+            }
+        }
+    };
+}
+
+/// Alternative compile-time constructor capable of creating `const` [`FKSMap`] instances.
+///
+/// Works like [`new_fks_map!`], but the resulting map is a `const` item referencing its memory
+/// immutably - so, unlike a `static` map, it can be referenced from other const expressions.
+///
+/// # Parameters
+///
+/// - `name`: The name of the resulting const item.
+/// - `data`: The data to be hashed.
+/// - `hasher_type`: Hasher type that should be used to hash the keys.
+/// - `seed`: The seed for the random number generator.
+/// - `min_load_factor`: The minimum load factor.
+/// - `max_keys_per_bucket`: Optional, the maximum number of keys hashing into one L1 bucket, `5`
+///                          by default.
+///
+/// # Examples
+///
+/// ```rust
+/// use o1_core::HashMap;
+/// use o1::fks::FKSMap;
+/// use o1::hashing::hashers::msp::MSPHasher;
+/// use o1::const_fks_map;
+///
+/// const_fks_map!(HTTP_STATUSES, u16, &'static str, [
+///     (200, "OK"),
+///     (404, "Not Found"),
+///     (500, "Internal Server Error"),
+/// ], MSPHasher<u16>, 42, 0.75);
+///
+/// const STATUSES: &FKSMap<'static, u16, &'static str, MSPHasher<u16>> = &HTTP_STATUSES;
+///
+/// assert_eq!(STATUSES.get(&404), Some(&"Not Found"));
+/// assert_eq!(HTTP_STATUSES.get(&418), None);
+/// ```
+///
+/// # Panics
+///
+/// - Fails to compile under the same conditions as [`new_fks_map!`].
+/// - The methods that mutate the map - [`FKSMap::iter_mut`], [`FKSMap::values_mut`],
+///   [`FKSMap::get_many_mut`] and [`FKSMap::compact`] - panic, since its memory is immutable.
+///   Copying the const item into a mutable binding doesn't change that, clone the map instead.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_fks_map {
+    ($name:ident, $K:ty, $V:ty, $data:expr, $HasherType:ty, $seed:expr, $min_load_factor:expr$(,)?) => {
+        $crate::const_fks_map!(
+            $name,
+            $K,
+            $V,
+            $data,
+            $HasherType,
+            $seed,
+            $min_load_factor,
+            5
+        );
+    };
+    (
+        $name:ident,
+        $K:ty,
+        $V:ty,
+        $data:expr,
+        $HasherType:ty,
+        $seed:expr,
+        $min_load_factor:expr,
+        $max_keys_per_bucket:expr$(,)?
+    ) => {
+        const $name: $crate::fks::FKSMap<'static, $K, $V, $HasherType> = $crate::new_fks_map_seeded!(
+            @build const,
+            $K,
+            $V,
+            $data,
+            $HasherType,
+            &[$seed],
+            $min_load_factor,
+            $max_keys_per_bucket
+        );
     };
 }
 
+/// Checks at compile time whether the hash functions of a [`FKSMap`] can be resolved for the
+/// data and the seed.
+///
+/// Runs only the resolution step of [`new_fks_map!`] - without building the map - and evaluates to
+/// `true` if it succeeds, so that a pinned seed could be validated with `const { assert!(...) }`
+/// (e.g. next to a hand-authored table) and the failure is reported by a readable message.
+///
+/// # Parameters
+///
+/// - `data`: The data to be hashed.
+/// - `key_type`: The type of the keys.
+/// - `value_type`: The type of the values.
+/// - `hasher_type`: Hasher type that should be used to hash the keys.
+/// - `seed`: The seed for the random number generator.
+/// - `min_load_factor`: The minimum load factor.
+/// - `max_keys_per_bucket`: Optional, the maximum number of keys hashing into one L1 bucket, `5`
+///                          by default.
+///
+/// # Examples
+///
+/// ```rust
+/// use o1::hashing::hashers::msp::MSPHasher;
+/// use o1::check_perfect;
+///
+/// const DATA: [(u32, &str); 3] = [(1, "one"), (2, "two"), (3, "three")];
+///
+/// const _: () = assert!(
+///     check_perfect!(DATA, u32, &'static str, MSPHasher<u32>, 42, 0.75),
+///     "Seed 42 doesn't fit DATA"
+/// );
+/// ```
+///
+/// # Panics
+///
+/// - Fails to compile if the configuration is invalid - under the same conditions as
+///   [`new_fks_map!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! check_perfect {
+    ($data:expr, $K:ty, $V:ty, $HasherType:ty, $seed:expr, $min_load_factor:expr$(,)?) => {
+        $crate::check_perfect!($data, $K, $V, $HasherType, $seed, $min_load_factor, 5)
+    };
+    (
+        $data:expr,
+        $K:ty,
+        $V:ty,
+        $HasherType:ty,
+        $seed:expr,
+        $min_load_factor:expr,
+        $max_keys_per_bucket:expr$(,)?
+    ) => {{
+        $crate::new_fks_map_seeded!(
+            @resolve $K,
+            $V,
+            $data,
+            $HasherType,
+            $min_load_factor,
+            $max_keys_per_bucket
+        );
+
+        const IS_PERFECT: bool = try_resolve_seeded::<MAX_NUM_BUCKETS, KEY_BIT_ARRAY_LEN>(
+            DATA_REF,
+            &[$seed],
+            $min_load_factor,
+        )
+        .is_some();
+
+        IS_PERFECT
+    }};
+}
+
 #[allow(unused_imports)]
 pub use new_fks_map as new_const;
+#[allow(unused_imports)]
+pub use new_fks_map_seeded as new_const_seeded;
 
 #[cfg(test)]
 mod tests {
     #![allow(long_running_const_eval)]
+    use crate::fks::FKSMap;
     use crate::hashing::hashers::msp::MSPHasher;
-    use crate::new_fks_map;
+    use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+    use core::num::NonZeroU32;
+    use core::ops::Range;
+    use core::time::Duration;
     use o1_core::HashMap;
     use o1_test::data::*;
     use o1_test::generate_static_map_tests;
@@ -448,4 +839,470 @@ mod tests {
         I32_MAP, I32_DATA, U64_MAP, U64_DATA, I64_MAP, I64_DATA, U128_MAP, U128_DATA, I128_MAP,
         I128_DATA, STR_MAP, STR_DATA,
     );
+
+    const IPV4_DATA: [(Ipv4Addr, &str); 6] = [
+        (Ipv4Addr::new(1, 1, 1, 1), "cloudflare"),
+        (Ipv4Addr::new(8, 8, 8, 8), "google"),
+        (Ipv4Addr::new(9, 9, 9, 9), "quad9"),
+        (Ipv4Addr::new(208, 67, 222, 222), "opendns"),
+        (Ipv4Addr::LOCALHOST, "localhost"),
+        (Ipv4Addr::BROADCAST, "broadcast"),
+    ];
+    new_fks_map!(
+        IPV4_MAP,
+        Ipv4Addr,
+        &'static str,
+        IPV4_DATA,
+        MSPHasher<Ipv4Addr>,
+        42,
+        0.75,
+    );
+
+    #[test]
+    fn test_static_map_ipv4_addr() {
+        for (key, val) in &IPV4_DATA {
+            assert_eq!(IPV4_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(IPV4_MAP.get(&Ipv4Addr::new(10, 0, 0, 1)), None);
+    }
+
+    const SOCKET_ADDR_DATA: [(SocketAddr, &str); 6] = [
+        (
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(1, 1, 1, 1), 53)),
+            "cloudflare dns",
+        ),
+        (
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(8, 8, 8, 8), 53)),
+            "google dns",
+        ),
+        (
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(8, 8, 8, 8), 443)),
+            "google dns over https",
+        ),
+        (
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080)),
+            "local http",
+        ),
+        (
+            SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0)),
+            "local http v6",
+        ),
+        (
+            SocketAddr::V6(SocketAddrV6::new(
+                Ipv6Addr::new(0x2606, 0x4700, 0x4700, 0, 0, 0, 0, 0x1111),
+                53,
+                0,
+                0,
+            )),
+            "cloudflare dns v6",
+        ),
+    ];
+    new_fks_map!(
+        SOCKET_ADDR_MAP,
+        SocketAddr,
+        &'static str,
+        SOCKET_ADDR_DATA,
+        MSPHasher<SocketAddr>,
+        42,
+        0.75,
+    );
+
+    #[test]
+    fn test_static_map_socket_addr() {
+        for (key, val) in &SOCKET_ADDR_DATA {
+            assert_eq!(SOCKET_ADDR_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(
+            SOCKET_ADDR_MAP.get(&SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::new(1, 1, 1, 1),
+                443
+            ))),
+            None
+        );
+        assert_eq!(
+            SOCKET_ADDR_MAP.get(&SocketAddr::V6(SocketAddrV6::new(
+                Ipv6Addr::LOCALHOST,
+                8080,
+                0,
+                1
+            ))),
+            None
+        );
+    }
+
+    const RANGE_DATA: [(Range<u32>, &str); 5] = [
+        (0..10, "first"),
+        (10..20, "second"),
+        (20..20, "empty"),
+        (0..u32::MAX, "everything"),
+        (1000..1_000_000, "wide"),
+    ];
+    new_fks_map!(
+        RANGE_MAP,
+        Range<u32>,
+        &'static str,
+        RANGE_DATA,
+        MSPHasher<Range<u32>>,
+        42,
+        0.75,
+    );
+
+    #[test]
+    fn test_static_map_range() {
+        for (key, val) in &RANGE_DATA {
+            assert_eq!(RANGE_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(RANGE_MAP.get(&(0..20)), None);
+        assert_eq!(RANGE_MAP.get(&(10..10)), None);
+    }
+
+    const NON_ZERO_U32_DATA: [(NonZeroU32, &str); 6] = [
+        (NonZeroU32::MIN, "first"),
+        (NonZeroU32::new(2).unwrap(), "second"),
+        (NonZeroU32::new(1000).unwrap(), "thousandth"),
+        (NonZeroU32::new(65_536).unwrap(), "power of two"),
+        (NonZeroU32::new(123_456_789).unwrap(), "large"),
+        (NonZeroU32::MAX, "last"),
+    ];
+    new_fks_map!(
+        NON_ZERO_U32_MAP,
+        NonZeroU32,
+        &'static str,
+        NON_ZERO_U32_DATA,
+        MSPHasher<NonZeroU32>,
+        42,
+        0.75,
+    );
+
+    #[test]
+    fn test_static_map_non_zero_u32() {
+        for (key, val) in &NON_ZERO_U32_DATA {
+            assert_eq!(NON_ZERO_U32_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(NON_ZERO_U32_MAP.get(&NonZeroU32::new(3).unwrap()), None);
+    }
+
+    new_fks_map_seeded!(
+        U64_SEEDED_MAP,
+        u64,
+        u64,
+        U64_DATA,
+        MSPHasher<u64>,
+        &[42],
+        0.75,
+    );
+
+    #[test]
+    fn test_static_map_seeded() {
+        for (key, val) in &U64_DATA {
+            assert_eq!(U64_SEEDED_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(U64_SEEDED_MAP, U64_MAP);
+    }
+
+    // With the seed the first L1 hash function puts 6 keys into a bucket - it's rejected under the
+    // default cap of 5 keys, but accepted under the higher one.
+    new_fks_map!(
+        U64_WIDE_BUCKETS_MAP,
+        u64,
+        u64,
+        U64_DATA,
+        MSPHasher<u64>,
+        5,
+        1.0,
+        12,
+    );
+
+    #[test]
+    fn test_static_map_max_keys_per_bucket() {
+        for (key, val) in &U64_DATA {
+            assert_eq!(U64_WIDE_BUCKETS_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(U64_WIDE_BUCKETS_MAP.iter().count(), U64_DATA.len());
+        assert!(U64_WIDE_BUCKETS_MAP
+            .buckets
+            .iter()
+            .any(|bucket| bucket.slots.count_ones() > 5));
+    }
+
+    const DURATION_DATA: [(Duration, &str); 6] = [
+        (Duration::ZERO, "zero"),
+        (Duration::from_nanos(1), "nanosecond"),
+        (Duration::from_millis(1500), "one and a half seconds"),
+        (Duration::new(1, 500), "one second and 500 nanoseconds"),
+        (Duration::from_secs(3600), "hour"),
+        (Duration::MAX, "max"),
+    ];
+    new_fks_map!(
+        DURATION_MAP,
+        Duration,
+        &'static str,
+        DURATION_DATA,
+        MSPHasher<Duration>,
+        42,
+        0.75,
+    );
+
+    #[test]
+    fn test_static_map_duration() {
+        for (key, val) in &DURATION_DATA {
+            assert_eq!(DURATION_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(DURATION_MAP.get(&Duration::from_secs(1)), None);
+    }
+
+    new_fks_map!(EMPTY_MAP, u32, u64, [], MSPHasher<u32>, 42, 0.75);
+
+    #[test]
+    fn test_static_map_empty() {
+        assert!(EMPTY_MAP.is_empty());
+        assert_eq!(EMPTY_MAP.iter().count(), 0);
+        for key in 0..1000_u32 {
+            assert_eq!(EMPTY_MAP.get(&key), None);
+        }
+    }
+
+    /// The number of buckets derived from the number of entries and the load factor isn't
+    /// a power of two here.
+    const FIVE_DATA: [(u32, u32); 5] = [(1, 10), (2, 20), (3, 30), (4, 40), (5, 50)];
+    new_fks_map!(FIVE_MAP, u32, u32, FIVE_DATA, MSPHasher<u32>, 42, 0.75);
+
+    #[test]
+    fn test_static_map_non_power_of_two_buckets() {
+        for (key, val) in &FIVE_DATA {
+            assert_eq!(FIVE_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(FIVE_MAP.get(&6), None);
+    }
+
+    /// More entries than the fixed-point arithmetic calculating the number of buckets used to
+    /// support.
+    const fn large_data() -> [(u64, u64); 3000] {
+        let mut data = [(0, 0); 3000];
+        let mut i = 0;
+        while i < data.len() {
+            data[i] = (i as u64 * 7919, i as u64);
+            i += 1;
+        }
+        data
+    }
+    new_fks_map!(LARGE_MAP, u64, u64, large_data(), MSPHasher<u64>, 42, 0.75);
+
+    #[test]
+    fn test_static_map_large() {
+        assert!(LARGE_MAP.verify());
+        assert_eq!(LARGE_MAP.len(), 3000);
+        for (key, val) in &large_data() {
+            assert_eq!(LARGE_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(LARGE_MAP.get(&1), None);
+    }
+
+    /// Makes a string literal of 300 bytes ending with the given suffix.
+    macro_rules! long_key {
+        ($suffix:literal) => {
+            concat!(
+                "o1::fks::ctors::new_const::tests::a_very_long_identifier_that_exceeds_the_length",
+                "_threshold_of_the_vector_hashing_and_hence_is_hashed_by_the_polynomial_hash_func",
+                "tion_which_splits_its_input_into_the_chunks_of_256_bytes_and_combines_the_hashes",
+                "_of_the_chunks_with_horner_rule_",
+                $suffix,
+            )
+        };
+    }
+
+    const LONG_STR_DATA: [(&str, u64); 6] = [
+        (long_key!("1"), 1),
+        (long_key!("2"), 2),
+        (long_key!("3"), 3),
+        (long_key!("10"), 10),
+        (long_key!("20"), 20),
+        (
+            concat!(
+                long_key!("0"),
+                long_key!("0"),
+                long_key!("spans_three_chunks")
+            ),
+            0,
+        ),
+    ];
+    new_fks_map!(
+        LONG_STR_MAP,
+        &'static str,
+        u64,
+        LONG_STR_DATA,
+        MSPHasher<&'static str>,
+        42,
+        0.75,
+    );
+
+    #[test]
+    fn test_static_map_long_str() {
+        for (key, val) in &LONG_STR_DATA {
+            assert!(key.len() > 256);
+            assert_eq!(LONG_STR_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(LONG_STR_MAP.get(&long_key!("4")), None);
+    }
+
+    // The low minimum load factor makes `MAX_NUM_BUCKETS` about ten times larger than the number
+    // of buckets actually used, and seeding the string hashers is expensive - initializing all of
+    // the intermediate buckets used to dominate the const-evaluation of the map.
+    new_fks_map!(
+        STR_LOW_LOAD_FACTOR_MAP,
+        &'static str,
+        u64,
+        STR_DATA,
+        MSPHasher<&'static str>,
+        42,
+        0.1,
+    );
+
+    #[test]
+    fn test_static_map_low_min_load_factor() {
+        for (key, val) in &STR_DATA {
+            assert_eq!(
+                STR_LOW_LOAD_FACTOR_MAP.get(key),
+                Some(val),
+                "Key: {:?}",
+                key
+            );
+        }
+        assert_eq!(STR_LOW_LOAD_FACTOR_MAP.get(&"missing"), None);
+        assert!(STR_LOW_LOAD_FACTOR_MAP.buckets.len() < 1024);
+    }
+
+    const_fks_map!(U64_CONST_MAP, u64, u64, U64_DATA, MSPHasher<u64>, 42, 0.75);
+    const_fks_map!(
+        STR_CONST_MAP,
+        &'static str,
+        u64,
+        STR_DATA,
+        MSPHasher<&'static str>,
+        42,
+        0.75,
+    );
+
+    /// The const map can be referenced from other const items.
+    const U64_CONST_MAP_REF: &FKSMap<'static, u64, u64, MSPHasher<u64>> = &U64_CONST_MAP;
+    const U64_CONST_MAP_NUM_BUCKETS: usize = U64_CONST_MAP_REF.buckets.as_slice().len();
+
+    #[test]
+    fn test_const_map() {
+        for (key, val) in &U64_DATA {
+            assert_eq!(U64_CONST_MAP_REF.get(key), Some(val), "Key: {:?}", key);
+            assert_eq!(U64_CONST_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        for (key, val) in &STR_DATA {
+            assert_eq!(STR_CONST_MAP.get(key), Some(val), "Key: {:?}", key);
+        }
+        assert_eq!(U64_CONST_MAP.get(&u64::MAX), None);
+        assert!(U64_CONST_MAP_REF.verify());
+        assert!(U64_CONST_MAP_REF.slots.is_shared());
+        assert_eq!(U64_CONST_MAP_NUM_BUCKETS, U64_MAP.buckets.len());
+    }
+
+    /// Copying a const map into a mutable binding doesn't make its memory mutable.
+    #[test]
+    #[should_panic(expected = "Cannot borrow a shared slice as mutable.")]
+    fn test_const_map_mut_panics() {
+        let mut map = U64_CONST_MAP;
+        let [value] = map.get_many_mut([&U64_DATA[0].0]).unwrap();
+        *value += 1;
+    }
+
+    /// Cloning a const map produces a map owning its memory.
+    #[test]
+    fn test_const_map_clone() {
+        let mut map = U64_CONST_MAP_REF.clone();
+        assert!(map.slots.is_owned() && map.buckets.is_owned());
+        for (key, val) in &U64_DATA {
+            let [value] = map.get_many_mut([key]).unwrap();
+            *value = val + 1;
+        }
+        for (key, val) in &U64_DATA {
+            assert_eq!(map.get(key), Some(&(val + 1)), "Key: {:?}", key);
+        }
+    }
+
+    /// With one key per bucket and no slack the keys fit only if the L1 hash function has no
+    /// collisions, which takes about a thousand trials on average - so some seeds run out of
+    /// them.
+    const NO_COLLISIONS_DATA: [(u32, u32); 13] = [
+        (2675342405, 0),
+        (1097127993, 1),
+        (3185950873, 2),
+        (1539898300, 3),
+        (3415330359, 4),
+        (2965446622, 5),
+        (4047793130, 6),
+        (3614262064, 7),
+        (3177840169, 8),
+        (2800454814, 9),
+        (3961355680, 10),
+        (2276503845, 11),
+        (124576495, 12),
+    ];
+
+    /// The duplicate keys always collide in the L2 tables, so no seed works.
+    const DUPLICATE_KEYS_DATA: [(u32, u32); 4] = [(1, 10), (2, 20), (3, 30), (2, 40)];
+
+    #[test]
+    fn test_check_perfect() {
+        const { assert!(check_perfect!(U64_DATA, u64, u64, MSPHasher<u64>, 42, 0.75)) };
+        const {
+            assert!(check_perfect!(
+                STR_DATA,
+                &'static str,
+                u64,
+                MSPHasher<&'static str>,
+                42,
+                0.75
+            ))
+        };
+        const {
+            assert!(check_perfect!(
+                U64_DATA,
+                u64,
+                u64,
+                MSPHasher<u64>,
+                5,
+                1.0,
+                12
+            ))
+        };
+        // A valid data set with a pinned seed that doesn't fit it, while the next one does.
+        const {
+            assert!(!check_perfect!(
+                NO_COLLISIONS_DATA,
+                u32,
+                u32,
+                MSPHasher<u32>,
+                1,
+                1.0,
+                1
+            ))
+        };
+        const {
+            assert!(check_perfect!(
+                NO_COLLISIONS_DATA,
+                u32,
+                u32,
+                MSPHasher<u32>,
+                2,
+                1.0,
+                1
+            ))
+        };
+        const {
+            assert!(!check_perfect!(
+                DUPLICATE_KEYS_DATA,
+                u32,
+                u32,
+                MSPHasher<u32>,
+                42,
+                0.75
+            ))
+        };
+    }
 }
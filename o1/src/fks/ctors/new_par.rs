@@ -0,0 +1,181 @@
+//! Implements a parallel runtime constructor for [`FKSMap`].
+use super::new::TrialBudget;
+use crate::fks::core::Bucket;
+use crate::fks::FKSMap;
+use bitvec::prelude::*;
+use o1_core::Hasher;
+use o1_core::O1Error;
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::fmt::Debug;
+use std::mem::MaybeUninit;
+use std::ops::Range;
+use std::thread;
+
+impl<K, V, H> FKSMap<'_, K, V, H>
+where
+    K: Eq + Debug + Send + Sync,
+    V: Sync,
+    H: Hasher<K> + Send,
+{
+    /// Makes a random number generator for the given bucket.
+    ///
+    /// The generator depends only on `l2_seed` and `bucket_idx`, which makes the results
+    /// independent of how the buckets are distributed between the threads.
+    fn bucket_rng(l2_seed: u64, bucket_idx: usize) -> Xoshiro256PlusPlus {
+        Xoshiro256PlusPlus::seed_from_u64(l2_seed.wrapping_add(bucket_idx as u64))
+    }
+
+    /// Resolves L2 hash functions for a contiguous range of buckets.
+    ///
+    /// Offsets of the resolved buckets are relative to the first bucket of the range.
+    fn try_resolve_bucket_range(
+        l2_seed: u64,
+        bucket_range: Range<usize>,
+        data: &[(K, V)],
+        bucket_to_keys: &[BitVec],
+    ) -> Result<Vec<Bucket<K, H>>, O1Error> {
+        let mut buckets = Vec::with_capacity(bucket_range.len());
+        let mut current_offset: usize = 0;
+
+        for bucket_idx in bucket_range {
+            let resolved_bucket = Self::try_resolve_bucket(
+                &mut Self::bucket_rng(l2_seed, bucket_idx),
+                bucket_idx,
+                current_offset,
+                data,
+                bucket_to_keys,
+                Self::MAX_L2_TRIALS,
+                &mut TrialBudget::unlimited(),
+            )?;
+
+            current_offset += resolved_bucket.num_slots();
+            buckets.push(resolved_bucket);
+        }
+
+        Ok(buckets)
+    }
+
+    /// Creates a new [`FKSMap`] with the given data, seed, and minimum load factor resolving
+    /// the L2 hash functions on multiple threads.
+    ///
+    /// The resulting map is fully determined by `data` and `seed` and doesn't depend on the
+    /// number of threads, though it is not identical to the one built by [`FKSMap::new`].
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    /// - `min_load_factor`: The minimum load factor.
+    pub fn new_par(data: Box<[(K, V)]>, seed: u64, min_load_factor: f32) -> Result<Self, O1Error> {
+        Self::validate_config(data.len(), min_load_factor)?;
+
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+
+        let (l1_hasher, bucket_to_keys) = Self::resolve_l1(
+            &mut rng,
+            min_load_factor,
+            &data,
+            &mut TrialBudget::unlimited(),
+        )?;
+        let l2_seed = rng.next_u64();
+
+        let l1_num_buckets = l1_hasher.num_buckets() as usize;
+        let num_threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = l1_num_buckets.div_ceil(num_threads);
+
+        let chunks = thread::scope(|scope| {
+            let handles: Vec<_> = (0..l1_num_buckets)
+                .step_by(chunk_size)
+                .map(|start| {
+                    let bucket_range = start..(start + chunk_size).min(l1_num_buckets);
+                    let data = &data;
+                    let bucket_to_keys = &bucket_to_keys;
+                    scope.spawn(move || {
+                        Self::try_resolve_bucket_range(l2_seed, bucket_range, data, bucket_to_keys)
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("L2 resolution thread panicked"))
+                .collect::<Result<Vec<_>, O1Error>>()
+        })?;
+
+        let mut buckets = Vec::<Bucket<K, H>>::with_capacity(l1_num_buckets);
+        let mut current_offset: usize = 0;
+
+        for chunk in chunks {
+            let mut chunk_len: usize = 0;
+            for mut bucket in chunk {
+                chunk_len += bucket.num_slots();
+                if bucket.num_slots() > 0 {
+                    bucket.offset += current_offset;
+                }
+                buckets.push(bucket);
+            }
+            current_offset += chunk_len;
+        }
+
+        let mut slots = Vec::<MaybeUninit<(K, V)>>::with_capacity(current_offset);
+        unsafe { slots.set_len(slots.capacity()) };
+
+        let len = data.len();
+        Self::fill_slots(data, &buckets, &mut slots, &l1_hasher);
+
+        Ok(Self {
+            l1_hasher,
+            buckets: buckets.into(),
+            slots: slots.into(),
+            len,
+            is_compact: false,
+            min_load_factor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::*;
+    use o1_core::HashMap;
+    use o1_test::generate_map_tests;
+    use std::fmt::Debug;
+
+    fn factory<'a, K, V, H>(data: Box<[(K, V)]>) -> FKSMap<'a, K, V, H>
+    where
+        K: Eq + Debug + Send + Sync,
+        V: Copy + Debug + Sync,
+        H: Hasher<K> + Send,
+    {
+        FKSMap::new_par(data, 0, 0.75).unwrap()
+    }
+
+    generate_map_tests!(FKSMap, MSPHasher, factory);
+
+    #[test]
+    fn test_new_par_equivalent_to_new() {
+        let data: Box<[(u64, u64)]> = (0..10_000_u64).map(|i| (i * 7919, i)).collect();
+
+        let sequential = FKSMap::<u64, u64, MSPHasher<u64>>::new(data.clone(), 42, 0.75).unwrap();
+        let parallel = FKSMap::<u64, u64, MSPHasher<u64>>::new_par(data.clone(), 42, 0.75).unwrap();
+        let parallel_again =
+            FKSMap::<u64, u64, MSPHasher<u64>>::new_par(data.clone(), 42, 0.75).unwrap();
+
+        for (key, value) in data.iter() {
+            assert_eq!(sequential.get(key), Some(value));
+            assert_eq!(parallel.get(key), Some(value));
+        }
+        for key in (0..1000_u64).map(|i| i * 7919 + 1) {
+            assert_eq!(parallel.get(&key), sequential.get(&key));
+        }
+
+        assert_eq!(parallel.buckets.len(), parallel_again.buckets.len());
+        for (first, second) in parallel.buckets.iter().zip(parallel_again.buckets.iter()) {
+            assert_eq!(first.offset, second.offset);
+            assert_eq!(first.slots, second.slots);
+        }
+    }
+}
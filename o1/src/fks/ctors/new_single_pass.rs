@@ -0,0 +1,102 @@
+//! Implements a runtime constructor for [`FKSMap`] whose L1 and L2 hash functions share the
+//! digest of the key.
+use super::new::TrialBudget;
+use crate::fks::core::Bucket;
+use crate::fks::FKSMap;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::mem::MaybeUninit;
+use o1_core::{DigestHasher, O1Error};
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+impl<K: Eq + Debug, V, H: DigestHasher<K>> FKSMap<'_, K, V, H> {
+    /// Creates a new [`FKSMap`] with the given data, seed, and minimum load factor whose L2 hash
+    /// functions share the digest function of the L1 hash function.
+    ///
+    /// Lookups in the resulting map can compute the digest of the key once and derive both the
+    /// index of the bucket and the index of the slot from it - see [`FKSMap::get_single_pass`].
+    /// [`get()`](o1_core::HashMap::get) and the rest of the methods work as usual. Exposed
+    /// through [`SinglePassFKSMap::new`](crate::fks::SinglePassFKSMap::new).
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    /// - `min_load_factor`: The minimum load factor.
+    ///
+    /// # Errors
+    ///
+    /// The same as of [`FKSMap::new`].
+    pub(crate) fn new_single_pass(
+        data: Box<[(K, V)]>,
+        seed: u64,
+        min_load_factor: f32,
+    ) -> Result<Self, O1Error> {
+        Self::validate_config(data.len(), min_load_factor)?;
+
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        let mut budget = TrialBudget::unlimited();
+
+        let (l1_hasher, bucket_to_keys) =
+            Self::resolve_l1(&mut rng, min_load_factor, &data, &mut budget)?;
+
+        let l1_num_buckets = l1_hasher.num_buckets() as usize;
+        let mut buckets = Vec::<Bucket<K, H>>::with_capacity(l1_num_buckets);
+
+        let mut current_offset: usize = 0;
+
+        for keys in bucket_to_keys.iter() {
+            let resolved_bucket = Self::try_resolve_bucket_keys(
+                &mut |num_keys| l1_hasher.with_shared_digest(rng.next_u64(), num_keys),
+                current_offset,
+                &data,
+                keys.iter_ones(),
+                keys.count_ones(),
+                Self::MAX_L2_TRIALS,
+                &mut budget,
+            )?;
+
+            current_offset += resolved_bucket.num_slots();
+            buckets.push(resolved_bucket);
+        }
+        drop(bucket_to_keys);
+
+        let mut slots = Vec::<MaybeUninit<(K, V)>>::with_capacity(current_offset);
+        unsafe { slots.set_len(slots.capacity()) };
+
+        let len = data.len();
+        Self::fill_slots(data, &buckets, &mut slots, &l1_hasher);
+
+        Ok(Self {
+            l1_hasher,
+            buckets: buckets.into(),
+            slots: slots.into(),
+            len,
+            is_compact: false,
+            min_load_factor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::hashers::msp::*;
+    use o1_core::Hasher;
+
+    #[test]
+    fn test_buckets_share_digest() {
+        let data: Box<[(String, u32)]> = (0..1000_u32).map(|i| (i.to_string(), i)).collect();
+        let map = FKSMap::<String, u32, MSPHasherDigest<String>>::new_single_pass(data, 42, 0.75)
+            .unwrap();
+
+        assert!(map.verify());
+        for (key, _) in map.iter() {
+            let digest = map.l1_hasher.digest(key);
+            let bucket = &map.buckets[map.l1_hasher.hash(key) as usize];
+            assert_eq!(bucket.hasher.digest(key), digest, "Key: {key:?}");
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Implements a runtime constructor for [`FKSMap`] with memory usage linear in the number of
+//! entries.
+use super::new::TrialBudget;
+use crate::fks::core::Bucket;
+use crate::fks::FKSMap;
+use crate::utils::const_hacks::ceil_f32;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::mem::MaybeUninit;
+use o1_core::Hasher;
+use o1_core::O1Error;
+use o1_core::O1Error::UnableToFindHashFunction;
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// Assignment of the keys to the L1 buckets.
+struct BucketKeys {
+    /// The indices of the keys in the input data sorted by the buckets.
+    keys: Vec<u32>,
+    /// The position of the first key of each bucket in `keys`, followed by the number of keys.
+    starts: Vec<usize>,
+}
+
+impl BucketKeys {
+    /// The indices of the keys of the bucket in the input data.
+    fn bucket(&self, bucket_idx: usize) -> &[u32] {
+        &self.keys[self.starts[bucket_idx]..self.starts[bucket_idx + 1]]
+    }
+}
+
+impl<K: Eq + Debug, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// Attempts to find the L1 hash function counting the keys per bucket instead of recording
+    /// them.
+    ///
+    /// Consumes the random number generator in the same way as [`FKSMap::try_resolve_l1`].
+    ///
+    /// # Parameters
+    ///
+    /// - `rng`: A random number generator.
+    /// - `load_factor`: The desirable load factor.
+    /// - `num_trials`: The maximum number of trials to find the hash function.
+    /// - `data`: The data to be hashed.
+    /// - `bucket_indices`: A buffer for the L1 hash values of the keys.
+    fn try_resolve_l1_streaming(
+        rng: &mut Xoshiro256PlusPlus,
+        load_factor: f32,
+        num_trials: usize,
+        data: &[(K, V)],
+        bucket_indices: &mut [u32],
+    ) -> Result<(H, Vec<usize>), O1Error> {
+        for _ in 0..num_trials {
+            let l1_hasher = H::from_seed(
+                rng.next_u64(),
+                ceil_f32(data.len() as f32 / load_factor) as u32,
+            );
+            let num_buckets = l1_hasher.num_buckets() as usize;
+
+            // Shifted by one, so that the prefix sum turns the counts into the starts.
+            let mut counts = vec![0_usize; num_buckets + 1];
+            for ((k, _), bucket_idx) in data.iter().zip(bucket_indices.iter_mut()) {
+                *bucket_idx = l1_hasher.hash(k);
+                counts[*bucket_idx as usize + 1] += 1;
+            }
+
+            let max_keys_per_bucket = counts.iter().copied().max().unwrap_or(0);
+            if max_keys_per_bucket <= Self::MAX_KEYS_PER_BUCKET as usize {
+                return Ok((l1_hasher, counts));
+            }
+        }
+        Err(UnableToFindHashFunction)
+    }
+
+    /// Resolves the L1 hash function gradually lowering the load factor after each failure and
+    /// sorts the keys by the buckets with a counting sort.
+    ///
+    /// # Parameters
+    ///
+    /// - `rng`: A random number generator.
+    /// - `min_load_factor`: The minimum load factor.
+    /// - `data`: The data to be hashed.
+    fn resolve_l1_streaming(
+        rng: &mut Xoshiro256PlusPlus,
+        min_load_factor: f32,
+        data: &[(K, V)],
+    ) -> Result<(H, BucketKeys), O1Error> {
+        let mut bucket_indices = vec![0_u32; data.len()];
+        let mut load_factor = 1.0;
+
+        let (l1_hasher, mut starts) = loop {
+            if let Ok(l1_result) = Self::try_resolve_l1_streaming(
+                rng,
+                load_factor,
+                Self::MAX_L1_TRIALS,
+                data,
+                &mut bucket_indices,
+            ) {
+                break l1_result;
+            }
+            load_factor -= 0.05;
+
+            if load_factor < min_load_factor {
+                return Err(UnableToFindHashFunction);
+            }
+        };
+
+        for i in 1..starts.len() {
+            starts[i] += starts[i - 1];
+        }
+        let mut cursors = starts.clone();
+        let mut keys = vec![0_u32; data.len()];
+        for (key_idx, &bucket_idx) in bucket_indices.iter().enumerate() {
+            let cursor = &mut cursors[bucket_idx as usize];
+            keys[*cursor] = key_idx as u32;
+            *cursor += 1;
+        }
+
+        Ok((l1_hasher, BucketKeys { keys, starts }))
+    }
+
+    /// Creates a new [`FKSMap`] with the given data, seed, and minimum load factor using memory
+    /// linear in the number of entries.
+    ///
+    /// [`FKSMap::new`] records the keys of each L1 bucket in a bit-mask over all the keys, which
+    /// takes memory proportional to the number of buckets times the number of keys. This
+    /// constructor instead stores the L1 hash value of each key and sorts the keys by the buckets
+    /// with a counting sort - it is the one to use for large data sets.
+    ///
+    /// The resulting map is identical to the one built by [`FKSMap::new`] with the same `data`
+    /// and `seed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    /// - `min_load_factor`: The minimum load factor.
+    ///
+    /// # Errors
+    ///
+    /// The same as of [`FKSMap::new`].
+    pub fn new_streaming(
+        data: Box<[(K, V)]>,
+        seed: u64,
+        min_load_factor: f32,
+    ) -> Result<Self, O1Error> {
+        Self::validate_config(data.len(), min_load_factor)?;
+
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+
+        let (l1_hasher, bucket_keys) =
+            Self::resolve_l1_streaming(&mut rng, min_load_factor, &data)?;
+
+        let l1_num_buckets = l1_hasher.num_buckets() as usize;
+        let mut buckets = Vec::<Bucket<K, H>>::with_capacity(l1_num_buckets);
+
+        let mut current_offset: usize = 0;
+
+        for bucket_idx in 0..l1_num_buckets {
+            let keys = bucket_keys.bucket(bucket_idx);
+            let resolved_bucket = Self::try_resolve_bucket_keys(
+                &mut |num_keys| H::from_seed(rng.next_u64(), num_keys),
+                current_offset,
+                &data,
+                keys.iter().map(|&key_idx| key_idx as usize),
+                keys.len(),
+                Self::MAX_L2_TRIALS,
+                &mut TrialBudget::unlimited(),
+            )?;
+
+            current_offset += resolved_bucket.num_slots();
+            buckets.push(resolved_bucket);
+        }
+        drop(bucket_keys);
+
+        let mut slots = Vec::<MaybeUninit<(K, V)>>::with_capacity(current_offset);
+        unsafe { slots.set_len(slots.capacity()) };
+
+        let len = data.len();
+        Self::fill_slots(data, &buckets, &mut slots, &l1_hasher);
+
+        Ok(Self {
+            l1_hasher,
+            buckets: buckets.into(),
+            slots: slots.into(),
+            len,
+            is_compact: false,
+            min_load_factor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::*;
+    use o1_core::HashMap;
+    use o1_test::generate_map_tests;
+    use std::fmt::Debug;
+
+    fn factory<'a, K: Eq + Debug, V: Copy + Debug, H: Hasher<K>>(
+        data: Box<[(K, V)]>,
+    ) -> FKSMap<'a, K, V, H> {
+        FKSMap::new_streaming(data, 0, 0.75).unwrap()
+    }
+
+    generate_map_tests!(FKSMap, MSPHasher, factory);
+
+    #[test]
+    fn test_new_streaming_equivalent_to_new() {
+        let data: Box<[(u64, u64)]> = (0..10_000_u64).map(|i| (i * 7919, i)).collect();
+
+        let map = FKSMap::<u64, u64, MSPHasher<u64>>::new(data.clone(), 42, 0.75).unwrap();
+        let streaming =
+            FKSMap::<u64, u64, MSPHasher<u64>>::new_streaming(data.clone(), 42, 0.75).unwrap();
+
+        assert!(map.iter().eq(streaming.iter()));
+        assert_eq!(map.buckets.len(), streaming.buckets.len());
+        for (first, second) in map.buckets.iter().zip(streaming.buckets.iter()) {
+            assert_eq!(first.offset, second.offset);
+            assert_eq!(first.slots, second.slots);
+        }
+    }
+
+    #[test]
+    fn test_new_streaming_large() {
+        let data: Box<[(u64, u64)]> = (0..1_000_000_u64).map(|i| (i * 7919, i)).collect();
+
+        let map =
+            FKSMap::<u64, u64, MSPHasher<u64>>::new_streaming(data.clone(), 42, 0.75).unwrap();
+
+        assert!(map.verify());
+        for (key, value) in data.iter() {
+            assert_eq!(map.get(key), Some(value), "Key: {:?}", key);
+        }
+        for key in (0..1000_u64).map(|i| i * 7919 + 1) {
+            assert_eq!(map.get(&key), None);
+        }
+    }
+
+    #[test]
+    fn test_bucket_keys_are_sorted_by_buckets() {
+        let data: Box<[(u32, u32)]> = (0..1000_u32).map(|i| (i, i)).collect();
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+        let (l1_hasher, bucket_keys) =
+            FKSMap::<u32, u32, MSPHasher<u32>>::resolve_l1_streaming(&mut rng, 0.75, &data)
+                .unwrap();
+
+        let mut seen = vec![false; data.len()];
+        for bucket_idx in 0..l1_hasher.num_buckets() as usize {
+            for &key_idx in bucket_keys.bucket(bucket_idx) {
+                assert_eq!(
+                    l1_hasher.hash(&data[key_idx as usize].0) as usize,
+                    bucket_idx
+                );
+                seen[key_idx as usize] = true;
+            }
+        }
+        assert!(seen.into_iter().all(|seen| seen));
+    }
+}
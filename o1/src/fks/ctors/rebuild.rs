@@ -0,0 +1,194 @@
+//! Implements rebuilding of [`FKSMap`] with a modified set of entries.
+use crate::fks::FKSMap;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use bitvec::prelude::*;
+use core::fmt::Debug;
+use o1_core::Hasher;
+use o1_core::O1Error;
+
+impl<'a, K, V, H> FKSMap<'a, K, V, H>
+where
+    K: Eq + Debug + Clone,
+    V: Clone,
+    H: Hasher<K> + Clone,
+{
+    /// Rebuilds the map keeping only the entries for which `f` returns `true`.
+    ///
+    /// Since the map is immutable, the entries are moved out and a new map is constructed from
+    /// the survivors with [`FKSMap::new`] under the minimum load factor the map has been built
+    /// with. It takes `O(n)` time and reallocates the memory.
+    ///
+    /// The entries of a map built by the compile-time constructor are cloned out of it - see
+    /// [`FKSMap::into_iter`].
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: The predicate deciding which entries to keep.
+    /// - `seed`: The seed for the random number generator of the new map.
+    ///
+    /// # Errors
+    ///
+    /// The same as of [`FKSMap::new`].
+    pub fn retain<F: FnMut(&K, &V) -> bool>(self, f: F, seed: u64) -> Result<Self, O1Error> {
+        let min_load_factor = self.min_load_factor;
+        Self::new(self.retained_entries(f), seed, min_load_factor)
+    }
+
+    /// Moves the entries for which `f` returns `true` out of the map - see [`FKSMap::retain`].
+    pub(crate) fn retained_entries<F: FnMut(&K, &V) -> bool>(self, mut f: F) -> Box<[(K, V)]> {
+        self.into_iter().filter(|(k, v)| f(k, v)).collect()
+    }
+
+    /// Rebuilds the map with the entries of `extra` added to it.
+    ///
+    /// The entries of `extra` override the existing entries with equal keys. Like
+    /// [`FKSMap::retain`] it constructs a new map with [`FKSMap::new`] under the minimum load
+    /// factor the map has been built with, so it takes `O(n + m)` time and reallocates the memory.
+    ///
+    /// # Parameters
+    ///
+    /// - `extra`: The entries to add. Their keys must be distinct.
+    /// - `seed`: The seed for the random number generator of the new map.
+    ///
+    /// # Errors
+    ///
+    /// The same as of [`FKSMap::new`].
+    pub fn extended<I: IntoIterator<Item = (K, V)>>(
+        self,
+        extra: I,
+        seed: u64,
+    ) -> Result<Self, O1Error> {
+        let min_load_factor = self.min_load_factor;
+        Self::new(self.extended_entries(extra), seed, min_load_factor)
+    }
+
+    /// Moves the entries out of the map together with the entries of `extra` overriding the
+    /// ones with equal keys - see [`FKSMap::extended`].
+    pub(crate) fn extended_entries<I: IntoIterator<Item = (K, V)>>(
+        self,
+        extra: I,
+    ) -> Box<[(K, V)]> {
+        let extra: Vec<(K, V)> = extra.into_iter().collect();
+
+        let mut overridden = bitvec![0; self.slots.len()];
+        for (k, _) in extra.iter() {
+            if let Some(data_idx) = self.slot_idx(k) {
+                overridden.set(data_idx, true);
+            }
+        }
+        // `iter()` and `into_iter()` yield the entries in the same order.
+        let keep: BitVec = self
+            .iter()
+            .map(|(k, _)| {
+                self.slot_idx(k)
+                    .is_none_or(|data_idx| !overridden[data_idx])
+            })
+            .collect();
+
+        self.into_iter()
+            .zip(keep)
+            .filter_map(|(entry, keep)| keep.then_some(entry))
+            .chain(extra)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_core::HashMap;
+
+    #[test]
+    fn test_retain() {
+        let data: Box<[(u32, u32)]> = (0..1000_u32).map(|i| (i, i * 3)).collect();
+        let map = FKSMap::<u32, u32, MSPHasher<u32>>::new(data, 7, 0.75).unwrap();
+
+        let map = map.retain(|_, v| v % 2 == 0, 7).unwrap();
+
+        assert_eq!(map.iter().count(), 500);
+        for i in 0..1000_u32 {
+            let expected = (i % 2 == 0).then_some(i * 3);
+            assert_eq!(map.get(&i).copied(), expected, "Key: {:?}", i);
+        }
+    }
+
+    #[test]
+    fn test_retain_owned_keys() {
+        let data: Box<[(String, u32)]> = (0..100_u32).map(|i| (i.to_string(), i)).collect();
+        let map = FKSMap::<String, u32, MSPHasher<String>>::new(data, 7, 0.75).unwrap();
+
+        let map = map.retain(|k, _| k.ends_with('7'), 7).unwrap();
+
+        assert_eq!(map.iter().count(), 10);
+        assert_eq!(map.get(&String::from("57")), Some(&57));
+        assert_eq!(map.get(&String::from("56")), None);
+    }
+
+    #[test]
+    fn test_retain_none() {
+        let data: Box<[(String, u32)]> = (0..100_u32).map(|i| (i.to_string(), i)).collect();
+        let map = FKSMap::<String, u32, MSPHasher<String>>::new(data, 7, 0.75).unwrap();
+
+        let map = map.retain(|_, _| false, 7).unwrap();
+
+        assert!(map.is_empty());
+        assert_eq!(map.get(&String::from("1")), None);
+    }
+
+    #[test]
+    fn test_retain_reproduces_new() {
+        let data: Box<[(u32, u32)]> = (0..1000_u32).map(|i| (i, i * 3)).collect();
+        let kept: Box<[(u32, u32)]> = data.iter().copied().filter(|(k, _)| k % 3 != 0).collect();
+
+        for (seed, min_load_factor) in [(1, 0.5), (2, 0.75), (3, 0.9)] {
+            let map =
+                FKSMap::<u32, u32, MSPHasher<u32>>::new(data.clone(), 7, min_load_factor).unwrap();
+            let map = map.retain(|k, _| k % 3 != 0, seed).unwrap();
+            let expected =
+                FKSMap::<u32, u32, MSPHasher<u32>>::new(kept.clone(), seed, min_load_factor)
+                    .unwrap();
+
+            assert_eq!(map.min_load_factor, min_load_factor);
+            assert_eq!(map.buckets.len(), expected.buckets.len());
+            for (first, second) in map.buckets.iter().zip(expected.buckets.iter()) {
+                assert_eq!(first.offset, second.offset);
+                assert_eq!(first.slots, second.slots);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extended() {
+        let data: Box<[(u32, u32)]> = (0..100_u32).map(|i| (i, i)).collect();
+        let map = FKSMap::<u32, u32, MSPHasher<u32>>::new(data, 7, 0.75).unwrap();
+
+        // 10 keys override the existing entries and 10 keys are new.
+        let extra = (90..110_u32).map(|i| (i, i * 100));
+        let map = map.extended(extra, 7).unwrap();
+
+        assert_eq!(map.iter().count(), 110);
+        for i in 0..110_u32 {
+            let expected = if i < 90 { i } else { i * 100 };
+            assert_eq!(map.get(&i), Some(&expected), "Key: {:?}", i);
+        }
+        assert_eq!(map.get(&110), None);
+    }
+
+    #[test]
+    fn test_extended_drops_overridden_values() {
+        let data: Box<[(String, String)]> = (0..10_u32)
+            .map(|i| (i.to_string(), format!("old-{i}")))
+            .collect();
+        let map = FKSMap::<String, String, MSPHasher<String>>::new(data, 7, 0.75).unwrap();
+
+        let map = map
+            .extended([(String::from("3"), String::from("new-3"))], 7)
+            .unwrap();
+
+        assert_eq!(map.iter().count(), 10);
+        assert_eq!(map.get(&String::from("3")), Some(&String::from("new-3")));
+        assert_eq!(map.get(&String::from("4")), Some(&String::from("old-4")));
+    }
+}
@@ -0,0 +1,56 @@
+//! Implements [`Default`] for [`FKSMap`].
+use crate::fks::{Bucket, FKSMap};
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+use o1_core::Hasher;
+
+/// Creates an empty map.
+///
+/// The map has no slots, but it has the minimal number of empty buckets the L1 hasher
+/// addresses - so that lookups need no special handling of empty maps and always miss.
+impl<K: Eq, V, H: Hasher<K>> Default for FKSMap<'_, K, V, H> {
+    fn default() -> Self {
+        let l1_hasher = H::from_seed(0, 0);
+        let buckets: Vec<Bucket<K, H>> = (0..l1_hasher.num_buckets())
+            .map(|_| Bucket::default())
+            .collect();
+        let slots: Vec<MaybeUninit<(K, V)>> = Vec::new();
+
+        Self {
+            l1_hasher,
+            buckets: buckets.into(),
+            slots: slots.into(),
+            len: 0,
+            is_compact: false,
+            min_load_factor: Self::DEFAULT_MIN_LOAD_FACTOR,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_core::HashMap;
+
+    #[test]
+    fn test_default_is_empty() {
+        let map = FKSMap::<u32, u64, MSPHasher<u32>>::default();
+
+        assert!(map.is_empty());
+        assert_eq!(map.len(), 0);
+        assert_eq!(map.iter().count(), 0);
+        for key in 0..1000_u32 {
+            assert_eq!(map.get(&key), None);
+        }
+        assert_eq!(map.into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_default_string_keys() {
+        let map = FKSMap::<String, String, MSPHasher<String>>::default();
+
+        assert_eq!(map.get(&String::from("key")), None);
+        assert_eq!(map.clone(), map);
+    }
+}
@@ -20,8 +20,8 @@ impl<K: Eq, V, H: Hasher<K>> Drop for FKSMap<'_, K, V, H> {
                 continue;
             }
 
-            for slot_idx in bucket.slots.view_bits::<Lsb0>().iter_ones() {
-                let data_idx = bucket.offset + slot_idx;
+            for pos in bucket.slots.view_bits::<Lsb0>().iter_ones() {
+                let data_idx = bucket.slot_idx(pos, self.is_compact);
                 unsafe { self.slots[data_idx].assume_init_drop() };
             }
         }
@@ -0,0 +1,47 @@
+//! Implements [`PartialEq`] and [`Eq`] for [`FKSMap`].
+use crate::fks::FKSMap;
+use o1_core::Hasher;
+
+/// Maps are equal when they contain the same entries regardless of their internal layout.
+impl<'b, K: Eq, V: PartialEq, H: Hasher<K>> PartialEq<FKSMap<'b, K, V, H>> for FKSMap<'_, K, V, H> {
+    fn eq(&self, other: &FKSMap<'b, K, V, H>) -> bool {
+        // Number of entries rather than `len()`, since the latter depends on the slot layout.
+        self.iter().count() == other.iter().count()
+            && self.iter().all(|(k, v)| other.get_value(k) == Some(v))
+    }
+}
+
+impl<K: Eq, V: Eq, H: Hasher<K>> Eq for FKSMap<'_, K, V, H> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+
+    fn make_map(data: &[(u32, u32)], seed: u64) -> FKSMap<'static, u32, u32, MSPHasher<u32>> {
+        FKSMap::new(data.into(), seed, 0.75).unwrap()
+    }
+
+    #[test]
+    fn test_eq_different_seeds() {
+        let data: Vec<(u32, u32)> = (0..1000_u32).map(|i| (i * 7, i)).collect();
+        assert_eq!(make_map(&data, 1), make_map(&data, 2));
+    }
+
+    #[test]
+    fn test_ne_different_values() {
+        let data: Vec<(u32, u32)> = (0..1000_u32).map(|i| (i * 7, i)).collect();
+        let mut other_data = data.clone();
+        other_data[500].1 += 1;
+        assert_ne!(make_map(&data, 1), make_map(&other_data, 1));
+    }
+
+    #[test]
+    fn test_ne_different_keys() {
+        let data: Vec<(u32, u32)> = (0..1000_u32).map(|i| (i * 7, i)).collect();
+        assert_ne!(make_map(&data, 1), make_map(&data[..999], 1));
+        let mut other_data = data.clone();
+        other_data[500].0 = 1;
+        assert_ne!(make_map(&data, 1), make_map(&other_data, 1));
+    }
+}
@@ -1,48 +1,28 @@
 //! Implements [`HashMap`] for [`FKSMap`].
 use crate::fks::FKSMap;
-use bitvec::prelude::*;
-use bitvec::view::BitView;
+use core::fmt::Debug;
 use o1_core::{HashMap, Hasher};
-use std::fmt::Debug;
 
 impl<K: Eq + Debug, V, H: Hasher<K>> HashMap<K, V, H> for FKSMap<'_, K, V, H> {
     fn get(&self, key: &K) -> Option<&V> {
-        let bucket_idx = self.l1_hasher.hash(key) as usize;
-        let bucket = &self.buckets[bucket_idx];
-        let data_idx: usize = match bucket.num_slots() {
-            0 => return None,
-            1 => bucket.offset,
-            _ => {
-                let hash = bucket.hasher.hash(key);
-                let is_set = unsafe {
-                    bucket
-                        .slots
-                        .view_bits::<Lsb0>()
-                        .get(hash as usize)
-                        .unwrap_unchecked()
-                };
-                if !is_set {
-                    return None;
-                }
-                bucket.offset + hash as usize
-            }
-        };
-
-        let (k, v) = unsafe { &self.slots[data_idx].assume_init_ref() };
+        self.get_value(key)
+    }
 
-        if k == key {
-            Some(v)
-        } else {
-            None
-        }
+    fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        self.get_entry(key)
     }
 
     fn len(&self) -> usize {
-        self.slots.len()
+        self.len
     }
 
     fn is_empty(&self) -> bool {
-        self.slots.is_empty()
+        self.len == 0
+    }
+
+    /// The total number of slots including the unoccupied positions of the L2 tables.
+    fn capacity(&self) -> usize {
+        self.slots.len()
     }
 
     fn load_factor(&self) -> f64 {
@@ -62,3 +42,77 @@ impl<K: Eq + Debug, V, H: Hasher<K>> HashMap<K, V, H> for FKSMap<'_, K, V, H> {
             .sum()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::{FKSMap, InlineFKSMap};
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_core::HashMap;
+    use o1_test::data::{STR_DATA, U64_DATA};
+
+    #[test]
+    fn test_capacity_and_slack() {
+        let str_data: Box<[(&str, u64)]> = STR_DATA.into();
+        let u64_data: Box<[(u64, u64)]> = U64_DATA.into();
+        let maps = [
+            (
+                FKSMap::<&str, u64, MSPHasher<&str>>::new(str_data.clone(), 42, 0.75).unwrap(),
+                str_data.len(),
+            ),
+            (
+                FKSMap::<&str, u64, MSPHasher<&str>>::new(str_data[..10].into(), 7, 0.5).unwrap(),
+                10,
+            ),
+            (
+                FKSMap::<&str, u64, MSPHasher<&str>>::new(Box::new([]), 42, 0.75).unwrap(),
+                0,
+            ),
+        ];
+        for (map, num_entries) in maps {
+            assert_eq!(map.len(), num_entries);
+            assert_eq!(map.is_empty(), num_entries == 0);
+            assert_eq!(map.capacity(), map.occupancy_mask().len());
+            assert_eq!(map.slack(), map.capacity() - num_entries);
+        }
+
+        let mut map = FKSMap::<u64, u64, MSPHasher<u64>>::new(u64_data, 42, 0.75).unwrap();
+        assert_eq!(map.len(), U64_DATA.len());
+        assert!(map.slack() > 0);
+        assert_eq!(map.slack(), map.capacity() - U64_DATA.len());
+        assert_eq!(
+            map.slack(),
+            map.occupancy_mask().iter().filter(|o| !**o).count()
+        );
+        map.compact();
+        assert_eq!(map.len(), U64_DATA.len());
+        assert_eq!(map.capacity(), U64_DATA.len());
+        assert_eq!(map.slack(), 0);
+
+        let map = InlineFKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        assert_eq!(map.len(), U64_DATA.len());
+        assert_eq!(map.capacity(), U64_DATA.len());
+        assert_eq!(map.slack(), 0);
+    }
+
+    #[test]
+    fn test_get_or() {
+        let map = FKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        let default = u64::MAX;
+        for (key, val) in &U64_DATA {
+            assert_eq!(map.get_or(key, &default), val, "Key: {:?}", key);
+        }
+        let absent = (0..).find(|key| map.get(key).is_none()).unwrap();
+        assert!(core::ptr::eq(map.get_or(&absent, &default), &default));
+    }
+
+    #[test]
+    fn test_get_or_default() {
+        let data = STR_DATA.map(|(key, val)| (key, val.to_string()));
+        let map = InlineFKSMap::<&str, String, MSPHasher<&str>>::new(data.clone().into(), 42, 0.75)
+            .unwrap();
+        for (key, val) in &data {
+            assert_eq!(&map.get_or_default(key), val, "Key: {:?}", key);
+        }
+        assert_eq!(map.get_or_default(&"absent key"), String::new());
+    }
+}
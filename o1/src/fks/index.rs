@@ -0,0 +1,166 @@
+//! Implements [`PerfectHashIndex`] - a perfect hash function over a fixed key set without value
+//! storage.
+use crate::fks::FKSMap;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use o1_core::{Hasher, O1Error};
+
+/// Minimal perfect hash function for a fixed set of keys built with the FKS scheme.
+///
+/// Maps each of the construction keys to a distinct index within `[0, n)` where `n` is the number
+/// of keys - for example to index into parallel arrays maintained by the caller. The keys are
+/// stored to reject the keys outside of the set, but no values are.
+///
+/// # Examples
+///
+/// ```rust
+/// use o1::fks::PerfectHashIndex;
+/// use o1::hashing::hashers::msp::MSPHasher;
+///
+/// let index = PerfectHashIndex::<&str, MSPHasher<&str>>::with_seed(
+///     ["apple", "banana", "cherry"].into(),
+///     42,
+/// ).unwrap();
+///
+/// let mut prices = [0.0; 3];
+/// prices[index.index_of(&"banana").unwrap()] = 0.25;
+///
+/// assert!(index.index_of(&"durian").is_none());
+/// ```
+#[derive(Debug)]
+pub struct PerfectHashIndex<'a, K: Eq, H: Hasher<K>> {
+    map: FKSMap<'a, K, (), H>,
+    /// Number of keys stored in the buckets preceding each bucket.
+    ranks: Box<[usize]>,
+}
+
+impl<K: Eq + Debug, H: Hasher<K>> PerfectHashIndex<'_, K, H> {
+    /// Creates a new [`PerfectHashIndex`] for the given keys, seed, and minimum load factor.
+    ///
+    /// # Parameters
+    ///
+    /// - `keys`: The distinct keys to be indexed.
+    /// - `seed`: The seed for the random number generator.
+    /// - `min_load_factor`: The minimum load factor of the underlying [`FKSMap`].
+    ///
+    /// # Errors
+    ///
+    /// The same as of [`FKSMap::new`].
+    pub fn new(keys: Box<[K]>, seed: u64, min_load_factor: f32) -> Result<Self, O1Error> {
+        let data: Vec<(K, ())> = keys.into_vec().into_iter().map(|k| (k, ())).collect();
+        let map = FKSMap::new(data.into(), seed, min_load_factor)?;
+
+        let mut num_keys = 0;
+        let ranks = map
+            .buckets
+            .iter()
+            .map(|bucket| {
+                let rank = num_keys;
+                num_keys += bucket.slots.count_ones() as usize;
+                rank
+            })
+            .collect();
+
+        Ok(Self { map, ranks })
+    }
+
+    /// Creates a new [`PerfectHashIndex`] with the given keys and seed and with the default
+    /// minimum load factor - [`FKSMap::DEFAULT_MIN_LOAD_FACTOR`].
+    ///
+    /// # Parameters
+    ///
+    /// - `keys`: The distinct keys to be indexed.
+    /// - `seed`: The seed for the random number generator.
+    pub fn with_seed(keys: Box<[K]>, seed: u64) -> Result<Self, O1Error> {
+        Self::new(keys, seed, FKSMap::<K, (), H>::DEFAULT_MIN_LOAD_FACTOR)
+    }
+}
+
+impl<K: Eq, H: Hasher<K>> PerfectHashIndex<'_, K, H> {
+    /// Returns the index of the given `key` within `[0, len())`.
+    ///
+    /// Returns `None` if the key isn't one of the construction keys.
+    #[inline]
+    pub fn index_of(&self, key: &K) -> Option<usize> {
+        let bucket_idx = self.map.l1_hasher.hash(key) as usize;
+        let slot_idx = self.map.candidate_slot_idx(bucket_idx, key)?;
+        if !self.map.slot_contains(slot_idx, key) {
+            return None;
+        }
+
+        // The index is the number of keys preceding the key's slot.
+        let bucket = &self.map.buckets[bucket_idx];
+        let preceding_slots = bucket.slots & !(u16::MAX << (slot_idx - bucket.offset));
+        Some(self.ranks[bucket_idx] + preceding_slots.count_ones() as usize)
+    }
+
+    /// Number of the indexed keys.
+    pub fn len(&self) -> usize {
+        self.map.buckets.last().map_or(0, |bucket| {
+            self.ranks[self.ranks.len() - 1] + bucket.slots.count_ones() as usize
+        })
+    }
+
+    /// Whether the index contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_test::data::{STR_DATA, U64_DATA};
+
+    fn assert_dense<K: Eq + Debug, H: Hasher<K>>(index: &PerfectHashIndex<'_, K, H>, keys: &[K]) {
+        assert_eq!(index.len(), keys.len());
+        let mut seen = vec![false; keys.len()];
+        for key in keys {
+            let idx = index.index_of(key).unwrap();
+            assert!(idx < keys.len(), "Index {idx} of {key:?} is out of range");
+            assert!(!seen[idx], "Index {idx} of {key:?} is not unique");
+            seen[idx] = true;
+        }
+    }
+
+    #[test]
+    fn test_index_of_u64() {
+        let keys: Vec<u64> = U64_DATA.iter().map(|(k, _)| *k).collect();
+        for min_load_factor in [0.5, 0.75, 1.0] {
+            let index = PerfectHashIndex::<u64, MSPHasher<u64>>::new(
+                keys.clone().into(),
+                42,
+                min_load_factor,
+            )
+            .unwrap();
+            assert_dense(&index, &keys);
+        }
+    }
+
+    #[test]
+    fn test_index_of_str() {
+        let keys: Vec<&str> = STR_DATA.iter().map(|(k, _)| *k).collect();
+        let index =
+            PerfectHashIndex::<&str, MSPHasher<&str>>::with_seed(keys.clone().into(), 7).unwrap();
+        assert_dense(&index, &keys);
+        assert_eq!(index.index_of(&"not a construction key"), None);
+    }
+
+    #[test]
+    fn test_index_of_absent_keys() {
+        let keys: Vec<u64> = (0..100).map(|i| i * 2).collect();
+        let index = PerfectHashIndex::<u64, MSPHasher<u64>>::with_seed(keys.into(), 42).unwrap();
+        for key in (0..100).map(|i| i * 2 + 1) {
+            assert_eq!(index.index_of(&key), None, "Key: {key}");
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let index = PerfectHashIndex::<u64, MSPHasher<u64>>::with_seed(Box::new([]), 42).unwrap();
+        assert!(index.is_empty());
+        assert_eq!(index.index_of(&1), None);
+    }
+}
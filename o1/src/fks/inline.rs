@@ -0,0 +1,285 @@
+//! Implements an alternative layout of [`FKSMap`] storing the entries of singleton buckets
+//! inline.
+use crate::fks::core::slot_idx;
+use crate::fks::FKSMap;
+use crate::utils::maybe_owned_slice::MaybeOwnedSliceMut;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use o1_core::{HashMap, Hasher, O1Error};
+
+/// Static hash-table based on the FKS scheme, storing the entry of a bucket with a single key
+/// inside the bucket itself.
+///
+/// Most of the buckets of [`FKSMap`] hold at most one key, yet a lookup of such a key reads
+/// both the bucket and the slot it points to - two cache misses for a map that doesn't fit into
+/// the CPU cache. [`InlineFKSMap`] resolves these keys with a single memory read at the cost of
+/// larger buckets. The keys of the buckets with collisions are resolved by the L2 hash functions
+/// in the same way as in a compact [`FKSMap`]. For the maps that fit into the CPU cache the
+/// larger buckets make the lookups slower than the ones of [`FKSMap`] - see the
+/// `inline_buckets` benchmark.
+///
+/// There is no compile-time constructor.
+///
+/// # Examples
+///
+/// ```rust
+/// use o1_core::HashMap;
+/// use o1::hashing::hashers::msp::MSPHasher;
+/// use o1::fks::InlineFKSMap;
+///
+/// let map = InlineFKSMap::<u32, &str, MSPHasher<u32>>::new(
+///     [(1, "one"), (2, "two"), (3, "three")].into(),
+///     42,
+///     0.75,
+/// ).unwrap();
+///
+/// assert_eq!(map.get(&2), Some(&"two"));
+/// assert_eq!(map.get(&4), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct InlineFKSMap<K: Eq, V, H: Hasher<K>> {
+    l1_hasher: H,
+    buckets: Box<[InlineBucket<K, V, H>]>,
+    /// The entries of the buckets with collisions, only the occupied positions of their L2
+    /// tables have a slot.
+    slots: Box<[(K, V)]>,
+    /// The number of entries.
+    len: usize,
+}
+
+/// A bucket of [`InlineFKSMap`].
+#[derive(Debug, Clone)]
+enum InlineBucket<K, V, H> {
+    Empty,
+    /// The only entry of the bucket.
+    Inline((K, V)),
+    /// A bucket with collisions - the same as [`Bucket`](crate::fks::Bucket) of a compact
+    /// [`FKSMap`].
+    Table {
+        /// The offset of the first slot in the bucket.
+        offset: usize,
+        /// A bit-mask of the occupied positions of the L2 table.
+        slots: u16,
+        /// L2 hasher that contains parameters for the L2 hash function.
+        hasher: H,
+    },
+}
+
+impl<K: Eq + Debug, V, H: Hasher<K>> InlineFKSMap<K, V, H> {
+    /// Creates a new [`InlineFKSMap`] with the given data, seed, and minimum load factor.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    /// - `min_load_factor`: The minimum load factor.
+    ///
+    /// # Errors
+    ///
+    /// The same as of [`FKSMap::new`].
+    pub fn new(data: Box<[(K, V)]>, seed: u64, min_load_factor: f32) -> Result<Self, O1Error> {
+        let map = FKSMap::new(data, seed, min_load_factor)?;
+        Ok(Self::from_map(map))
+    }
+
+    /// Creates a new [`InlineFKSMap`] with the given data and seed and with the default minimum
+    /// load factor - [`FKSMap::DEFAULT_MIN_LOAD_FACTOR`].
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    pub fn with_seed(data: Box<[(K, V)]>, seed: u64) -> Result<Self, O1Error> {
+        Self::new(data, seed, FKSMap::<K, V, H>::DEFAULT_MIN_LOAD_FACTOR)
+    }
+}
+
+impl<K: Eq, V, H: Hasher<K>> InlineFKSMap<K, V, H> {
+    /// Converts an [`FKSMap`] into an [`InlineFKSMap`] keeping its hash functions.
+    ///
+    /// # Panics
+    ///
+    /// - If the map has been built by the compile-time constructor and hence does not own its memory.
+    pub fn from_map(mut map: FKSMap<'_, K, V, H>) -> Self {
+        // Compacting makes every slot occupied and orders the slots by the buckets.
+        map.compact();
+        let l1_hasher = core::mem::take(&mut map.l1_hasher);
+        let buckets =
+            core::mem::replace(&mut map.buckets, MaybeOwnedSliceMut::from_vec(Vec::new()))
+                .owned_into_vec();
+        let old_slots =
+            core::mem::replace(&mut map.slots, MaybeOwnedSliceMut::from_vec(Vec::new()))
+                .owned_into_vec();
+        let len = old_slots.len();
+        // SAFETY: all slots of a compact map are initialized and the emptied map won't drop them.
+        let mut entries = old_slots
+            .into_iter()
+            .map(|slot| unsafe { slot.assume_init() });
+
+        let mut slots = Vec::with_capacity(len);
+        let buckets = buckets
+            .into_iter()
+            .map(|bucket| match bucket.slots.count_ones() {
+                0 => InlineBucket::Empty,
+                1 => InlineBucket::Inline(entries.next().unwrap()),
+                num_entries => {
+                    let offset = slots.len();
+                    slots.extend(entries.by_ref().take(num_entries as usize));
+                    InlineBucket::Table {
+                        offset,
+                        slots: bucket.slots,
+                        hasher: bucket.hasher,
+                    }
+                }
+            })
+            .collect();
+
+        Self {
+            l1_hasher,
+            buckets,
+            slots: slots.into(),
+            len,
+        }
+    }
+
+    /// Get the stored key and the value associated with the given `key`.
+    #[inline]
+    fn get_entry(&self, key: &K) -> Option<(&K, &V)> {
+        let entry = match &self.buckets[self.l1_hasher.hash(key) as usize] {
+            InlineBucket::Empty => return None,
+            InlineBucket::Inline(entry) => entry,
+            InlineBucket::Table {
+                offset,
+                slots,
+                hasher,
+            } => {
+                let pos = hasher.hash(key) as usize;
+                if slots & (1 << pos) == 0 {
+                    return None;
+                }
+                &self.slots[slot_idx(*offset, *slots, pos, true)]
+            }
+        };
+        (entry.0 == *key).then_some((&entry.0, &entry.1))
+    }
+
+    /// The number of entries stored inside the buckets.
+    pub fn num_inline(&self) -> usize {
+        self.len - self.slots.len()
+    }
+}
+
+impl<K: Eq + Debug, V, H: Hasher<K>> HashMap<K, V, H> for InlineFKSMap<K, V, H> {
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get_entry(key).map(|(_, v)| v)
+    }
+
+    fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        self.get_entry(key)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Every entry occupies either a bucket or a slot, so the load factor is always `1.0`.
+    fn load_factor(&self) -> f64 {
+        1.0
+    }
+
+    fn num_collisions(&self) -> usize {
+        self.slots.len()
+            - self
+                .buckets
+                .iter()
+                .filter(|bucket| matches!(bucket, InlineBucket::Table { .. }))
+                .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_test::data::{STR_DATA, U64_DATA};
+    use o1_test::generate_map_tests;
+    use std::rc::Rc;
+
+    fn factory<K: Eq + Debug, V: Copy + Debug, H: Hasher<K>>(
+        data: Box<[(K, V)]>,
+    ) -> InlineFKSMap<K, V, H> {
+        InlineFKSMap::new(data, 0, 0.75).unwrap()
+    }
+
+    generate_map_tests!(InlineFKSMap, MSPHasher, factory);
+
+    #[test]
+    fn test_inline_and_table_entries_resolve() {
+        let map = InlineFKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+
+        assert_eq!(map.len(), U64_DATA.len());
+        assert!(map.num_inline() > 0);
+        assert!(map.num_inline() < map.len());
+        for (key, val) in &U64_DATA {
+            assert_eq!(map.get_key_value(key), Some((key, val)), "Key: {:?}", key);
+        }
+        for key in 0..1000_u64 {
+            let expected = U64_DATA.iter().find(|(k, _)| *k == key).map(|(_, v)| v);
+            assert_eq!(map.get(&key), expected, "Key: {:?}", key);
+        }
+    }
+
+    #[test]
+    fn test_from_map_keeps_hash_functions() {
+        let map = FKSMap::<&str, u64, MSPHasher<&str>>::new(STR_DATA.into(), 42, 0.75).unwrap();
+        let num_buckets = map.buckets.len();
+        let num_collisions: usize = map
+            .buckets
+            .iter()
+            .map(|b| (b.slots.count_ones() as usize).saturating_sub(1))
+            .sum();
+
+        let inline = InlineFKSMap::from_map(map.clone());
+
+        assert_eq!(inline.buckets.len(), num_buckets);
+        assert_eq!(inline.len() - inline.num_inline(), inline.slots.len(),);
+        assert_eq!(inline.num_collisions(), num_collisions);
+        for (key, val) in map.iter() {
+            assert_eq!(inline.get(key), Some(val), "Key: {:?}", key);
+        }
+    }
+
+    #[test]
+    fn test_most_buckets_have_at_most_one_key() {
+        let map = InlineFKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        let num_tables = map
+            .buckets
+            .iter()
+            .filter(|bucket| matches!(bucket, InlineBucket::Table { .. }))
+            .count();
+        assert!(num_tables * 2 < map.buckets.len());
+    }
+
+    #[test]
+    fn test_empty() {
+        let map = InlineFKSMap::<u64, u64, MSPHasher<u64>>::new(Box::new([]), 42, 0.75).unwrap();
+        assert!(map.is_empty());
+        assert_eq!(map.get(&0), None);
+    }
+
+    #[test]
+    fn test_drops_each_entry_once() {
+        let value = Rc::new(());
+        let data: Box<[(u32, Rc<()>)]> = (0..1000_u32).map(|i| (i, Rc::clone(&value))).collect();
+        let map = InlineFKSMap::<u32, Rc<()>, MSPHasher<u32>>::new(data, 42, 0.75).unwrap();
+        assert_eq!(Rc::strong_count(&value), 1001);
+
+        drop(map);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
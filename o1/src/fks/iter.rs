@@ -0,0 +1,338 @@
+//! Implements iteration over the entries of [`FKSMap`].
+use crate::fks::core::{slot_idx, Bucket};
+use crate::fks::FKSMap;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::{ptr, slice};
+use o1_core::Hasher;
+
+/// Iterator over the entries of [`FKSMap`].
+///
+/// Created by [`FKSMap::iter`].
+pub struct FKSMapIter<'a, K: Eq, V, H: Hasher<K>> {
+    buckets: slice::Iter<'a, Bucket<K, H>>,
+    slots: &'a [MaybeUninit<(K, V)>],
+    /// Whether the map is compact.
+    is_compact: bool,
+    /// The offset of the bucket that is being iterated over.
+    offset: usize,
+    /// The bit-mask of the occupied slots of the bucket that is being iterated over.
+    slots_mask: u16,
+    /// The remaining occupied slots of the bucket that is being iterated over.
+    occupied: u16,
+}
+
+impl<'a, K: Eq, V, H: Hasher<K>> Iterator for FKSMapIter<'a, K, V, H> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.occupied == 0 {
+            let bucket = self.buckets.next()?;
+            self.offset = bucket.offset;
+            self.slots_mask = bucket.slots;
+            self.occupied = bucket.slots;
+        }
+        let pos = self.occupied.trailing_zeros() as usize;
+        let data_idx = slot_idx(self.offset, self.slots_mask, pos, self.is_compact);
+        self.occupied &= self.occupied - 1;
+
+        let (k, v) = unsafe { self.slots[data_idx].assume_init_ref() };
+        Some((k, v))
+    }
+}
+
+/// Mutable iterator over the entries of [`FKSMap`].
+///
+/// Created by [`FKSMap::iter_mut`].
+pub struct FKSMapIterMut<'a, K: Eq, V, H: Hasher<K>> {
+    buckets: slice::Iter<'a, Bucket<K, H>>,
+    slots: *mut MaybeUninit<(K, V)>,
+    /// Whether the map is compact.
+    is_compact: bool,
+    /// The offset of the bucket that is being iterated over.
+    offset: usize,
+    /// The bit-mask of the occupied slots of the bucket that is being iterated over.
+    slots_mask: u16,
+    /// The remaining occupied slots of the bucket that is being iterated over.
+    occupied: u16,
+    _slots: PhantomData<&'a mut [MaybeUninit<(K, V)>]>,
+}
+
+impl<'a, K: Eq, V, H: Hasher<K>> Iterator for FKSMapIterMut<'a, K, V, H> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.occupied == 0 {
+            let bucket = self.buckets.next()?;
+            self.offset = bucket.offset;
+            self.slots_mask = bucket.slots;
+            self.occupied = bucket.slots;
+        }
+        let pos = self.occupied.trailing_zeros() as usize;
+        let data_idx = slot_idx(self.offset, self.slots_mask, pos, self.is_compact);
+        self.occupied &= self.occupied - 1;
+
+        // SAFETY: every occupied slot belongs to exactly one bucket and is yielded only once,
+        //         so the mutable references never alias.
+        let (k, v) = unsafe { (*self.slots.add(data_idx)).assume_init_mut() };
+        Some((&*k, v))
+    }
+}
+
+/// Owning iterator over the entries of [`FKSMap`].
+///
+/// Created by [`FKSMap::into_iter`]. Entries that have not been yielded are dropped together with
+/// the iterator.
+pub struct FKSMapIntoIter<K: Eq, V, H: Hasher<K>> {
+    buckets: vec::IntoIter<Bucket<K, H>>,
+    slots: Box<[MaybeUninit<(K, V)>]>,
+    /// Whether the map is compact.
+    is_compact: bool,
+    /// The offset of the bucket that is being iterated over.
+    offset: usize,
+    /// The bit-mask of the occupied slots of the bucket that is being iterated over.
+    slots_mask: u16,
+    /// The remaining occupied slots of the bucket that is being iterated over.
+    occupied: u16,
+}
+
+impl<K: Eq, V, H: Hasher<K>> Iterator for FKSMapIntoIter<K, V, H> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.occupied == 0 {
+            let bucket = self.buckets.next()?;
+            self.offset = bucket.offset;
+            self.slots_mask = bucket.slots;
+            self.occupied = bucket.slots;
+        }
+        let pos = self.occupied.trailing_zeros() as usize;
+        let data_idx = slot_idx(self.offset, self.slots_mask, pos, self.is_compact);
+        self.occupied &= self.occupied - 1;
+
+        // SAFETY: the slot is occupied and since its bit has just been cleared it is never read
+        //         again - neither by the iterator nor by its `Drop`.
+        Some(unsafe { self.slots[data_idx].assume_init_read() })
+    }
+}
+
+/// Drops the entries that have not been yielded yet.
+impl<K: Eq, V, H: Hasher<K>> Drop for FKSMapIntoIter<K, V, H> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+/// Consumes the map yielding its entries in an unspecified order.
+///
+/// A map built by the compile-time constructor does not own its memory, so its entries are
+/// cloned out of it instead of being moved.
+impl<K, V, H> IntoIterator for FKSMap<'_, K, V, H>
+where
+    K: Eq + Clone,
+    V: Clone,
+    H: Hasher<K> + Clone,
+{
+    type Item = (K, V);
+    type IntoIter = FKSMapIntoIter<K, V, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        if !(self.slots.is_owned() && self.buckets.is_owned()) {
+            return self.clone().into_iter();
+        }
+        // The entries are moved out by the iterator, so the `Drop` of the map must not run.
+        let map = ManuallyDrop::new(self);
+        let (l1_hasher, buckets, slots) = unsafe {
+            (
+                ptr::read(&map.l1_hasher),
+                ptr::read(&map.buckets),
+                ptr::read(&map.slots),
+            )
+        };
+        drop(l1_hasher);
+
+        FKSMapIntoIter {
+            buckets: buckets.owned_into_vec().into_iter(),
+            slots: slots.owned_into_vec().into_boxed_slice(),
+            is_compact: map.is_compact,
+            offset: 0,
+            slots_mask: 0,
+            occupied: 0,
+        }
+    }
+}
+
+impl<K: Eq, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// Iterate over all entries of the map in an unspecified order.
+    pub fn iter(&self) -> FKSMapIter<'_, K, V, H> {
+        FKSMapIter {
+            buckets: self.buckets.iter(),
+            slots: &self.slots,
+            is_compact: self.is_compact,
+            offset: 0,
+            slots_mask: 0,
+            occupied: 0,
+        }
+    }
+
+    /// Iterate over all entries of the map in an unspecified order allowing to modify the values.
+    ///
+    /// # Panics
+    ///
+    /// - If the map has been built by [`const_fks_map!`](crate::const_fks_map) and hence
+    ///   references its memory immutably.
+    pub fn iter_mut(&mut self) -> FKSMapIterMut<'_, K, V, H> {
+        FKSMapIterMut {
+            buckets: self.buckets.iter(),
+            slots: self.slots.as_mut_ptr(),
+            is_compact: self.is_compact,
+            offset: 0,
+            slots_mask: 0,
+            occupied: 0,
+            _slots: PhantomData,
+        }
+    }
+
+    /// Iterate over all values of the map in an unspecified order allowing to modify them.
+    ///
+    /// # Panics
+    ///
+    /// - If the map has been built by [`const_fks_map!`](crate::const_fks_map) and hence
+    ///   references its memory immutably.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, v)| v)
+    }
+}
+
+impl<K, V, H> FKSMap<'_, K, V, H>
+where
+    K: Eq + Clone,
+    V: Clone,
+    H: Hasher<K> + Clone,
+{
+    /// Consume the map collecting its entries into a vector in an unspecified order.
+    ///
+    /// A shortcut for [`FKSMap::into_iter`] - e.g. to rebuild the map from the entries later.
+    pub fn into_entries(self) -> Vec<(K, V)> {
+        self.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_fks_map;
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_core::HashMap;
+    use o1_test::data::U64_DATA;
+    use std::collections::HashSet;
+    use std::rc::Rc;
+
+    fn make_map() -> FKSMap<'static, u32, u32, MSPHasher<u32>> {
+        let data: Box<[(u32, u32)]> = (0..1000_u32).map(|i| (i * 3, i)).collect();
+        FKSMap::new(data, 42, 0.75).unwrap()
+    }
+
+    #[test]
+    fn test_iter() {
+        let map = make_map();
+        let entries: HashSet<(u32, u32)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+        let expected: HashSet<(u32, u32)> = (0..1000_u32).map(|i| (i * 3, i)).collect();
+        assert_eq!(map.iter().count(), 1000);
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn test_iter_mut_yields_each_entry_once() {
+        let mut map = make_map();
+        let keys: HashSet<u32> = map.iter_mut().map(|(k, _)| *k).collect();
+        assert_eq!(map.iter_mut().count(), 1000);
+        assert_eq!(keys.len(), 1000);
+    }
+
+    #[test]
+    fn test_values_mut() {
+        let mut map = make_map();
+        for v in map.values_mut() {
+            *v *= 2;
+        }
+        for i in 0..1000_u32 {
+            assert_eq!(map.get(&(i * 3)), Some(&(i * 2)));
+        }
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let map = make_map();
+        let entries: HashSet<(u32, u32)> = map.into_iter().collect();
+        let expected: HashSet<(u32, u32)> = (0..1000_u32).map(|i| (i * 3, i)).collect();
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn test_into_iter_drops_each_entry_once() {
+        let value = Rc::new(());
+        let data: Box<[(u32, Rc<()>)]> = (0..1000_u32).map(|i| (i, Rc::clone(&value))).collect();
+        let map = FKSMap::<u32, Rc<()>, MSPHasher<u32>>::new(data, 42, 0.75).unwrap();
+        assert_eq!(Rc::strong_count(&value), 1001);
+
+        let mut iter = map.into_iter();
+        let taken: Vec<(u32, Rc<()>)> = iter.by_ref().take(300).collect();
+        assert_eq!(Rc::strong_count(&value), 1001);
+
+        drop(iter);
+        assert_eq!(Rc::strong_count(&value), 301);
+
+        drop(taken);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_into_entries_round_trip() {
+        let value = Rc::new(());
+        let data: Box<[(String, Rc<()>)]> = (0..1000_u32)
+            .map(|i| (i.to_string(), Rc::clone(&value)))
+            .collect();
+        let map = FKSMap::<String, Rc<()>, MSPHasher<String>>::new(data, 42, 0.75).unwrap();
+
+        let entries = map.into_entries();
+        assert_eq!(entries.len(), 1000);
+        assert_eq!(Rc::strong_count(&value), 1001);
+
+        let map =
+            FKSMap::<String, Rc<()>, MSPHasher<String>>::new(entries.into(), 42, 0.75).unwrap();
+        for i in 0..1000_u32 {
+            assert!(map.get(&i.to_string()).is_some(), "Key: {i}");
+        }
+        drop(map);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_into_iter_owned_values() {
+        let data: Box<[(u32, String)]> = (0..1000_u32).map(|i| (i, i.to_string())).collect();
+        let map = FKSMap::<u32, String, MSPHasher<u32>>::new(data, 42, 0.75).unwrap();
+        let mut entries: Vec<(u32, String)> = map.into_iter().collect();
+        entries.sort();
+        let expected: Vec<(u32, String)> = (0..1000_u32).map(|i| (i, i.to_string())).collect();
+        assert_eq!(entries, expected);
+    }
+
+    const_fks_map!(CONST_MAP, u64, u64, U64_DATA, MSPHasher<u64>, 42, 0.75);
+
+    #[test]
+    fn test_into_iter_borrowed_memory() {
+        assert!(CONST_MAP.slots.is_borrowed() && CONST_MAP.buckets.is_borrowed());
+        let expected: HashSet<(u64, u64)> = U64_DATA.into_iter().collect();
+
+        let entries: HashSet<(u64, u64)> = CONST_MAP.into_iter().collect();
+        assert_eq!(entries, expected);
+
+        let entries: HashSet<(u64, u64)> = CONST_MAP.into_entries().into_iter().collect();
+        assert_eq!(entries, expected);
+        assert_eq!(CONST_MAP.iter().count(), U64_DATA.len());
+    }
+}
@@ -0,0 +1,449 @@
+//! Implements key lookups for [`FKSMap`].
+use crate::fks::FKSMap;
+use crate::utils::prefetch::prefetch_read;
+use alloc::vec::Vec;
+use bitvec::prelude::*;
+use bitvec::view::BitView;
+use core::fmt::Debug;
+use o1_core::{DigestHasher, Hasher};
+use thiserror::Error;
+
+/// Number of keys [`FKSMap::get_batch`] resolves simultaneously.
+const BATCH_CHUNK_SIZE: usize = 32;
+
+/// The error returned by [`FKSMap::try_get`] when the key is not present in the map.
+///
+/// Carries the context of the lookup to help debugging the keys that are expected to be present.
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Key {key:?} not found in the bucket {bucket_idx}")]
+pub struct KeyNotFound<'a, K: Debug> {
+    /// The key that was looked up.
+    pub key: &'a K,
+    /// The index of the L1 bucket the key resolved to.
+    pub bucket_idx: usize,
+}
+
+impl<K: Eq, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// Resolve the given `key` within the bucket `bucket_idx` to the index of a slot that may
+    /// contain it.
+    ///
+    /// Returns `None` if the bucket has no slot for the key.
+    #[inline]
+    pub(super) fn candidate_slot_idx(&self, bucket_idx: usize, key: &K) -> Option<usize> {
+        if self.is_compact {
+            self.candidate_slot_idx_in::<true>(bucket_idx, key)
+        } else {
+            self.candidate_slot_idx_in::<false>(bucket_idx, key)
+        }
+    }
+
+    /// [`FKSMap::candidate_slot_idx`] specialized for the mode of the map - `IS_COMPACT` must be
+    /// equal to `self.is_compact`.
+    ///
+    /// Only the compact maps need to count the occupied positions preceding the slot. The
+    /// specialization keeps the popcount out of the lookups of the other maps, and the callers
+    /// branch on the mode once - before hashing the key, or once per batch of keys.
+    #[inline(always)]
+    fn candidate_slot_idx_in<const IS_COMPACT: bool>(
+        &self,
+        bucket_idx: usize,
+        key: &K,
+    ) -> Option<usize> {
+        debug_assert_eq!(IS_COMPACT, self.is_compact);
+        let bucket = &self.buckets[bucket_idx];
+        match bucket.num_slots() {
+            0 => None,
+            1 => Some(bucket.offset),
+            _ => {
+                let hash = bucket.hasher.hash(key);
+                let is_set = unsafe {
+                    bucket
+                        .slots
+                        .view_bits::<Lsb0>()
+                        .get(hash as usize)
+                        .unwrap_unchecked()
+                };
+                is_set.then_some(bucket.slot_idx(hash as usize, IS_COMPACT))
+            }
+        }
+    }
+
+    /// Check whether the slot `data_idx` contains the given `key`.
+    #[inline]
+    pub(super) fn slot_contains(&self, data_idx: usize, key: &K) -> bool {
+        let (k, _) = unsafe { self.slots[data_idx].assume_init_ref() };
+        k == key
+    }
+
+    /// Resolve the given `key` to the index of its slot.
+    ///
+    /// Returns `None` if the key is not present in the map.
+    #[inline]
+    pub(crate) fn slot_idx(&self, key: &K) -> Option<usize> {
+        if self.is_compact {
+            self.slot_idx_in::<true>(key)
+        } else {
+            self.slot_idx_in::<false>(key)
+        }
+    }
+
+    /// [`FKSMap::slot_idx`] specialized for the mode of the map.
+    #[inline(always)]
+    fn slot_idx_in<const IS_COMPACT: bool>(&self, key: &K) -> Option<usize> {
+        let bucket_idx = self.l1_hasher.hash(key) as usize;
+        let data_idx = self.candidate_slot_idx_in::<IS_COMPACT>(bucket_idx, key)?;
+        self.slot_contains(data_idx, key).then_some(data_idx)
+    }
+
+    /// Index of the slot holding the given `key` or `None` if the key is not present in the map.
+    ///
+    /// The index is the one the map uses internally - within `[0, capacity())` where
+    /// `capacity()` includes the unoccupied slots, see [`FKSMap::occupancy_mask`]. It allows to
+    /// keep the values in external arrays aligned with the storage of the map.
+    /// [`FKSMap::compact`] changes the indices.
+    #[inline]
+    pub fn raw_slot(&self, key: &K) -> Option<usize> {
+        self.slot_idx(key)
+    }
+
+    /// Get the value associated with the given `key`.
+    #[inline]
+    pub(crate) fn get_value(&self, key: &K) -> Option<&V> {
+        self.get_entry(key).map(|(_, v)| v)
+    }
+
+    /// Get the stored key and the value associated with the given `key`.
+    #[inline]
+    pub(crate) fn get_entry(&self, key: &K) -> Option<(&K, &V)> {
+        let data_idx = self.slot_idx(key)?;
+        let (k, v) = unsafe { self.slots[data_idx].assume_init_ref() };
+        Some((k, v))
+    }
+
+    /// Get the value associated with the given `key` or the context of the failed lookup.
+    ///
+    /// Equivalent to [`get()`](o1_core::HashMap::get), the error is built only if the key is
+    /// missing.
+    #[inline]
+    pub fn try_get<'k>(&self, key: &'k K) -> Result<&V, KeyNotFound<'k, K>>
+    where
+        K: Debug,
+    {
+        let bucket_idx = self.l1_hasher.hash(key) as usize;
+        match self.candidate_slot_idx(bucket_idx, key) {
+            Some(data_idx) if self.slot_contains(data_idx, key) => {
+                let (_, v) = unsafe { self.slots[data_idx].assume_init_ref() };
+                Ok(v)
+            }
+            _ => Err(KeyNotFound { key, bucket_idx }),
+        }
+    }
+
+    /// Look up all of the given `keys` replacing the contents of `out` with the results.
+    ///
+    /// Equivalent to calling [`get()`](o1_core::HashMap::get) for each key, but resolves the keys
+    /// in chunks: first computes the bucket indices of the whole chunk, then the slot indices
+    /// and only then compares the keys - prefetching the memory needed by the next step in
+    /// advance. This hides the memory latency when the map doesn't fit into the CPU cache.
+    pub fn get_batch<'a>(&'a self, keys: &[K], out: &mut Vec<Option<&'a V>>) {
+        if self.is_compact {
+            self.get_batch_in::<true>(keys, out);
+        } else {
+            self.get_batch_in::<false>(keys, out);
+        }
+    }
+
+    /// [`FKSMap::get_batch`] specialized for the mode of the map.
+    fn get_batch_in<'a, const IS_COMPACT: bool>(
+        &'a self,
+        keys: &[K],
+        out: &mut Vec<Option<&'a V>>,
+    ) {
+        out.clear();
+        out.reserve(keys.len());
+
+        let mut bucket_indices = [0_usize; BATCH_CHUNK_SIZE];
+        let mut data_indices = [None; BATCH_CHUNK_SIZE];
+
+        for chunk in keys.chunks(BATCH_CHUNK_SIZE) {
+            for (key, bucket_idx) in chunk.iter().zip(bucket_indices.iter_mut()) {
+                *bucket_idx = self.l1_hasher.hash(key) as usize;
+                prefetch_read(&self.buckets[*bucket_idx]);
+            }
+
+            for ((key, bucket_idx), data_idx) in chunk
+                .iter()
+                .zip(bucket_indices.iter())
+                .zip(data_indices.iter_mut())
+            {
+                *data_idx = self.candidate_slot_idx_in::<IS_COMPACT>(*bucket_idx, key);
+                if let Some(data_idx) = *data_idx {
+                    prefetch_read(&self.slots[data_idx]);
+                }
+            }
+
+            for (key, data_idx) in chunk.iter().zip(data_indices.iter()) {
+                out.push(match *data_idx {
+                    Some(data_idx) if self.slot_contains(data_idx, key) => {
+                        let (_, v) = unsafe { self.slots[data_idx].assume_init_ref() };
+                        Some(v)
+                    }
+                    _ => None,
+                });
+            }
+        }
+    }
+
+    /// Get references to the values associated with all of the given `keys` at once.
+    ///
+    /// The immutable counterpart of [`FKSMap::get_many_mut`] - the result for each key is
+    /// independent, so the keys may repeat and may be missing. Resolves the keys in the same
+    /// phases as [`FKSMap::get_batch`], but without allocating.
+    pub fn get_disjoint<const M: usize>(&self, keys: [&K; M]) -> [Option<&V>; M] {
+        if self.is_compact {
+            self.get_disjoint_in::<M, true>(keys)
+        } else {
+            self.get_disjoint_in::<M, false>(keys)
+        }
+    }
+
+    /// [`FKSMap::get_disjoint`] specialized for the mode of the map.
+    #[inline(always)]
+    fn get_disjoint_in<const M: usize, const IS_COMPACT: bool>(
+        &self,
+        keys: [&K; M],
+    ) -> [Option<&V>; M] {
+        let bucket_indices = keys.map(|key| {
+            let bucket_idx = self.l1_hasher.hash(key) as usize;
+            prefetch_read(&self.buckets[bucket_idx]);
+            bucket_idx
+        });
+
+        let mut data_indices = [None; M];
+        for ((key, bucket_idx), data_idx) in keys
+            .iter()
+            .zip(bucket_indices.iter())
+            .zip(data_indices.iter_mut())
+        {
+            *data_idx = self.candidate_slot_idx_in::<IS_COMPACT>(*bucket_idx, key);
+            if let Some(data_idx) = *data_idx {
+                prefetch_read(&self.slots[data_idx]);
+            }
+        }
+
+        core::array::from_fn(|i| match data_indices[i] {
+            Some(data_idx) if self.slot_contains(data_idx, keys[i]) => {
+                let (_, v) = unsafe { self.slots[data_idx].assume_init_ref() };
+                Some(v)
+            }
+            _ => None,
+        })
+    }
+
+    /// Get mutable references to the values associated with all of the given `keys` at once.
+    ///
+    /// Returns `None` if any of the keys is missing or if the same key is given more than once.
+    ///
+    /// # Panics
+    ///
+    /// - If the map has been built by [`const_fks_map!`](crate::const_fks_map) and hence
+    ///   references its memory immutably.
+    pub fn get_many_mut<const M: usize>(&mut self, keys: [&K; M]) -> Option<[&mut V; M]> {
+        let mut indices = [0_usize; M];
+        for (i, key) in keys.iter().enumerate() {
+            let data_idx = self.slot_idx(key)?;
+            if indices[..i].contains(&data_idx) {
+                return None;
+            }
+            indices[i] = data_idx;
+        }
+
+        let slots = self.slots.as_mut_ptr();
+        // SAFETY: all indices are in bounds, point to initialized slots and are pairwise
+        //         distinct, so the resulting mutable references don't alias.
+        Some(indices.map(|data_idx| unsafe { &mut (*slots.add(data_idx)).assume_init_mut().1 }))
+    }
+}
+
+impl<K: Eq, V, H: DigestHasher<K>> FKSMap<'_, K, V, H> {
+    /// Get the value associated with the given `key` computing the digest of the key only once.
+    ///
+    /// Derives both the index of the bucket and the index of the slot from the same digest
+    /// instead of hashing the key with the L1 and then with the L2 hash function - which halves
+    /// the cost of the lookup for the hashers dominated by hashing the key, like
+    /// [`MSPHasherDigest`](crate::hashing::hashers::msp::MSPHasherDigest) with long keys.
+    ///
+    /// The map must be built by [`FKSMap::new_single_pass`] - the L2 hash functions of the maps
+    /// built otherwise don't share the digest of the L1 one, and the present keys may be missed.
+    /// [`SinglePassFKSMap`](crate::fks::SinglePassFKSMap) guarantees it.
+    #[inline]
+    pub(crate) fn get_single_pass(&self, key: &K) -> Option<&V> {
+        if self.is_compact {
+            self.get_single_pass_in::<true>(key)
+        } else {
+            self.get_single_pass_in::<false>(key)
+        }
+    }
+
+    /// [`FKSMap::get_single_pass`] specialized for the mode of the map.
+    #[inline(always)]
+    fn get_single_pass_in<const IS_COMPACT: bool>(&self, key: &K) -> Option<&V> {
+        let digest = self.l1_hasher.digest(key);
+        let bucket = &self.buckets[self.l1_hasher.hash_digest(digest) as usize];
+        let data_idx = match bucket.num_slots() {
+            0 => return None,
+            1 => bucket.offset,
+            _ => {
+                let hash = bucket.hasher.hash_digest(digest);
+                debug_assert_eq!(
+                    hash,
+                    bucket.hasher.hash(key),
+                    "The L2 hash function doesn't share the digest of the L1 one"
+                );
+                let is_set = unsafe {
+                    bucket
+                        .slots
+                        .view_bits::<Lsb0>()
+                        .get(hash as usize)
+                        .unwrap_unchecked()
+                };
+                if !is_set {
+                    return None;
+                }
+                bucket.slot_idx(hash as usize, IS_COMPACT)
+            }
+        };
+        let (k, v) = unsafe { self.slots[data_idx].assume_init_ref() };
+        (k == key).then_some(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_core::HashMap;
+
+    fn make_map() -> FKSMap<'static, u32, u32, MSPHasher<u32>> {
+        let data: Box<[(u32, u32)]> = (0..100_u32).map(|i| (i, i * 10)).collect();
+        FKSMap::new(data, 42, 0.75).unwrap()
+    }
+
+    #[test]
+    fn test_get_key_value_returns_stored_key() {
+        let data: Box<[(String, u32)]> = (0..100_u32).map(|i| (i.to_string(), i)).collect();
+        let map = FKSMap::<String, u32, MSPHasher<String>>::new(data, 42, 0.75).unwrap();
+
+        let query = String::from("42");
+        let (k, v) = map.get_key_value(&query).unwrap();
+        assert_eq!(k, &query);
+        assert_eq!(v, &42);
+        let (stored, _) = map.iter().find(|(k, _)| *k == "42").unwrap();
+        assert!(core::ptr::eq(k, stored));
+        assert!(!core::ptr::eq(k, &query));
+
+        assert!(map.get_key_value(&String::from("100")).is_none());
+    }
+
+    #[test]
+    fn test_try_get() {
+        let map = make_map();
+        assert_eq!(map.try_get(&42), Ok(&420));
+
+        let key = 1000;
+        let error = map.try_get(&key).unwrap_err();
+        assert!(core::ptr::eq(error.key, &key));
+        assert_eq!(error.bucket_idx, map.bucket_of(&key));
+        assert_eq!(
+            error.to_string(),
+            format!("Key 1000 not found in the bucket {}", map.bucket_of(&key))
+        );
+    }
+
+    #[test]
+    fn test_raw_slot() {
+        let mut map = make_map();
+        for is_compact in [false, true] {
+            if is_compact {
+                map.compact();
+            }
+            let mask = map.occupancy_mask();
+            assert_eq!(mask.len(), map.capacity());
+
+            let mut seen = vec![false; map.capacity()];
+            for key in 0..100_u32 {
+                let slot = map.raw_slot(&key).unwrap();
+                assert!(slot < map.capacity(), "Key: {key}");
+                assert!(mask[slot], "Key: {key}");
+                assert!(!seen[slot], "Key: {key}");
+                seen[slot] = true;
+            }
+            assert_eq!(map.raw_slot(&100), None);
+            assert_eq!(map.raw_slot(&u32::MAX), None);
+        }
+    }
+
+    #[test]
+    fn test_get_many_mut() {
+        let mut map = make_map();
+        let [a, b, c] = map.get_many_mut([&1, &50, &99]).unwrap();
+        *a += 1;
+        *b += 2;
+        *c += 3;
+        assert_eq!(map.get(&1), Some(&11));
+        assert_eq!(map.get(&50), Some(&502));
+        assert_eq!(map.get(&99), Some(&993));
+        assert_eq!(map.get(&2), Some(&20));
+    }
+
+    #[test]
+    fn test_get_many_mut_missing_key() {
+        let mut map = make_map();
+        assert!(map.get_many_mut([&1, &1000]).is_none());
+    }
+
+    #[test]
+    fn test_get_many_mut_duplicate_keys() {
+        let mut map = make_map();
+        assert!(map.get_many_mut([&1, &2, &1]).is_none());
+    }
+
+    #[test]
+    fn test_get_disjoint_equivalent_to_get() {
+        let mut map = make_map();
+        for is_compact in [false, true] {
+            if is_compact {
+                map.compact();
+            }
+            let keys = [&5, &99, &1000, &5, &0, &u32::MAX];
+            let values = map.get_disjoint(keys);
+            for (key, value) in keys.iter().zip(values.iter()) {
+                assert_eq!(*value, map.get(key), "Key: {:?}", key);
+            }
+            assert_eq!(
+                values,
+                [Some(&50), Some(&990), None, Some(&50), Some(&0), None]
+            );
+            assert!(core::ptr::eq(values[0].unwrap(), values[3].unwrap()));
+            assert_eq!(map.get_disjoint::<0>([]), []);
+        }
+    }
+
+    #[test]
+    fn test_get_batch_equivalent_to_get() {
+        let mut map = make_map();
+        for is_compact in [false, true] {
+            if is_compact {
+                map.compact();
+            }
+            let keys: Vec<u32> = (0..150_u32).rev().collect();
+            let mut out = vec![None];
+            map.get_batch(&keys, &mut out);
+            assert_eq!(out.len(), keys.len());
+            for (key, value) in keys.iter().zip(out.iter()) {
+                assert_eq!(*value, map.get(key), "Key: {:?}", key);
+                assert_eq!(value.is_some(), *key < 100, "Key: {:?}", key);
+            }
+        }
+    }
+}
@@ -3,6 +3,23 @@
 //! [(Fredman et al., 1984)]: https://dl.acm.org/doi/10.1145/828.1884
 mod core;
 pub use core::*;
+mod clone;
+mod compact;
 mod ctors;
+mod default;
 mod drop;
+mod eq;
 mod hash_map;
+mod index;
+mod inline;
+mod iter;
+mod lookup;
+mod shrink;
+mod single_pass;
+mod stats;
+mod verify;
+pub use index::*;
+pub use inline::*;
+pub use iter::*;
+pub use lookup::KeyNotFound;
+pub use single_pass::*;
@@ -0,0 +1,155 @@
+//! Implements releasing of the unused memory of [`FKSMap`].
+use crate::fks::{Bucket, FKSMap};
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+use o1_core::Hasher;
+
+impl<K: Eq, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// Shrinks the slot array so that it has only the slots addressed by the buckets.
+    ///
+    /// The slots that no bucket refers to - before, between or after the slots of the buckets -
+    /// are dropped and the offsets of the buckets are updated. Unlike [`FKSMap::compact`] the
+    /// unoccupied positions of the L2 tables keep their slots, so the hash functions and the
+    /// layout of the buckets stay the same. The bucket array is a boxed slice of the exact size
+    /// already.
+    ///
+    /// Does nothing if the map has been built by the compile-time constructor and hence does not
+    /// own its memory.
+    pub fn shrink_to_fit(&mut self) {
+        if !(self.slots.is_owned() && self.buckets.is_owned()) {
+            return;
+        }
+
+        let is_compact = self.is_compact;
+        let num_bucket_slots = |bucket: &Bucket<K, H>| {
+            // Compact maps store only the occupied slots.
+            if is_compact {
+                bucket.slots.count_ones() as usize
+            } else {
+                bucket.num_slots()
+            }
+        };
+        let num_used_slots: usize = self.buckets.iter().map(num_bucket_slots).sum();
+        if num_used_slots == self.slots.len() {
+            return;
+        }
+
+        let mut slots: Vec<MaybeUninit<(K, V)>> = Vec::with_capacity(num_used_slots);
+        for bucket in self.buckets.iter_mut() {
+            let offset = slots.len();
+            for slot_idx in bucket.offset..bucket.offset + num_bucket_slots(bucket) {
+                // SAFETY: the slot is moved bitwise and the old slots are dropped without being
+                //         read again - `MaybeUninit` doesn't drop its contents.
+                slots.push(unsafe { core::ptr::read(&self.slots[slot_idx]) });
+            }
+            bucket.offset = offset;
+        }
+
+        self.slots = slots.into();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use crate::utils::maybe_owned_slice::MaybeOwnedSliceMut;
+    use core::mem::MaybeUninit;
+    use o1_core::HashMap;
+    use o1_test::data::U64_DATA;
+
+    type Map = FKSMap<'static, u64, u64, MSPHasher<u64>>;
+
+    /// Pads the slots of the map with unused slots before the first bucket, between the buckets
+    /// and after the last one.
+    fn over_allocate(map: &mut Map) {
+        let old_slots = core::mem::replace(&mut map.slots, MaybeOwnedSliceMut::from_vec(vec![]));
+        let mut old_slots = old_slots.owned_into_vec();
+        let mut slots = Vec::new();
+        for bucket in map.buckets.iter_mut() {
+            slots.push(MaybeUninit::uninit());
+            let offset = slots.len();
+            slots.extend(old_slots.drain(..bucket.num_slots()));
+            bucket.offset = offset;
+        }
+        slots.resize_with(slots.len() + 100, MaybeUninit::uninit);
+        map.slots = slots.into();
+    }
+
+    fn assert_lookups(map: &Map) {
+        assert!(map.verify());
+        for (key, val) in &U64_DATA {
+            assert_eq!(map.get(key), Some(val), "Key: {:?}", key);
+        }
+        for key in 0..1000_u64 {
+            let expected = U64_DATA.iter().find(|(k, _)| *k == key).map(|(_, v)| v);
+            assert_eq!(map.get(&key), expected, "Key: {:?}", key);
+        }
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut map = Map::new(U64_DATA.into(), 42, 0.75).unwrap();
+        let num_slots = map.slots.len();
+        let total_memory_bytes = map.total_memory_bytes();
+        over_allocate(&mut map);
+        let over_allocated_bytes = map.total_memory_bytes();
+        assert!(over_allocated_bytes > total_memory_bytes);
+        assert_lookups(&map);
+
+        map.shrink_to_fit();
+
+        assert!(map.total_memory_bytes() < over_allocated_bytes);
+        assert_eq!(map.slots.len(), num_slots);
+        assert_eq!(map.total_memory_bytes(), total_memory_bytes);
+        assert_lookups(&map);
+        assert_eq!(map.iter().count(), U64_DATA.len());
+    }
+
+    #[test]
+    fn test_shrink_to_fit_exact_map_unchanged() {
+        let mut map = Map::new(U64_DATA.into(), 42, 0.75).unwrap();
+        let offsets: Vec<_> = map.buckets.iter().map(|b| b.offset).collect();
+        let total_memory_bytes = map.total_memory_bytes();
+
+        map.shrink_to_fit();
+
+        assert_eq!(map.total_memory_bytes(), total_memory_bytes);
+        assert!(map.buckets.iter().map(|b| b.offset).eq(offsets));
+        assert_lookups(&map);
+    }
+
+    #[test]
+    fn test_shrink_to_fit_compact() {
+        let mut map = Map::new(U64_DATA.into(), 42, 0.75).unwrap();
+        map.compact();
+        let old_slots = core::mem::replace(&mut map.slots, MaybeOwnedSliceMut::from_vec(vec![]));
+        let mut slots = old_slots.owned_into_vec();
+        slots.resize_with(slots.len() + 100, MaybeUninit::uninit);
+        map.slots = slots.into();
+
+        map.shrink_to_fit();
+
+        assert_eq!(map.slots.len(), U64_DATA.len());
+        assert_lookups(&map);
+    }
+
+    /// A map borrowing its memory can't reallocate it, like the maps of the compile-time
+    /// constructor.
+    #[test]
+    fn test_shrink_to_fit_borrowed() {
+        let mut map = Map::new(U64_DATA.into(), 42, 0.75).unwrap();
+        over_allocate(&mut map);
+        let buckets = core::mem::replace(&mut map.buckets, MaybeOwnedSliceMut::from_vec(vec![]));
+        let slots = core::mem::replace(&mut map.slots, MaybeOwnedSliceMut::from_vec(vec![]));
+        map.buckets = MaybeOwnedSliceMut::from_slice(Box::leak(buckets.owned_into_vec().into()));
+        map.slots = MaybeOwnedSliceMut::from_slice(Box::leak(slots.owned_into_vec().into()));
+        let total_memory_bytes = map.total_memory_bytes();
+
+        map.shrink_to_fit();
+
+        assert!(map.slots.is_borrowed());
+        assert_eq!(map.total_memory_bytes(), total_memory_bytes);
+        assert_lookups(&map);
+    }
+}
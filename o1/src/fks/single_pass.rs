@@ -0,0 +1,263 @@
+//! Implements a wrapper of [`FKSMap`] whose L1 and L2 hash functions share the digest of the key.
+use crate::fks::FKSMap;
+use alloc::boxed::Box;
+use core::fmt::Debug;
+use core::ops::Deref;
+use o1_core::{DigestHasher, HashMap, O1Error};
+
+/// [`FKSMap`] whose L2 hash functions share the digest function of the L1 hash function.
+///
+/// Lookups with [`SinglePassFKSMap::get_single_pass`] compute the digest of the key once and
+/// derive both the index of the bucket and the index of the slot from it - which halves the cost
+/// of the lookup for the hashers dominated by hashing the key, like
+/// [`MSPHasherDigest`](crate::hashing::hashers::msp::MSPHasherDigest) with long keys.
+///
+/// The lookup is only correct if every L2 hash function shares the digest, which the maps built
+/// by [`FKSMap::new`] don't - so it's available only on this type, and the methods rebuilding the
+/// map keep the digest shared. The rest of the methods of [`FKSMap`] are available through
+/// [`Deref`].
+///
+/// There is no compile-time constructor.
+///
+/// # Examples
+///
+/// ```rust
+/// use o1::fks::SinglePassFKSMap;
+/// use o1::hashing::hashers::msp::MSPHasherDigest;
+///
+/// let map = SinglePassFKSMap::<&str, u32, MSPHasherDigest<&str>>::new(
+///     [("one", 1), ("two", 2), ("three", 3)].into(),
+///     42,
+///     0.75,
+/// ).unwrap();
+///
+/// assert_eq!(map.get_single_pass(&"two"), Some(&2));
+/// assert_eq!(map.get_single_pass(&"four"), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SinglePassFKSMap<'a, K: Eq, V, H: DigestHasher<K>> {
+    map: FKSMap<'a, K, V, H>,
+}
+
+impl<K: Eq + Debug, V, H: DigestHasher<K>> SinglePassFKSMap<'_, K, V, H> {
+    /// Creates a new [`SinglePassFKSMap`] with the given data, seed, and minimum load factor.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    /// - `min_load_factor`: The minimum load factor.
+    ///
+    /// # Errors
+    ///
+    /// The same as of [`FKSMap::new`].
+    pub fn new(data: Box<[(K, V)]>, seed: u64, min_load_factor: f32) -> Result<Self, O1Error> {
+        let map = FKSMap::new_single_pass(data, seed, min_load_factor)?;
+        Ok(Self { map })
+    }
+
+    /// Creates a new [`SinglePassFKSMap`] with the given data and seed and with the default
+    /// minimum load factor - [`FKSMap::DEFAULT_MIN_LOAD_FACTOR`].
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The data to be hashed.
+    /// - `seed`: The seed for the random number generator.
+    pub fn with_seed(data: Box<[(K, V)]>, seed: u64) -> Result<Self, O1Error> {
+        Self::new(data, seed, FKSMap::<K, V, H>::DEFAULT_MIN_LOAD_FACTOR)
+    }
+}
+
+impl<'a, K: Eq, V, H: DigestHasher<K>> SinglePassFKSMap<'a, K, V, H> {
+    /// Get the value associated with the given `key` computing the digest of the key only once.
+    ///
+    /// Equivalent to [`get()`](o1_core::HashMap::get).
+    #[inline]
+    pub fn get_single_pass(&self, key: &K) -> Option<&V> {
+        self.map.get_single_pass(key)
+    }
+
+    /// Compacts the map - see [`FKSMap::compact`].
+    ///
+    /// Compaction keeps the hash functions, so the digest stays shared.
+    pub fn compact(&mut self) {
+        self.map.compact();
+    }
+
+    /// Converts the map into the underlying [`FKSMap`].
+    pub fn into_map(self) -> FKSMap<'a, K, V, H> {
+        self.map
+    }
+}
+
+impl<K, V, H> SinglePassFKSMap<'_, K, V, H>
+where
+    K: Eq + Debug + Clone,
+    V: Clone,
+    H: DigestHasher<K> + Clone,
+{
+    /// Rebuilds the map keeping only the entries for which `f` returns `true` - see
+    /// [`FKSMap::retain`].
+    ///
+    /// The L2 hash functions of the new map share the digest of its L1 hash function too.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: The predicate deciding which entries to keep.
+    /// - `seed`: The seed for the random number generator of the new map.
+    ///
+    /// # Errors
+    ///
+    /// The same as of [`FKSMap::new`].
+    pub fn retain<F: FnMut(&K, &V) -> bool>(self, f: F, seed: u64) -> Result<Self, O1Error> {
+        let min_load_factor = self.map.min_load_factor;
+        Self::new(self.map.retained_entries(f), seed, min_load_factor)
+    }
+
+    /// Rebuilds the map with the entries of `extra` added to it - see [`FKSMap::extended`].
+    ///
+    /// The L2 hash functions of the new map share the digest of its L1 hash function too.
+    ///
+    /// # Parameters
+    ///
+    /// - `extra`: The entries to add. Their keys must be distinct.
+    /// - `seed`: The seed for the random number generator of the new map.
+    ///
+    /// # Errors
+    ///
+    /// The same as of [`FKSMap::new`].
+    pub fn extended<I: IntoIterator<Item = (K, V)>>(
+        self,
+        extra: I,
+        seed: u64,
+    ) -> Result<Self, O1Error> {
+        let min_load_factor = self.map.min_load_factor;
+        Self::new(self.map.extended_entries(extra), seed, min_load_factor)
+    }
+}
+
+impl<'a, K: Eq, V, H: DigestHasher<K>> Deref for SinglePassFKSMap<'a, K, V, H> {
+    type Target = FKSMap<'a, K, V, H>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<K: Eq + Debug, V, H: DigestHasher<K>> HashMap<K, V, H> for SinglePassFKSMap<'_, K, V, H> {
+    fn get(&self, key: &K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        self.map.get_key_value(key)
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    fn load_factor(&self) -> f64 {
+        self.map.load_factor()
+    }
+
+    fn num_collisions(&self) -> usize {
+        self.map.num_collisions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::hashers::msp::MSPHasherDigest;
+    use alloc::string::String;
+    use o1_test::{generate_map_str_special_tests, generate_map_str_tests};
+
+    fn factory<'a, K: Eq + Debug, V: Copy + Debug, H: DigestHasher<K>>(
+        data: Box<[(K, V)]>,
+    ) -> SinglePassFKSMap<'a, K, V, H> {
+        SinglePassFKSMap::new(data, 0, 0.75).unwrap()
+    }
+
+    generate_map_str_tests!(SinglePassFKSMap, MSPHasherDigest, factory);
+    generate_map_str_special_tests!(SinglePassFKSMap, MSPHasherDigest, factory);
+
+    fn key(i: u32) -> String {
+        format!("{i:08}").repeat(128)
+    }
+
+    #[test]
+    fn test_get_single_pass_equivalent_to_get() {
+        let data: Box<[(String, u32)]> = (0..1000_u32).map(|i| (key(i), i)).collect();
+        let mut map =
+            SinglePassFKSMap::<String, u32, MSPHasherDigest<String>>::new(data, 42, 0.75).unwrap();
+
+        for i in 0..1100_u32 {
+            let key = key(i);
+            assert_eq!(map.get_single_pass(&key), map.get(&key), "Key: {:?}", i);
+            assert_eq!(
+                map.get_single_pass(&key).is_some(),
+                i < 1000,
+                "Key: {:?}",
+                i
+            );
+        }
+        for len in [0, 1, 8, 256, 4096] {
+            assert_eq!(map.get_single_pass(&"a".repeat(len)), None);
+        }
+
+        map.compact();
+        for i in 0..1000_u32 {
+            assert_eq!(map.get_single_pass(&key(i)), Some(&i));
+        }
+    }
+
+    #[test]
+    fn test_retain_keeps_digest_shared() {
+        let data: Box<[(String, u32)]> = (0..1000_u32).map(|i| (key(i), i)).collect();
+        let map =
+            SinglePassFKSMap::<String, u32, MSPHasherDigest<String>>::new(data, 42, 0.9).unwrap();
+
+        let map = map.retain(|_, v| v % 3 != 0, 7).unwrap();
+
+        assert_eq!(map.min_load_factor, 0.9);
+        for i in 0..1000_u32 {
+            let expected = (i % 3 != 0).then_some(i);
+            assert_eq!(
+                map.get_single_pass(&key(i)).copied(),
+                expected,
+                "Key: {:?}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn test_extended_keeps_digest_shared() {
+        let data: Box<[(String, u32)]> = (0..500_u32).map(|i| (key(i), i)).collect();
+        let map =
+            SinglePassFKSMap::<String, u32, MSPHasherDigest<String>>::new(data, 42, 0.75).unwrap();
+
+        let map = map
+            .extended((250..1000_u32).map(|i| (key(i), i + 1)), 7)
+            .unwrap();
+
+        for i in 0..1000_u32 {
+            let expected = if i < 250 { i } else { i + 1 };
+            assert_eq!(
+                map.get_single_pass(&key(i)),
+                Some(&expected),
+                "Key: {:?}",
+                i
+            );
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Implements statistics describing how efficiently [`FKSMap`] uses its memory and how the keys
+//! are distributed over its buckets.
+use crate::fks::{Bucket, FKSMap};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use bitvec::prelude::*;
+use core::mem::size_of;
+use o1_core::Hasher;
+
+impl<K: Eq, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// Ratio of the number of entries to the total number of slots.
+    ///
+    /// Unlike [`HashMap::load_factor()`](o1_core::HashMap::load_factor) - the ratio of slots to
+    /// buckets - it reaches `1.0` only when no slot is wasted, e.g. once the map is compact.
+    pub fn slot_utilization(&self) -> f32 {
+        if self.slots.is_empty() {
+            return 0.0;
+        }
+        self.len as f32 / self.slots.len() as f32
+    }
+
+    /// Ratio of the number of non-empty buckets to the total number of buckets.
+    pub fn bucket_occupancy(&self) -> f32 {
+        if self.buckets.is_empty() {
+            return 0.0;
+        }
+        let num_occupied = self.buckets.iter().filter(|b| b.slots != 0).count();
+        num_occupied as f32 / self.buckets.len() as f32
+    }
+
+    /// Number of bytes occupied by the map including its bucket and slot arrays.
+    ///
+    /// The arrays reside on the heap if the map owns its memory or in static memory if it has
+    /// been built by the compile-time constructor - either way they are accounted for.
+    /// The memory referenced by the keys and values themselves (e.g. contents of `String`s)
+    /// isn't included.
+    pub fn total_memory_bytes(&self) -> usize {
+        size_of::<Self>()
+            + size_of::<Bucket<K, H>>() * self.buckets.len()
+            + size_of::<(K, V)>() * self.slots.len()
+    }
+
+    /// Number of keys in each of the L1 buckets.
+    ///
+    /// Helps to find the overloaded buckets when the construction of a map is slow.
+    pub fn bucket_sizes(&self) -> Vec<usize> {
+        self.buckets
+            .iter()
+            .map(|b| b.slots.count_ones() as usize)
+            .collect()
+    }
+
+    /// Index of the L1 bucket the key maps to - regardless of whether the key is in the map.
+    pub fn bucket_of(&self, key: &K) -> usize {
+        self.l1_hasher.hash(key) as usize
+    }
+
+    /// Marks which of the slots are occupied by entries and which are gaps.
+    ///
+    /// Has an element per slot, so it allows to keep the values in an external array indexed the
+    /// same way as the slots.
+    pub fn occupancy_mask(&self) -> Box<[bool]> {
+        let mut mask = vec![false; self.slots.len()];
+        for bucket in self.buckets.iter() {
+            for pos in bucket.slots.view_bits::<Lsb0>().iter_ones() {
+                mask[bucket.slot_idx(pos, self.is_compact)] = true;
+            }
+        }
+        mask.into_boxed_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_core::{HashMap, Hasher};
+    use o1_test::data::{STR_DATA, U64_DATA};
+
+    /// Every L2 table of [`MSPHasher`] rounds its number of slots up to a power of two and has at
+    /// least 2 slots - at most twice the number of its keys, so at least half of the slots are
+    /// occupied regardless of `min_load_factor`.
+    #[test]
+    fn test_slot_utilization_within_bounds() {
+        for min_load_factor in [0.1, 0.25, 0.5, 0.75, 0.9] {
+            let maps = [
+                FKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, min_load_factor)
+                    .unwrap()
+                    .slot_utilization(),
+                FKSMap::<&str, u64, MSPHasher<&str>>::new(STR_DATA.into(), 42, min_load_factor)
+                    .unwrap()
+                    .slot_utilization(),
+            ];
+            for slot_utilization in maps {
+                let min_slot_utilization = min_load_factor.min(0.5);
+                assert!(
+                    (min_slot_utilization..=1.0).contains(&slot_utilization),
+                    "Slot utilization {slot_utilization} is out of [{min_slot_utilization}, 1.0]"
+                );
+            }
+        }
+
+        let mut map = FKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        assert!(map.slot_utilization() < 1.0);
+        map.compact();
+        assert_eq!(map.slot_utilization(), 1.0);
+    }
+
+    #[test]
+    fn test_load_factor() {
+        let map = FKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        assert_eq!(
+            map.load_factor(),
+            map.slots.len() as f64 / map.buckets.len() as f64
+        );
+    }
+
+    #[test]
+    fn test_bucket_sizes() {
+        let mut map = FKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        let bucket_sizes = map.bucket_sizes();
+        assert_eq!(bucket_sizes.len(), map.buckets.len());
+        assert_eq!(bucket_sizes.iter().sum::<usize>(), U64_DATA.len());
+
+        // Only a compact map has no empty slots.
+        map.compact();
+        assert_eq!(map.bucket_sizes(), bucket_sizes);
+        assert_eq!(bucket_sizes.iter().sum::<usize>(), map.len());
+    }
+
+    #[test]
+    fn test_bucket_of() {
+        let map = FKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        let mut bucket_sizes = vec![0; map.buckets.len()];
+        for (key, _) in &U64_DATA {
+            let bucket_idx = map.bucket_of(key);
+            assert_eq!(bucket_idx, map.l1_hasher.hash(key) as usize);
+            bucket_sizes[bucket_idx] += 1;
+        }
+        assert_eq!(bucket_sizes, map.bucket_sizes());
+    }
+
+    #[test]
+    fn test_occupancy_mask() {
+        let mut map = FKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        for is_compact in [false, true] {
+            if is_compact {
+                map.compact();
+            }
+            let mask = map.occupancy_mask();
+            assert_eq!(mask.len(), map.capacity());
+            assert_eq!(mask.iter().filter(|&&b| b).count(), U64_DATA.len());
+            assert_eq!(mask.iter().all(|&b| b), is_compact);
+
+            for (slot_idx, _) in mask.iter().enumerate().filter(|(_, &b)| b) {
+                let (key, value) = unsafe { map.slots[slot_idx].assume_init_ref() };
+                assert!(
+                    map.get(key).is_some_and(|v| core::ptr::eq(v, value)),
+                    "Slot: {slot_idx}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bucket_occupancy() {
+        let map = FKSMap::<u64, u64, MSPHasher<u64>>::new(U64_DATA.into(), 42, 0.75).unwrap();
+        let bucket_occupancy = map.bucket_occupancy();
+        assert!(bucket_occupancy > 0.0 && bucket_occupancy <= 1.0);
+
+        let num_occupied = (bucket_occupancy * map.buckets.len() as f32).round() as usize;
+        assert!(num_occupied <= U64_DATA.len());
+    }
+}
@@ -0,0 +1,135 @@
+//! Implements the integrity check of [`FKSMap`].
+use crate::fks::{Bucket, FKSMap};
+use bitvec::prelude::*;
+use o1_core::Hasher;
+
+impl<K: Eq, V, H: Hasher<K>> FKSMap<'_, K, V, H> {
+    /// Re-check the perfect hashing invariant of the map.
+    ///
+    /// Re-hashes every stored key with the L1 hash function and the L2 hash function of its
+    /// bucket and confirms that the key resolves to its own slot - so no two stored keys collide
+    /// and every one of them is reachable by lookups. It catches corruption caused by a bad
+    /// deserialization or an incorrect manual construction of the map.
+    ///
+    /// # Notes
+    ///
+    /// - The bit-masks of the buckets are checked to stay within the slot array, but otherwise
+    ///   they are trusted to mark the initialized slots only, since the keys are read from them.
+    pub fn verify(&self) -> bool {
+        if self.buckets.len() != self.l1_hasher.num_buckets() as usize {
+            return false;
+        }
+        self.buckets
+            .iter()
+            .enumerate()
+            .all(|(bucket_idx, bucket)| self.verify_bucket(bucket_idx, bucket))
+    }
+
+    /// Check that the bucket `bucket_idx` is laid out correctly and its keys resolve to their
+    /// own slots.
+    fn verify_bucket(&self, bucket_idx: usize, bucket: &Bucket<K, H>) -> bool {
+        let num_slots = bucket.num_slots();
+        // Compact maps store only the occupied slots.
+        let num_data_slots = if self.is_compact {
+            bucket.slots.count_ones() as usize
+        } else {
+            num_slots
+        };
+        let is_in_bounds = num_slots <= u16::BITS as usize
+            && bucket
+                .offset
+                .checked_add(num_data_slots)
+                .is_some_and(|end| end <= self.slots.len())
+            && (bucket.slots as u32) >> num_slots == 0;
+        // Lookups index the bit-mask with the L2 hash values directly.
+        if !is_in_bounds || (num_slots > 1 && bucket.hasher.num_buckets() as usize > num_slots) {
+            return false;
+        }
+
+        bucket.slots.view_bits::<Lsb0>().iter_ones().all(|pos| {
+            let data_idx = bucket.slot_idx(pos, self.is_compact);
+            let (key, _) = unsafe { self.slots[data_idx].assume_init_ref() };
+            self.l1_hasher.hash(key) as usize == bucket_idx
+                && self.candidate_slot_idx(bucket_idx, key) == Some(data_idx)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fks::FKSMap;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use crate::new_fks_map;
+    use o1_core::Hasher;
+    use o1_test::data::U64_DATA;
+
+    type Map = FKSMap<'static, u64, u64, MSPHasher<u64>>;
+
+    fn make_map() -> Map {
+        Map::new(U64_DATA.into(), 42, 0.75).unwrap()
+    }
+
+    /// Indices of the buckets that have at least one occupied slot.
+    fn occupied_buckets(map: &Map) -> Vec<usize> {
+        (0..map.buckets.len())
+            .filter(|&i| map.buckets[i].slots != 0)
+            .collect()
+    }
+
+    new_fks_map!(STATIC_MAP, u64, u64, U64_DATA, MSPHasher<u64>, 42, 0.75);
+
+    #[test]
+    fn test_verify_valid_maps() {
+        let map = make_map();
+        assert!(map.verify());
+        assert!(map.clone().verify());
+        assert!(STATIC_MAP.verify());
+        assert!(Map::default().verify());
+    }
+
+    #[test]
+    fn test_verify_swapped_slots() {
+        let mut map = make_map();
+        let buckets = occupied_buckets(&map);
+        let first = map.buckets[buckets[0]].offset
+            + map.buckets[buckets[0]].slots.trailing_zeros() as usize;
+        let second = map.buckets[buckets[1]].offset
+            + map.buckets[buckets[1]].slots.trailing_zeros() as usize;
+        map.slots.swap(first, second);
+
+        assert!(!map.verify());
+    }
+
+    #[test]
+    fn test_verify_duplicate_key() {
+        let mut map = make_map();
+        let bucket_idx = *occupied_buckets(&map)
+            .iter()
+            .find(|&&i| map.buckets[i].slots.count_ones() >= 2)
+            .unwrap();
+        let bucket = &map.buckets[bucket_idx];
+        let first = bucket.offset + bucket.slots.trailing_zeros() as usize;
+        let last = bucket.offset + (u16::BITS - 1 - bucket.slots.leading_zeros()) as usize;
+        let entry = unsafe { map.slots[first].assume_init_read() };
+        map.slots[last].write(entry);
+
+        assert!(!map.verify());
+    }
+
+    #[test]
+    fn test_verify_corrupted_buckets() {
+        let mut map = make_map();
+        let bucket_idx = occupied_buckets(&map)[0];
+        let bucket = &mut map.buckets[bucket_idx];
+        let original = bucket.slots;
+        // Marks a slot beyond the end of the bucket as occupied.
+        bucket.slots |= 1 << bucket.num_slots;
+        assert!(!map.verify());
+        map.buckets[bucket_idx].slots = original;
+        assert!(map.verify());
+
+        let mut map = make_map();
+        map.l1_hasher = MSPHasher::from_seed(7, map.l1_hasher.num_buckets());
+        assert!(!map.verify());
+    }
+}
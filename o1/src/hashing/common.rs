@@ -1,3 +1,41 @@
+use alloc::borrow::Cow;
+
+/// Extract the top `num_bits` bits from a `SOURCE_BITS`-bit value.
+///
+/// Useful as a faster alternative to the modulo operation of this kind: `value % (2 ** num_bits)`.
+///
+/// The rest of the `extract_bits*` functions are thin wrappers over this one.
+#[inline]
+pub const fn extract_bits<const SOURCE_BITS: u32>(value: u128, num_bits: u32) -> u64 {
+    debug_assert!(SOURCE_BITS <= 128, r#""SOURCE_BITS" must be <= 128"#);
+    debug_assert!(
+        num_bits <= 64 && num_bits <= SOURCE_BITS,
+        r#""num_bits" must be <= min(64, "SOURCE_BITS")"#
+    );
+
+    (value >> (SOURCE_BITS - num_bits)) as u64
+}
+
+/// Extract the top `num_bits` bits from a `SOURCE_BITS`-bit value into a `u16`.
+///
+/// Suitable for small numbers of buckets - up to `2 ** 16`.
+#[inline]
+pub const fn extract_bits_16<const SOURCE_BITS: u32>(value: u128, num_bits: u32) -> u16 {
+    debug_assert!(num_bits <= 16, r#""num_bits" must be <= 16"#);
+
+    extract_bits::<SOURCE_BITS>(value, num_bits) as u16
+}
+
+/// Extract the top `num_bits` bits from a `SOURCE_BITS`-bit value into a `u8`.
+///
+/// Suitable for small numbers of buckets - up to `2 ** 8`.
+#[inline]
+pub const fn extract_bits_8<const SOURCE_BITS: u32>(value: u128, num_bits: u32) -> u8 {
+    debug_assert!(num_bits <= 8, r#""num_bits" must be <= 8"#);
+
+    extract_bits::<SOURCE_BITS>(value, num_bits) as u8
+}
+
 /// Extract the top `num_bits` bits from a 64-bit value.
 ///
 /// Useful as a faster alternative to the modulo operation of this kind: `value % (2 ** num_bits)`.
@@ -5,27 +43,367 @@
 pub const fn extract_bits_64<const SOURCE_BITS: u32>(value: u64, num_bits: u32) -> u32 {
     debug_assert!(num_bits <= 32, r#""num_bits" must be <= 32"#);
 
-    (value >> (SOURCE_BITS - num_bits)) as u32
+    extract_bits::<SOURCE_BITS>(value as u128, num_bits) as u32
+}
+
+/// Extract the lowest `num_bits` bits from a 64-bit value.
+///
+/// The counterpart of [`extract_bits_64`] for combining the value with another hash value
+/// when the high bits are already consumed.
+///
+/// # Notes
+///
+/// Multiply-shift is universal only for the *top* bits of the product: the lowest `num_bits` bits
+/// of `a * x` depend only on the lowest `num_bits` bits of `x`, so all keys that differ only in
+/// the higher bits collide regardless of the seed. Never use it to derive bucket or slot indices
+/// (e.g. of [`FKSMap`](crate::fks::FKSMap)) from multiply-shift products - only from outputs of
+/// hash functions whose every bit is uniform.
+#[inline]
+pub const fn extract_low_bits_64(value: u64, num_bits: u32) -> u32 {
+    debug_assert!(num_bits <= 32, r#""num_bits" must be <= 32"#);
+
+    (value & ((1 << num_bits) - 1)) as u32
+}
+
+/// Extract the top `num_bits` bits from a 64-bit value, allowing up to 64 bits of output.
+#[inline]
+pub const fn extract_bits_64_wide<const SOURCE_BITS: u32>(value: u64, num_bits: u32) -> u64 {
+    extract_bits::<SOURCE_BITS>(value as u128, num_bits)
 }
 
 #[inline]
 pub const fn extract_bits_128<const SOURCE_BITS: u32>(value: u128, num_bits: u32) -> u32 {
     debug_assert!(num_bits <= 32, r#""num_bits" must be <= 32"#);
 
-    (value >> (SOURCE_BITS - num_bits)) as u32
+    extract_bits::<SOURCE_BITS>(value, num_bits) as u32
 }
 
 /// Calculate the number of bits required to represent a given number of buckets.
-#[allow(dead_code)]
 pub const fn num_bits_for_buckets(num_buckets: u32) -> u32 {
     match num_buckets {
         0 => 0,
         1 => 1,
-        _ => num_buckets.next_power_of_two().ilog2(),
+        // Equivalent to `next_power_of_two().ilog2()`, but doesn't overflow above `2 ** 31`.
+        _ => u32::BITS - (num_buckets - 1).leading_zeros(),
     }
 }
 
-/// Calculate the number of bits required to represent a given number of buckets.
+/// Calculate the number of buckets addressable by a given number of bits.
+///
+/// Saturates at `u32::MAX` for 32 bits, since `2 ** 32` isn't representable as `u32`.
 pub const fn num_buckets_for_bits(num_bits: u32) -> u32 {
+    if num_bits >= u32::BITS {
+        return u32::MAX;
+    }
     1 << num_bits
 }
+
+/// Clamp the number of bits of a hash value to the range `[1, 32]` supported by the hashers.
+///
+/// Hashers derive the number of bits from the requested number of buckets, so it ensures that
+/// a hasher is valid even for a degenerate number of buckets (e.g. `0` for an empty map).
+pub const fn clamp_num_bits(num_bits: u32) -> u32 {
+    if num_bits < 1 {
+        1
+    } else if num_bits > 32 {
+        32
+    } else {
+        num_bits
+    }
+}
+
+/// Calculate the number of buckets required to hold `len` values under the given load factor.
+///
+/// Rounds up, so that the resulting load factor never exceeds `load_factor`. The result is the
+/// raw number of buckets, hashers may round it up further.
+///
+/// The division is performed on integers - `load_factor` is decomposed into its mantissa and
+/// exponent, so the result is exact for any `len` and saturates at `u32::MAX`.
+///
+/// # Parameters
+///
+/// - `len`: The number of values.
+/// - `load_factor`: The desired load factor, must be within `(0.0, 1.0]`.
+#[inline]
+pub const fn num_buckets_for_len(len: usize, load_factor: f32) -> u32 {
+    debug_assert!(
+        load_factor > 0.0 && load_factor <= 1.0,
+        r#""load_factor" must be within (0.0, 1.0]"#
+    );
+
+    // `load_factor == mantissa / 2 ** shift`.
+    let bits = load_factor.to_bits();
+    let biased_exponent = (bits >> 23) & 0xFF;
+    let (mantissa, shift) = if biased_exponent == 0 {
+        (bits & 0x7F_FFFF, 149)
+    } else {
+        ((bits & 0x7F_FFFF) | 0x80_0000, 150 - biased_exponent)
+    };
+    if len == 0 {
+        return 0;
+    }
+    // `mantissa < 2 ** 24`, so the result exceeds `u32::MAX` for any non-zero `len`.
+    if shift >= 64 {
+        return u32::MAX;
+    }
+
+    let num_buckets = ((len as u128) << shift).div_ceil(mantissa as u128);
+    if num_buckets > u32::MAX as u128 {
+        u32::MAX
+    } else {
+        num_buckets as u32
+    }
+}
+
+/// A borrowed or owned string.
+///
+/// The const methods of the hashers take the keys by reference, so the ones for [`Cow`] keys
+/// have to take `&Cow` - spelled through this alias, which `clippy::ptr_arg` doesn't flag.
+pub type CowStr<'a> = Cow<'a, str>;
+
+/// A borrowed or owned byte-slice - see [`CowStr`].
+pub type CowBytes<'a> = Cow<'a, [u8]>;
+
+/// Returns the bytes of a [`CowStr`] regardless of whether it's borrowed or owned.
+#[inline]
+pub const fn cow_str_as_bytes<'b>(value: &'b CowStr<'_>) -> &'b [u8] {
+    match value {
+        Cow::Borrowed(value) => value.as_bytes(),
+        Cow::Owned(value) => value.as_bytes(),
+    }
+}
+
+/// Returns the bytes of a [`CowBytes`] regardless of whether it's borrowed or owned.
+#[inline]
+pub const fn cow_bytes_as_slice<'b>(value: &'b CowBytes<'_>) -> &'b [u8] {
+    match value {
+        Cow::Borrowed(value) => value,
+        Cow::Owned(value) => value.as_slice(),
+    }
+}
+
+/// Packs an array of `bool`s into bytes, the first `bool` goes to the lowest bit of the first byte.
+///
+/// Only the first `N.div_ceil(8)` bytes of the returned array are used - the array can't be
+/// sized by them without `generic_const_exprs`.
+pub const fn pack_bools<const N: usize>(value: &[bool; N]) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    let mut i = 0;
+    while i < N {
+        bytes[i / 8] |= (value[i] as u8) << (i % 8);
+        i += 1;
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_extract_bits_equivalent_to_extract_bits_64() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        for _ in 0..1000 {
+            let value = rng.random::<u64>();
+            for num_bits in 1..=32 {
+                let expected = extract_bits_64::<{ u64::BITS }>(value, num_bits);
+                assert_eq!(
+                    extract_bits::<{ u64::BITS }>(value as u128, num_bits),
+                    expected as u64
+                );
+                assert_eq!(
+                    extract_bits_64_wide::<{ u64::BITS }>(value, num_bits),
+                    expected as u64
+                );
+                if num_bits <= 16 {
+                    assert_eq!(
+                        extract_bits_16::<{ u64::BITS }>(value as u128, num_bits),
+                        expected as u16
+                    );
+                }
+                if num_bits <= 8 {
+                    assert_eq!(
+                        extract_bits_8::<{ u64::BITS }>(value as u128, num_bits),
+                        expected as u8
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_extract_bits_equivalent_to_extract_bits_128() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        for _ in 0..1000 {
+            let value = rng.random::<u128>() >> (u128::BITS - 89);
+            for num_bits in 1..=32 {
+                let expected = extract_bits_128::<89>(value, num_bits);
+                assert_eq!(extract_bits::<89>(value, num_bits), expected as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_num_bits_round_trip() {
+        for num_bits in 1..=32 {
+            let num_buckets = num_buckets_for_bits(num_bits);
+            assert_eq!(
+                num_bits_for_buckets(num_buckets),
+                num_bits,
+                "Bits: {num_bits}"
+            );
+            assert_eq!(
+                clamp_num_bits(num_bits_for_buckets(num_buckets)),
+                num_bits,
+                "Bits: {num_bits}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_num_bits_for_buckets_rounds_up() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        for _ in 0..10000 {
+            let num_buckets = rng.random_range(2..=u32::MAX);
+            let num_bits = num_bits_for_buckets(num_buckets);
+            assert!(num_buckets_for_bits(num_bits) >= num_buckets);
+            assert!(num_buckets_for_bits(num_bits - 1) < num_buckets);
+        }
+    }
+
+    #[test]
+    fn test_clamp_num_bits() {
+        assert_eq!(clamp_num_bits(num_bits_for_buckets(0)), 1);
+        assert_eq!(clamp_num_bits(num_bits_for_buckets(1)), 1);
+        assert_eq!(clamp_num_bits(num_bits_for_buckets(u32::MAX)), 32);
+        assert_eq!(clamp_num_bits(33), 32);
+    }
+
+    #[test]
+    fn test_extract_bits_top_bits() {
+        assert_eq!(extract_bits::<{ u128::BITS }>(u128::MAX, 64), u64::MAX);
+        assert_eq!(extract_bits::<{ u64::BITS }>(1 << 63, 1), 1);
+        assert_eq!(extract_bits_16::<{ u32::BITS }>(0xABCD_0123, 16), 0xABCD);
+        assert_eq!(extract_bits_8::<{ u16::BITS }>(0xAB01, 8), 0xAB);
+    }
+
+    #[test]
+    fn test_extract_low_bits_64() {
+        assert_eq!(extract_low_bits_64(0xABCD_0123_4567_89EF, 0), 0);
+        assert_eq!(extract_low_bits_64(0xABCD_0123_4567_89EF, 4), 0xF);
+        assert_eq!(extract_low_bits_64(0xABCD_0123_4567_89EF, 16), 0x89EF);
+        assert_eq!(extract_low_bits_64(0xABCD_0123_4567_89EF, 32), 0x4567_89EF);
+        assert_eq!(extract_low_bits_64(u64::MAX, 32), u32::MAX);
+
+        let mut rng = ChaCha20Rng::from_os_rng();
+        for _ in 0..1000 {
+            let value = rng.random::<u64>();
+            for num_bits in 1..=32 {
+                let low_bits = extract_low_bits_64(value, num_bits);
+                assert!((low_bits as u64) < (1 << num_bits));
+                assert_eq!(low_bits as u64, value % (1 << num_bits));
+            }
+        }
+    }
+
+    /// The low bits of a multiply-shift product don't depend on the high bits of the key, so they
+    /// must not be used as a bucket index.
+    #[test]
+    fn test_extract_low_bits_64_of_multiply_shift_collide() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+        let num_bits = 16;
+        for _ in 0..1000 {
+            let seed = rng.random::<u64>() | 1;
+            let key = rng.random::<u64>();
+            let other_key = key ^ (1 << 63);
+            let product = seed.wrapping_mul(key);
+            let other_product = seed.wrapping_mul(other_key);
+
+            assert_eq!(
+                extract_low_bits_64(product, num_bits),
+                extract_low_bits_64(other_product, num_bits),
+            );
+            assert_ne!(
+                extract_bits_64::<{ u64::BITS }>(product, num_bits),
+                extract_bits_64::<{ u64::BITS }>(other_product, num_bits),
+            );
+        }
+    }
+
+    #[test]
+    fn test_num_buckets_for_len() {
+        const CASES: [(usize, f32, u32); 9] = [
+            (0, 1.0, 0),
+            (0, 0.5, 0),
+            (1, 1.0, 1),
+            (1, 0.5, 2),
+            (3, 0.75, 4),
+            (999, 1.0, 999),
+            (999, 0.5, 1998),
+            (1000, 0.75, 1334),
+            (2000, 0.3, 6667),
+        ];
+
+        for (len, load_factor, expected) in CASES {
+            let num_buckets = num_buckets_for_len(len, load_factor);
+            assert_eq!(
+                num_buckets, expected,
+                "Len: {len}, load factor: {load_factor}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_num_buckets_for_len_large() {
+        const CASES: [(usize, f32, u32); 6] = [
+            (2148, 1.0, 2148),
+            (100_000, 0.75, 133_334),
+            (1 << 30, 0.5, 1 << 31),
+            (u32::MAX as usize, 1.0, u32::MAX),
+            (u32::MAX as usize, 0.5, u32::MAX),
+            (1, 1e-30, u32::MAX),
+        ];
+
+        for (len, load_factor, expected) in CASES {
+            assert_eq!(
+                num_buckets_for_len(len, load_factor),
+                expected,
+                "Len: {len}, load factor: {load_factor}"
+            );
+        }
+        for len in (0..1_000_000).step_by(997) {
+            for load_factor in [1.0_f32, 0.9, 0.75, 0.5, 0.3, 0.1, 1e-3] {
+                let expected = (len as f64 / load_factor as f64).ceil() as u32;
+                assert_eq!(
+                    num_buckets_for_len(len, load_factor),
+                    expected,
+                    "Len: {len}, load factor: {load_factor}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_num_buckets_for_len_const() {
+        const NUM_BUCKETS: u32 = num_buckets_for_len(100, 0.5);
+        assert_eq!(NUM_BUCKETS, 200);
+    }
+
+    #[test]
+    fn test_pack_bools() {
+        let mut value = [false; 13];
+        value[0] = true;
+        value[9] = true;
+        value[12] = true;
+        let bytes = pack_bools(&value);
+
+        assert_eq!(bytes[..2], [0b0000_0001, 0b0001_0010]);
+        assert!(bytes[2..].iter().all(|&byte| byte == 0));
+        assert_eq!(pack_bools(&[]), []);
+    }
+}
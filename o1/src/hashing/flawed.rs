@@ -43,4 +43,26 @@ mod tests {
             0.01,
         );
     }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(not(feature = "_slow-tests"), ignore)]
+    fn test_lowest_bits_avalanche() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        avalanche::<ChaCha20Rng, u64>(
+            &mut rng,
+            &|_, num_buckets| {
+                let num_bits = num_bits_for_buckets(num_buckets as u32);
+                (
+                    Box::new(move |value: &u64| lowest_bits(*value, num_bits) as usize),
+                    num_buckets_for_bits(num_bits) as usize,
+                )
+            },
+            1 << 16,
+            10000,
+            32,
+            0.02,
+        );
+    }
 }
@@ -0,0 +1,235 @@
+//! Implements Hasher based on the aHash hash function.
+//!
+//! aHash is fast on short keys, especially with the AES instructions available, but its output
+//! isn't specified - it differs between the versions of the crate, the platforms and the enabled
+//! target features. Use it for non-adversarial workloads where the hash values don't have to be
+//! reproducible. There is no const implementation.
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, num_bits_for_buckets, num_buckets_for_bits,
+};
+use ahash::RandomState;
+use alloc::string::String;
+use core::fmt::{Debug, Formatter};
+use core::hash::{BuildHasher, Hasher as _};
+use o1_core::Hasher;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// Hasher based on aHash algorithm.
+///
+/// Integers and arrays of integers are hashed with their [`Hash`](core::hash::Hash)
+/// implementations, byte-strings are hashed as their bytes.
+///
+/// Contains only the runtime implementation - it can't be used with the compile-time
+/// constructors of the maps.
+#[derive(Clone)]
+pub struct AHashHasher<T: Eq>
+where
+    AHashHasher<T>: Hasher<T>,
+{
+    state: <AHashHasher<T> as Hasher<T>>::State,
+}
+
+impl<T: Eq + Clone> Copy for AHashHasher<T>
+where
+    AHashHasher<T>: Hasher<T>,
+    <AHashHasher<T> as Hasher<T>>::State: Copy,
+{
+}
+
+impl<T: Eq> Default for AHashHasher<T>
+where
+    AHashHasher<T>: Hasher<T>,
+{
+    fn default() -> Self {
+        <Self as Hasher<T>>::from_state(<Self as Hasher<T>>::State::default())
+    }
+}
+
+impl<T> Debug for AHashHasher<T>
+where
+    T: Eq,
+    AHashHasher<T>: Hasher<T>,
+    <AHashHasher<T> as Hasher<T>>::State: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AHashHasher")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// State of [`AHashHasher`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AHashState {
+    num_bits: u32,
+    /// The four 64-bit keys of the [`RandomState`].
+    keys: [u64; 4],
+}
+
+impl AHashState {
+    pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            num_bits,
+            keys: rng.random(),
+        }
+    }
+
+    #[inline]
+    fn random_state(&self) -> RandomState {
+        let [k0, k1, k2, k3] = self.keys;
+        RandomState::with_seeds(k0, k1, k2, k3)
+    }
+
+    #[inline]
+    fn reduce(&self, hash_value: u64) -> u32 {
+        debug_assert!(
+            (1..=32).contains(&self.num_bits),
+            r#""num_bits" must be [1, 32]"#
+        );
+
+        extract_bits_64::<{ u64::BITS }>(hash_value, self.num_bits)
+    }
+}
+
+/// Generates [`Hasher`] implementations for the given types.
+///
+/// `$hash_value` is the 64-bit hash value of the value bound to `$value` computed with the state
+/// bound to `$state`, the brackets hold the generic parameters of the implementation.
+macro_rules! impl_ahash {
+    ($(
+        [$($generics:tt)*] $T:ty => |$state:ident, $value:ident| $hash_value:expr
+    ),* $(,)?) => {
+        $(
+            impl<$($generics)*> Hasher<$T> for AHashHasher<$T> {
+                type State = AHashState;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    AHashState::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = AHashState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                #[inline]
+                fn hash(&self, $value: &$T) -> u32 {
+                    let $state = &self.state;
+                    $state.reduce($hash_value)
+                }
+            }
+        )*
+    };
+}
+
+/// Generates [`Hasher`] implementations for byte-string types.
+macro_rules! impl_ahash_bytes {
+    ($($T:ty => |$value:ident| $bytes:expr),* $(,)?) => {
+        impl_ahash!($([] $T => |state, $value| {
+            let mut hasher = state.random_state().build_hasher();
+            hasher.write($bytes);
+            hasher.finish()
+        }),*);
+    };
+}
+
+impl_ahash_bytes!(
+    &[u8] => |value| value,
+    &str => |value| value.as_bytes(),
+    String => |value| value.as_bytes(),
+);
+
+/// Generates [`Hasher`] implementations for integer types and arrays of them.
+macro_rules! impl_ahash_int {
+    ($($T:ty),*) => {
+        impl_ahash!($(
+            [] $T => |state, value| state.random_state().hash_one(value),
+            [const N: usize] [$T; N] => |state, value| state.random_state().hash_one(value),
+        )*);
+    };
+}
+
+impl_ahash_int!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fks::FKSMap;
+    use alloc::vec::Vec;
+    use core::fmt::Debug;
+    use o1_core::HashMap;
+    use o1_test::{generate_hasher_tests, generate_map_tests};
+
+    fn factory<'a, K: Eq + Debug, V: Copy + Debug, H: Hasher<K>>(
+        data: Box<[(K, V)]>,
+    ) -> FKSMap<'a, K, V, H> {
+        FKSMap::new(data, 0, 0.75).unwrap()
+    }
+
+    generate_map_tests!(FKSMap, AHashHasher, factory);
+
+    generate_hasher_tests!(runtime: AHashHasher<u8>, u8, |rng: &mut ChaCha20Rng| rng
+        .random::<u8>());
+    generate_hasher_tests!(runtime: AHashHasher<i32>, i32, |rng: &mut ChaCha20Rng| rng
+        .random::<i32>());
+    generate_hasher_tests!(runtime: AHashHasher<u64>, u64, |rng: &mut ChaCha20Rng| rng
+        .random::<u64>());
+    generate_hasher_tests!(runtime: AHashHasher<u128>, u128, |rng: &mut ChaCha20Rng| rng
+        .random::<u128>());
+    generate_hasher_tests!(
+        runtime: AHashHasher<[u8; 16]>,
+        [u8; 16],
+        |rng: &mut ChaCha20Rng| rng.random::<[u8; 16]>()
+    );
+    generate_hasher_tests!(
+        runtime: AHashHasher<[u64; 4]>,
+        [u64; 4],
+        |rng: &mut ChaCha20Rng| rng.random::<[u64; 4]>()
+    );
+    generate_hasher_tests!(
+        runtime: AHashHasher<String>,
+        String,
+        |rng: &mut ChaCha20Rng| rng.random::<u64>().to_string()
+    );
+
+    #[test]
+    fn test_build_get_map_arrays() {
+        let data: Vec<([u32; 3], u32)> = (0..10_000_u32).map(|i| ([i, i / 7, !i], i)).collect();
+        let map = FKSMap::<[u32; 3], u32, AHashHasher<[u32; 3]>>::new(
+            data.clone().into_boxed_slice(),
+            42,
+            0.75,
+        )
+        .unwrap();
+
+        assert!(map.verify());
+        for (key, value) in data.iter() {
+            assert_eq!(map.get(key), Some(value), "Key: {key:?}");
+        }
+        assert_eq!(map.get(&[0, 1, 2]), None);
+    }
+
+    #[test]
+    fn test_representations_agree() {
+        let str_hasher = AHashHasher::<&str>::from_seed(42, 1 << 16);
+        let string_hasher = AHashHasher::<String>::from_seed(42, 1 << 16);
+        let bytes_hasher = AHashHasher::<&[u8]>::from_seed(42, 1 << 16);
+        for i in 0..1000_u32 {
+            let value = i.to_string();
+            let hash = str_hasher.hash(&value.as_str());
+            assert_eq!(hash, string_hasher.hash(&value));
+            assert_eq!(hash, bytes_hasher.hash(&value.as_bytes()));
+        }
+    }
+}
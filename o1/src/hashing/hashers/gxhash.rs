@@ -0,0 +1,174 @@
+//! Implements Hasher based on the GxHash hash function.
+//!
+//! GxHash is built around the AES round instruction and the `gxhash` crate has no portable
+//! fallback - it requires the `aes` and `sse2` target features on x86-64 and the `aes` and `neon`
+//! ones on aarch64, e.g. `RUSTFLAGS="-C target-feature=+aes,+sse2"`. There is no const
+//! implementation.
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, num_bits_for_buckets, num_buckets_for_bits,
+};
+use alloc::string::String;
+use core::fmt::{Debug, Formatter};
+use gxhash::gxhash64;
+use o1_core::Hasher;
+
+/// Hasher based on GxHash algorithm.
+///
+/// Integers are hashed by their little-endian byte representation.
+///
+/// Contains only the runtime implementation - it can't be used with the compile-time
+/// constructors of the maps.
+#[derive(Clone)]
+pub struct GxHasher<T: Eq>
+where
+    GxHasher<T>: Hasher<T>,
+{
+    state: <GxHasher<T> as Hasher<T>>::State,
+}
+
+impl<T: Eq + Clone> Copy for GxHasher<T>
+where
+    GxHasher<T>: Hasher<T>,
+    <GxHasher<T> as Hasher<T>>::State: Copy,
+{
+}
+
+impl<T: Eq> Default for GxHasher<T>
+where
+    GxHasher<T>: Hasher<T>,
+{
+    fn default() -> Self {
+        <Self as Hasher<T>>::from_state(<Self as Hasher<T>>::State::default())
+    }
+}
+
+impl<T> Debug for GxHasher<T>
+where
+    T: Eq,
+    GxHasher<T>: Hasher<T>,
+    <GxHasher<T> as Hasher<T>>::State: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GxHasher")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// State of [`GxHasher`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GxState {
+    num_bits: u32,
+    seed: u64,
+}
+
+impl GxState {
+    pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self { num_bits, seed }
+    }
+}
+
+#[inline]
+fn hash(state: &GxState, value: &[u8]) -> u32 {
+    debug_assert!(
+        (1..=32).contains(&state.num_bits),
+        r#""num_bits" must be [1, 32]"#
+    );
+
+    extract_bits_64::<{ u64::BITS }>(gxhash64(value, state.seed as i64), state.num_bits)
+}
+
+/// Generates [`Hasher`] implementations for the given types.
+///
+/// `$bytes` is the byte representation of the value bound to `$value`.
+macro_rules! impl_gxhash {
+    ($($T:ty => |$value:ident| $bytes:expr),* $(,)?) => {
+        $(
+            impl Hasher<$T> for GxHasher<$T> {
+                type State = GxState;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    GxState::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = GxState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, $value: &$T) -> u32 {
+                    hash(&self.state, AsRef::<[u8]>::as_ref(&$bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_gxhash!(
+    &[u8] => |value| value,
+    &str => |value| value.as_bytes(),
+    String => |value| value.as_bytes(),
+);
+
+/// Generates [`Hasher`] implementations for integer types.
+macro_rules! impl_gxhash_int {
+    ($($T:ty),*) => {
+        impl_gxhash!($($T => |value| value.to_le_bytes()),*);
+    };
+}
+
+impl_gxhash_int!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fks::FKSMap;
+    use alloc::vec::Vec;
+    use core::fmt::Debug;
+    use o1_core::HashMap;
+    use o1_test::{generate_hasher_tests, generate_map_tests};
+    use rand::Rng;
+
+    fn factory<'a, K: Eq + Debug, V: Copy + Debug, H: Hasher<K>>(
+        data: Box<[(K, V)]>,
+    ) -> FKSMap<'a, K, V, H> {
+        FKSMap::new(data, 0, 0.75).unwrap()
+    }
+
+    generate_map_tests!(FKSMap, GxHasher, factory);
+
+    generate_hasher_tests!(runtime: GxHasher<u64>, u64, |rng: &mut ChaCha20Rng| rng
+        .random::<u64>());
+    generate_hasher_tests!(
+        runtime: GxHasher<String>,
+        String,
+        |rng: &mut ChaCha20Rng| rng.random::<u64>().to_string()
+    );
+
+    #[test]
+    fn test_representations_agree() {
+        let str_hasher = GxHasher::<&str>::from_seed(42, 1 << 16);
+        let string_hasher = GxHasher::<String>::from_seed(42, 1 << 16);
+        let bytes_hasher = GxHasher::<&[u8]>::from_seed(42, 1 << 16);
+        let int_hasher = GxHasher::<u32>::from_seed(42, 1 << 16);
+        for i in 0..1000_u32 {
+            let value = i.to_string();
+            let hash = str_hasher.hash(&value.as_str());
+            assert_eq!(hash, string_hasher.hash(&value));
+            assert_eq!(hash, bytes_hasher.hash(&value.as_bytes()));
+            assert_eq!(
+                int_hasher.hash(&i),
+                bytes_hasher.hash(&i.to_le_bytes().as_slice())
+            );
+        }
+    }
+}
@@ -1,4 +1,14 @@
 //! [`crate::core::Hasher`] implementations.
+#[cfg(feature = "ahash")]
+pub mod ahash;
+#[cfg(feature = "gxhash")]
+pub mod gxhash;
+#[cfg(feature = "highway")]
+pub mod highway;
 pub mod msp;
+pub mod murmur3;
+pub mod tabulation;
+mod transparent;
+pub mod wyhash;
 #[cfg(feature = "xxh3")]
 pub mod xxh3;
@@ -5,7 +5,7 @@
 //! Internally it treats big integers as vectors uses the [`multiply_shift_u8`] hash function.
 
 use super::core::MSPHasher;
-use crate::hashing::common::{num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::common::{clamp_num_bits, num_bits_for_buckets, num_buckets_for_bits};
 use crate::hashing::multiply_shift::{
     pair_multiply_shift_u128, pair_multiply_shift_vector_u128,
     pair_multiply_shift_vector_u128_const,
@@ -25,7 +25,7 @@ where
 {
     pub(super) num_bits: u32,
     seed: [u64; SEED_LEN],
-    _type: std::marker::PhantomData<T>,
+    _type: core::marker::PhantomData<T>,
 }
 
 impl<T> Default for BigIntState<T>
@@ -36,7 +36,7 @@ where
         Self {
             num_bits: 0,
             seed: [0; SEED_LEN],
-            _type: std::marker::PhantomData,
+            _type: core::marker::PhantomData,
         }
     }
 }
@@ -46,41 +46,27 @@ where
     T: Default + Clone,
 {
     pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
         let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
         let seed = rng.random();
 
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         BigIntState {
             num_bits,
             seed,
-            _type: std::marker::PhantomData,
+            _type: core::marker::PhantomData,
         }
     }
 
     pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
         let seed = generate_random_array!(u64, SEED_LEN, seed);
 
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         BigIntState {
             num_bits,
             seed,
-            _type: std::marker::PhantomData,
+            _type: core::marker::PhantomData,
         }
     }
 }
@@ -165,14 +151,9 @@ impl<const N: usize> Default for BigIntArrayState<N> {
 
 impl<const N: usize> BigIntArrayState<N> {
     fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
         let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
 
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         let seed = rng.random();
         let value_seed = rng.random();
@@ -185,12 +166,7 @@ impl<const N: usize> BigIntArrayState<N> {
     }
 
     const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         let mut value_seed = [[0u64; 4]; N];
         let mut i = 0;
@@ -0,0 +1,107 @@
+//! Implements [`Hasher`] for arrays of [`bool`]s.
+//!
+//! The arrays are packed into bytes - 8 `bool`s per byte - and hashed like arrays of `u8`. Since
+//! the packing looks only at the values of the `bool`s, the hash values don't depend on the
+//! in-memory representation of `bool` and the packed arrays are 8 times shorter.
+//!
+//! The state is the one of `[u8; N]` - it holds `2 * N` seeds, while only the first
+//! `N.div_ceil(8)` of them are used, so it's 8 times larger than needed (e.g. 512 bytes of seeds
+//! per hasher for `[bool; 32]`, each L2 bucket of a map has its own hasher). Sizing it by the
+//! packed length requires `generic_const_exprs`. Prefer packing the flags into an integer key
+//! (e.g. `u32` for `[bool; 32]`) for maps with many buckets.
+
+use super::core::MSPHasher;
+use super::smallint::{hash_bytes, hash_bytes_const, SmallArrayState};
+use crate::hashing::common::pack_bools;
+use o1_core::Hasher;
+
+impl<const N: usize> Hasher<[bool; N]> for MSPHasher<[bool; N]> {
+    type State = SmallArrayState<N>;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        <MSPHasher<[u8; N]> as Hasher<[u8; N]>>::make_state(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        MSPHasher::<[u8; N]>::from_state(self.state).num_buckets()
+    }
+    fn hash(&self, value: &[bool; N]) -> u32 {
+        hash_bytes(&self.state, &pack_bools(value)[..N.div_ceil(8)])
+    }
+}
+
+impl<const N: usize> MSPHasher<[bool; N]> {
+    pub const fn make_state_const(
+        seed: u64,
+        num_buckets: u32,
+    ) -> <Self as Hasher<[bool; N]>>::State {
+        MSPHasher::<[u8; N]>::make_state_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<[bool; N]>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        MSPHasher::<[u8; N]>::from_state_const(self.state).num_buckets_const()
+    }
+    pub const fn hash_const(&self, value: &[bool; N]) -> u32 {
+        let bytes = pack_bools(value);
+        hash_bytes_const(&self.state, bytes.split_at(N.div_ceil(8)).0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(
+        MSPHasher<[bool; 32]>,
+        [bool; 32],
+        |rng: &mut ChaCha20Rng| rng.random::<[bool; 32]>()
+    );
+    generate_hasher_tests!(MSPHasher<[bool; 5]>, [bool; 5], |rng: &mut ChaCha20Rng| rng
+        .random::<[bool; 5]>());
+
+    #[test]
+    fn test_hash_equals_packed_bytes() {
+        let hasher = MSPHasher::<[bool; 32]>::from_seed(42, 1 << 16);
+        let bytes_hasher = MSPHasher::<[u8; 4]>::from_seed(42, 1 << 16);
+        let mut value = [false; 32];
+        value[0] = true;
+        value[9] = true;
+        value[31] = true;
+        let bytes = [0b0000_0001, 0b0000_0010, 0, 0b1000_0000];
+
+        assert_eq!(hasher.hash(&value), bytes_hasher.hash(&bytes));
+        assert_eq!(hasher.hash_const(&value), bytes_hasher.hash_const(&bytes));
+    }
+
+    #[test]
+    fn test_single_flip_hashes_differently() {
+        let hasher = MSPHasher::<[bool; 32]>::from_seed(42, u32::MAX);
+        let value = [false; 32];
+        for i in 0..32 {
+            let mut flipped = value;
+            flipped[i] = true;
+            assert_ne!(
+                hasher.hash(&value),
+                hasher.hash(&flipped),
+                "Position: {}",
+                i
+            );
+        }
+    }
+}
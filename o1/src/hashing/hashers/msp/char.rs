@@ -0,0 +1,87 @@
+//! Implements [`Hasher`] for arrays of [`char`]s.
+//!
+//! The arrays are hashed by delegating to the hasher of `[u32; N]` - each `char` is
+//! reinterpreted as its Unicode scalar value without any re-encoding. Since a `char` is always
+//! a valid scalar value (surrogates can't be represented by it), the mapping is injective and
+//! there is no need for special handling of surrogates.
+
+use super::core::MSPHasher;
+use super::smallint::SmallArrayState;
+use o1_core::Hasher;
+
+/// Reinterprets an array of `char`s as an array of their scalar values.
+const fn as_u32_array<const N: usize>(value: &[char; N]) -> &[u32; N] {
+    // SAFETY: `char` has the same size and alignment as `u32` and every `char` is a valid `u32`.
+    unsafe { &*(value as *const [char; N] as *const [u32; N]) }
+}
+
+impl<const N: usize> Hasher<[char; N]> for MSPHasher<[char; N]> {
+    type State = SmallArrayState<N>;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        <MSPHasher<[u32; N]> as Hasher<[u32; N]>>::make_state(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        MSPHasher::<[u32; N]>::from_state(self.state).num_buckets()
+    }
+    fn hash(&self, value: &[char; N]) -> u32 {
+        MSPHasher::<[u32; N]>::from_state(self.state).hash(as_u32_array(value))
+    }
+}
+
+impl<const N: usize> MSPHasher<[char; N]> {
+    pub const fn make_state_const(
+        seed: u64,
+        num_buckets: u32,
+    ) -> <Self as Hasher<[char; N]>>::State {
+        MSPHasher::<[u32; N]>::make_state_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<[char; N]>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        MSPHasher::<[u32; N]>::from_state_const(self.state).num_buckets_const()
+    }
+    pub const fn hash_const(&self, value: &[char; N]) -> u32 {
+        MSPHasher::<[u32; N]>::from_state_const(self.state).hash_const(as_u32_array(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(MSPHasher<[char; 8]>, [char; 8], |rng: &mut ChaCha20Rng| rng
+        .random::<[char; 8]>());
+    generate_hasher_tests!(MSPHasher<[char; 1]>, [char; 1], |rng: &mut ChaCha20Rng| rng
+        .random::<[char; 1]>());
+
+    #[test]
+    fn test_hash_equals_scalar_values() {
+        let hasher = MSPHasher::<[char; 4]>::from_seed(42, 1 << 16);
+        let scalar_hasher = MSPHasher::<[u32; 4]>::from_state(*hasher.state());
+        let value = ['a', 'é', '€', '😀'];
+        let scalars = value.map(u32::from);
+
+        assert_eq!(hasher.hash(&value), scalar_hasher.hash(&scalars));
+        assert_eq!(
+            hasher.hash_const(&value),
+            scalar_hasher.hash_const(&scalars)
+        );
+    }
+}
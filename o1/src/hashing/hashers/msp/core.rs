@@ -1,5 +1,5 @@
+use core::fmt::{Debug, Formatter};
 use o1_core::Hasher;
-use std::fmt::{Debug, Formatter};
 
 /// Hasher based on multiply-shift and polynomial hashing.
 ///
@@ -35,7 +35,7 @@ where
     MSPHasher<T>: Hasher<T>,
     <MSPHasher<T> as Hasher<T>>::State: Debug,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("MSPHasher")
             .field("state", &self.state)
             .finish()
@@ -52,3 +52,22 @@ where
         Self { state: self.state }
     }
 }
+
+impl<T: Eq> MSPHasher<T>
+where
+    MSPHasher<T>: Hasher<T>,
+{
+    /// Wraps the state into the hasher - used by the implementations generated by the `O1Hash`
+    /// derive-macro and [`impl_transparent_hasher!`], which can't access the private field.
+    #[doc(hidden)]
+    pub const fn __from_state(state: <MSPHasher<T> as Hasher<T>>::State) -> Self {
+        Self { state }
+    }
+
+    /// Gets the state of the hasher - used by the implementations generated by the `O1Hash`
+    /// derive-macro and [`impl_transparent_hasher!`], which can't access the private field.
+    #[doc(hidden)]
+    pub const fn __state(&self) -> &<MSPHasher<T> as Hasher<T>>::State {
+        &self.state
+    }
+}
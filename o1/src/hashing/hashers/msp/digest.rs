@@ -0,0 +1,209 @@
+//! Implements an alternative hasher for byte-strings computing the polynomial digest of the value
+//! once for all hashers that share it.
+//!
+//! The value is hashed with [`polynomial_digest`] to an 89-bit digest and the digest is reduced
+//! to the hash value with [`pair_multiply_shift_u128`]. Unlike [`MSPHasher`](super::MSPHasher)
+//! the polynomial is used for the values of any length, so the hasher pays off only for long
+//! keys - where hashing the value dominates the lookup.
+
+use crate::hashing::common::{clamp_num_bits, num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::multiply_shift::pair_multiply_shift_u128;
+use crate::hashing::polynomial::{polynomial_digest, PolynomialSeed};
+use alloc::string::String;
+use core::fmt::{Debug, Formatter};
+use o1_core::{DigestHasher, Hasher};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// Hasher for byte-strings implementing [`DigestHasher`] with the polynomial digest.
+///
+/// [`SinglePassFKSMap`](crate::fks::SinglePassFKSMap) builds maps whose L1 and L2 hashers share
+/// the digest, so that
+/// [`SinglePassFKSMap::get_single_pass`](crate::fks::SinglePassFKSMap::get_single_pass) hashes
+/// the key only once.
+///
+/// Contains only the runtime implementation - it can't be used with the compile-time
+/// constructors of the maps.
+#[derive(Clone)]
+pub struct MSPHasherDigest<T: Eq>
+where
+    MSPHasherDigest<T>: Hasher<T>,
+{
+    state: <MSPHasherDigest<T> as Hasher<T>>::State,
+}
+
+impl<T: Eq + Clone> Copy for MSPHasherDigest<T>
+where
+    MSPHasherDigest<T>: Hasher<T>,
+    <MSPHasherDigest<T> as Hasher<T>>::State: Copy,
+{
+}
+
+impl<T: Eq> Default for MSPHasherDigest<T>
+where
+    MSPHasherDigest<T>: Hasher<T>,
+{
+    fn default() -> Self {
+        <Self as Hasher<T>>::from_state(<Self as Hasher<T>>::State::default())
+    }
+}
+
+impl<T> Debug for MSPHasherDigest<T>
+where
+    T: Eq,
+    MSPHasherDigest<T>: Hasher<T>,
+    <MSPHasherDigest<T> as Hasher<T>>::State: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MSPHasherDigest")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// State of [`MSPHasherDigest`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DigestState {
+    num_bits: u32,
+    /// The seed of the reduction of the digest to the hash value.
+    reduction_seed: [u64; 5],
+    /// The seed of the digest.
+    polynomial_seed: PolynomialSeed,
+}
+
+impl DigestState {
+    pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        let polynomial_seed = PolynomialSeed::from_rng(&mut rng);
+        Self::with_polynomial_seed(&mut rng, num_buckets, polynomial_seed)
+    }
+
+    /// Creates the state with the given digest seed and the reduction seed taken from `rng`.
+    fn with_polynomial_seed(
+        rng: &mut Xoshiro256PlusPlus,
+        num_buckets: u32,
+        polynomial_seed: PolynomialSeed,
+    ) -> Self {
+        Self {
+            num_bits: clamp_num_bits(num_bits_for_buckets(num_buckets)),
+            reduction_seed: rng.random(),
+            polynomial_seed,
+        }
+    }
+}
+
+#[inline]
+fn hash_digest(state: &DigestState, digest: u128) -> u32 {
+    debug_assert!(
+        (1..=32).contains(&state.num_bits),
+        r#""num_bits" must be [1, 32]"#
+    );
+    pair_multiply_shift_u128(digest, state.num_bits, &state.reduction_seed)
+}
+
+/// Generates [`Hasher`] and [`DigestHasher`] implementations for the given types.
+///
+/// `$bytes` is the byte representation of the value bound to `$value`.
+macro_rules! impl_digest {
+    ($($T:ty => |$value:ident| $bytes:expr),* $(,)?) => {
+        $(
+            impl Hasher<$T> for MSPHasherDigest<$T> {
+                type State = DigestState;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    DigestState::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = DigestState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, value: &$T) -> u32 {
+                    hash_digest(&self.state, self.digest(value))
+                }
+            }
+
+            impl DigestHasher<$T> for MSPHasherDigest<$T> {
+                type Digest = u128;
+
+                #[inline]
+                fn digest(&self, $value: &$T) -> u128 {
+                    polynomial_digest($bytes, &self.state.polynomial_seed)
+                }
+                #[inline]
+                fn hash_digest(&self, digest: u128) -> u32 {
+                    hash_digest(&self.state, digest)
+                }
+                fn with_shared_digest(&self, seed: u64, num_buckets: u32) -> Self {
+                    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+                    let state = DigestState::with_polynomial_seed(
+                        &mut rng,
+                        num_buckets,
+                        self.state.polynomial_seed,
+                    );
+                    Self { state }
+                }
+            }
+        )*
+    };
+}
+
+impl_digest!(
+    &[u8] => |value| value,
+    &str => |value| value.as_bytes(),
+    String => |value| value.as_bytes(),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(
+        runtime: MSPHasherDigest<String>,
+        String,
+        |rng: &mut ChaCha20Rng| rng.random::<u64>().to_string().repeat(10)
+    );
+
+    #[test]
+    fn test_shared_digest() {
+        let l1 = MSPHasherDigest::<String>::from_seed(42, 1 << 16);
+        let l2 = l1.with_shared_digest(43, 16);
+        assert_eq!(l2.num_buckets(), 16);
+
+        let other = MSPHasherDigest::<String>::from_seed(43, 16);
+        let mut num_different = 0;
+        for len in [0, 1, 8, 255, 256, 257, 4096] {
+            let value = "a".repeat(len);
+            let digest = l1.digest(&value);
+            assert_eq!(l2.digest(&value), digest, "Length: {len}");
+            assert_eq!(l1.hash_digest(digest), l1.hash(&value), "Length: {len}");
+            assert_eq!(l2.hash_digest(digest), l2.hash(&value), "Length: {len}");
+            if other.digest(&value) != digest {
+                num_different += 1;
+            }
+        }
+        assert!(num_different > 0);
+    }
+
+    #[test]
+    fn test_representations_agree() {
+        let str_hasher = MSPHasherDigest::<&str>::from_seed(42, 1 << 16);
+        let string_hasher = MSPHasherDigest::<String>::from_seed(42, 1 << 16);
+        let bytes_hasher = MSPHasherDigest::<&[u8]>::from_seed(42, 1 << 16);
+        for i in 0..1000_u32 {
+            let value = i.to_string().repeat(100);
+            let hash = str_hasher.hash(&value.as_str());
+            assert_eq!(hash, string_hasher.hash(&value));
+            assert_eq!(hash, bytes_hasher.hash(&value.as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Implements the combining of the discriminant and the fields of an enum value used by the
+//! [`Hasher`](o1_core::Hasher) implementations generated by the `O1Hash` derive-macro.
+//!
+//! The discriminant is hashed by multiply-shift and the hash values of the fields are folded
+//! into it one by one with pair-multiply-shift - the same way as the tag and the inner value of
+//! `Option<T>` are combined.
+
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, num_bits_for_buckets, num_buckets_for_bits,
+};
+use crate::hashing::multiply_shift::{multiply_shift, pair_multiply_shift};
+use crate::utils::xorshift::generate_random_array;
+
+/// Combines the index of the variant of an enum value with the hash values of its fields.
+///
+/// `N` is the maximum number of fields of a variant. The intermediate hash values are 32-bit wide
+/// and only [`EnumCombiner::finish`] truncates the result to the number of buckets.
+#[derive(Debug, Clone, Copy)]
+pub struct EnumCombiner<const N: usize> {
+    tag_seed: [u64; 2],
+    combiner_seeds: [[u64; 3]; N],
+    num_bits: u32,
+}
+
+// Implemented manually since `Default` isn't implemented for arrays longer than 32 elements.
+impl<const N: usize> Default for EnumCombiner<N> {
+    fn default() -> Self {
+        Self {
+            tag_seed: [0; 2],
+            combiner_seeds: [[0; 3]; N],
+            num_bits: 0,
+        }
+    }
+}
+
+impl<const N: usize> EnumCombiner<N> {
+    pub const fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let mut tag_seed: [u64; 2] = generate_random_array!(u64, 2, seed.wrapping_add(1000));
+        tag_seed[0] |= 1;
+        let mut combiner_seeds = [[0; 3]; N];
+        let mut i = 0;
+        while i < N {
+            combiner_seeds[i] = generate_random_array!(u64, 3, seed.wrapping_add(2000 + i as u64));
+            combiner_seeds[i][0] |= 1;
+            i += 1;
+        }
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            tag_seed,
+            combiner_seeds,
+            num_bits,
+        }
+    }
+
+    /// Derives the seed of the state of the hasher of a field from the seed of the enum's hasher.
+    ///
+    /// `field_idx` counts the fields of all variants in the order of declaration.
+    pub const fn field_seed(seed: u64, field_idx: usize) -> u64 {
+        seed.wrapping_add(3000 + field_idx as u64)
+    }
+
+    pub const fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.num_bits)
+    }
+
+    /// Hashes the index of the variant.
+    #[inline]
+    pub const fn start(&self, variant_idx: u32) -> u32 {
+        multiply_shift(variant_idx, u32::BITS, &self.tag_seed)
+    }
+
+    /// Folds the 32-bit hash value of the `field_idx`-th field of the variant into `acc`.
+    #[inline]
+    pub const fn combine(&self, acc: u32, field_idx: usize, field_hash: u32) -> u32 {
+        let combined = ((acc as u64) << 32) | field_hash as u64;
+        pair_multiply_shift(combined, u32::BITS, &self.combiner_seeds[field_idx])
+    }
+
+    /// Truncates `acc` to the number of buckets.
+    #[inline]
+    pub const fn finish(&self, acc: u32) -> u32 {
+        extract_bits_64::<{ u32::BITS }>(acc as u64, self.num_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_change_hashes() {
+        let combiner = EnumCombiner::<1>::from_seed(42, u32::MAX);
+        let acc = combiner.start(0);
+        assert!((0..100).any(|field_hash| {
+            combiner.combine(acc, 0, field_hash) != combiner.combine(acc, 0, field_hash + 1)
+        }));
+        assert_ne!(combiner.start(0), combiner.start(1));
+    }
+}
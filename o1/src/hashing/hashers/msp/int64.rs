@@ -1,7 +1,7 @@
 //! Implements Hasher for u64 and i64 using [`pair_multiply_shift`] hash-function.
 
 use super::core::MSPHasher;
-use crate::hashing::common::{num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::common::{clamp_num_bits, num_bits_for_buckets, num_buckets_for_bits};
 use crate::hashing::multiply_shift::{
     pair_multiply_shift, pair_multiply_shift_vector_u64, pair_multiply_shift_vector_u64_const,
 };
@@ -18,29 +18,16 @@ pub struct U64State {
 
 impl U64State {
     pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
         let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
         let seed: [u64; 3] = rng.random();
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
 
     pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
         let seed: [u64; 3] = generate_random_array!(u64, 3, seed);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
@@ -139,14 +126,8 @@ impl<const N: usize> Default for Array64State<N> {
 
 impl<const N: usize> Array64State<N> {
     fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
         let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         let seed = rng.random();
         let value_seed = rng.random();
@@ -159,13 +140,7 @@ impl<const N: usize> Array64State<N> {
     }
 
     const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            num_bits > 0 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#,
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         let mut value_seed = [[0; 2]; N];
         let mut i = 0;
@@ -183,7 +158,7 @@ impl<const N: usize> Array64State<N> {
     }
 
     const fn value_seed_as_slice(&self) -> &[u64] {
-        unsafe { std::slice::from_raw_parts(self.value_seed.as_ptr() as *const u64, N * 2) }
+        unsafe { core::slice::from_raw_parts(self.value_seed.as_ptr() as *const u64, N * 2) }
     }
 }
 
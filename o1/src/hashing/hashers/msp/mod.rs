@@ -12,3 +12,20 @@ mod string;
 pub use string::*;
 mod option;
 pub use option::*;
+mod result;
+pub use result::*;
+mod net;
+pub use net::*;
+mod split;
+pub use split::*;
+mod digest;
+pub use digest::*;
+mod enums;
+pub use enums::*;
+mod bool;
+mod char;
+mod nonzero;
+mod range;
+mod reference;
+mod time;
+mod unit;
@@ -0,0 +1,556 @@
+//! Implements [`Hasher`] for IP addresses ([`Ipv4Addr`], [`Ipv6Addr`] and [`IpAddr`]) and socket
+//! addresses ([`SocketAddrV4`], [`SocketAddrV6`] and [`SocketAddr`]).
+//!
+//! [`Ipv4Addr`] and [`Ipv6Addr`] are hashed through their `u32` and `u128` representations
+//! by delegating to the existing [`MSPHasher<u32>`] and [`MSPHasher<u128>`]. [`IpAddr`]
+//! combines a hash of the address family tag with a hash of the inner address, the same way
+//! [`OptionState`](super::OptionState) does.
+//!
+//! The address and the port of [`SocketAddrV4`] are packed into a single `u64` and hashed by
+//! [`MSPHasher<u64>`]. [`SocketAddrV6`] doesn't fit into 128 bits, so the address and the packed
+//! `(port, flowinfo, scope_id)` triple are hashed to full 32-bit values and combined like the
+//! halves in [`MSPHasherSplit`](super::MSPHasherSplit). [`SocketAddr`] combines the tag and the
+//! inner address like [`IpAddr`].
+
+use super::bigint::BigIntState;
+use super::core::MSPHasher;
+use super::int64::U64State;
+use super::smallint::SmallIntState;
+use crate::hashing::common::{clamp_num_bits, num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::multiply_shift::{multiply_shift, pair_multiply_shift};
+use crate::utils::xorshift::generate_random_array;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use o1_core::Hasher;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+impl Hasher<Ipv4Addr> for MSPHasher<Ipv4Addr> {
+    type State = SmallIntState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        <MSPHasher<u32> as Hasher<u32>>::make_state(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        MSPHasher::<u32>::from_state(self.state).num_buckets()
+    }
+    fn hash(&self, value: &Ipv4Addr) -> u32 {
+        MSPHasher::<u32>::from_state(self.state).hash(&value.to_bits())
+    }
+}
+
+impl MSPHasher<Ipv4Addr> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> SmallIntState {
+        MSPHasher::<u32>::make_state_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Ipv4Addr>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        MSPHasher::<u32>::from_state_const(self.state).num_buckets_const()
+    }
+    pub const fn hash_const(&self, value: &Ipv4Addr) -> u32 {
+        MSPHasher::<u32>::from_state_const(self.state).hash_const(&value.to_bits())
+    }
+}
+
+impl Hasher<Ipv6Addr> for MSPHasher<Ipv6Addr> {
+    type State = BigIntState<u128>;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        <MSPHasher<u128> as Hasher<u128>>::make_state(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        MSPHasher::<u128>::from_state(self.state).num_buckets()
+    }
+    fn hash(&self, value: &Ipv6Addr) -> u32 {
+        MSPHasher::<u128>::from_state(self.state).hash(&value.to_bits())
+    }
+}
+
+impl MSPHasher<Ipv6Addr> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> BigIntState<u128> {
+        MSPHasher::<u128>::make_state_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Ipv6Addr>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        MSPHasher::<u128>::from_state_const(self.state).num_buckets_const()
+    }
+    pub const fn hash_const(&self, value: &Ipv6Addr) -> u32 {
+        MSPHasher::<u128>::from_state_const(self.state).hash_const(&value.to_bits())
+    }
+}
+
+/// State for hashing [`IpAddr`] values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IpAddrState {
+    tag_seed: [u64; 2],
+    combiner_seed: [u64; 3],
+    v4: SmallIntState,
+    v6: BigIntState<u128>,
+    num_bits: u32,
+}
+
+impl IpAddrState {
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed.wrapping_add(1000));
+        let tag_seed: [u64; 2] = rng.random();
+        let combiner_seed: [u64; 3] = rng.random();
+        let v4 = <MSPHasher<Ipv4Addr> as Hasher<Ipv4Addr>>::make_state(
+            seed.wrapping_add(2000),
+            num_buckets,
+        );
+        let v6 = <MSPHasher<Ipv6Addr> as Hasher<Ipv6Addr>>::make_state(
+            seed.wrapping_add(3000),
+            num_buckets,
+        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            tag_seed,
+            combiner_seed,
+            v4,
+            v6,
+            num_bits,
+        }
+    }
+}
+
+impl Hasher<IpAddr> for MSPHasher<IpAddr> {
+    type State = IpAddrState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        IpAddrState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = IpAddrState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &IpAddr) -> u32 {
+        let (tag, inner_hash) = match value {
+            IpAddr::V4(v) => (
+                0u32,
+                MSPHasher::<Ipv4Addr>::from_state(self.state.v4).hash(v),
+            ),
+            IpAddr::V6(v) => (
+                1u32,
+                MSPHasher::<Ipv6Addr>::from_state(self.state.v6).hash(v),
+            ),
+        };
+        let tag_hash = multiply_shift(tag, self.state.num_bits, &self.state.tag_seed);
+        let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
+        pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
+    }
+}
+
+impl MSPHasher<IpAddr> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> IpAddrState {
+        let mut tag_seed: [u64; 2] = generate_random_array!(u64, 2, seed.wrapping_add(1000));
+        tag_seed[0] |= 1;
+        let mut combiner_seed: [u64; 3] = generate_random_array!(u64, 3, seed.wrapping_add(2000));
+        combiner_seed[0] |= 1;
+        let v4 = MSPHasher::<Ipv4Addr>::make_state_const(seed.wrapping_add(3000), num_buckets);
+        let v6 = MSPHasher::<Ipv6Addr>::make_state_const(seed.wrapping_add(4000), num_buckets);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        IpAddrState {
+            tag_seed,
+            combiner_seed,
+            v4,
+            v6,
+            num_bits,
+        }
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<IpAddr>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &IpAddr) -> u32 {
+        let (tag, inner_hash) = match value {
+            IpAddr::V4(v) => (
+                0u32,
+                MSPHasher::<Ipv4Addr>::from_state_const(self.state.v4).hash_const(v),
+            ),
+            IpAddr::V6(v) => (
+                1u32,
+                MSPHasher::<Ipv6Addr>::from_state_const(self.state.v6).hash_const(v),
+            ),
+        };
+        let tag_hash = multiply_shift(tag, self.state.num_bits, &self.state.tag_seed);
+        let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
+        pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
+    }
+}
+
+/// Packs the address and the port into a single `u64` - the address in the higher bits.
+#[inline]
+const fn socket_addr_v4_to_bits(value: &SocketAddrV4) -> u64 {
+    ((value.ip().to_bits() as u64) << u16::BITS) | value.port() as u64
+}
+
+/// Packs the port, the flow info and the scope ID into a single `u128`.
+#[inline]
+const fn socket_addr_v6_rest_to_bits(value: &SocketAddrV6) -> u128 {
+    ((value.port() as u128) << 64) | ((value.flowinfo() as u128) << 32) | value.scope_id() as u128
+}
+
+impl Hasher<SocketAddrV4> for MSPHasher<SocketAddrV4> {
+    type State = U64State;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        <MSPHasher<u64> as Hasher<u64>>::make_state(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        MSPHasher::<u64>::from_state(self.state).num_buckets()
+    }
+    fn hash(&self, value: &SocketAddrV4) -> u32 {
+        MSPHasher::<u64>::from_state(self.state).hash(&socket_addr_v4_to_bits(value))
+    }
+}
+
+impl MSPHasher<SocketAddrV4> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> U64State {
+        MSPHasher::<u64>::make_state_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<SocketAddrV4>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        MSPHasher::<u64>::from_state_const(self.state).num_buckets_const()
+    }
+    pub const fn hash_const(&self, value: &SocketAddrV4) -> u32 {
+        MSPHasher::<u64>::from_state_const(self.state).hash_const(&socket_addr_v4_to_bits(value))
+    }
+}
+
+/// State for hashing [`SocketAddrV6`] values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SocketAddrV6State {
+    ip: BigIntState<u128>,
+    rest: BigIntState<u128>,
+    combiner_seed: [u64; 3],
+    num_bits: u32,
+}
+
+impl SocketAddrV6State {
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed.wrapping_add(1000));
+        let combiner_seed: [u64; 3] = rng.random();
+        let ip = <MSPHasher<u128> as Hasher<u128>>::make_state(seed.wrapping_add(2000), u32::MAX);
+        let rest = <MSPHasher<u128> as Hasher<u128>>::make_state(seed.wrapping_add(3000), u32::MAX);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            ip,
+            rest,
+            combiner_seed,
+            num_bits,
+        }
+    }
+
+    const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let mut combiner_seed: [u64; 3] = generate_random_array!(u64, 3, seed.wrapping_add(1000));
+        combiner_seed[0] |= 1;
+        let ip = MSPHasher::<u128>::make_state_const(seed.wrapping_add(2000), u32::MAX);
+        let rest = MSPHasher::<u128>::make_state_const(seed.wrapping_add(3000), u32::MAX);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            ip,
+            rest,
+            combiner_seed,
+            num_bits,
+        }
+    }
+}
+
+impl Hasher<SocketAddrV6> for MSPHasher<SocketAddrV6> {
+    type State = SocketAddrV6State;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        SocketAddrV6State::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = SocketAddrV6State::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &SocketAddrV6) -> u32 {
+        let ip_hash = MSPHasher::<u128>::from_state(self.state.ip).hash(&value.ip().to_bits());
+        let rest_hash = MSPHasher::<u128>::from_state(self.state.rest)
+            .hash(&socket_addr_v6_rest_to_bits(value));
+        let combined = ((ip_hash as u64) << 32) | rest_hash as u64;
+        pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
+    }
+}
+
+impl MSPHasher<SocketAddrV6> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> SocketAddrV6State {
+        SocketAddrV6State::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<SocketAddrV6>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &SocketAddrV6) -> u32 {
+        let ip_hash =
+            MSPHasher::<u128>::from_state_const(self.state.ip).hash_const(&value.ip().to_bits());
+        let rest_hash = MSPHasher::<u128>::from_state_const(self.state.rest)
+            .hash_const(&socket_addr_v6_rest_to_bits(value));
+        let combined = ((ip_hash as u64) << 32) | rest_hash as u64;
+        pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
+    }
+}
+
+/// State for hashing [`SocketAddr`] values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SocketAddrState {
+    tag_seed: [u64; 2],
+    combiner_seed: [u64; 3],
+    v4: U64State,
+    v6: SocketAddrV6State,
+    num_bits: u32,
+}
+
+impl SocketAddrState {
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed.wrapping_add(1000));
+        let tag_seed: [u64; 2] = rng.random();
+        let combiner_seed: [u64; 3] = rng.random();
+        let v4 = <MSPHasher<SocketAddrV4> as Hasher<SocketAddrV4>>::make_state(
+            seed.wrapping_add(2000),
+            num_buckets,
+        );
+        let v6 = <MSPHasher<SocketAddrV6> as Hasher<SocketAddrV6>>::make_state(
+            seed.wrapping_add(3000),
+            num_buckets,
+        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            tag_seed,
+            combiner_seed,
+            v4,
+            v6,
+            num_bits,
+        }
+    }
+}
+
+impl Hasher<SocketAddr> for MSPHasher<SocketAddr> {
+    type State = SocketAddrState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        SocketAddrState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = SocketAddrState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &SocketAddr) -> u32 {
+        let (tag, inner_hash) = match value {
+            SocketAddr::V4(v) => (
+                0u32,
+                MSPHasher::<SocketAddrV4>::from_state(self.state.v4).hash(v),
+            ),
+            SocketAddr::V6(v) => (
+                1u32,
+                MSPHasher::<SocketAddrV6>::from_state(self.state.v6).hash(v),
+            ),
+        };
+        let tag_hash = multiply_shift(tag, self.state.num_bits, &self.state.tag_seed);
+        let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
+        pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
+    }
+}
+
+impl MSPHasher<SocketAddr> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> SocketAddrState {
+        let mut tag_seed: [u64; 2] = generate_random_array!(u64, 2, seed.wrapping_add(1000));
+        tag_seed[0] |= 1;
+        let mut combiner_seed: [u64; 3] = generate_random_array!(u64, 3, seed.wrapping_add(2000));
+        combiner_seed[0] |= 1;
+        let v4 = MSPHasher::<SocketAddrV4>::make_state_const(seed.wrapping_add(3000), num_buckets);
+        let v6 = MSPHasher::<SocketAddrV6>::make_state_const(seed.wrapping_add(4000), num_buckets);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        SocketAddrState {
+            tag_seed,
+            combiner_seed,
+            v4,
+            v6,
+            num_bits,
+        }
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<SocketAddr>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &SocketAddr) -> u32 {
+        let (tag, inner_hash) = match value {
+            SocketAddr::V4(v) => (
+                0u32,
+                MSPHasher::<SocketAddrV4>::from_state_const(self.state.v4).hash_const(v),
+            ),
+            SocketAddr::V6(v) => (
+                1u32,
+                MSPHasher::<SocketAddrV6>::from_state_const(self.state.v6).hash_const(v),
+            ),
+        };
+        let tag_hash = multiply_shift(tag, self.state.num_bits, &self.state.tag_seed);
+        let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
+        pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(MSPHasher<Ipv4Addr>, Ipv4Addr, |rng: &mut ChaCha20Rng| {
+        Ipv4Addr::from_bits(rng.random::<u32>())
+    });
+
+    generate_hasher_tests!(MSPHasher<Ipv6Addr>, Ipv6Addr, |rng: &mut ChaCha20Rng| {
+        Ipv6Addr::from_bits(rng.random::<u128>())
+    });
+
+    generate_hasher_tests!(MSPHasher<IpAddr>, IpAddr, |rng: &mut ChaCha20Rng| {
+        if rng.random::<bool>() {
+            IpAddr::V4(Ipv4Addr::from_bits(rng.random::<u32>()))
+        } else {
+            IpAddr::V6(Ipv6Addr::from_bits(rng.random::<u128>()))
+        }
+    });
+    generate_hasher_tests!(
+        MSPHasher<SocketAddrV4>,
+        SocketAddrV4,
+        |rng: &mut ChaCha20Rng| {
+            SocketAddrV4::new(
+                Ipv4Addr::from_bits(rng.random::<u32>()),
+                rng.random::<u16>(),
+            )
+        }
+    );
+
+    generate_hasher_tests!(
+        MSPHasher<SocketAddrV6>,
+        SocketAddrV6,
+        |rng: &mut ChaCha20Rng| {
+            SocketAddrV6::new(
+                Ipv6Addr::from_bits(rng.random::<u128>()),
+                rng.random::<u16>(),
+                rng.random::<u32>(),
+                rng.random::<u32>(),
+            )
+        }
+    );
+
+    generate_hasher_tests!(
+        MSPHasher<SocketAddr>,
+        SocketAddr,
+        |rng: &mut ChaCha20Rng| {
+            if rng.random::<bool>() {
+                SocketAddr::V4(SocketAddrV4::new(
+                    Ipv4Addr::from_bits(rng.random::<u32>()),
+                    rng.random::<u16>(),
+                ))
+            } else {
+                SocketAddr::V6(SocketAddrV6::new(
+                    Ipv6Addr::from_bits(rng.random::<u128>()),
+                    rng.random::<u16>(),
+                    rng.random::<u32>(),
+                    rng.random::<u32>(),
+                ))
+            }
+        }
+    );
+}
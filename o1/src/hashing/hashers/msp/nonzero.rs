@@ -0,0 +1,144 @@
+//! Implements [`Hasher`] for the non-zero integer types ([`NonZeroU32`], [`NonZeroI64`], etc).
+//!
+//! The values are hashed by delegating to the hasher of the underlying integer type.
+
+use super::core::MSPHasher;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use o1_core::Hasher;
+
+/// Generates [`Hasher`] implementations for non-zero integer types.
+///
+/// The generated impls call the implementation of the underlying integer type with the value
+/// returned by `get()`.
+macro_rules! impl_multiply_shift_non_zero {
+    ($(($k:ty, $int:ty)),*) => {
+        $(
+            impl Hasher<$k> for MSPHasher<$k> {
+                type State = <MSPHasher<$int> as Hasher<$int>>::State;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    <MSPHasher<$int> as Hasher<$int>>::make_state(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = Self::make_state(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    MSPHasher::<$int>::from_state(self.state).num_buckets()
+                }
+                fn hash(&self, value: &$k) -> u32 {
+                    MSPHasher::<$int>::from_state(self.state).hash(&value.get())
+                }
+            }
+
+            impl MSPHasher<$k> {
+                pub const fn make_state_const(
+                    seed: u64,
+                    num_buckets: u32,
+                ) -> <Self as Hasher<$k>>::State {
+                    MSPHasher::<$int>::make_state_const(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = Self::make_state_const(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<$k>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    MSPHasher::<$int>::from_state_const(self.state).num_buckets_const()
+                }
+                pub const fn hash_const(&self, value: &$k) -> u32 {
+                    MSPHasher::<$int>::from_state_const(self.state).hash_const(&value.get())
+                }
+            }
+        )*
+    };
+}
+
+impl_multiply_shift_non_zero!(
+    (NonZeroU8, u8),
+    (NonZeroI8, i8),
+    (NonZeroU16, u16),
+    (NonZeroI16, i16),
+    (NonZeroU32, u32),
+    (NonZeroI32, i32),
+    (NonZeroU64, u64),
+    (NonZeroI64, i64),
+    (NonZeroU128, u128),
+    (NonZeroI128, i128),
+    (NonZeroUsize, usize),
+    (NonZeroIsize, isize)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(MSPHasher<NonZeroU8>, NonZeroU8, |rng: &mut ChaCha20Rng| rng
+        .random::<NonZeroU8>());
+    generate_hasher_tests!(MSPHasher<NonZeroI8>, NonZeroI8, |rng: &mut ChaCha20Rng| rng
+        .random::<NonZeroI8>());
+    generate_hasher_tests!(
+        MSPHasher<NonZeroU16>,
+        NonZeroU16,
+        |rng: &mut ChaCha20Rng| rng.random::<NonZeroU16>()
+    );
+    generate_hasher_tests!(
+        MSPHasher<NonZeroI16>,
+        NonZeroI16,
+        |rng: &mut ChaCha20Rng| rng.random::<NonZeroI16>()
+    );
+    generate_hasher_tests!(
+        MSPHasher<NonZeroU32>,
+        NonZeroU32,
+        |rng: &mut ChaCha20Rng| rng.random::<NonZeroU32>()
+    );
+    generate_hasher_tests!(
+        MSPHasher<NonZeroI32>,
+        NonZeroI32,
+        |rng: &mut ChaCha20Rng| rng.random::<NonZeroI32>()
+    );
+    generate_hasher_tests!(
+        MSPHasher<NonZeroU64>,
+        NonZeroU64,
+        |rng: &mut ChaCha20Rng| rng.random::<NonZeroU64>()
+    );
+    generate_hasher_tests!(
+        MSPHasher<NonZeroI64>,
+        NonZeroI64,
+        |rng: &mut ChaCha20Rng| rng.random::<NonZeroI64>()
+    );
+    generate_hasher_tests!(
+        MSPHasher<NonZeroU128>,
+        NonZeroU128,
+        |rng: &mut ChaCha20Rng| rng.random::<NonZeroU128>()
+    );
+    generate_hasher_tests!(
+        MSPHasher<NonZeroI128>,
+        NonZeroI128,
+        |rng: &mut ChaCha20Rng| rng.random::<NonZeroI128>()
+    );
+    generate_hasher_tests!(
+        MSPHasher<NonZeroUsize>,
+        NonZeroUsize,
+        |rng: &mut ChaCha20Rng| NonZeroUsize::new(rng.random::<u32>() as usize)
+            .unwrap_or(NonZeroUsize::MIN)
+    );
+    generate_hasher_tests!(
+        MSPHasher<NonZeroIsize>,
+        NonZeroIsize,
+        |rng: &mut ChaCha20Rng| NonZeroIsize::new(rng.random::<i32>() as isize)
+            .unwrap_or(NonZeroIsize::MIN)
+    );
+}
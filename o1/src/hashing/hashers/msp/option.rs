@@ -1,22 +1,28 @@
-//! Implements [`Hasher`] for `Option<T>` where `T` is a primitive type.
+//! Implements [`Hasher`] for `Option<T>` for every `T` supported by [`MSPHasher<T>`] - including
+//! nested options like `Option<Option<T>>`.
 //!
-//! The implementation delegates to the existing [`MSPHasher<T>`].
+//! The implementation delegates to the existing [`MSPHasher<T>`]. The const methods can't be
+//! implemented generically, so they are provided for the primitive types, arrays and strings
+//! and for the same types wrapped into one more `Option`.
+//!
+//! Arrays of options `[Option<T>; N]` are hashed by folding the hashes of the elements - each
+//! hashed like `Option<T>` - with [`pair_multiply_shift`], so `None` and `Some(0)` are told
+//! apart at every position. Their const methods are provided for the primitive integer types
+//! and strings.
 
 use super::core::MSPHasher;
-use crate::hashing::common::{num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::common::{clamp_num_bits, num_bits_for_buckets, num_buckets_for_bits};
 use crate::hashing::multiply_shift::{multiply_shift, pair_multiply_shift};
-use crate::utils::xorshift::generate_random_array;
+use crate::utils::xorshift::{generate_random_array, XorShift};
 use o1_core::Hasher;
 use rand::{Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
 
 /// State for hashing `Option<T>` values.
-#[derive(Debug, Clone, Copy)]
 pub struct OptionState<T>
 where
     T: Eq,
     MSPHasher<T>: Hasher<T>,
-    <MSPHasher<T> as Hasher<T>>::State: Copy + Clone + core::fmt::Debug + Default,
 {
     tag_seed: [u64; 2],
     combiner_seed: [u64; 3],
@@ -24,25 +30,57 @@ where
     num_bits: u32,
 }
 
+// Implemented manually since deriving would require `T` itself to implement the traits.
+impl<T> Clone for OptionState<T>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            tag_seed: self.tag_seed,
+            combiner_seed: self.combiner_seed,
+            inner: self.inner.clone(),
+            num_bits: self.num_bits,
+        }
+    }
+}
+
+impl<T> Copy for OptionState<T>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+    <MSPHasher<T> as Hasher<T>>::State: Copy,
+{
+}
+
+impl<T> core::fmt::Debug for OptionState<T>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+    <MSPHasher<T> as Hasher<T>>::State: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OptionState")
+            .field("tag_seed", &self.tag_seed)
+            .field("combiner_seed", &self.combiner_seed)
+            .field("inner", &self.inner)
+            .field("num_bits", &self.num_bits)
+            .finish()
+    }
+}
+
 impl<T> OptionState<T>
 where
     T: Eq,
     MSPHasher<T>: Hasher<T>,
-    <MSPHasher<T> as Hasher<T>>::State: Copy + Clone + core::fmt::Debug + Default,
 {
     fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
         let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed.wrapping_add(1000));
         let tag_seed: [u64; 2] = rng.random();
         let combiner_seed: [u64; 3] = rng.random();
         let inner = <MSPHasher<T> as Hasher<T>>::make_state(seed.wrapping_add(2000), num_buckets);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self {
             tag_seed,
@@ -57,7 +95,6 @@ impl<T> Default for OptionState<T>
 where
     T: Eq,
     MSPHasher<T>: Hasher<T>,
-    <MSPHasher<T> as Hasher<T>>::State: Copy + Clone + core::fmt::Debug + Default,
 {
     fn default() -> Self {
         Self {
@@ -69,43 +106,58 @@ where
     }
 }
 
-macro_rules! impl_option_msp {
-    ($($t:ty),*) => {
-        $(
-            impl Hasher<Option<$t>> for MSPHasher<Option<$t>> {
-                type State = OptionState<$t>;
+impl<T> Hasher<Option<T>> for MSPHasher<Option<T>>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+{
+    type State = OptionState<T>;
 
-                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
-                    OptionState::<$t>::from_seed(seed, num_buckets)
-                }
-                fn from_seed(seed: u64, num_buckets: u32) -> Self {
-                    let state = OptionState::<$t>::from_seed(seed, num_buckets);
-                    Self { state }
-                }
-                fn from_state(state: Self::State) -> Self { Self { state } }
-                fn state(&self) -> &Self::State { &self.state }
-                fn num_buckets(&self) -> u32 { num_buckets_for_bits(self.state.num_bits) }
-                fn hash(&self, value: &Option<$t>) -> u32 {
-                    let tag_hash = multiply_shift(
-                        match value { None => 0u32, Some(_) => 1u32 },
-                        self.state.num_bits,
-                        &self.state.tag_seed,
-                    );
-                    let inner_hash = match value {
-                        None => 0u32,
-                        Some(v) => {
-                            let inner = MSPHasher::<$t>::from_state(self.state.inner);
-                            inner.hash(v)
-                        }
-                    };
-                    let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
-                    pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
-                }
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        OptionState::<T>::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = OptionState::<T>::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Option<T>) -> u32 {
+        let tag_hash = multiply_shift(
+            match value {
+                None => 0u32,
+                Some(_) => 1u32,
+            },
+            self.state.num_bits,
+            &self.state.tag_seed,
+        );
+        let inner_hash = match value {
+            None => 0u32,
+            Some(v) => {
+                let inner = MSPHasher::<T>::from_state(self.state.inner.clone());
+                inner.hash(v)
             }
+        };
+        let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
+        pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
+    }
+}
 
-            impl MSPHasher<Option<$t>> {
+/// Generates the const methods of `MSPHasher<Option<T>>` for the specified types.
+///
+/// Each type is preceded by the generic parameters of the impl in square brackets.
+macro_rules! impl_option_msp_const {
+    ($([$($generics:tt)*] $t:ty),* $(,)?) => {
+        $(
+            impl<$($generics)*> MSPHasher<Option<$t>> {
                 pub const fn make_state_const(seed: u64, num_buckets: u32) -> OptionState<$t> {
-                    debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
                     let mut tag_seed: [u64; 2] =
                         generate_random_array!(u64, 2, seed.wrapping_add(1000));
                     tag_seed[0] |= 1;
@@ -114,12 +166,7 @@ macro_rules! impl_option_msp {
                     combiner_seed[0] |= 1;
                     let inner =
                         MSPHasher::<$t>::make_state_const(seed.wrapping_add(3000), num_buckets);
-                    let num_bits = num_bits_for_buckets(num_buckets);
-
-                    debug_assert!(
-                        num_bits >= 1 && num_bits <= 32,
-                        r#""num_bits" must be [1, 32]"#
-                    );
+                    let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
                     OptionState { tag_seed, combiner_seed, inner, num_bits }
                 }
@@ -154,204 +201,217 @@ macro_rules! impl_option_msp {
     };
 }
 
-macro_rules! impl_option_msp_array {
+/// Generates the const methods of `MSPHasher<Option<T>>` for the integer types, the arrays of
+/// them and the same types wrapped into one more `Option`.
+macro_rules! impl_option_msp_int {
     ($($t:ty),*) => {
-        $(
-            impl<const N: usize> Hasher<Option<[$t; N]>> for MSPHasher<Option<[$t; N]>>
-            where
-                [$t; N]: Eq,
-                MSPHasher<[$t; N]>: Hasher<[$t; N]>,
-                <MSPHasher<[$t; N]> as Hasher<[$t; N]>>::State:
-                    Copy + Clone + core::fmt::Debug + Default,
-            {
-                type State = OptionState<[$t; N]>;
-
-                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
-                    OptionState::<[$t; N]>::from_seed(seed, num_buckets)
-                }
-                fn from_seed(seed: u64, num_buckets: u32) -> Self {
-                    let state = OptionState::<[$t; N]>::from_seed(seed, num_buckets);
-                    Self { state }
-                }
-                fn from_state(state: Self::State) -> Self { Self { state } }
-                fn state(&self) -> &Self::State { &self.state }
-                fn num_buckets(&self) -> u32 { num_buckets_for_bits(self.state.num_bits) }
-                fn hash(&self, value: &Option<[$t; N]>) -> u32 {
-                    let tag_hash = multiply_shift(
-                        match value { None => 0u32, Some(_) => 1u32 },
-                        self.state.num_bits,
-                        &self.state.tag_seed,
-                    );
-                    let inner_hash = match value {
-                        None => 0u32,
-                        Some(v) => {
-                            let inner = MSPHasher::<[$t; N]>::from_state(self.state.inner);
-                            inner.hash(v)
-                        }
-                    };
-                    let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
-                    pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
-                }
-            }
+        impl_option_msp_const!(
+            $(
+                [] $t,
+                [const N: usize] [$t; N],
+                [] Option<$t>,
+                [const N: usize] Option<[$t; N]>,
+            )*
+        );
+    };
+}
 
-            impl<const N: usize> MSPHasher<Option<[$t; N]>> {
-                pub const fn make_state_const(seed: u64, num_buckets: u32) -> OptionState<[$t; N]> {
-                    debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-                    let mut tag_seed: [u64; 2] =
-                        generate_random_array!(u64, 2, seed.wrapping_add(1000));
-                    tag_seed[0] |= 1;
-                    let mut combiner_seed: [u64; 3] =
-                        generate_random_array!(u64, 3, seed.wrapping_add(2000));
-                    combiner_seed[0] |= 1;
-                    let inner = MSPHasher::<[$t; N]>::make_state_const(
-                        seed.wrapping_add(3000),
-                        num_buckets,
-                    );
-                    let num_bits = num_bits_for_buckets(num_buckets);
+impl_option_msp_int!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+#[cfg(target_pointer_width = "64")]
+impl_option_msp_int!(usize, isize);
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "16"))]
+impl_option_msp_int!(usize, isize);
 
-                    debug_assert!(
-                        num_bits >= 1 && num_bits <= 32,
-                        r#""num_bits" must be [1, 32]"#
-                    );
+impl_option_msp_const!(
+    ['a] &'a [u8],
+    ['a] &'a str,
+    ['a] Option<&'a [u8]>,
+    ['a] Option<&'a str>,
+);
 
-                    OptionState { tag_seed, combiner_seed, inner, num_bits }
-                }
-                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-                    let state = Self::make_state_const(seed, num_buckets);
-                    Self { state }
-                }
-                pub const fn from_state_const(
-                    state: <Self as Hasher<Option<[$t; N]>>>::State,
-                ) -> Self {
-                    Self { state }
-                }
-                pub const fn num_buckets_const(&self) -> u32 {
-                    num_buckets_for_bits(self.state.num_bits)
-                }
-                pub const fn hash_const(&self, value: &Option<[$t; N]>) -> u32 {
-                    let tag_hash = multiply_shift(
-                        match value { None => 0u32, Some(_) => 1u32 },
-                        self.state.num_bits,
-                        &self.state.tag_seed,
-                    );
-                    let inner_hash = match value {
-                        None => 0u32,
-                        Some(v) => {
-                            let inner = MSPHasher::<[$t; N]>::from_state_const(self.state.inner);
-                            inner.hash_const(v)
-                        }
-                    };
-                    let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
-                    pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
-                }
-            }
-        )*
-    };
+/// State for hashing `[Option<T>; N]` values.
+pub struct OptionArrayState<T, const N: usize>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+{
+    /// The state of the 32-bit hashes of the elements.
+    element: OptionState<T>,
+    /// The seed of folding the hash of each element into the hash of the array.
+    fold_seed: [[u64; 3]; N],
+    num_bits: u32,
 }
 
-macro_rules! impl_option_msp_ref {
-    ($($t:ty),*) => {
-        $(
-            impl<'a> Hasher<Option<$t>> for MSPHasher<Option<$t>>
-            where
-                MSPHasher<$t>: Hasher<$t>,
-                <MSPHasher<$t> as Hasher<$t>>::State:
-                    Copy + Clone + core::fmt::Debug + Default,
-            {
-                type State = OptionState<$t>;
-
-                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
-                    OptionState::<$t>::from_seed(seed, num_buckets)
-                }
-                fn from_seed(seed: u64, num_buckets: u32) -> Self {
-                    let state = OptionState::<$t>::from_seed(seed, num_buckets);
-                    Self { state }
-                }
-                fn from_state(state: Self::State) -> Self { Self { state } }
-                fn state(&self) -> &Self::State { &self.state }
-                fn num_buckets(&self) -> u32 { num_buckets_for_bits(self.state.num_bits) }
-                fn hash(&self, value: &Option<$t>) -> u32 {
-                    let tag_hash = multiply_shift(
-                        match value { None => 0u32, Some(_) => 1u32 },
-                        self.state.num_bits,
-                        &self.state.tag_seed,
-                    );
-                    let inner_hash = match value {
-                        None => 0u32,
-                        Some(v) => {
-                            let inner = MSPHasher::<$t>::from_state(self.state.inner);
-                            inner.hash(v)
-                        }
-                    };
-                    let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
-                    pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
-                }
-            }
+impl<T, const N: usize> Clone for OptionArrayState<T, N>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            element: self.element.clone(),
+            fold_seed: self.fold_seed,
+            num_bits: self.num_bits,
+        }
+    }
+}
 
-            impl<'a> MSPHasher<Option<$t>> {
-                pub const fn make_state_const(seed: u64, num_buckets: u32) -> OptionState<$t> {
-                    debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-                    let mut tag_seed: [u64; 2] =
-                        generate_random_array!(u64, 2, seed.wrapping_add(1000));
-                    tag_seed[0] |= 1;
-                    let mut combiner_seed: [u64; 3] =
-                        generate_random_array!(u64, 3, seed.wrapping_add(2000));
-                    combiner_seed[0] |= 1;
-                    let inner =
-                        MSPHasher::<$t>::make_state_const(seed.wrapping_add(3000), num_buckets);
-                    let num_bits = num_bits_for_buckets(num_buckets);
+impl<T, const N: usize> Copy for OptionArrayState<T, N>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+    <MSPHasher<T> as Hasher<T>>::State: Copy,
+{
+}
 
-                    debug_assert!(
-                        num_bits >= 1 && num_bits <= 32,
-                        r#""num_bits" must be [1, 32]"#
-                    );
+impl<T, const N: usize> core::fmt::Debug for OptionArrayState<T, N>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+    <MSPHasher<T> as Hasher<T>>::State: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OptionArrayState")
+            .field("element", &self.element)
+            .field("fold_seed", &self.fold_seed)
+            .field("num_bits", &self.num_bits)
+            .finish()
+    }
+}
 
-                    OptionState { tag_seed, combiner_seed, inner, num_bits }
+impl<T, const N: usize> Default for OptionArrayState<T, N>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+{
+    fn default() -> Self {
+        Self {
+            element: OptionState::default(),
+            fold_seed: [[0; 3]; N],
+            num_bits: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> OptionArrayState<T, N>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+{
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed.wrapping_add(4000));
+        let fold_seed: [[u64; 3]; N] = rng.random();
+        let element = OptionState::<T>::from_seed(seed.wrapping_add(5000), u32::MAX);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            element,
+            fold_seed,
+            num_bits,
+        }
+    }
+}
+
+/// Folds the 32-bit hash of an element into the 32-bit hash of the preceding elements.
+#[inline]
+const fn fold_option_hash(hash: u32, element_hash: u32, seed: &[u64; 3]) -> u32 {
+    pair_multiply_shift(((hash as u64) << 32) | element_hash as u64, u32::BITS, seed)
+}
+
+impl<T, const N: usize> Hasher<[Option<T>; N]> for MSPHasher<[Option<T>; N]>
+where
+    T: Eq,
+    MSPHasher<T>: Hasher<T>,
+{
+    type State = OptionArrayState<T, N>;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        OptionArrayState::<T, N>::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = OptionArrayState::<T, N>::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &[Option<T>; N]) -> u32 {
+        let element_hasher = MSPHasher::<Option<T>>::from_state(self.state.element.clone());
+        let hash = value
+            .iter()
+            .zip(self.state.fold_seed.iter())
+            .fold(0, |hash, (element, seed)| {
+                fold_option_hash(hash, element_hasher.hash(element), seed)
+            });
+        hash >> (u32::BITS - self.state.num_bits)
+    }
+}
+
+/// Generates the const methods of `MSPHasher<[Option<T>; N]>` for the specified types.
+///
+/// Each type is preceded by the generic parameters of the impl in square brackets.
+macro_rules! impl_option_array_msp_const {
+    ($([$($generics:tt)*] $t:ty),* $(,)?) => {
+        $(
+            impl<$($generics)* const N: usize> MSPHasher<[Option<$t>; N]> {
+                pub const fn make_state_const(
+                    seed: u64,
+                    num_buckets: u32,
+                ) -> OptionArrayState<$t, N> {
+                    let mut rng = XorShift::<u64>::new(seed.wrapping_add(4000));
+                    let mut fold_seed = [[0u64; 3]; N];
+                    let mut i = 0;
+                    while i < N {
+                        fold_seed[i] = [rng.next() | 1, rng.next(), rng.next()];
+                        i += 1;
+                    }
+                    let element =
+                        MSPHasher::<Option<$t>>::make_state_const(seed.wrapping_add(5000), u32::MAX);
+                    let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+                    OptionArrayState { element, fold_seed, num_bits }
                 }
                 pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
                     let state = Self::make_state_const(seed, num_buckets);
                     Self { state }
                 }
-                pub const fn from_state_const(state: <Self as Hasher<Option<$t>>>::State) -> Self {
+                pub const fn from_state_const(
+                    state: <Self as Hasher<[Option<$t>; N]>>::State,
+                ) -> Self {
                     Self { state }
                 }
                 pub const fn num_buckets_const(&self) -> u32 {
                     num_buckets_for_bits(self.state.num_bits)
                 }
-                pub const fn hash_const(&self, value: &Option<$t>) -> u32 {
-                    let tag_hash = multiply_shift(
-                        match value { None => 0u32, Some(_) => 1u32 },
-                        self.state.num_bits,
-                        &self.state.tag_seed,
-                    );
-                    let inner_hash = match value {
-                        None => 0u32,
-                        Some(v) => {
-                            let inner = MSPHasher::<$t>::from_state_const(self.state.inner);
-                            inner.hash_const(v)
-                        }
-                    };
-                    let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
-                    pair_multiply_shift(combined, self.state.num_bits, &self.state.combiner_seed)
+                pub const fn hash_const(&self, value: &[Option<$t>; N]) -> u32 {
+                    let element_hasher =
+                        MSPHasher::<Option<$t>>::from_state_const(self.state.element);
+                    let mut hash = 0;
+                    let mut i = 0;
+                    while i < N {
+                        let element_hash = element_hasher.hash_const(&value[i]);
+                        hash = fold_option_hash(hash, element_hash, &self.state.fold_seed[i]);
+                        i += 1;
+                    }
+                    hash >> (u32::BITS - self.state.num_bits)
                 }
             }
         )*
     };
 }
 
-impl_option_msp!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
-#[cfg(target_pointer_width = "64")]
-impl_option_msp!(usize, isize);
-#[cfg(any(target_pointer_width = "32", target_pointer_width = "16"))]
-impl_option_msp!(usize, isize);
-
-impl_option_msp_array!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
-#[cfg(target_pointer_width = "64")]
-impl_option_msp_array!(usize, isize);
-#[cfg(any(target_pointer_width = "32", target_pointer_width = "16"))]
-impl_option_msp_array!(usize, isize);
-
-impl_option_msp_ref!(&'a [u8], &'a str);
+impl_option_array_msp_const!(
+    [] u8, [] i8, [] u16, [] i16, [] u32, [] i32, [] u64, [] i64, [] u128, [] i128,
+    [] usize, [] isize,
+    ['a,] &'a [u8],
+    ['a,] &'a str,
+);
 
 #[cfg(test)]
 mod tests {
@@ -426,6 +486,31 @@ mod tests {
         }
     );
 
+    generate_hasher_tests!(
+        MSPHasher<Option<Option<u32>>>,
+        Option<Option<u32>>,
+        |rng: &mut ChaCha20Rng| {
+            match rng.random::<u32>() % 10 {
+                0..2 => None,
+                2..4 => Some(None),
+                _ => Some(Some(rng.random::<u32>())),
+            }
+        }
+    );
+
+    generate_hasher_tests!(
+        MSPHasher<Option<Option<&'static str>>>,
+        Option<Option<&'static str>>,
+        |rng: &mut ChaCha20Rng| {
+            let options = ["alpha", "beta", "gamma", "delta"];
+            match rng.random::<u32>() % 10 {
+                0..2 => None,
+                2..4 => Some(None),
+                _ => Some(Some(options[(rng.next_u32() as usize) % options.len()])),
+            }
+        }
+    );
+
     generate_hasher_tests!(
         MSPHasher<Option<[u32; 32]>>,
         Option<[u32; 32]>,
@@ -438,4 +523,82 @@ mod tests {
             }
         }
     );
+
+    generate_hasher_tests!(
+        MSPHasher<[Option<u32>; 4]>,
+        [Option<u32>; 4],
+        |rng: &mut ChaCha20Rng| {
+            core::array::from_fn(|_| match rng.random::<u32>() % 10 {
+                0..3 => None,
+                3..5 => Some(0),
+                _ => Some(rng.random::<u32>()),
+            })
+        }
+    );
+
+    generate_hasher_tests!(
+        MSPHasher<[Option<&'static str>; 3]>,
+        [Option<&'static str>; 3],
+        |rng: &mut ChaCha20Rng| {
+            let options = ["alpha", "beta", "gamma", "delta"];
+            core::array::from_fn(|_| match rng.random::<u32>() % 10 {
+                0..3 => None,
+                _ => Some(options[(rng.next_u32() as usize) % options.len()]),
+            })
+        }
+    );
+
+    /// Every combination of the positions holding `None` and `Some(0)` hashes differently.
+    #[test]
+    fn test_option_array_distinguishes_none_positions() {
+        let values: [[Option<u32>; 4]; 16] = core::array::from_fn(|mask| {
+            core::array::from_fn(|i| (mask >> i & 1 == 1).then_some(0))
+        });
+        for seed in 0..20 {
+            let hasher = MSPHasher::<[Option<u32>; 4]>::from_seed(seed, u32::MAX);
+            let const_hasher = MSPHasher::<[Option<u32>; 4]>::from_seed_const(seed, u32::MAX);
+            let hashes = values.map(|v| hasher.hash(&v));
+            let const_hashes = values.map(|v| const_hasher.hash_const(&v));
+            for i in 0..values.len() {
+                for j in i + 1..values.len() {
+                    assert_ne!(hashes[i], hashes[j], "Seed: {seed}, i: {i}, j: {j}");
+                    assert_ne!(
+                        const_hashes[i], const_hashes[j],
+                        "Seed: {seed}, i: {i}, j: {j}"
+                    );
+                }
+            }
+        }
+    }
+
+    /// `None`, `Some(None)` and `Some(Some(x))` must be told apart even when the inner value is
+    /// zero - the value every absent level hashes as.
+    #[test]
+    fn test_nested_option_distinguishes_levels() {
+        let values = [None, Some(None), Some(Some(0)), Some(Some(1))];
+        for seed in 0..100 {
+            let hasher = MSPHasher::<Option<Option<u32>>>::from_seed(seed, u32::MAX);
+            let const_hasher = MSPHasher::<Option<Option<u32>>>::from_seed_const(seed, u32::MAX);
+            let hashes = values.map(|v| hasher.hash(&v));
+            let const_hashes = values.map(|v| const_hasher.hash_const(&v));
+            for i in 0..values.len() {
+                for j in i + 1..values.len() {
+                    assert_ne!(hashes[i], hashes[j], "Seed: {seed}, i: {i}, j: {j}");
+                    assert_ne!(
+                        const_hashes[i], const_hashes[j],
+                        "Seed: {seed}, i: {i}, j: {j}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_deeply_nested_option() {
+        let hasher = MSPHasher::<Option<Option<Option<u64>>>>::from_seed(42, 1 << 16);
+        let values = [None, Some(None), Some(Some(None)), Some(Some(Some(7)))];
+        for value in values {
+            assert!(hasher.hash(&value) < hasher.num_buckets());
+        }
+    }
 }
@@ -0,0 +1,100 @@
+//! Implements [`Hasher`] for [`Range`] of `u32` and `u64`.
+//!
+//! The `(start, end)` pair is packed into a single integer twice as wide - `start` in the higher
+//! half and `end` in the lower one - and hashed by delegating to [`MSPHasher<u64>`] and
+//! [`MSPHasher<u128>`] respectively. The packing is injective, so distinct ranges never share an
+//! input.
+
+use super::bigint::BigIntState;
+use super::core::MSPHasher;
+use super::int64::U64State;
+use core::ops::Range;
+use o1_core::Hasher;
+
+/// Generates [`Hasher`] implementations for ranges of `$T` packed into `$Packed`.
+macro_rules! impl_msp_range {
+    ($($T:ty => $Packed:ty, $State:ty);* $(;)?) => {
+        $(
+            impl Hasher<Range<$T>> for MSPHasher<Range<$T>> {
+                type State = $State;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    <MSPHasher<$Packed> as Hasher<$Packed>>::make_state(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = Self::make_state(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    MSPHasher::<$Packed>::from_state(self.state).num_buckets()
+                }
+                fn hash(&self, value: &Range<$T>) -> u32 {
+                    let bits = ((value.start as $Packed) << <$T>::BITS) | value.end as $Packed;
+                    MSPHasher::<$Packed>::from_state(self.state).hash(&bits)
+                }
+            }
+
+            impl MSPHasher<Range<$T>> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> $State {
+                    MSPHasher::<$Packed>::make_state_const(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = Self::make_state_const(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<Range<$T>>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    MSPHasher::<$Packed>::from_state_const(self.state).num_buckets_const()
+                }
+                pub const fn hash_const(&self, value: &Range<$T>) -> u32 {
+                    let bits = ((value.start as $Packed) << <$T>::BITS) | value.end as $Packed;
+                    MSPHasher::<$Packed>::from_state_const(self.state).hash_const(&bits)
+                }
+            }
+        )*
+    };
+}
+
+impl_msp_range!(
+    u32 => u64, U64State;
+    u64 => u128, BigIntState<u128>;
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(
+        MSPHasher<Range<u32>>,
+        Range<u32>,
+        |rng: &mut ChaCha20Rng| {
+            let start = rng.random::<u32>();
+            start..rng.random_range(start..=u32::MAX)
+        }
+    );
+    generate_hasher_tests!(
+        MSPHasher<Range<u64>>,
+        Range<u64>,
+        |rng: &mut ChaCha20Rng| {
+            let start = rng.random::<u64>();
+            start..rng.random_range(start..=u64::MAX)
+        }
+    );
+
+    #[test]
+    fn test_adjacent_ranges_hash_differently() {
+        let hasher = MSPHasher::<Range<u32>>::from_seed(42, u32::MAX);
+        assert_ne!(hasher.hash(&(0..10)), hasher.hash(&(10..20)));
+        let hasher = MSPHasher::<Range<u64>>::from_seed(42, u32::MAX);
+        assert_ne!(hasher.hash(&(0..10)), hasher.hash(&(10..20)));
+    }
+}
@@ -0,0 +1,125 @@
+//! Implements [`Hasher`] for references to the primitive integer types (`&u32`, `&i64`, etc).
+//!
+//! The values are hashed by delegating to the hasher of the referenced type, which allows
+//! building maps keyed by references to externally-owned keys without copying them.
+
+use super::core::MSPHasher;
+use o1_core::Hasher;
+
+/// Generates [`Hasher`] implementations for references to integer types.
+///
+/// The generated impls call the implementation of the referenced type with the dereferenced
+/// value.
+macro_rules! impl_multiply_shift_reference {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> Hasher<&'a $t> for MSPHasher<&'a $t> {
+                type State = <MSPHasher<$t> as Hasher<$t>>::State;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    <MSPHasher<$t> as Hasher<$t>>::make_state(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = Self::make_state(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    MSPHasher::<$t>::from_state(self.state).num_buckets()
+                }
+                fn hash(&self, value: &&'a $t) -> u32 {
+                    MSPHasher::<$t>::from_state(self.state).hash(*value)
+                }
+            }
+
+            impl<'a> MSPHasher<&'a $t> {
+                pub const fn make_state_const(
+                    seed: u64,
+                    num_buckets: u32,
+                ) -> <Self as Hasher<&'a $t>>::State {
+                    MSPHasher::<$t>::make_state_const(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = Self::make_state_const(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<&'a $t>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    MSPHasher::<$t>::from_state_const(self.state).num_buckets_const()
+                }
+                pub const fn hash_const(&self, value: &&'a $t) -> u32 {
+                    MSPHasher::<$t>::from_state_const(self.state).hash_const(*value)
+                }
+            }
+        )*
+    };
+}
+
+impl_multiply_shift_reference!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fks::FKSMap;
+    use o1_core::HashMap;
+    use o1_test::data::{I128_DATA, I16_DATA, U32_DATA, U64_DATA, U8_DATA, USIZE_DATA};
+    use o1_test::generate_hasher_tests;
+
+    // The keys are borrowed from statics since the tests require `'static` keys.
+    static U8_KEYS: [(u8, u64); 255] = U8_DATA;
+    static I16_KEYS: [(i16, u64); 999] = I16_DATA;
+    static U32_KEYS: [(u32, u64); 999] = U32_DATA;
+    static U64_KEYS: [(u64, u64); 999] = U64_DATA;
+    static I128_KEYS: [(i128, u64); 999] = I128_DATA;
+    static USIZE_KEYS: [(usize, u64); 999] = USIZE_DATA;
+
+    generate_hasher_tests!(MSPHasher<&u8>, &'static u8, |rng: &mut ChaCha20Rng| {
+        &U8_KEYS[rng.random_range(0..U8_KEYS.len())].0
+    });
+    generate_hasher_tests!(MSPHasher<&i16>, &'static i16, |rng: &mut ChaCha20Rng| {
+        &I16_KEYS[rng.random_range(0..I16_KEYS.len())].0
+    });
+    generate_hasher_tests!(MSPHasher<&u32>, &'static u32, |rng: &mut ChaCha20Rng| {
+        &U32_KEYS[rng.random_range(0..U32_KEYS.len())].0
+    });
+    generate_hasher_tests!(MSPHasher<&u64>, &'static u64, |rng: &mut ChaCha20Rng| {
+        &U64_KEYS[rng.random_range(0..U64_KEYS.len())].0
+    });
+    generate_hasher_tests!(MSPHasher<&i128>, &'static i128, |rng: &mut ChaCha20Rng| {
+        &I128_KEYS[rng.random_range(0..I128_KEYS.len())].0
+    });
+    generate_hasher_tests!(
+        MSPHasher<&usize>,
+        &'static usize,
+        |rng: &mut ChaCha20Rng| { &USIZE_KEYS[rng.random_range(0..USIZE_KEYS.len())].0 }
+    );
+
+    #[test]
+    fn test_hash_equals_referenced_type() {
+        let values = [0_u64, 1, 42, u64::MAX];
+        let hasher = MSPHasher::<&u64>::from_seed(42, 1 << 16);
+        let owned = MSPHasher::<u64>::from_state(*hasher.state());
+        for value in values.iter() {
+            assert_eq!(hasher.hash(&value), owned.hash(value));
+        }
+    }
+
+    #[test]
+    fn test_map_keyed_by_references() {
+        let keys: Vec<u64> = (0..1000_u64).map(|i| i * 7919).collect();
+        let data: Box<[(&u64, usize)]> = keys.iter().enumerate().map(|(i, k)| (k, i)).collect();
+        let map = FKSMap::<&u64, usize, MSPHasher<&u64>>::new(data, 42, 0.75).unwrap();
+
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(map.get(&key), Some(&i));
+        }
+        assert_eq!(map.get(&&1_u64), None);
+    }
+}
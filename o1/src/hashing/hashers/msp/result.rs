@@ -0,0 +1,288 @@
+//! Implements [`Hasher`] for `Result<T, E>` for every `T` and `E` supported by [`MSPHasher`].
+//!
+//! The discriminant is hashed separately and combined with the hash value of the contained
+//! value - produced by [`MSPHasher<T>`] for `Ok` and by [`MSPHasher<E>`] for `Err` - in the same
+//! way as [`OptionState`](super::OptionState) does. The const methods can't be implemented
+//! generically, so they are provided for all the combinations of the primitive integer types.
+
+use super::core::MSPHasher;
+use crate::hashing::common::{clamp_num_bits, num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::multiply_shift::{multiply_shift, pair_multiply_shift};
+use crate::utils::xorshift::generate_random_array;
+use o1_core::Hasher;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// State for hashing `Result<T, E>` values.
+pub struct ResultState<T, E>
+where
+    T: Eq,
+    E: Eq,
+    MSPHasher<T>: Hasher<T>,
+    MSPHasher<E>: Hasher<E>,
+{
+    tag_seed: [u64; 2],
+    combiner_seed: [u64; 3],
+    ok: <MSPHasher<T> as Hasher<T>>::State,
+    err: <MSPHasher<E> as Hasher<E>>::State,
+    num_bits: u32,
+}
+
+// Implemented manually since deriving would require `T` and `E` themselves to implement the
+// traits.
+impl<T, E> Clone for ResultState<T, E>
+where
+    T: Eq,
+    E: Eq,
+    MSPHasher<T>: Hasher<T>,
+    MSPHasher<E>: Hasher<E>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            tag_seed: self.tag_seed,
+            combiner_seed: self.combiner_seed,
+            ok: self.ok.clone(),
+            err: self.err.clone(),
+            num_bits: self.num_bits,
+        }
+    }
+}
+
+impl<T, E> Copy for ResultState<T, E>
+where
+    T: Eq,
+    E: Eq,
+    MSPHasher<T>: Hasher<T>,
+    MSPHasher<E>: Hasher<E>,
+    <MSPHasher<T> as Hasher<T>>::State: Copy,
+    <MSPHasher<E> as Hasher<E>>::State: Copy,
+{
+}
+
+impl<T, E> core::fmt::Debug for ResultState<T, E>
+where
+    T: Eq,
+    E: Eq,
+    MSPHasher<T>: Hasher<T>,
+    MSPHasher<E>: Hasher<E>,
+    <MSPHasher<T> as Hasher<T>>::State: core::fmt::Debug,
+    <MSPHasher<E> as Hasher<E>>::State: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ResultState")
+            .field("tag_seed", &self.tag_seed)
+            .field("combiner_seed", &self.combiner_seed)
+            .field("ok", &self.ok)
+            .field("err", &self.err)
+            .field("num_bits", &self.num_bits)
+            .finish()
+    }
+}
+
+impl<T, E> ResultState<T, E>
+where
+    T: Eq,
+    E: Eq,
+    MSPHasher<T>: Hasher<T>,
+    MSPHasher<E>: Hasher<E>,
+{
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed.wrapping_add(1000));
+        let mut tag_seed: [u64; 2] = rng.random();
+        tag_seed[0] |= 1;
+        let combiner_seed: [u64; 3] = rng.random();
+        let ok = <MSPHasher<T> as Hasher<T>>::make_state(seed.wrapping_add(2000), num_buckets);
+        let err = <MSPHasher<E> as Hasher<E>>::make_state(seed.wrapping_add(3000), num_buckets);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            tag_seed,
+            combiner_seed,
+            ok,
+            err,
+            num_bits,
+        }
+    }
+}
+
+impl<T, E> Default for ResultState<T, E>
+where
+    T: Eq,
+    E: Eq,
+    MSPHasher<T>: Hasher<T>,
+    MSPHasher<E>: Hasher<E>,
+{
+    fn default() -> Self {
+        Self {
+            tag_seed: [0; 2],
+            combiner_seed: [0; 3],
+            ok: <MSPHasher<T> as Hasher<T>>::State::default(),
+            err: <MSPHasher<E> as Hasher<E>>::State::default(),
+            num_bits: 0,
+        }
+    }
+}
+
+/// Combines the hash value of the discriminant with the hash value of the contained value.
+#[inline]
+const fn combine(
+    tag: u32,
+    inner_hash: u32,
+    num_bits: u32,
+    tag_seed: &[u64; 2],
+    combiner_seed: &[u64; 3],
+) -> u32 {
+    let tag_hash = multiply_shift(tag, num_bits, tag_seed);
+    let combined = ((tag_hash as u64) << 32) | inner_hash as u64;
+    pair_multiply_shift(combined, num_bits, combiner_seed)
+}
+
+impl<T, E> Hasher<Result<T, E>> for MSPHasher<Result<T, E>>
+where
+    T: Eq,
+    E: Eq,
+    MSPHasher<T>: Hasher<T>,
+    MSPHasher<E>: Hasher<E>,
+{
+    type State = ResultState<T, E>;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        ResultState::<T, E>::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = ResultState::<T, E>::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Result<T, E>) -> u32 {
+        let (tag, inner_hash) = match value {
+            Ok(v) => (0, MSPHasher::<T>::from_state(self.state.ok.clone()).hash(v)),
+            Err(e) => (
+                1,
+                MSPHasher::<E>::from_state(self.state.err.clone()).hash(e),
+            ),
+        };
+        combine(
+            tag,
+            inner_hash,
+            self.state.num_bits,
+            &self.state.tag_seed,
+            &self.state.combiner_seed,
+        )
+    }
+}
+
+/// Generates the const methods of `MSPHasher<Result<T, E>>` for the specified pairs of types.
+macro_rules! impl_result_msp_const {
+    ($(($t:ty, $e:ty)),* $(,)?) => {
+        $(
+            impl MSPHasher<Result<$t, $e>> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> ResultState<$t, $e> {
+                    let mut tag_seed: [u64; 2] =
+                        generate_random_array!(u64, 2, seed.wrapping_add(1000));
+                    tag_seed[0] |= 1;
+                    let mut combiner_seed: [u64; 3] =
+                        generate_random_array!(u64, 3, seed.wrapping_add(2000));
+                    combiner_seed[0] |= 1;
+                    let ok = MSPHasher::<$t>::make_state_const(seed.wrapping_add(3000), num_buckets);
+                    let err = MSPHasher::<$e>::make_state_const(seed.wrapping_add(4000), num_buckets);
+                    let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+                    ResultState { tag_seed, combiner_seed, ok, err, num_bits }
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = Self::make_state_const(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(
+                    state: <Self as Hasher<Result<$t, $e>>>::State,
+                ) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                pub const fn hash_const(&self, value: &Result<$t, $e>) -> u32 {
+                    let (tag, inner_hash) = match value {
+                        Ok(v) => (0, MSPHasher::<$t>::from_state_const(self.state.ok).hash_const(v)),
+                        Err(e) => (1, MSPHasher::<$e>::from_state_const(self.state.err).hash_const(e)),
+                    };
+                    combine(
+                        tag,
+                        inner_hash,
+                        self.state.num_bits,
+                        &self.state.tag_seed,
+                        &self.state.combiner_seed,
+                    )
+                }
+            }
+        )*
+    };
+}
+
+/// Generates the const methods of `MSPHasher<Result<T, E>>` for every pair of the given types.
+macro_rules! impl_result_msp_int {
+    ($($t:ty),*) => {
+        impl_result_msp_int!(@ok [$($t),*] $($t),*);
+    };
+    (@ok $all:tt $($t:ty),*) => {
+        $(impl_result_msp_int!(@err $t, $all);)*
+    };
+    (@err $t:ty, [$($e:ty),*]) => {
+        impl_result_msp_const!($(($t, $e)),*);
+    };
+}
+
+impl_result_msp_int!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(
+        MSPHasher<Result<u32, u16>>,
+        Result<u32, u16>,
+        |rng: &mut ChaCha20Rng| {
+            if rng.random::<u32>() % 10 < 3 {
+                Err(rng.random::<u16>())
+            } else {
+                Ok(rng.random::<u32>())
+            }
+        }
+    );
+
+    generate_hasher_tests!(
+        MSPHasher<Result<u64, i8>>,
+        Result<u64, i8>,
+        |rng: &mut ChaCha20Rng| {
+            if rng.random::<u32>() % 10 < 3 {
+                Err(rng.random::<i8>())
+            } else {
+                Ok(rng.random::<u64>())
+            }
+        }
+    );
+
+    #[test]
+    fn test_ok_and_err_differ() {
+        for seed in 0..100 {
+            let hasher = MSPHasher::<Result<u32, u16>>::from_seed(seed, u32::MAX);
+            let const_hasher = MSPHasher::<Result<u32, u16>>::from_seed_const(seed, u32::MAX);
+            assert_ne!(hasher.hash(&Ok(0)), hasher.hash(&Err(0)), "Seed: {seed}");
+            assert_ne!(
+                const_hasher.hash_const(&Ok(0)),
+                const_hasher.hash_const(&Err(0)),
+                "Seed: {seed}"
+            );
+        }
+    }
+}
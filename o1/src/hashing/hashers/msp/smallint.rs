@@ -7,9 +7,12 @@
 //!   first, there should be specialized hash functions for these cases, so it's a TODO.
 
 use super::core::MSPHasher;
-use crate::hashing::common::{num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, num_bits_for_buckets, num_buckets_for_bits,
+};
 use crate::hashing::multiply_shift::{
     multiply_shift, pair_multiply_shift, pair_multiply_shift_vector_u8,
+    pair_multiply_shift_vector_u8_const,
 };
 use crate::utils::xorshift::generate_random_array;
 use o1_core::Hasher;
@@ -24,31 +27,17 @@ pub struct SmallIntState {
 
 impl SmallIntState {
     pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
         let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
         let seed: [u64; 2] = rng.random();
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
 
     pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
         let mut seed: [u64; 2] = generate_random_array!(u64, 2, seed);
         seed[0] |= 1;
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
@@ -169,7 +158,8 @@ impl_multiply_shift_small_int!(usize, isize);
 // We mirror the approach used for 64-bit arrays in int64.rs:
 // - Maintain a global seed (u64) and per-position seeds (two u64 values per element)
 // - Hash arrays by treating them as a byte vector and using the same vector-u8
-//   multiply-shift scheme for both runtime and const paths
+//   multiply-shift scheme for both runtime and const paths, so empty arrays hash to a constant
+//   derived from the global seed
 
 #[derive(Debug, Clone, Copy)]
 pub struct SmallArrayState<const N: usize> {
@@ -191,15 +181,8 @@ impl<const N: usize> Default for SmallArrayState<N> {
 
 impl<const N: usize> SmallArrayState<N> {
     fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
         let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         let seed = rng.random();
         let value_seed = rng.random();
@@ -212,13 +195,7 @@ impl<const N: usize> SmallArrayState<N> {
     }
 
     const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         let mut value_seed = [[0u64; 2]; N];
         let mut i = 0;
@@ -235,10 +212,90 @@ impl<const N: usize> SmallArrayState<N> {
     }
 
     const fn value_seed_as_slice(&self) -> &[u64] {
-        unsafe { std::slice::from_raw_parts(self.value_seed.as_ptr() as *const u64, N * 2) }
+        unsafe { core::slice::from_raw_parts(self.value_seed.as_ptr() as *const u64, N * 2) }
+    }
+}
+
+/// Hashes arrays of 4, 8 and 16 bytes by loading them directly into integers.
+///
+/// The length is known at compile time, so after inlining only the matching branch remains and
+/// the length dispatch of [`pair_multiply_shift_vector_u8`] is skipped. Produces the same hash
+/// values as the generic path. Returns `None` for the other lengths.
+#[inline(always)]
+const fn hash_fixed_bytes<const N: usize>(bytes: &[u8], state: &SmallArrayState<N>) -> Option<u32> {
+    let value_seed = state.value_seed_as_slice();
+    match bytes.len() {
+        4 => {
+            let value = u32::from_le_bytes(unsafe { *(bytes.as_ptr() as *const [u8; 4]) });
+            Some(multiply_shift(
+                value,
+                state.num_bits,
+                &[state.seed, value_seed[0]],
+            ))
+        }
+        8 => {
+            let value = u64::from_le_bytes(unsafe { *(bytes.as_ptr() as *const [u8; 8]) });
+            Some(pair_multiply_shift(
+                value,
+                state.num_bits,
+                &[state.seed, value_seed[0], value_seed[1]],
+            ))
+        }
+        16 => {
+            let value = u128::from_le_bytes(unsafe { *(bytes.as_ptr() as *const [u8; 16]) });
+            // Same as the vector path - each 64-bit half is multiplied as a pair of its high and
+            // its full value.
+            let first = value as u64;
+            let second = (value >> 64) as u64;
+            let sum = state
+                .seed
+                .wrapping_add(
+                    value_seed[0]
+                        .wrapping_add(first >> 32)
+                        .wrapping_mul(value_seed[1].wrapping_add(first)),
+                )
+                .wrapping_add(
+                    value_seed[2]
+                        .wrapping_add(second >> 32)
+                        .wrapping_mul(value_seed[3].wrapping_add(second)),
+                );
+            Some(extract_bits_64::<{ u64::BITS }>(sum, state.num_bits))
+        }
+        _ => None,
     }
 }
 
+/// Hashes the bytes of a fixed-size array, they may be a packed representation of a value.
+///
+/// `bytes` must not be longer than `N` elements of `u32` - the state has the seeds for no more.
+pub(super) fn hash_bytes<const N: usize>(state: &SmallArrayState<N>, bytes: &[u8]) -> u32 {
+    if let Some(hash) = hash_fixed_bytes(bytes, state) {
+        return hash;
+    }
+    pair_multiply_shift_vector_u8(
+        bytes,
+        state.num_bits,
+        state.seed,
+        state.value_seed_as_slice(),
+    )
+}
+
+/// Const version of [`hash_bytes`].
+pub(super) const fn hash_bytes_const<const N: usize>(
+    state: &SmallArrayState<N>,
+    bytes: &[u8],
+) -> u32 {
+    if let Some(hash) = hash_fixed_bytes(bytes, state) {
+        return hash;
+    }
+    pair_multiply_shift_vector_u8_const(
+        bytes,
+        state.num_bits,
+        state.seed,
+        state.value_seed_as_slice(),
+    )
+}
+
 macro_rules! impl_smallint_array_hasher {
     ($($t:ty),*) => {
         $(
@@ -257,13 +314,8 @@ macro_rules! impl_smallint_array_hasher {
                 fn num_buckets(&self) -> u32 { num_buckets_for_bits(self.state.num_bits) }
                 fn hash(&self, value: &[$t; N]) -> u32 {
                     let bytes_len = N * core::mem::size_of::<$t>();
-                    let bytes = unsafe { std::slice::from_raw_parts(value.as_ptr() as *const u8, bytes_len) };
-                    pair_multiply_shift_vector_u8(
-                        bytes,
-                        self.state.num_bits,
-                        self.state.seed,
-                        self.state.value_seed_as_slice(),
-                    )
+                    let bytes = unsafe { core::slice::from_raw_parts(value.as_ptr() as *const u8, bytes_len) };
+                    hash_bytes(&self.state, bytes)
                 }
             }
 
@@ -278,70 +330,9 @@ macro_rules! impl_smallint_array_hasher {
                 pub const fn from_state_const(state: <Self as Hasher<[$t; N]>>::State) -> Self { Self { state } }
                 pub const fn num_buckets_const(&self) -> u32 { num_buckets_for_bits(self.state.num_bits) }
                 pub const fn hash_const(&self, value: &[$t; N]) -> u32 {
-                    use core::ptr::copy_nonoverlapping;
                     let bytes_len = N * core::mem::size_of::<$t>();
-                    if bytes_len == 0 {
-                        return crate::hashing::common::extract_bits_64::<{ u64::BITS }>(self.state.seed, self.state.num_bits);
-                    }
-                    if bytes_len <= 3 {
-                        let mut padded = [0u8; 4];
-                        unsafe { copy_nonoverlapping(value.as_ptr() as *const u8, padded.as_mut_ptr(), bytes_len) }
-                        let v = u32::from_le_bytes(padded);
-                        let seed_arr = [self.state.seed, self.state.value_seed_as_slice()[0]];
-                        return multiply_shift(v, self.state.num_bits, &seed_arr);
-                    }
-                    if bytes_len == 4 {
-                        let mut bytes = [0u8; 4];
-                        unsafe { copy_nonoverlapping(value.as_ptr() as *const u8, bytes.as_mut_ptr(), 4) }
-                        let v = u32::from_le_bytes(bytes);
-                        let seed_arr = [self.state.seed, self.state.value_seed_as_slice()[0]];
-                        return multiply_shift(v, self.state.num_bits, &seed_arr);
-                    }
-                    if bytes_len <= 7 {
-                        let mut padded = [0u8; 8];
-                        unsafe { copy_nonoverlapping(value.as_ptr() as *const u8, padded.as_mut_ptr(), bytes_len) }
-                        let v = u64::from_le_bytes(padded);
-                        let seeds = self.state.value_seed_as_slice();
-                        let seed_arr = [self.state.seed, seeds[0], seeds[1]];
-                        return pair_multiply_shift(v, self.state.num_bits, &seed_arr);
-                    }
-                    if bytes_len == 8 {
-                        let mut bytes = [0u8; 8];
-                        unsafe { copy_nonoverlapping(value.as_ptr() as *const u8, bytes.as_mut_ptr(), 8) }
-                        let v = u64::from_le_bytes(bytes);
-                        let seeds = self.state.value_seed_as_slice();
-                        let seed_arr = [self.state.seed, seeds[0], seeds[1]];
-                        return pair_multiply_shift(v, self.state.num_bits, &seed_arr);
-                    }
-
-                    // General case: chunk accumulation
-                    let mut sum = self.state.seed;
-                    let num_chunks = (bytes_len + 7) >> 3;
-                    let mut i = 0;
-                    let seeds = self.state.value_seed_as_slice();
-                    while i < num_chunks {
-                        let byte_idx = i << 3;
-                        let remaining = bytes_len - byte_idx;
-                        let to_copy = if remaining < 8 { remaining } else { 8 };
-                        let mut bytes = [0u8; 8];
-                        unsafe {
-                            copy_nonoverlapping(
-                                (value.as_ptr() as *const u8).add(byte_idx),
-                                bytes.as_mut_ptr(),
-                                to_copy,
-                            );
-                        }
-                        let v = u64::from_le_bytes(bytes);
-                        let low = v;
-                        let high = v >> 32;
-                        sum = sum.wrapping_add(
-                            seeds[i * 2]
-                                .wrapping_add(high)
-                                .wrapping_mul(seeds[i * 2 + 1].wrapping_add(low)),
-                        );
-                        i += 1;
-                    }
-                    crate::hashing::common::extract_bits_64::<{ u64::BITS }>(sum, self.state.num_bits)
+                    let bytes = unsafe { core::slice::from_raw_parts(value.as_ptr() as *const u8, bytes_len) };
+                    hash_bytes_const(&self.state, bytes)
                 }
             }
         )*
@@ -402,4 +393,56 @@ mod tests {
         .random::<[u16; 64]>());
     generate_hasher_tests!(MSPHasher<[u8; 128]>, [u8; 128], |rng: &mut ChaCha20Rng| rng
         .random::<[u8; 128]>());
+
+    generate_hasher_tests!(MSPHasher<[u8; 4]>, [u8; 4], |rng: &mut ChaCha20Rng| rng
+        .random::<[u8; 4]>());
+    generate_hasher_tests!(MSPHasher<[u8; 8]>, [u8; 8], |rng: &mut ChaCha20Rng| rng
+        .random::<[u8; 8]>());
+    generate_hasher_tests!(MSPHasher<[u8; 16]>, [u8; 16], |rng: &mut ChaCha20Rng| rng
+        .random::<[u8; 16]>());
+
+    /// The fast paths must produce the same hash values as the generic byte-vector path.
+    #[test]
+    fn test_fixed_bytes_match_vector_path() {
+        use rand_chacha::ChaCha20Rng;
+
+        fn check<const N: usize>(rng: &mut ChaCha20Rng)
+        where
+            rand::distr::StandardUniform: rand::distr::Distribution<[u8; N]>,
+        {
+            for seed in 0..100 {
+                let hasher = MSPHasher::<[u8; N]>::from_seed(seed, 1 << 20);
+                let state = hasher.state();
+                let value: [u8; N] = rng.random();
+                let expected = pair_multiply_shift_vector_u8(
+                    &value,
+                    state.num_bits,
+                    state.seed,
+                    state.value_seed_as_slice(),
+                );
+                assert_eq!(hasher.hash(&value), expected, "N: {N}, seed: {seed}");
+                assert_eq!(
+                    MSPHasher::<[u8; N]>::from_state_const(*state).hash_const(&value),
+                    expected,
+                    "N: {N}, seed: {seed}"
+                );
+            }
+        }
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        check::<4>(&mut rng);
+        check::<8>(&mut rng);
+        check::<16>(&mut rng);
+    }
+
+    #[test]
+    fn test_zero_buckets_clamped() {
+        let hasher = MSPHasher::<u32>::from_seed(42, 0);
+        let const_hasher = MSPHasher::<u32>::from_seed_const(42, 0);
+
+        assert_eq!(hasher.num_buckets(), 2);
+        assert_eq!(const_hasher.num_buckets_const(), 2);
+        assert!(hasher.hash(&7) < 2);
+        assert!(const_hasher.hash_const(&7) < 2);
+    }
 }
@@ -0,0 +1,213 @@
+//! Implements an alternative hasher for 128-bit integers.
+//!
+//! Instead of hashing the value at once with [`pair_multiply_shift_u128`] the high and the low
+//! 64-bit halves are hashed independently with [`pair_multiply_shift`] and their hash values are
+//! combined in the same way as [`OptionState`](super::OptionState) combines the hash values of the
+//! discriminant and of the contained value.
+//!
+//! [`pair_multiply_shift_u128`]: crate::hashing::multiply_shift::pair_multiply_shift_u128
+
+use crate::hashing::common::{clamp_num_bits, num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::multiply_shift::pair_multiply_shift;
+use crate::utils::xorshift::generate_random_array;
+use core::fmt::{Debug, Formatter};
+use o1_core::Hasher;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// Hasher for 128-bit integers that hashes their 64-bit halves independently.
+///
+/// An alternative to [`MSPHasher<u128>`](super::MSPHasher) for key distributions on which
+/// [`pair_multiply_shift_u128`](crate::hashing::multiply_shift::pair_multiply_shift_u128) shows
+/// weaker uniformity. Costs three multiply-shifts per value instead of one.
+///
+/// Contains both runtime and compile-time (const) implementations.
+#[derive(Clone)]
+pub struct MSPHasherSplit<T: Eq>
+where
+    MSPHasherSplit<T>: Hasher<T>,
+{
+    state: <MSPHasherSplit<T> as Hasher<T>>::State,
+}
+
+// Implement Copy for MSPHasherSplit if its State is Copy
+impl<T: Eq + Clone> Copy for MSPHasherSplit<T>
+where
+    MSPHasherSplit<T>: Hasher<T>,
+    <MSPHasherSplit<T> as Hasher<T>>::State: Copy,
+{
+}
+
+impl<T: Eq> Default for MSPHasherSplit<T>
+where
+    MSPHasherSplit<T>: Hasher<T>,
+{
+    fn default() -> Self {
+        <Self as Hasher<T>>::from_state(<Self as Hasher<T>>::State::default())
+    }
+}
+
+impl<T> Debug for MSPHasherSplit<T>
+where
+    T: Eq,
+    MSPHasherSplit<T>: Hasher<T>,
+    <MSPHasherSplit<T> as Hasher<T>>::State: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MSPHasherSplit")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<T: Eq> MSPHasherSplit<T>
+where
+    MSPHasherSplit<T>: Hasher<T>,
+    <MSPHasherSplit<T> as Hasher<T>>::State: Copy,
+{
+    /// Clone the hasher in a const context.
+    pub const fn clone_const(&self) -> Self {
+        Self { state: self.state }
+    }
+}
+
+/// State of [`MSPHasherSplit`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SplitState {
+    num_bits: u32,
+    low_seed: [u64; 3],
+    high_seed: [u64; 3],
+    combiner_seed: [u64; 3],
+}
+
+impl SplitState {
+    pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            num_bits,
+            low_seed: rng.random(),
+            high_seed: rng.random(),
+            combiner_seed: rng.random(),
+        }
+    }
+
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self {
+            num_bits,
+            low_seed: generate_random_array!(u64, 3, seed),
+            high_seed: generate_random_array!(u64, 3, seed.wrapping_add(1000)),
+            combiner_seed: generate_random_array!(u64, 3, seed.wrapping_add(2000)),
+        }
+    }
+}
+
+/// Hashes the halves to full 32-bit values and then hashes their concatenation.
+#[inline]
+const fn hash(state: &SplitState, value: u128) -> u32 {
+    let low_hash = pair_multiply_shift(value as u64, u32::BITS, &state.low_seed);
+    let high_hash = pair_multiply_shift((value >> 64) as u64, u32::BITS, &state.high_seed);
+    let combined = ((high_hash as u64) << 32) | low_hash as u64;
+    pair_multiply_shift(combined, state.num_bits, &state.combiner_seed)
+}
+
+/// Generates [`Hasher`] and const implementations for 128-bit integer types.
+macro_rules! impl_split_int {
+    ($($T:ty),*) => {
+        $(
+            impl Hasher<$T> for MSPHasherSplit<$T> {
+                type State = SplitState;
+
+                fn make_state(seed: u64, num_buckets: u32) -> SplitState {
+                    SplitState::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = SplitState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, value: &$T) -> u32 {
+                    hash(&self.state, *value as u128)
+                }
+            }
+
+            impl MSPHasherSplit<$T> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> SplitState {
+                    SplitState::from_seed_const(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = SplitState::from_seed_const(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<$T>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                pub const fn hash_const(&self, value: &$T) -> u32 {
+                    hash(&self.state, *value as u128)
+                }
+            }
+        )*
+    };
+}
+
+impl_split_int!(u128, i128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::hashers::msp::MSPHasher;
+    use o1_test::*;
+    use rand_chacha::ChaCha20Rng;
+
+    generate_hasher_tests!(MSPHasherSplit<u128>, u128, |rng: &mut ChaCha20Rng| rng
+        .random::<u128>());
+    generate_hasher_tests!(MSPHasherSplit<i128>, i128, |rng: &mut ChaCha20Rng| rng
+        .random::<i128>());
+
+    /// Runs the same strong universality test against both modes of hashing `u128` values, the
+    /// failure message of either of them contains the results of both.
+    #[test]
+    #[cfg_attr(not(feature = "_slow-tests"), ignore)]
+    fn test_split_and_default_strong_universality_comparison() {
+        let test = UniversalityTest::new();
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let default = test.run::<ChaCha20Rng, u128>(&mut rng, &|rng, num_buckets| {
+            let hasher = MSPHasher::<u128>::from_seed(rng.random(), num_buckets as u32);
+            let num_buckets = hasher.num_buckets() as usize;
+            (
+                Box::new(move |value: &u128| hasher.hash(value) as usize),
+                num_buckets,
+            )
+        });
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let split = test.run::<ChaCha20Rng, u128>(&mut rng, &|rng, num_buckets| {
+            let hasher = MSPHasherSplit::<u128>::from_seed(rng.random(), num_buckets as u32);
+            let num_buckets = hasher.num_buckets() as usize;
+            (
+                Box::new(move |value: &u128| hasher.hash(value) as usize),
+                num_buckets,
+            )
+        });
+
+        assert!(
+            default.outcome && split.outcome,
+            "Default:\n{default:?}\nSplit:\n{split:?}"
+        );
+    }
+}
@@ -1,16 +1,32 @@
-//! Implements Hasher for unbounded strings represented as `&[u8]`.
+//! Implements Hasher for unbounded strings represented as `&[u8]` and for slices of integers.
 //!
 //! # Notes
 //!
 //! Internally it uses the [`polynomial`] hash function.
+//!
+//! Slices of integers are reinterpreted as bytes in the native byte order, so their hash
+//! values differ between little-endian and big-endian targets.
+//!
+//! With the `metrics` feature the runtime hash functions count the hashed bytes - see
+//! [`bytes_hashed()`].
 
 use super::core::MSPHasher;
-use crate::hashing::common::{num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::common::{
+    clamp_num_bits, cow_bytes_as_slice, cow_str_as_bytes, num_bits_for_buckets,
+    num_buckets_for_bits, CowBytes, CowStr,
+};
 use crate::hashing::multiply_shift::{
     pair_multiply_shift_vector_u8, pair_multiply_shift_vector_u8_const,
+    pair_multiply_shift_vector_u8_with_scratch,
 };
 use crate::hashing::polynomial::{polynomial, polynomial_const, PolynomialSeed};
 use crate::utils::xorshift::{generate_random, generate_random_array};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "metrics")]
+use core::sync::atomic::{AtomicUsize, Ordering};
 use o1_core::Hasher;
 use rand::{Rng, RngCore, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
@@ -20,6 +36,27 @@ const P: u128 = 2_u128.pow(N) - 1;
 const MAX_STR_VECTOR_LEN: usize = 256;
 const MUL_SHIFT_SEED_SIZE: usize = MAX_STR_VECTOR_LEN.div_ceil(4);
 
+/// The total number of bytes hashed by the runtime hash functions of the process.
+#[cfg(feature = "metrics")]
+static BYTES_HASHED: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the total number of bytes hashed by the string hashers since the start of the process
+/// or since the last [`reset_bytes_hashed()`].
+///
+/// Counts the inputs of the runtime hash functions of [`MSPHasher`] for strings, byte strings and
+/// slices of integers - the const hash functions aren't counted. The counter is global, so it
+/// includes the bytes hashed by all threads.
+#[cfg(feature = "metrics")]
+pub fn bytes_hashed() -> usize {
+    BYTES_HASHED.load(Ordering::Relaxed)
+}
+
+/// Resets the counter of [`bytes_hashed()`] to zero and returns its previous value.
+#[cfg(feature = "metrics")]
+pub fn reset_bytes_hashed() -> usize {
+    BYTES_HASHED.swap(0, Ordering::Relaxed)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct StringState {
     num_bits: u32,
@@ -44,17 +81,12 @@ impl Default for StringState {
 
 impl StringState {
     pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
 
+        // Unlike `PolynomialSeed::from_rng()`, draws all of the seed values from `[0, P)`, which
+        // keeps the hash values of the existing seeds.
         let mut polynomial_seed_value = [0_u64; 132];
         polynomial_seed_value[0] = rng.random_range(1..P) as u64;
         polynomial_seed_value[1..].fill_with(|| rng.random_range(0..P) as u64);
@@ -73,27 +105,17 @@ impl StringState {
     }
 
     pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         let mul_shift_seed = generate_random!(u64, seed);
         let mul_shift_value_seed = generate_random_array!(u64, MUL_SHIFT_SEED_SIZE, seed);
 
+        // Every `u64` value is already less than the Mersenne prime, so only the first seed value
+        // needs to be adjusted to be non-zero. The rest are the multiply-shift seeds hashing the
+        // 256-byte chunks and must keep all of their bits.
         let mut polynomial_seed_value = generate_random_array!(u64, 132, seed.wrapping_add(1));
         polynomial_seed_value[0] |= 1;
 
-        let mut i = 0;
-        while i < polynomial_seed_value.len() {
-            polynomial_seed_value[i] &= (1u64 << (N - 64)) - 1; // Clamp by P
-            i += 1;
-        }
-
         let polynomial_seed = PolynomialSeed::from_slice(&polynomial_seed_value);
 
         StringState {
@@ -111,6 +133,8 @@ fn hash(state: &StringState, value: &[u8]) -> u32 {
         (1..=32).contains(&state.num_bits),
         r#""num_bits" must be [1, 32]"#
     );
+    #[cfg(feature = "metrics")]
+    BYTES_HASHED.fetch_add(value.len(), Ordering::Relaxed);
     if value.len() <= MAX_STR_VECTOR_LEN {
         pair_multiply_shift_vector_u8(
             value,
@@ -123,6 +147,24 @@ fn hash(state: &StringState, value: &[u8]) -> u32 {
     }
 }
 
+/// Equivalent of [`hash`] that packs the inputs of the vector path into `scratch` instead of
+/// allocating a buffer.
+#[inline]
+fn hash_with_scratch(state: &StringState, value: &[u8], scratch: &mut Vec<u64>) -> u32 {
+    if value.len() > MAX_STR_VECTOR_LEN {
+        return hash(state, value);
+    }
+    #[cfg(feature = "metrics")]
+    BYTES_HASHED.fetch_add(value.len(), Ordering::Relaxed);
+    pair_multiply_shift_vector_u8_with_scratch(
+        value,
+        state.num_bits,
+        state.mul_shift_seed,
+        &state.mul_shift_value_seed,
+        scratch,
+    )
+}
+
 #[inline]
 const fn hash_const(state: &StringState, value: &[u8]) -> u32 {
     debug_assert!(
@@ -182,6 +224,13 @@ impl MSPHasher<&[u8]> {
     pub const fn hash_const(&self, value: &&[u8]) -> u32 {
         hash_const(&self.state, value)
     }
+    /// Equivalent to [`hash()`](Hasher::hash) but reuses the `scratch` buffer instead of
+    /// allocating one for every input of medium length.
+    ///
+    /// Useful for hashing large numbers of keys with a single buffer.
+    pub fn hash_with_scratch(&self, value: &[u8], scratch: &mut Vec<u64>) -> u32 {
+        hash_with_scratch(&self.state, value, scratch)
+    }
 }
 
 impl Hasher<String> for MSPHasher<String> {
@@ -208,6 +257,16 @@ impl Hasher<String> for MSPHasher<String> {
     }
 }
 
+impl MSPHasher<String> {
+    /// Equivalent to [`hash()`](Hasher::hash) but reuses the `scratch` buffer instead of
+    /// allocating one for every input of medium length.
+    ///
+    /// Useful for hashing large numbers of keys with a single buffer.
+    pub fn hash_with_scratch(&self, value: &str, scratch: &mut Vec<u64>) -> u32 {
+        hash_with_scratch(&self.state, value.as_bytes(), scratch)
+    }
+}
+
 impl<'a> Hasher<&'a str> for MSPHasher<&'a str> {
     type State = StringState;
 
@@ -251,11 +310,249 @@ impl<'a> MSPHasher<&'a str> {
     }
 }
 
+impl Hasher<Vec<u8>> for MSPHasher<Vec<u8>> {
+    type State = StringState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        StringState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: StringState) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Vec<u8>) -> u32 {
+        hash(&self.state, value.as_slice())
+    }
+}
+
+impl MSPHasher<Vec<u8>> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+        StringState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Vec<u8>>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &Vec<u8>) -> u32 {
+        hash_const(&self.state, value.as_slice())
+    }
+}
+
+impl Hasher<Box<[u8]>> for MSPHasher<Box<[u8]>> {
+    type State = StringState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        StringState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: StringState) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Box<[u8]>) -> u32 {
+        hash(&self.state, value)
+    }
+}
+
+impl MSPHasher<Box<[u8]>> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+        StringState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Box<[u8]>>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    #[allow(clippy::borrowed_box)]
+    pub const fn hash_const(&self, value: &Box<[u8]>) -> u32 {
+        hash_const(&self.state, value)
+    }
+}
+
+impl<'a> Hasher<Cow<'a, str>> for MSPHasher<Cow<'a, str>> {
+    type State = StringState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        StringState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: StringState) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Cow<'a, str>) -> u32 {
+        hash(&self.state, cow_str_as_bytes(value))
+    }
+}
+
+impl<'a> MSPHasher<Cow<'a, str>> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+        StringState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Cow<'a, str>>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &CowStr<'a>) -> u32 {
+        hash_const(&self.state, cow_str_as_bytes(value))
+    }
+}
+
+impl<'a> Hasher<Cow<'a, [u8]>> for MSPHasher<Cow<'a, [u8]>> {
+    type State = StringState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        StringState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: StringState) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Cow<'a, [u8]>) -> u32 {
+        hash(&self.state, cow_bytes_as_slice(value))
+    }
+}
+
+impl<'a> MSPHasher<Cow<'a, [u8]>> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+        StringState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Cow<'a, [u8]>>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &CowBytes<'a>) -> u32 {
+        hash_const(&self.state, cow_bytes_as_slice(value))
+    }
+}
+
+/// Reinterprets a slice of integers as a slice of bytes in the native byte order.
+#[inline]
+const fn int_slice_as_bytes<T>(value: &[T]) -> &[u8] {
+    // SAFETY: only called for primitive integer types that have no padding and for which
+    //         any byte is properly aligned.
+    unsafe { core::slice::from_raw_parts(value.as_ptr() as *const u8, size_of_val(value)) }
+}
+
+/// Generates [`Hasher`] implementations for slices of integers.
+///
+/// The generated impls reinterpret the slice as bytes and hash it the same way as `&[u8]`.
+macro_rules! impl_int_slice {
+    ($($t:ty),*) => {
+        $(
+            impl Hasher<&[$t]> for MSPHasher<&[$t]> {
+                type State = StringState;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    StringState::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = StringState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: StringState) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, value: &&[$t]) -> u32 {
+                    hash(&self.state, int_slice_as_bytes(value))
+                }
+            }
+
+            impl MSPHasher<&[$t]> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+                    StringState::from_seed_const(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = StringState::from_seed_const(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<&[$t]>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                pub const fn hash_const(&self, value: &&[$t]) -> u32 {
+                    hash_const(&self.state, int_slice_as_bytes(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_int_slice!(u32, u64, u128);
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use o1_test::generate::Generate;
     use o1_test::generate_hasher_tests;
+    use rand::distr::{Distribution, StandardUniform};
+    use rand_chacha::ChaCha20Rng;
+    use std::collections::HashSet;
 
     generate_hasher_tests!(MSPHasher<&str>, &'static str, |rng| {
         String::generate(
@@ -273,4 +570,159 @@ mod tests {
         .into_bytes()
         .leak()
     });
+
+    generate_hasher_tests!(MSPHasher<Vec<u8>>, Vec<u8>, |rng| {
+        Vec::<u8>::generate(
+            rng,
+            &<Vec<u8> as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+    });
+
+    generate_hasher_tests!(MSPHasher<Box<[u8]>>, Box<[u8]>, |rng| {
+        Vec::<u8>::generate(
+            rng,
+            &<Vec<u8> as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+        .into_boxed_slice()
+    });
+
+    // The borrowed values hash like the owned ones - see
+    // `test_cow_borrowed_and_owned_hash_equally`.
+    generate_hasher_tests!(MSPHasher<Cow<str>>, Cow<'static, str>, |rng| {
+        Cow::Owned(String::generate(
+            rng,
+            &<String as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        ))
+    });
+
+    // The borrowed values hash like the owned ones - see
+    // `test_cow_borrowed_and_owned_hash_equally`.
+    generate_hasher_tests!(MSPHasher<Cow<[u8]>>, Cow<'static, [u8]>, |rng| {
+        Cow::Owned(Vec::<u8>::generate(
+            rng,
+            &<Vec<u8> as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        ))
+    });
+
+    /// Pins the hash values of the runtime hasher, so that changing the order in which
+    /// `from_seed()` draws the seed values from the RNG doesn't go unnoticed.
+    #[test]
+    fn test_from_seed_hash_values_pinned() {
+        let hasher = MSPHasher::<&str>::from_seed(42, 1 << 16);
+        let long = "x".repeat(300);
+        assert_eq!(hasher.hash(&""), 37999);
+        assert_eq!(hasher.hash(&"short"), 62492);
+        assert_eq!(hasher.hash(&long.as_str()), 14979);
+    }
+
+    #[test]
+    fn test_cow_borrowed_and_owned_hash_equally() {
+        let hasher = MSPHasher::<Cow<str>>::from_seed(42, 1 << 16);
+        let str_hasher = MSPHasher::<&str>::from_state(*hasher.state());
+        for value in [
+            "",
+            "x",
+            "short",
+            "a string longer than the vector hashing threshold",
+        ] {
+            let borrowed = Cow::Borrowed(value);
+            let owned: Cow<str> = Cow::Owned(String::from(value));
+            assert_eq!(
+                hasher.hash(&borrowed),
+                hasher.hash(&owned),
+                "Value: {value}"
+            );
+            assert_eq!(
+                hasher.hash(&borrowed),
+                str_hasher.hash(&value),
+                "Value: {value}"
+            );
+            assert_eq!(
+                hasher.hash_const(&owned),
+                hasher.hash(&owned),
+                "Value: {value}"
+            );
+        }
+
+        let hasher = MSPHasher::<Cow<[u8]>>::from_seed(42, 1 << 16);
+        let borrowed: Cow<[u8]> = Cow::Borrowed(b"x");
+        let owned: Cow<[u8]> = Cow::Owned(b"x".to_vec());
+        assert_eq!(hasher.hash(&borrowed), hasher.hash(&owned));
+        assert_eq!(hasher.hash_const(&borrowed), hasher.hash_const(&owned));
+    }
+
+    /// Generates a randomly-sized slice that is long enough to cover all the hashing strategies.
+    fn random_int_slice<T>(rng: &mut ChaCha20Rng) -> &'static [T]
+    where
+        StandardUniform: Distribution<T>,
+    {
+        let max_len = 2 * MAX_STR_VECTOR_LEN / size_of::<T>();
+        let len = rng.random_range(0..=max_len);
+        (0..len).map(|_| rng.random()).collect::<Vec<T>>().leak()
+    }
+
+    generate_hasher_tests!(MSPHasher<&[u32]>, &'static [u32], random_int_slice::<u32>);
+    generate_hasher_tests!(MSPHasher<&[u64]>, &'static [u64], random_int_slice::<u64>);
+    generate_hasher_tests!(
+        MSPHasher<&[u128]>,
+        &'static [u128],
+        random_int_slice::<u128>
+    );
+
+    #[test]
+    fn test_int_slice_collisions() {
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let slices: HashSet<&[u64]> = (0..10_000)
+            .map(|_| random_int_slice::<u64>(&mut rng))
+            .collect();
+        let hasher = MSPHasher::<&[u64]>::from_seed(42, 1 << 31);
+        let hashes: HashSet<u32> = slices.iter().map(|slice| hasher.hash(slice)).collect();
+        assert!(
+            slices.len() - hashes.len() <= 2,
+            "Too many collisions: {}",
+            slices.len() - hashes.len()
+        );
+    }
+
+    #[test]
+    fn test_hash_with_scratch_equivalent_to_hash() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+        let bytes_hasher = MSPHasher::<&[u8]>::from_seed(rng.random(), 1 << 20);
+        let string_hasher = MSPHasher::<String>::from_state(*bytes_hasher.state());
+
+        let mut scratch = Vec::new();
+        let mut num_grows = 0;
+        let mut capacity = scratch.capacity();
+
+        let longest: String = "x".repeat(MAX_STR_VECTOR_LEN);
+        let strings = core::iter::once(longest).chain((0..10_000).map(|_| {
+            let len = rng.random_range(0..=MAX_STR_VECTOR_LEN * 2);
+            (0..len)
+                .map(|_| rng.random::<char>())
+                .take(len)
+                .collect::<String>()
+        }));
+        for string in strings {
+            let expected = bytes_hasher.hash(&string.as_bytes());
+            assert_eq!(
+                bytes_hasher.hash_with_scratch(string.as_bytes(), &mut scratch),
+                expected
+            );
+            assert_eq!(
+                string_hasher.hash_with_scratch(&string, &mut scratch),
+                expected
+            );
+            assert_eq!(string_hasher.hash(&string), expected);
+
+            if scratch.capacity() != capacity {
+                num_grows += 1;
+                capacity = scratch.capacity();
+            }
+        }
+
+        assert!(
+            num_grows <= 1,
+            "The scratch buffer has been grown {num_grows} times"
+        );
+    }
 }
@@ -0,0 +1,74 @@
+//! Implements [`Hasher`] for [`Duration`].
+//!
+//! The `(as_secs(), subsec_nanos())` pair is packed into a single `u128` - seconds in the higher
+//! bits and nanoseconds in the lowest 32 bits - and hashed by delegating to
+//! [`MSPHasher<u128>`]. The packing is injective, so distinct durations never share an input.
+//!
+//! # Notes
+//!
+//! `SystemTime` is not hashable portably since its representation is platform-specific - convert
+//! it to a [`Duration`] since `UNIX_EPOCH` first with `SystemTime::duration_since()`.
+
+use super::bigint::BigIntState;
+use super::core::MSPHasher;
+use core::time::Duration;
+use o1_core::Hasher;
+
+/// Packs the seconds and the nanoseconds of the duration into a single `u128`.
+#[inline]
+const fn to_bits(value: &Duration) -> u128 {
+    ((value.as_secs() as u128) << u32::BITS) | value.subsec_nanos() as u128
+}
+
+impl Hasher<Duration> for MSPHasher<Duration> {
+    type State = BigIntState<u128>;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        <MSPHasher<u128> as Hasher<u128>>::make_state(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        MSPHasher::<u128>::from_state(self.state).num_buckets()
+    }
+    fn hash(&self, value: &Duration) -> u32 {
+        MSPHasher::<u128>::from_state(self.state).hash(&to_bits(value))
+    }
+}
+
+impl MSPHasher<Duration> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> BigIntState<u128> {
+        MSPHasher::<u128>::make_state_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Duration>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        MSPHasher::<u128>::from_state_const(self.state).num_buckets_const()
+    }
+    pub const fn hash_const(&self, value: &Duration) -> u32 {
+        MSPHasher::<u128>::from_state_const(self.state).hash_const(&to_bits(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(MSPHasher<Duration>, Duration, |rng: &mut ChaCha20Rng| {
+        Duration::new(rng.random::<u64>(), rng.random_range(0..1_000_000_000))
+    });
+}
@@ -0,0 +1,110 @@
+//! Implements [`Hasher`] for the unit type `()`.
+//!
+//! `()` has a single value, so it's hashed by delegating to the hasher of the empty array
+//! `[u8; 0]` - both always hash to the same constant derived from the seed.
+
+use super::core::MSPHasher;
+use super::smallint::SmallArrayState;
+use o1_core::Hasher;
+
+impl Hasher<()> for MSPHasher<()> {
+    type State = SmallArrayState<0>;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        <MSPHasher<[u8; 0]> as Hasher<[u8; 0]>>::make_state(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        MSPHasher::<[u8; 0]>::from_state(self.state).num_buckets()
+    }
+    fn hash(&self, _value: &()) -> u32 {
+        MSPHasher::<[u8; 0]>::from_state(self.state).hash(&[])
+    }
+}
+
+impl MSPHasher<()> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> <Self as Hasher<()>>::State {
+        MSPHasher::<[u8; 0]>::make_state_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<()>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        MSPHasher::<[u8; 0]>::from_state_const(self.state).num_buckets_const()
+    }
+    pub const fn hash_const(&self, _value: &()) -> u32 {
+        MSPHasher::<[u8; 0]>::from_state_const(self.state).hash_const(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NUM_BUCKETS: [u32; 5] = [0, 1, 2, 1000, u32::MAX];
+
+    #[test]
+    fn test_unit_hashes_to_constant() {
+        for seed in 0..100 {
+            for num_buckets in NUM_BUCKETS {
+                let hasher = MSPHasher::<()>::from_seed(seed, num_buckets);
+                let const_hasher = MSPHasher::<()>::from_seed_const(seed, num_buckets);
+                let array_hasher = MSPHasher::<[u8; 0]>::from_state(*hasher.state());
+
+                let hash = hasher.hash(&());
+                assert!(hash < hasher.num_buckets(), "Seed: {seed}");
+                assert_eq!(hasher.hash(&()), hash, "Seed: {seed}");
+                assert_eq!(array_hasher.hash(&[]), hash, "Seed: {seed}");
+
+                let const_hash = const_hasher.hash_const(&());
+                assert!(
+                    const_hash < const_hasher.num_buckets_const(),
+                    "Seed: {seed}"
+                );
+                assert_eq!(const_hasher.hash_const(&()), const_hash, "Seed: {seed}");
+            }
+        }
+    }
+
+    /// Empty arrays of every element type hash to a constant without tripping the debug
+    /// assertions.
+    #[test]
+    fn test_empty_arrays_hash_to_constant() {
+        macro_rules! check {
+            ($($t:ty),*) => {
+                $(
+                    for num_buckets in NUM_BUCKETS {
+                        let hasher = MSPHasher::<[$t; 0]>::from_seed(42, num_buckets);
+                        let const_hasher = MSPHasher::<[$t; 0]>::from_seed_const(42, num_buckets);
+                        let hash = hasher.hash(&[]);
+                        let const_hash = const_hasher.hash_const(&[]);
+
+                        assert!(hash < hasher.num_buckets(), "Type: {}", stringify!($t));
+                        assert_eq!(hasher.hash(&[]), hash, "Type: {}", stringify!($t));
+                        assert_eq!(
+                            const_hasher.hash_const(&[]),
+                            const_hash,
+                            "Type: {}",
+                            stringify!($t)
+                        );
+                    }
+                )*
+            };
+        }
+
+        check!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, char);
+    }
+}
@@ -0,0 +1,109 @@
+//! Implements [`Hasher`] for arrays of integers.
+//!
+//! The arrays are hashed as the concatenation of the little-endian bytes of their elements.
+
+use super::core::{truncate, Murmur3Hasher, Murmur3State};
+use crate::hashing::common::num_buckets_for_bits;
+use crate::hashing::murmur3::Murmur3x86_32;
+use o1_core::Hasher;
+
+/// Generates [`Hasher`] implementations for arrays of integer types.
+///
+/// The elements are cast to the type after `=>` before taking their bytes.
+macro_rules! impl_murmur3_array {
+    ($($k:ty => $as:ty),*) => {
+        $(
+            impl<const N: usize> Hasher<[$k; N]> for Murmur3Hasher<[$k; N]> {
+                type State = Murmur3State;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    Murmur3State::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = Murmur3State::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, value: &[$k; N]) -> u32 {
+                    let hash_value = value
+                        .iter()
+                        .fold(Murmur3x86_32::new(self.state.seed), |murmur, item| {
+                            murmur.write(&(*item as $as).to_le_bytes())
+                        })
+                        .finish();
+                    truncate(&self.state, hash_value)
+                }
+            }
+
+            impl<const N: usize> Murmur3Hasher<[$k; N]> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> Murmur3State {
+                    Murmur3State::from_seed(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = Murmur3State::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<[$k; N]>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                pub const fn hash_const(&self, value: &[$k; N]) -> u32 {
+                    let mut murmur = Murmur3x86_32::new(self.state.seed);
+                    let mut i = 0;
+                    while i < N {
+                        murmur = murmur.write(&(value[i] as $as).to_le_bytes());
+                        i += 1;
+                    }
+                    truncate(&self.state, murmur.finish())
+                }
+            }
+        )*
+    };
+}
+
+impl_murmur3_array!(
+    u8 => u8, i8 => i8, u16 => u16, i16 => i16, u32 => u32, i32 => i32, u64 => u64, i64 => i64,
+    u128 => u128, i128 => i128, usize => u64, isize => i64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::murmur3::murmur3_32;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(
+        Murmur3Hasher<[u8; 16]>,
+        [u8; 16],
+        |rng: &mut ChaCha20Rng| rng.random::<[u8; 16]>()
+    );
+    generate_hasher_tests!(
+        Murmur3Hasher<[u32; 3]>,
+        [u32; 3],
+        |rng: &mut ChaCha20Rng| rng.random::<[u32; 3]>()
+    );
+    generate_hasher_tests!(
+        Murmur3Hasher<[u64; 8]>,
+        [u64; 8],
+        |rng: &mut ChaCha20Rng| rng.random::<[u64; 8]>()
+    );
+
+    #[test]
+    fn test_matches_hashing_bytes() {
+        let hasher = Murmur3Hasher::<[u16; 3]>::from_seed(7, u32::MAX);
+        let value = [0x2143_u16, 0x6587, 0xa9cb];
+        let expected = murmur3_32(&[0x43, 0x21, 0x87, 0x65, 0xcb, 0xa9], 7);
+        assert_eq!(hasher.hash(&value), expected);
+        assert_eq!(hasher.hash_const(&value), expected);
+    }
+}
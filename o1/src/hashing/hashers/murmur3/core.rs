@@ -0,0 +1,88 @@
+use crate::hashing::common::{clamp_num_bits, extract_bits_64, num_bits_for_buckets};
+use core::fmt::{Debug, Formatter};
+use o1_core::Hasher;
+
+/// Hasher based on MurmurHash3 x86_32.
+///
+/// Integers are hashed as their little-endian bytes, `usize` and `isize` are widened to 64 bits
+/// first so that the hash values don't depend on the platform. Arrays are hashed as the
+/// concatenation of the little-endian bytes of their elements.
+///
+/// Contains both runtime and compile-time (const) implementations.
+#[derive(Clone)]
+pub struct Murmur3Hasher<T: Eq>
+where
+    Murmur3Hasher<T>: Hasher<T>,
+{
+    pub(super) state: <Murmur3Hasher<T> as Hasher<T>>::State,
+}
+
+// Implement Copy for Murmur3Hasher if its State is Copy
+impl<T: Eq + Clone> Copy for Murmur3Hasher<T>
+where
+    Murmur3Hasher<T>: Hasher<T>,
+    <Murmur3Hasher<T> as Hasher<T>>::State: Copy,
+{
+}
+
+impl<T: Eq> Default for Murmur3Hasher<T>
+where
+    Murmur3Hasher<T>: Hasher<T>,
+{
+    fn default() -> Self {
+        <Self as Hasher<T>>::from_state(<Self as Hasher<T>>::State::default())
+    }
+}
+
+impl<T> Debug for Murmur3Hasher<T>
+where
+    T: Eq,
+    Murmur3Hasher<T>: Hasher<T>,
+    <Murmur3Hasher<T> as Hasher<T>>::State: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Murmur3Hasher")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<T: Eq> Murmur3Hasher<T>
+where
+    Murmur3Hasher<T>: Hasher<T>,
+    <Murmur3Hasher<T> as Hasher<T>>::State: Copy,
+{
+    /// Clone the hasher in a const context.
+    pub const fn clone_const(&self) -> Self {
+        Self { state: self.state }
+    }
+}
+
+/// State shared by all the [`Murmur3Hasher`] implementations.
+///
+/// The 64-bit seed is folded into the 32-bit seed of MurmurHash3 by XOR-ing its halves, so seeds
+/// below `2^32` are passed to MurmurHash3 unchanged.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Murmur3State {
+    pub(super) num_bits: u32,
+    pub(super) seed: u32,
+}
+
+impl Murmur3State {
+    pub const fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+        let seed = (seed ^ (seed >> 32)) as u32;
+
+        Self { num_bits, seed }
+    }
+}
+
+/// Truncates a MurmurHash3 hash value to the number of bits determined by the state.
+#[inline]
+pub(super) const fn truncate(state: &Murmur3State, hash_value: u32) -> u32 {
+    debug_assert!(
+        state.num_bits >= 1 && state.num_bits <= 32,
+        r#""num_bits" must be [1, 32]"#
+    );
+    extract_bits_64::<{ u32::BITS }>(hash_value as u64, state.num_bits)
+}
@@ -0,0 +1,104 @@
+//! Implements [`Hasher`] for integer types.
+//!
+//! The values are hashed as their little-endian bytes.
+
+use super::core::{truncate, Murmur3Hasher, Murmur3State};
+use crate::hashing::common::num_buckets_for_bits;
+use crate::hashing::murmur3::murmur3_32;
+use o1_core::Hasher;
+
+/// Generates [`Hasher`] implementations for integer types.
+///
+/// The input is cast to the type after `=>` before taking its bytes.
+macro_rules! impl_murmur3_int {
+    ($($k:ty => $as:ty),*) => {
+        $(
+            impl Hasher<$k> for Murmur3Hasher<$k> {
+                type State = Murmur3State;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    Murmur3State::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = Murmur3State::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, value: &$k) -> u32 {
+                    self.hash_const(value)
+                }
+            }
+
+            impl Murmur3Hasher<$k> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> Murmur3State {
+                    Murmur3State::from_seed(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = Murmur3State::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<$k>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                pub const fn hash_const(&self, value: &$k) -> u32 {
+                    let bytes = (*value as $as).to_le_bytes();
+                    truncate(&self.state, murmur3_32(&bytes, self.state.seed))
+                }
+            }
+        )*
+    };
+}
+
+impl_murmur3_int!(
+    u8 => u8, i8 => i8, u16 => u16, i16 => i16, u32 => u32, i32 => i32, u64 => u64, i64 => i64,
+    u128 => u128, i128 => i128, usize => u64, isize => i64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(Murmur3Hasher<u64>, u64, |rng: &mut ChaCha20Rng| rng
+        .random::<u64>());
+    generate_hasher_tests!(Murmur3Hasher<i64>, i64, |rng: &mut ChaCha20Rng| rng
+        .random::<i64>());
+    generate_hasher_tests!(Murmur3Hasher<u32>, u32, |rng: &mut ChaCha20Rng| rng
+        .random::<u32>());
+    generate_hasher_tests!(Murmur3Hasher<i32>, i32, |rng: &mut ChaCha20Rng| rng
+        .random::<i32>());
+    generate_hasher_tests!(Murmur3Hasher<u16>, u16, |rng: &mut ChaCha20Rng| rng
+        .random::<u16>());
+    generate_hasher_tests!(Murmur3Hasher<u8>, u8, |rng: &mut ChaCha20Rng| rng
+        .random::<u8>());
+    generate_hasher_tests!(Murmur3Hasher<u128>, u128, |rng: &mut ChaCha20Rng| rng
+        .random::<u128>());
+
+    #[test]
+    fn test_matches_reference_murmur3() {
+        // MurmurHash3_x86_32() of 0x87654321 as little-endian bytes with seed 0.
+        let hasher = Murmur3Hasher::<u32>::from_seed(0, u32::MAX);
+        assert_eq!(hasher.hash(&0x8765_4321), 0xf55b_516b);
+        assert_eq!(hasher.hash_const(&0x8765_4321), 0xf55b_516b);
+    }
+
+    #[test]
+    fn test_usize_matches_u64() {
+        let usize_hasher = Murmur3Hasher::<usize>::from_seed(42, 1 << 16);
+        let u64_hasher = Murmur3Hasher::<u64>::from_seed(42, 1 << 16);
+        for value in 0..1000_usize {
+            assert_eq!(usize_hasher.hash(&value), u64_hasher.hash(&(value as u64)));
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Implements a hasher based on MurmurHash3 x86_32.
+//!
+//! The hash values are compatible with the reference `MurmurHash3_x86_32()` - before the
+//! truncation to the number of bits required by the number of buckets the hasher produces exactly
+//! the same value as other tools hashing the same bytes with the same seed.
+mod core;
+pub use core::*;
+mod array;
+mod int;
+mod string;
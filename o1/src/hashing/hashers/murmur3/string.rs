@@ -0,0 +1,136 @@
+//! Implements [`Hasher`] for unbounded strings and byte slices.
+
+use super::core::{truncate, Murmur3Hasher, Murmur3State};
+use crate::hashing::common::num_buckets_for_bits;
+use crate::hashing::murmur3::murmur3_32;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use o1_core::Hasher;
+
+#[inline]
+const fn hash(state: &Murmur3State, value: &[u8]) -> u32 {
+    truncate(state, murmur3_32(value, state.seed))
+}
+
+/// Generates [`Hasher`] implementations and their const counterparts for string-like types.
+///
+/// `$as_bytes` converts the value into a byte slice.
+macro_rules! impl_murmur3_string {
+    ($([$($lt:lifetime)?] $k:ty => |$value:ident| $as_bytes:expr),*) => {
+        $(
+            impl<$($lt)?> Hasher<$k> for Murmur3Hasher<$k> {
+                type State = Murmur3State;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    Murmur3State::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = Murmur3State::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, $value: &$k) -> u32 {
+                    hash(&self.state, $as_bytes)
+                }
+            }
+
+            impl<$($lt)?> Murmur3Hasher<$k> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> Murmur3State {
+                    Murmur3State::from_seed(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = Murmur3State::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<$k>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                #[allow(clippy::borrowed_box)]
+                pub const fn hash_const(&self, $value: &$k) -> u32 {
+                    hash(&self.state, $as_bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_murmur3_string!(
+    ['a] &'a [u8] => |value| value,
+    ['a] &'a str => |value| value.as_bytes(),
+    [] String => |value| value.as_bytes(),
+    [] Vec<u8> => |value| value.as_slice(),
+    [] Box<[u8]> => |value| value
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate::Generate;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(Murmur3Hasher<&str>, &'static str, |rng| {
+        String::generate(
+            rng,
+            &<String as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+        .leak()
+    });
+
+    generate_hasher_tests!(Murmur3Hasher<&[u8]>, &'static [u8], |rng| {
+        String::generate(
+            rng,
+            &<String as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+        .into_bytes()
+        .leak()
+    });
+
+    generate_hasher_tests!(Murmur3Hasher<String>, String, |rng| {
+        String::generate(
+            rng,
+            &<String as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+    });
+
+    generate_hasher_tests!(Murmur3Hasher<Vec<u8>>, Vec<u8>, |rng| {
+        Vec::<u8>::generate(
+            rng,
+            &<Vec<u8> as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+    });
+
+    #[test]
+    fn test_matches_reference_murmur3() {
+        // MurmurHash3_x86_32() reference values.
+        let hasher = Murmur3Hasher::<&str>::from_seed(0x9747_b28c, u32::MAX);
+        assert_eq!(hasher.hash(&"Hello, world!"), 0x2488_4cba);
+        assert_eq!(hasher.hash_const(&"aaaa"), 0x5a97_808a);
+
+        let hasher = Murmur3Hasher::<String>::from_seed(0x9747_b28c, u32::MAX);
+        assert_eq!(
+            hasher.hash(&String::from("The quick brown fox jumps over the lazy dog")),
+            0x2fa8_26cd
+        );
+    }
+
+    #[test]
+    fn test_truncates_to_num_bits() {
+        let full = Murmur3Hasher::<&[u8]>::from_seed(0x9747_b28c, u32::MAX);
+        let hasher = Murmur3Hasher::<&[u8]>::from_seed(0x9747_b28c, 1 << 10);
+        let value: &[u8] = b"Hello, world!";
+        assert_eq!(hasher.num_buckets(), 1 << 10);
+        assert_eq!(hasher.hash(&value), full.hash(&value) >> 22);
+    }
+}
@@ -0,0 +1,54 @@
+use core::fmt::{Debug, Formatter};
+use o1_core::Hasher;
+
+/// Hasher based on simple tabulation hashing.
+///
+/// Contains both runtime and compile-time (const) implementations.
+#[derive(Clone)]
+pub struct TabulationHasher<T: Eq>
+where
+    TabulationHasher<T>: Hasher<T>,
+{
+    pub(super) state: <TabulationHasher<T> as Hasher<T>>::State,
+}
+
+// Implement Copy for TabulationHasher if its State is Copy
+impl<T: Eq + Clone> Copy for TabulationHasher<T>
+where
+    TabulationHasher<T>: Hasher<T>,
+    <TabulationHasher<T> as Hasher<T>>::State: Copy,
+{
+}
+
+impl<T: Eq> Default for TabulationHasher<T>
+where
+    TabulationHasher<T>: Hasher<T>,
+{
+    fn default() -> Self {
+        <Self as Hasher<T>>::from_state(<Self as Hasher<T>>::State::default())
+    }
+}
+
+impl<T> Debug for TabulationHasher<T>
+where
+    T: Eq,
+    TabulationHasher<T>: Hasher<T>,
+    <TabulationHasher<T> as Hasher<T>>::State: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TabulationHasher")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<T: Eq> TabulationHasher<T>
+where
+    TabulationHasher<T>: Hasher<T>,
+    <TabulationHasher<T> as Hasher<T>>::State: Copy,
+{
+    /// Clone the hasher in a const context.
+    pub const fn clone_const(&self) -> Self {
+        Self { state: self.state }
+    }
+}
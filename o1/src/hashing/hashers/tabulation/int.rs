@@ -0,0 +1,150 @@
+//! Implements [`Hasher`] for integer types of up to 64 bits.
+//!
+//! The values are widened to `u64` and hashed with [`tabulation_u64`].
+
+use super::core::TabulationHasher;
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, num_bits_for_buckets, num_buckets_for_bits,
+};
+use crate::hashing::tabulation::{tabulation_u64, tabulation_u64_const, TablesU64, NUM_TABLES_U64};
+use crate::utils::xorshift::XorShift;
+use o1_core::Hasher;
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[derive(Debug, Clone, Copy)]
+pub struct TabulationState {
+    num_bits: u32,
+    tables: TablesU64,
+}
+
+impl Default for TabulationState {
+    fn default() -> Self {
+        Self {
+            num_bits: 0,
+            tables: [[0; 256]; NUM_TABLES_U64],
+        }
+    }
+}
+
+impl TabulationState {
+    pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        let mut tables = [[0_u32; 256]; NUM_TABLES_U64];
+        tables
+            .iter_mut()
+            .for_each(|table| table.fill_with(|| rng.next_u32()));
+
+        Self { num_bits, tables }
+    }
+
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        let mut rng = XorShift::<u64>::new(seed);
+        let mut tables = [[0_u32; 256]; NUM_TABLES_U64];
+        let mut i = 0;
+        while i < NUM_TABLES_U64 {
+            let mut j = 0;
+            while j < 256 {
+                tables[i][j] = (rng.next() >> 32) as u32;
+                j += 1;
+            }
+            i += 1;
+        }
+
+        Self { num_bits, tables }
+    }
+}
+
+#[inline]
+const fn hash(state: &TabulationState, value: u64) -> u32 {
+    debug_assert!(
+        state.num_bits >= 1 && state.num_bits <= 32,
+        r#""num_bits" must be [1, 32]"#
+    );
+    extract_bits_64::<{ u32::BITS }>(
+        tabulation_u64_const(value, &state.tables) as u64,
+        state.num_bits,
+    )
+}
+
+/// Generates [`Hasher`] implementations for integer types of up to 64 bits.
+///
+/// The generated impls cast the input to `u64`.
+macro_rules! impl_tabulation_int {
+    ($($k:ty),*) => {
+        $(
+            impl Hasher<$k> for TabulationHasher<$k> {
+                type State = TabulationState;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    TabulationState::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = TabulationState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, value: &$k) -> u32 {
+                    let hash_value = tabulation_u64(*value as u64, &self.state.tables);
+                    extract_bits_64::<{ u32::BITS }>(hash_value as u64, self.state.num_bits)
+                }
+            }
+
+            impl TabulationHasher<$k> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> TabulationState {
+                    TabulationState::from_seed_const(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = TabulationState::from_seed_const(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<$k>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                pub const fn hash_const(&self, value: &$k) -> u32 {
+                    hash(&self.state, *value as u64)
+                }
+            }
+        )*
+    };
+}
+
+impl_tabulation_int!(u64, i64, u32, i32, u16, i16, u8, i8, usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(TabulationHasher<u64>, u64, |rng: &mut ChaCha20Rng| rng
+        .random::<u64>());
+    generate_hasher_tests!(TabulationHasher<i64>, i64, |rng: &mut ChaCha20Rng| rng
+        .random::<i64>());
+    generate_hasher_tests!(TabulationHasher<u32>, u32, |rng: &mut ChaCha20Rng| rng
+        .random::<u32>());
+    generate_hasher_tests!(TabulationHasher<i32>, i32, |rng: &mut ChaCha20Rng| rng
+        .random::<i32>());
+    generate_hasher_tests!(TabulationHasher<u16>, u16, |rng: &mut ChaCha20Rng| rng
+        .random::<u16>());
+    generate_hasher_tests!(TabulationHasher<i16>, i16, |rng: &mut ChaCha20Rng| rng
+        .random::<i16>());
+    generate_hasher_tests!(TabulationHasher<u8>, u8, |rng: &mut ChaCha20Rng| rng
+        .random::<u8>());
+    generate_hasher_tests!(TabulationHasher<i8>, i8, |rng: &mut ChaCha20Rng| rng
+        .random::<i8>());
+}
@@ -0,0 +1,5 @@
+//! Implements a hasher based on the simple tabulation hashing scheme.
+mod core;
+pub use core::*;
+mod int;
+pub use int::*;
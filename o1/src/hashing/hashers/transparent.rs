@@ -0,0 +1,122 @@
+//! Implements forwarding of [`Hasher`](o1_core::Hasher) to the inner type of newtype keys.
+
+/// Implements [`Hasher`](o1_core::Hasher) of [`MSPHasher`](crate::hashing::hashers::msp::MSPHasher)
+/// and, with the `xxh3` feature, of [`XXH3Hasher`](crate::hashing::hashers::xxh3::XXH3Hasher) for a
+/// newtype by forwarding to the hasher of its inner type.
+///
+/// The hash value of a newtype is the hash value of its inner value, and the state of the hasher is
+/// the state of the hasher of the inner type.
+///
+/// # Parameters
+///
+/// - `newtype`: The newtype, e.g. `struct UserId(u64)`.
+/// - `inner_type`: The inner type, it must have its own implementation of the hasher.
+/// - `inner`: Optional, the name of the method returning the inner value, e.g. `inner` for
+///            `fn inner(&self) -> u64`. If omitted, the newtype must have an accessible tuple
+///            field `0`.
+///
+/// # Examples
+///
+/// ```rust
+/// use o1::fks::FKSMap;
+/// use o1::hashing::hashers::msp::MSPHasher;
+/// use o1::impl_transparent_hasher;
+/// use o1_core::HashMap;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// #[repr(transparent)]
+/// struct UserId(u64);
+///
+/// impl_transparent_hasher!(UserId, u64);
+///
+/// let data = [(UserId(1), "alice"), (UserId(2), "bob")];
+/// let map = FKSMap::<UserId, &str, MSPHasher<UserId>>::new(data.into(), 42, 0.75).unwrap();
+/// assert_eq!(map.get(&UserId(2)), Some(&"bob"));
+/// ```
+///
+/// # Notes
+///
+/// - Rust allows inherent methods only in the crate defining the type, so the const methods of the
+///   hashers can't be generated for newtypes of other crates and the compile-time constructors of
+///   the maps can't be used with them.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_transparent_hasher {
+    ($T:ty, $Inner:ty$(,)?) => {
+        $crate::impl_transparent_hasher!(@impl $T, $Inner, value => &value.0);
+    };
+    ($T:ty, $Inner:ty, $inner:ident$(,)?) => {
+        $crate::impl_transparent_hasher!(@impl $T, $Inner, value => &value.$inner());
+    };
+    (@impl $T:ty, $Inner:ty, $value:ident => $access:expr) => {
+        $crate::impl_transparent_hasher!(
+            @hasher $crate::hashing::hashers::msp::MSPHasher<$T>,
+            $crate::hashing::hashers::msp::MSPHasher<$Inner>,
+            $T,
+            $Inner,
+            $value => $access
+        );
+        $crate::__impl_transparent_hasher_xxh3!($T, $Inner, $value => $access);
+    };
+    (
+        @hasher $Hasher:ty,
+        $InnerHasher:ty,
+        $T:ty,
+        $Inner:ty,
+        $value:ident => $access:expr
+    ) => {
+        impl ::o1_core::Hasher<$T> for $Hasher {
+            type State = <$InnerHasher as ::o1_core::Hasher<$Inner>>::State;
+
+            fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                <$InnerHasher as ::o1_core::Hasher<$Inner>>::make_state(seed, num_buckets)
+            }
+            fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                Self::__from_state(Self::make_state(seed, num_buckets))
+            }
+            fn from_state(state: Self::State) -> Self {
+                Self::__from_state(state)
+            }
+            fn state(&self) -> &Self::State {
+                self.__state()
+            }
+            fn num_buckets(&self) -> u32 {
+                <$InnerHasher as ::o1_core::Hasher<$Inner>>::num_buckets(
+                    &<$InnerHasher>::__from_state(self.__state().clone()),
+                )
+            }
+            fn hash(&self, $value: &$T) -> u32 {
+                <$InnerHasher as ::o1_core::Hasher<$Inner>>::hash(
+                    &<$InnerHasher>::__from_state(self.__state().clone()),
+                    $access,
+                )
+            }
+        }
+    };
+}
+
+/// Implements the forwarding [`Hasher`](o1_core::Hasher) of `XXH3Hasher` for
+/// [`impl_transparent_hasher!`] - a no-op without the `xxh3` feature.
+#[cfg(feature = "xxh3")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_transparent_hasher_xxh3 {
+    ($T:ty, $Inner:ty, $value:ident => $access:expr) => {
+        $crate::impl_transparent_hasher!(
+            @hasher $crate::hashing::hashers::xxh3::XXH3Hasher<$T>,
+            $crate::hashing::hashers::xxh3::XXH3Hasher<$Inner>,
+            $T,
+            $Inner,
+            $value => $access
+        );
+    };
+}
+
+/// Implements the forwarding [`Hasher`](o1_core::Hasher) of `XXH3Hasher` for
+/// [`impl_transparent_hasher!`] - a no-op without the `xxh3` feature.
+#[cfg(not(feature = "xxh3"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_transparent_hasher_xxh3 {
+    ($T:ty, $Inner:ty, $value:ident => $access:expr) => {};
+}
@@ -0,0 +1,90 @@
+//! Implements [`Hasher`] for arrays of integers.
+//!
+//! The arrays are hashed as their in-memory representation, which doesn't require copying the
+//! elements into a byte buffer.
+
+use super::core::{hash_bytes, WyHasher, WyState};
+use crate::hashing::common::num_buckets_for_bits;
+use o1_core::Hasher;
+
+/// Generates [`Hasher`] implementations for arrays of integer types.
+macro_rules! impl_wyhash_array {
+    ($($k:ty),*) => {
+        $(
+            impl<const N: usize> Hasher<[$k; N]> for WyHasher<[$k; N]> {
+                type State = WyState;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    WyState::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = WyState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, value: &[$k; N]) -> u32 {
+                    self.hash_const(value)
+                }
+            }
+
+            impl<const N: usize> WyHasher<[$k; N]> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> WyState {
+                    WyState::from_seed(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = WyState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<[$k; N]>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                pub const fn hash_const(&self, value: &[$k; N]) -> u32 {
+                    // SAFETY: integers have no padding and any byte is a valid `u8`.
+                    let bytes = unsafe {
+                        core::slice::from_raw_parts(
+                            value.as_ptr().cast::<u8>(),
+                            N * core::mem::size_of::<$k>(),
+                        )
+                    };
+                    hash_bytes(&self.state, bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_wyhash_array!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(WyHasher<[u8; 16]>, [u8; 16], |rng: &mut ChaCha20Rng| rng
+        .random::<[u8; 16]>());
+    generate_hasher_tests!(WyHasher<[u32; 3]>, [u32; 3], |rng: &mut ChaCha20Rng| rng
+        .random::<[u32; 3]>());
+    generate_hasher_tests!(WyHasher<[u64; 8]>, [u64; 8], |rng: &mut ChaCha20Rng| rng
+        .random::<[u64; 8]>());
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_matches_reference_wyhash() {
+        // wyhash() of the little-endian bytes of the elements with seed 7.
+        let hasher = WyHasher::<[u16; 4]>::from_seed(7, u32::MAX);
+        let value = [1_u16, 2, 3, 0xffff];
+        assert_eq!(hasher.hash(&value), 0xdcaf_e8b2);
+        assert_eq!(hasher.hash_const(&value), 0xdcaf_e8b2);
+    }
+}
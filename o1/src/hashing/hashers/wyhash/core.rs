@@ -0,0 +1,90 @@
+use crate::hashing::common::{clamp_num_bits, extract_bits_64, num_bits_for_buckets};
+use crate::hashing::wyhash::{wyhash, DEFAULT_SECRET};
+use core::fmt::{Debug, Formatter};
+use o1_core::Hasher;
+
+/// Hasher based on wyhash.
+///
+/// Integers are hashed as their little-endian bytes, `usize` and `isize` are widened to 64 bits
+/// first so that the hash values don't depend on the platform. Arrays are hashed as their
+/// in-memory representation - the concatenation of the little-endian bytes of their elements on
+/// little-endian platforms.
+///
+/// Contains both runtime and compile-time (const) implementations.
+#[derive(Clone)]
+pub struct WyHasher<T: Eq>
+where
+    WyHasher<T>: Hasher<T>,
+{
+    pub(super) state: <WyHasher<T> as Hasher<T>>::State,
+}
+
+// Implement Copy for WyHasher if its State is Copy
+impl<T: Eq + Clone> Copy for WyHasher<T>
+where
+    WyHasher<T>: Hasher<T>,
+    <WyHasher<T> as Hasher<T>>::State: Copy,
+{
+}
+
+impl<T: Eq> Default for WyHasher<T>
+where
+    WyHasher<T>: Hasher<T>,
+{
+    fn default() -> Self {
+        <Self as Hasher<T>>::from_state(<Self as Hasher<T>>::State::default())
+    }
+}
+
+impl<T> Debug for WyHasher<T>
+where
+    T: Eq,
+    WyHasher<T>: Hasher<T>,
+    <WyHasher<T> as Hasher<T>>::State: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WyHasher")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<T: Eq> WyHasher<T>
+where
+    WyHasher<T>: Hasher<T>,
+    <WyHasher<T> as Hasher<T>>::State: Copy,
+{
+    /// Clone the hasher in a const context.
+    pub const fn clone_const(&self) -> Self {
+        Self { state: self.state }
+    }
+}
+
+/// State shared by all the [`WyHasher`] implementations.
+///
+/// The seed is passed to wyhash unchanged, the default secret is used.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WyState {
+    pub(super) num_bits: u32,
+    pub(super) seed: u64,
+}
+
+impl WyState {
+    pub const fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+
+        Self { num_bits, seed }
+    }
+}
+
+/// Hashes the bytes with wyhash and truncates the hash value to the number of bits determined
+/// by the state.
+#[inline]
+pub(super) const fn hash_bytes(state: &WyState, bytes: &[u8]) -> u32 {
+    debug_assert!(
+        state.num_bits >= 1 && state.num_bits <= 32,
+        r#""num_bits" must be [1, 32]"#
+    );
+    let hash_value = wyhash(bytes, state.seed, &DEFAULT_SECRET);
+    extract_bits_64::<{ u64::BITS }>(hash_value, state.num_bits)
+}
@@ -0,0 +1,125 @@
+//! Implements [`Hasher`] for integer types.
+//!
+//! The values are hashed as their little-endian bytes.
+
+use super::core::{hash_bytes, WyHasher, WyState};
+use crate::hashing::common::num_buckets_for_bits;
+use o1_core::Hasher;
+
+/// Generates [`Hasher`] implementations for integer types.
+///
+/// The input is cast to the type after `=>` before taking its bytes.
+macro_rules! impl_wyhash_int {
+    ($($k:ty => $as:ty),*) => {
+        $(
+            impl Hasher<$k> for WyHasher<$k> {
+                type State = WyState;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    WyState::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = WyState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, value: &$k) -> u32 {
+                    self.hash_const(value)
+                }
+            }
+
+            impl WyHasher<$k> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> WyState {
+                    WyState::from_seed(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = WyState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<$k>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                pub const fn hash_const(&self, value: &$k) -> u32 {
+                    hash_bytes(&self.state, &(*value as $as).to_le_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_wyhash_int!(
+    u8 => u8, i8 => i8, u16 => u16, i16 => i16, u32 => u32, i32 => i32, u64 => u64, i64 => i64,
+    u128 => u128, i128 => i128, usize => u64, isize => i64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::new_fks_map;
+    use o1_core::HashMap;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(WyHasher<u64>, u64, |rng: &mut ChaCha20Rng| rng
+        .random::<u64>());
+    generate_hasher_tests!(WyHasher<i64>, i64, |rng: &mut ChaCha20Rng| rng
+        .random::<i64>());
+    generate_hasher_tests!(WyHasher<u32>, u32, |rng: &mut ChaCha20Rng| rng
+        .random::<u32>());
+    generate_hasher_tests!(WyHasher<i32>, i32, |rng: &mut ChaCha20Rng| rng
+        .random::<i32>());
+    generate_hasher_tests!(WyHasher<u16>, u16, |rng: &mut ChaCha20Rng| rng
+        .random::<u16>());
+    generate_hasher_tests!(WyHasher<u8>, u8, |rng: &mut ChaCha20Rng| rng.random::<u8>());
+    generate_hasher_tests!(WyHasher<u128>, u128, |rng: &mut ChaCha20Rng| rng
+        .random::<u128>());
+
+    #[test]
+    fn test_matches_reference_wyhash() {
+        // wyhash() of 0x87654321 as little-endian bytes with seed 0.
+        let hasher = WyHasher::<u32>::from_seed(0, u32::MAX);
+        assert_eq!(hasher.hash(&0x8765_4321), 0x1268_d6b6);
+        assert_eq!(hasher.hash_const(&0x8765_4321), 0x1268_d6b6);
+
+        // wyhash() of 0x0123456789abcdef as little-endian bytes with seed 7.
+        let hasher = WyHasher::<u64>::from_seed(7, 1 << 16);
+        assert_eq!(hasher.hash(&0x0123_4567_89ab_cdef), 0x6a37);
+    }
+
+    #[test]
+    fn test_usize_matches_u64() {
+        let usize_hasher = WyHasher::<usize>::from_seed(42, 1 << 16);
+        let u64_hasher = WyHasher::<u64>::from_seed(42, 1 << 16);
+        for value in 0..1000_usize {
+            assert_eq!(usize_hasher.hash(&value), u64_hasher.hash(&(value as u64)));
+        }
+    }
+
+    const DATA: [(u64, u32); 6] = [
+        (0, 0),
+        (1, 10),
+        (42, 420),
+        (1 << 32, 1),
+        (u64::MAX - 1, 2),
+        (u64::MAX, 3),
+    ];
+    new_fks_map!(MAP, u64, u32, DATA, WyHasher<u64>, 42, 0.75);
+
+    #[test]
+    fn test_static_map() {
+        for (key, value) in &DATA {
+            assert_eq!(MAP.get(key), Some(value));
+        }
+        assert_eq!(MAP.get(&2), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Implements [`Hasher`] for unbounded strings and byte slices.
+
+use super::core::{hash_bytes, WyHasher, WyState};
+use crate::hashing::common::num_buckets_for_bits;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use o1_core::Hasher;
+
+/// Generates [`Hasher`] implementations and their const counterparts for string-like types.
+///
+/// `$as_bytes` converts the value into a byte slice.
+macro_rules! impl_wyhash_string {
+    ($([$($lt:lifetime)?] $k:ty => |$value:ident| $as_bytes:expr),*) => {
+        $(
+            impl<$($lt)?> Hasher<$k> for WyHasher<$k> {
+                type State = WyState;
+
+                fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                    WyState::from_seed(seed, num_buckets)
+                }
+                fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                    let state = WyState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                fn from_state(state: Self::State) -> Self {
+                    Self { state }
+                }
+                fn state(&self) -> &Self::State {
+                    &self.state
+                }
+                fn num_buckets(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                fn hash(&self, $value: &$k) -> u32 {
+                    hash_bytes(&self.state, $as_bytes)
+                }
+            }
+
+            impl<$($lt)?> WyHasher<$k> {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> WyState {
+                    WyState::from_seed(seed, num_buckets)
+                }
+                pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+                    let state = WyState::from_seed(seed, num_buckets);
+                    Self { state }
+                }
+                pub const fn from_state_const(state: <Self as Hasher<$k>>::State) -> Self {
+                    Self { state }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    num_buckets_for_bits(self.state.num_bits)
+                }
+                #[allow(clippy::borrowed_box)]
+                pub const fn hash_const(&self, $value: &$k) -> u32 {
+                    hash_bytes(&self.state, $as_bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_wyhash_string!(
+    ['a] &'a [u8] => |value| value,
+    ['a] &'a str => |value| value.as_bytes(),
+    [] String => |value| value.as_bytes(),
+    [] Vec<u8> => |value| value.as_slice(),
+    [] Box<[u8]> => |value| value
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate::Generate;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(WyHasher<&str>, &'static str, |rng| {
+        String::generate(
+            rng,
+            &<String as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+        .leak()
+    });
+
+    generate_hasher_tests!(WyHasher<&[u8]>, &'static [u8], |rng| {
+        String::generate(
+            rng,
+            &<String as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+        .into_bytes()
+        .leak()
+    });
+
+    generate_hasher_tests!(WyHasher<String>, String, |rng| {
+        String::generate(
+            rng,
+            &<String as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+    });
+
+    generate_hasher_tests!(WyHasher<Vec<u8>>, Vec<u8>, |rng| {
+        Vec::<u8>::generate(
+            rng,
+            &<Vec<u8> as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+    });
+
+    #[test]
+    fn test_matches_reference_wyhash() {
+        // wyhash() reference values.
+        let hasher = WyHasher::<&str>::from_seed(42, u32::MAX);
+        assert_eq!(hasher.hash(&"Hello, world!"), 0x60a7_ec50);
+        assert_eq!(hasher.hash_const(&"Hello, world!"), 0x60a7_ec50);
+
+        let hasher = WyHasher::<String>::from_seed(6, u32::MAX);
+        let value = "1234567890".repeat(8);
+        assert_eq!(hasher.hash(&value), 0xc39c_ab13);
+    }
+
+    #[test]
+    fn test_truncates_to_num_bits() {
+        let full = WyHasher::<&[u8]>::from_seed(42, u32::MAX);
+        let hasher = WyHasher::<&[u8]>::from_seed(42, 1 << 10);
+        let value: &[u8] = b"Hello, world!";
+        assert_eq!(hasher.num_buckets(), 1 << 10);
+        assert_eq!(hasher.hash(&value), full.hash(&value) >> 22);
+    }
+}
@@ -0,0 +1,154 @@
+//! Implements [`Hasher`] for every type implementing [`core::hash::Hash`].
+
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, num_bits_for_buckets, num_buckets_for_bits,
+};
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher as _};
+use core::marker::PhantomData;
+use o1_core::Hasher;
+use xxhash_rust::xxh3::Xxh3;
+
+/// Hasher of any type implementing [`core::hash::Hash`] - handy for prototyping, since it doesn't
+/// require a dedicated [`Hasher`] implementation for the key type.
+///
+/// The key is fed into a seeded streaming XXH3 through the [`core::hash::Hasher`] trait and the
+/// hash value is truncated to the number of bits determined by the number of buckets.
+///
+/// # Notes
+///
+/// - There is no const implementation - [`Hash`] can't be called in const contexts, so the
+///   hasher can't be used with the compile-time constructors of the maps.
+/// - The hash values depend on how the [`Hash`] implementation of the key type feeds the data
+///   into the hasher, which isn't guaranteed to be stable across platforms and compiler versions.
+///   Use the dedicated hashers when the hash values have to be reproducible.
+pub struct StdHashAdapter<K> {
+    state: StdHashState,
+    _key: PhantomData<fn(&K)>,
+}
+
+// Implemented manually since deriving would require `K` itself to implement the traits.
+impl<K> Clone for StdHashAdapter<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for StdHashAdapter<K> {}
+
+impl<K> Default for StdHashAdapter<K> {
+    fn default() -> Self {
+        Self {
+            state: StdHashState::default(),
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<K> Debug for StdHashAdapter<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StdHashAdapter")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// State of [`StdHashAdapter`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdHashState {
+    num_bits: u32,
+    seed: u64,
+}
+
+impl StdHashState {
+    pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+        Self { num_bits, seed }
+    }
+}
+
+impl<K: Hash + Eq> Hasher<K> for StdHashAdapter<K> {
+    type State = StdHashState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        StdHashState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        Self::from_state(StdHashState::from_seed(seed, num_buckets))
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self {
+            state,
+            _key: PhantomData,
+        }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &K) -> u32 {
+        let mut hasher = Xxh3::with_seed(self.state.seed);
+        value.hash(&mut hasher);
+        extract_bits_64::<{ u64::BITS }>(hasher.finish(), self.state.num_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fks::FKSMap;
+    use o1_core::HashMap;
+    use o1_test::generate_hasher_tests;
+    use rand::Rng;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Employee {
+        id: u32,
+        name: &'static str,
+    }
+
+    const NAMES: [&str; 4] = ["Alice", "Bob", "Carol", "Dave"];
+
+    fn make_data() -> Vec<(Employee, u32)> {
+        (0..999)
+            .map(|i| {
+                let employee = Employee {
+                    id: i / 4,
+                    name: NAMES[i as usize % NAMES.len()],
+                };
+                (employee, i)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_map_with_derived_hash_keys() {
+        let data = make_data();
+        let map =
+            FKSMap::<Employee, u32, StdHashAdapter<Employee>>::new(data.clone().into(), 42, 0.75)
+                .unwrap();
+
+        assert!(map.verify());
+        for (key, val) in &data {
+            assert_eq!(map.get(key), Some(val), "Key: {:?}", key);
+        }
+        let absent = Employee {
+            id: 1000,
+            name: "Alice",
+        };
+        assert_eq!(map.get(&absent), None);
+    }
+
+    generate_hasher_tests!(
+        runtime: StdHashAdapter<Employee>,
+        Employee,
+        |rng: &mut ChaCha20Rng| Employee {
+            id: rng.random(),
+            name: NAMES[rng.random_range(0..NAMES.len())],
+        }
+    );
+    generate_hasher_tests!(runtime: StdHashAdapter<u64>, u64, |rng: &mut ChaCha20Rng| rng
+        .random::<u64>());
+}
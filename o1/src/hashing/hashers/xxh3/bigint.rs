@@ -1,8 +1,11 @@
 //! Implements Hasher for integers larger than 64-bit (u128, i128) using the XXH3 hash function.
 
 use super::core::XXH3Hasher;
-use crate::hashing::common::{extract_bits_64, num_bits_for_buckets, num_buckets_for_bits};
-use o1_core::Hasher;
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, extract_bits_64_wide, num_bits_for_buckets,
+    num_buckets_for_bits,
+};
+use o1_core::{Hasher, WideHasher};
 use xxhash_rust::const_xxh3::xxh3_64_with_seed as xxh3_64_with_seed_const;
 use xxhash_rust::xxh3::xxh3_64_with_seed;
 
@@ -34,12 +37,7 @@ where
     T: Default + Clone,
 {
     pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         BigIntState {
             num_bits,
@@ -49,12 +47,7 @@ where
     }
 
     pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         BigIntState {
             num_bits,
@@ -102,6 +95,19 @@ macro_rules! impl_xxh3_big_int {
                     let hash_value = xxh3_64_with_seed_const(bytes.as_slice(), self.state.seed);
                     extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
                 }
+                pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &$T) -> u64 {
+                    let bytes = value.to_le_bytes();
+                    let hash_value = xxh3_64_with_seed_const(bytes.as_slice(), self.state.seed);
+                    extract_bits_64_wide::<{ u64::BITS }>(hash_value, NUM_BITS)
+                }
+            }
+
+            impl WideHasher<$T> for XXH3Hasher<$T> {
+                fn hash_wide<const NUM_BITS: u32>(&self, value: &$T) -> u64 {
+                    let bytes = value.to_le_bytes();
+                    let hash_value = xxh3_64_with_seed(bytes.as_slice(), self.state.seed);
+                    extract_bits_64_wide::<{ u64::BITS }>(hash_value, NUM_BITS)
+                }
             }
         )*
     };
@@ -126,23 +132,13 @@ impl<const N: usize> Default for BigIntArrayState<N> {
 
 impl<const N: usize> BigIntArrayState<N> {
     fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
 
     const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
@@ -230,4 +226,18 @@ mod tests {
     generate_hasher_tests!(XXH3Hasher<[i128; 8]>, [i128; 8], |rng: &mut ChaCha20Rng| {
         rng.random::<[i128; 8]>()
     });
+
+    #[test]
+    fn test_hash_wide_u128() {
+        let hasher = XXH3Hasher::<u128>::from_seed(42, 1 << 16);
+        let mut max_hash = 0;
+        for i in 0..1000_u32 {
+            let value: u128 = i as u128;
+            let hash = hasher.hash_wide::<40>(&value);
+            assert!(hash < 1 << 40);
+            assert_eq!(hash, hasher.hash_wide_const::<40>(&value));
+            max_hash = max_hash.max(hash);
+        }
+        assert!(max_hash > u32::MAX as u64);
+    }
 }
@@ -1,5 +1,5 @@
+use core::fmt::{Debug, Formatter};
 use o1_core::Hasher;
-use std::fmt::{Debug, Formatter};
 
 /// Hasher based on XXH3 algorithm.
 ///
@@ -34,7 +34,7 @@ where
     XXH3Hasher<T>: Hasher<T>,
     <XXH3Hasher<T> as Hasher<T>>::State: Debug,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("XXH3Hasher")
             .field("state", &self.state)
             .finish()
@@ -51,3 +51,22 @@ where
         Self { state: self.state }
     }
 }
+
+impl<T: Eq> XXH3Hasher<T>
+where
+    XXH3Hasher<T>: Hasher<T>,
+{
+    /// Wraps the state into the hasher - used by the implementations generated by
+    /// [`impl_transparent_hasher!`], which can't access the private field.
+    #[doc(hidden)]
+    pub const fn __from_state(state: <XXH3Hasher<T> as Hasher<T>>::State) -> Self {
+        Self { state }
+    }
+
+    /// Gets the state of the hasher - used by the implementations generated by
+    /// [`impl_transparent_hasher!`], which can't access the private field.
+    #[doc(hidden)]
+    pub const fn __state(&self) -> &<XXH3Hasher<T> as Hasher<T>>::State {
+        &self.state
+    }
+}
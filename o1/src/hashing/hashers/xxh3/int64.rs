@@ -1,8 +1,11 @@
 //! Implements Hasher for u64 and i64 using the XXH3 hash function.
 
 use super::core::XXH3Hasher;
-use crate::hashing::common::{extract_bits_64, num_bits_for_buckets, num_buckets_for_bits};
-use o1_core::Hasher;
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, extract_bits_64_wide, num_bits_for_buckets,
+    num_buckets_for_bits,
+};
+use o1_core::{Hasher, WideHasher};
 use xxhash_rust::const_xxh3::xxh3_64_with_seed as xxh3_64_with_seed_const;
 use xxhash_rust::xxh3::xxh3_64_with_seed;
 
@@ -14,22 +17,12 @@ pub struct U64State {
 
 impl U64State {
     pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
         Self { num_bits, seed }
     }
 
     pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
         Self { num_bits, seed }
     }
 }
@@ -58,6 +51,22 @@ const fn hash_const(state: &U64State, value: u64) -> u32 {
     extract_bits_64::<{ u64::BITS }>(hash_value, state.num_bits)
 }
 
+#[inline]
+fn hash_wide<const NUM_BITS: u32>(state: &U64State, value: u64) -> u64 {
+    let bytes = &value.to_le_bytes();
+    let hash_value = xxh3_64_with_seed(bytes, state.seed);
+
+    extract_bits_64_wide::<{ u64::BITS }>(hash_value, NUM_BITS)
+}
+
+#[inline]
+const fn hash_wide_const<const NUM_BITS: u32>(state: &U64State, value: u64) -> u64 {
+    let bytes = &value.to_le_bytes();
+    let hash_value = xxh3_64_with_seed_const(bytes, state.seed);
+
+    extract_bits_64_wide::<{ u64::BITS }>(hash_value, NUM_BITS)
+}
+
 macro_rules! impl_xxh3_int_64 {
     ($($int_type:ty),*) => {
         $(
@@ -102,6 +111,15 @@ macro_rules! impl_xxh3_int_64 {
                 pub const fn hash_const(&self, value: &$int_type) -> u32 {
                     hash_const(&self.state, *value as u64)
                 }
+                pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &$int_type) -> u64 {
+                    hash_wide_const::<NUM_BITS>(&self.state, *value as u64)
+                }
+            }
+
+            impl WideHasher<$int_type> for XXH3Hasher<$int_type> {
+                fn hash_wide<const NUM_BITS: u32>(&self, value: &$int_type) -> u64 {
+                    hash_wide::<NUM_BITS>(&self.state, *value as u64)
+                }
             }
         )*
     };
@@ -129,25 +147,13 @@ impl<const N: usize> Default for Array64State<N> {
 
 impl<const N: usize> Array64State<N> {
     fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
 
     const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            num_bits > 0 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#,
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
@@ -278,4 +284,44 @@ mod tests {
             *(&rng.random::<[i64; 32]>() as *const [i64; 32] as *const [isize; 32])
         }
     );
+
+    #[test]
+    fn test_hash_wide_u64() {
+        let hasher = XXH3Hasher::<u64>::from_seed(42, 1 << 16);
+        let mut max_hash = 0;
+        for i in 0..1000_u32 {
+            let value: u64 = i as u64;
+            let hash = hasher.hash_wide::<40>(&value);
+            assert!(hash < 1 << 40);
+            assert_eq!(hash, hasher.hash_wide_const::<40>(&value));
+            max_hash = max_hash.max(hash);
+        }
+        assert!(max_hash > u32::MAX as u64);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "_slow-tests"), ignore)]
+    fn test_xxh3_u64_avalanche() {
+        use o1_test::avalanche;
+        use rand::prelude::*;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        avalanche::<ChaCha20Rng, u64>(
+            &mut rng,
+            &|rng, num_buckets| {
+                let hasher = XXH3Hasher::<u64>::from_seed(rng.random(), num_buckets as u32);
+                let num_buckets = hasher.num_buckets() as usize;
+                (
+                    Box::new(move |value: &u64| hasher.hash(value) as usize),
+                    num_buckets,
+                )
+            },
+            1 << 16,
+            10000,
+            32,
+            0.02,
+        );
+    }
 }
@@ -1,5 +1,7 @@
 mod core;
 pub use core::*;
+mod adapter;
+pub use adapter::*;
 mod bigint;
 pub use bigint::*;
 mod int64;
@@ -10,3 +12,12 @@ mod string;
 pub use string::*;
 mod option;
 pub use option::*;
+mod net;
+pub use net::*;
+mod bool;
+mod char;
+mod nonzero;
+mod range;
+mod reference;
+mod time;
+mod unit;
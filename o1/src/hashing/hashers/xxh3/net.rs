@@ -0,0 +1,555 @@
+//! Implements [`Hasher`] for IP addresses ([`Ipv4Addr`], [`Ipv6Addr`] and [`IpAddr`]) and socket
+//! addresses ([`SocketAddrV4`], [`SocketAddrV6`] and [`SocketAddr`]) using XXH3.
+//!
+//! [`Ipv4Addr`] and [`Ipv6Addr`] delegate to [`XXH3Hasher<u32>`] and [`XXH3Hasher<u128>`].
+//! [`IpAddr`] hashes the address family tag followed by the hash of the inner address.
+//!
+//! [`SocketAddrV4`] packs the address and the port into a single `u64` and delegates to
+//! [`XXH3Hasher<u64>`]. [`SocketAddrV6`] hashes the concatenated bytes of the address, the port,
+//! the flow info and the scope ID. [`SocketAddr`] combines the tag and the inner address like
+//! [`IpAddr`].
+
+use super::bigint::BigIntState;
+use super::core::XXH3Hasher;
+use super::int64::U64State;
+use super::smallint::SmallIntState;
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, num_bits_for_buckets, num_buckets_for_bits,
+};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use o1_core::Hasher;
+use xxhash_rust::const_xxh3::xxh3_64_with_seed as xxh3_64_with_seed_const;
+use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+impl Hasher<Ipv4Addr> for XXH3Hasher<Ipv4Addr> {
+    type State = SmallIntState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        SmallIntState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        XXH3Hasher::<u32>::from_state(self.state).num_buckets()
+    }
+    fn hash(&self, value: &Ipv4Addr) -> u32 {
+        XXH3Hasher::<u32>::from_state(self.state).hash(&value.to_bits())
+    }
+}
+
+impl XXH3Hasher<Ipv4Addr> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> SmallIntState {
+        SmallIntState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Ipv4Addr>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        XXH3Hasher::<u32>::from_state_const(self.state).num_buckets_const()
+    }
+    pub const fn hash_const(&self, value: &Ipv4Addr) -> u32 {
+        XXH3Hasher::<u32>::from_state_const(self.state).hash_const(&value.to_bits())
+    }
+}
+
+impl Hasher<Ipv6Addr> for XXH3Hasher<Ipv6Addr> {
+    type State = BigIntState<u128>;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        BigIntState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Ipv6Addr) -> u32 {
+        XXH3Hasher::<u128>::from_state(self.state).hash(&value.to_bits())
+    }
+}
+
+impl XXH3Hasher<Ipv6Addr> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> BigIntState<u128> {
+        BigIntState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Ipv6Addr>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &Ipv6Addr) -> u32 {
+        XXH3Hasher::<u128>::from_state_const(self.state).hash_const(&value.to_bits())
+    }
+}
+
+/// State for hashing [`IpAddr`] values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IpAddrState {
+    seed: u64,
+    v4: SmallIntState,
+    v6: BigIntState<u128>,
+    num_bits: u32,
+}
+
+impl IpAddrState {
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let v4 = SmallIntState::from_seed(seed, num_buckets);
+        let v6 = BigIntState::from_seed(seed.wrapping_add(1000), num_buckets);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+        Self {
+            seed: seed.wrapping_add(2000),
+            v4,
+            v6,
+            num_bits,
+        }
+    }
+}
+
+impl Hasher<IpAddr> for XXH3Hasher<IpAddr> {
+    type State = IpAddrState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        IpAddrState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = IpAddrState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &IpAddr) -> u32 {
+        debug_assert!(
+            (1..=32).contains(&self.state.num_bits),
+            r#""num_bits" must be [1, 32]"#
+        );
+        let (tag, inner_hash) = match value {
+            IpAddr::V4(v) => (
+                0u8,
+                XXH3Hasher::<Ipv4Addr>::from_state(self.state.v4).hash(v),
+            ),
+            IpAddr::V6(v) => (
+                1u8,
+                XXH3Hasher::<Ipv6Addr>::from_state(self.state.v6).hash(v),
+            ),
+        };
+        let mut buf = [tag; 5];
+        buf[1..5].copy_from_slice(&inner_hash.to_le_bytes());
+        let hash_value = xxh3_64_with_seed(&buf, self.state.seed);
+        extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
+    }
+}
+
+impl XXH3Hasher<IpAddr> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> IpAddrState {
+        let v4 = SmallIntState::from_seed_const(seed, num_buckets);
+        let v6 = BigIntState::from_seed_const(seed.wrapping_add(1000), num_buckets);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+        IpAddrState {
+            seed: seed.wrapping_add(2000),
+            v4,
+            v6,
+            num_bits,
+        }
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<IpAddr>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &IpAddr) -> u32 {
+        debug_assert!(
+            self.state.num_bits >= 1 && self.state.num_bits <= 32,
+            r#""num_bits" must be [1, 32]"#
+        );
+        let (tag, inner_hash) = match value {
+            IpAddr::V4(v) => (
+                0u8,
+                XXH3Hasher::<Ipv4Addr>::from_state_const(self.state.v4).hash_const(v),
+            ),
+            IpAddr::V6(v) => (
+                1u8,
+                XXH3Hasher::<Ipv6Addr>::from_state_const(self.state.v6).hash_const(v),
+            ),
+        };
+        let hash_bytes = inner_hash.to_le_bytes();
+        let buf = [
+            tag,
+            hash_bytes[0],
+            hash_bytes[1],
+            hash_bytes[2],
+            hash_bytes[3],
+        ];
+        let hash_value = xxh3_64_with_seed_const(&buf, self.state.seed);
+        extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
+    }
+}
+
+/// Packs the address and the port into a single `u64` - the address in the higher bits.
+#[inline]
+const fn socket_addr_v4_to_bits(value: &SocketAddrV4) -> u64 {
+    ((value.ip().to_bits() as u64) << u16::BITS) | value.port() as u64
+}
+
+/// The number of bytes of the address, the port, the flow info and the scope ID.
+const SOCKET_ADDR_V6_LEN: usize = 26;
+
+/// Concatenates the little-endian bytes of the address, the port, the flow info and the scope ID.
+#[inline]
+const fn socket_addr_v6_to_bytes(value: &SocketAddrV6) -> [u8; SOCKET_ADDR_V6_LEN] {
+    let ip = value.ip().to_bits().to_le_bytes();
+    let port = value.port().to_le_bytes();
+    let flowinfo = value.flowinfo().to_le_bytes();
+    let scope_id = value.scope_id().to_le_bytes();
+    let mut bytes = [0; SOCKET_ADDR_V6_LEN];
+    let mut i = 0;
+    while i < ip.len() {
+        bytes[i] = ip[i];
+        i += 1;
+    }
+    bytes[16] = port[0];
+    bytes[17] = port[1];
+    let mut i = 0;
+    while i < 4 {
+        bytes[18 + i] = flowinfo[i];
+        bytes[22 + i] = scope_id[i];
+        i += 1;
+    }
+    bytes
+}
+
+impl Hasher<SocketAddrV4> for XXH3Hasher<SocketAddrV4> {
+    type State = U64State;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        U64State::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        XXH3Hasher::<u64>::from_state(self.state).num_buckets()
+    }
+    fn hash(&self, value: &SocketAddrV4) -> u32 {
+        XXH3Hasher::<u64>::from_state(self.state).hash(&socket_addr_v4_to_bits(value))
+    }
+}
+
+impl XXH3Hasher<SocketAddrV4> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> U64State {
+        U64State::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<SocketAddrV4>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        XXH3Hasher::<u64>::from_state_const(self.state).num_buckets_const()
+    }
+    pub const fn hash_const(&self, value: &SocketAddrV4) -> u32 {
+        XXH3Hasher::<u64>::from_state_const(self.state).hash_const(&socket_addr_v4_to_bits(value))
+    }
+}
+
+/// State for hashing [`SocketAddrV6`] values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SocketAddrV6State {
+    seed: u64,
+    num_bits: u32,
+}
+
+impl SocketAddrV6State {
+    const fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+        Self { seed, num_bits }
+    }
+}
+
+impl Hasher<SocketAddrV6> for XXH3Hasher<SocketAddrV6> {
+    type State = SocketAddrV6State;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        SocketAddrV6State::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = SocketAddrV6State::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &SocketAddrV6) -> u32 {
+        debug_assert!(
+            (1..=32).contains(&self.state.num_bits),
+            r#""num_bits" must be [1, 32]"#
+        );
+        let hash_value = xxh3_64_with_seed(&socket_addr_v6_to_bytes(value), self.state.seed);
+        extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
+    }
+}
+
+impl XXH3Hasher<SocketAddrV6> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> SocketAddrV6State {
+        SocketAddrV6State::from_seed(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<SocketAddrV6>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &SocketAddrV6) -> u32 {
+        debug_assert!(
+            self.state.num_bits >= 1 && self.state.num_bits <= 32,
+            r#""num_bits" must be [1, 32]"#
+        );
+        let hash_value = xxh3_64_with_seed_const(&socket_addr_v6_to_bytes(value), self.state.seed);
+        extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
+    }
+}
+
+/// State for hashing [`SocketAddr`] values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SocketAddrState {
+    seed: u64,
+    v4: U64State,
+    v6: SocketAddrV6State,
+    num_bits: u32,
+}
+
+impl SocketAddrState {
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let v4 = U64State::from_seed(seed, num_buckets);
+        let v6 = SocketAddrV6State::from_seed(seed.wrapping_add(1000), num_buckets);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+        Self {
+            seed: seed.wrapping_add(2000),
+            v4,
+            v6,
+            num_bits,
+        }
+    }
+}
+
+impl Hasher<SocketAddr> for XXH3Hasher<SocketAddr> {
+    type State = SocketAddrState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        SocketAddrState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = SocketAddrState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &SocketAddr) -> u32 {
+        debug_assert!(
+            (1..=32).contains(&self.state.num_bits),
+            r#""num_bits" must be [1, 32]"#
+        );
+        let (tag, inner_hash) = match value {
+            SocketAddr::V4(v) => (
+                0u8,
+                XXH3Hasher::<SocketAddrV4>::from_state(self.state.v4).hash(v),
+            ),
+            SocketAddr::V6(v) => (
+                1u8,
+                XXH3Hasher::<SocketAddrV6>::from_state(self.state.v6).hash(v),
+            ),
+        };
+        let mut buf = [tag; 5];
+        buf[1..5].copy_from_slice(&inner_hash.to_le_bytes());
+        let hash_value = xxh3_64_with_seed(&buf, self.state.seed);
+        extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
+    }
+}
+
+impl XXH3Hasher<SocketAddr> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> SocketAddrState {
+        let v4 = U64State::from_seed_const(seed, num_buckets);
+        let v6 = SocketAddrV6State::from_seed(seed.wrapping_add(1000), num_buckets);
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
+        SocketAddrState {
+            seed: seed.wrapping_add(2000),
+            v4,
+            v6,
+            num_bits,
+        }
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<SocketAddr>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &SocketAddr) -> u32 {
+        debug_assert!(
+            self.state.num_bits >= 1 && self.state.num_bits <= 32,
+            r#""num_bits" must be [1, 32]"#
+        );
+        let (tag, inner_hash) = match value {
+            SocketAddr::V4(v) => (
+                0u8,
+                XXH3Hasher::<SocketAddrV4>::from_state_const(self.state.v4).hash_const(v),
+            ),
+            SocketAddr::V6(v) => (
+                1u8,
+                XXH3Hasher::<SocketAddrV6>::from_state_const(self.state.v6).hash_const(v),
+            ),
+        };
+        let hash_bytes = inner_hash.to_le_bytes();
+        let buf = [
+            tag,
+            hash_bytes[0],
+            hash_bytes[1],
+            hash_bytes[2],
+            hash_bytes[3],
+        ];
+        let hash_value = xxh3_64_with_seed_const(&buf, self.state.seed);
+        extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(XXH3Hasher<Ipv4Addr>, Ipv4Addr, |rng: &mut ChaCha20Rng| {
+        Ipv4Addr::from_bits(rng.random::<u32>())
+    });
+
+    generate_hasher_tests!(XXH3Hasher<Ipv6Addr>, Ipv6Addr, |rng: &mut ChaCha20Rng| {
+        Ipv6Addr::from_bits(rng.random::<u128>())
+    });
+
+    generate_hasher_tests!(XXH3Hasher<IpAddr>, IpAddr, |rng: &mut ChaCha20Rng| {
+        if rng.random::<bool>() {
+            IpAddr::V4(Ipv4Addr::from_bits(rng.random::<u32>()))
+        } else {
+            IpAddr::V6(Ipv6Addr::from_bits(rng.random::<u128>()))
+        }
+    });
+    generate_hasher_tests!(
+        XXH3Hasher<SocketAddrV4>,
+        SocketAddrV4,
+        |rng: &mut ChaCha20Rng| {
+            SocketAddrV4::new(
+                Ipv4Addr::from_bits(rng.random::<u32>()),
+                rng.random::<u16>(),
+            )
+        }
+    );
+
+    generate_hasher_tests!(
+        XXH3Hasher<SocketAddrV6>,
+        SocketAddrV6,
+        |rng: &mut ChaCha20Rng| {
+            SocketAddrV6::new(
+                Ipv6Addr::from_bits(rng.random::<u128>()),
+                rng.random::<u16>(),
+                rng.random::<u32>(),
+                rng.random::<u32>(),
+            )
+        }
+    );
+
+    generate_hasher_tests!(
+        XXH3Hasher<SocketAddr>,
+        SocketAddr,
+        |rng: &mut ChaCha20Rng| {
+            if rng.random::<bool>() {
+                SocketAddr::V4(SocketAddrV4::new(
+                    Ipv4Addr::from_bits(rng.random::<u32>()),
+                    rng.random::<u16>(),
+                ))
+            } else {
+                SocketAddr::V6(SocketAddrV6::new(
+                    Ipv6Addr::from_bits(rng.random::<u128>()),
+                    rng.random::<u16>(),
+                    rng.random::<u32>(),
+                    rng.random::<u32>(),
+                ))
+            }
+        }
+    );
+
+    #[test]
+    fn test_socket_addr_v6_to_bytes() {
+        let value = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 0x0102, 0x0304_0506, 0x0708_090a);
+        let bytes = socket_addr_v6_to_bytes(&value);
+        assert_eq!(bytes[..16], Ipv6Addr::LOCALHOST.to_bits().to_le_bytes());
+        assert_eq!(
+            bytes[16..],
+            [0x02, 0x01, 0x06, 0x05, 0x04, 0x03, 0x0a, 0x09, 0x08, 0x07]
+        );
+    }
+}
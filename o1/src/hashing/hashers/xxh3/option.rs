@@ -1,41 +1,75 @@
-//! Implements [`Hasher`] for `Option<T>` where `T` is a primitive type using XXH3.
+//! Implements [`Hasher`] for `Option<T>` using XXH3 for every `T` supported by
+//! [`XXH3Hasher<T>`] - including nested options like `Option<Option<T>>`.
 //!
-//! The implementation delegates to the existing [`XXH3Hasher<T>`] and exposes
-//! a matching const-time interface.
+//! The implementation delegates to the existing [`XXH3Hasher<T>`]. The const methods can't be
+//! implemented generically, so they are provided for the primitive types, arrays and strings
+//! and for the same types wrapped into one more `Option`.
 
 use super::core::XXH3Hasher;
-use crate::hashing::common::{extract_bits_64, num_bits_for_buckets, num_buckets_for_bits};
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, num_bits_for_buckets, num_buckets_for_bits,
+};
 use o1_core::Hasher;
 use xxhash_rust::const_xxh3::xxh3_64_with_seed as xxh3_64_with_seed_const;
 use xxhash_rust::xxh3::xxh3_64_with_seed;
 
 /// State for hashing `Option<T>` values.
-#[derive(Debug, Clone, Copy)]
 pub struct OptionState<T>
 where
     T: Eq,
     XXH3Hasher<T>: Hasher<T>,
-    <XXH3Hasher<T> as Hasher<T>>::State: Copy + core::fmt::Debug + Default,
 {
     seed: u64,
     inner: <XXH3Hasher<T> as Hasher<T>>::State,
     num_bits: u32,
 }
 
+// Implemented manually since deriving would require `T` itself to implement the traits.
+impl<T> Clone for OptionState<T>
+where
+    T: Eq,
+    XXH3Hasher<T>: Hasher<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            seed: self.seed,
+            inner: self.inner.clone(),
+            num_bits: self.num_bits,
+        }
+    }
+}
+
+impl<T> Copy for OptionState<T>
+where
+    T: Eq,
+    XXH3Hasher<T>: Hasher<T>,
+    <XXH3Hasher<T> as Hasher<T>>::State: Copy,
+{
+}
+
+impl<T> core::fmt::Debug for OptionState<T>
+where
+    T: Eq,
+    XXH3Hasher<T>: Hasher<T>,
+    <XXH3Hasher<T> as Hasher<T>>::State: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OptionState")
+            .field("seed", &self.seed)
+            .field("inner", &self.inner)
+            .field("num_bits", &self.num_bits)
+            .finish()
+    }
+}
+
 impl<T> OptionState<T>
 where
     T: Eq,
     XXH3Hasher<T>: Hasher<T>,
-    <XXH3Hasher<T> as Hasher<T>>::State: Copy + core::fmt::Debug + Default,
 {
     fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
         let inner = <XXH3Hasher<T> as Hasher<T>>::make_state(seed, num_buckets);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#,
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
         Self {
             seed: seed.wrapping_add(2000),
             inner,
@@ -48,7 +82,6 @@ impl<T> Default for OptionState<T>
 where
     T: Eq,
     XXH3Hasher<T>: Hasher<T>,
-    <XXH3Hasher<T> as Hasher<T>>::State: Copy + core::fmt::Debug + Default,
 {
     fn default() -> Self {
         Self {
@@ -59,52 +92,63 @@ where
     }
 }
 
-macro_rules! impl_option_xxh3 {
-    ($($t:ty),*) => {
-        $(
-        impl Hasher<Option<$t>> for XXH3Hasher<Option<$t>> {
-            type State = OptionState<$t>;
+impl<T> Hasher<Option<T>> for XXH3Hasher<Option<T>>
+where
+    T: Eq,
+    XXH3Hasher<T>: Hasher<T>,
+{
+    type State = OptionState<T>;
 
-            fn make_state(seed: u64, num_buckets: u32) -> Self::State {
-                OptionState::<$t>::from_seed(seed, num_buckets)
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        OptionState::<T>::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = OptionState::<T>::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Option<T>) -> u32 {
+        debug_assert!(
+            (1..=32).contains(&self.state.num_bits),
+            r#""num_bits" must be [1, 32]"#
+        );
+        let mut buf = [0u8; 5];
+        let len = match value {
+            None => {
+                buf[0] = 0;
+                1
             }
-            fn from_seed(seed: u64, num_buckets: u32) -> Self {
-                let state = OptionState::<$t>::from_seed(seed, num_buckets);
-                Self { state }
+            Some(v) => {
+                buf[0] = 1;
+                let inner = XXH3Hasher::<T>::from_state(self.state.inner.clone());
+                let hash = inner.hash(v);
+                buf[1..5].copy_from_slice(&hash.to_le_bytes());
+                5
             }
-            fn from_state(state: Self::State) -> Self { Self { state } }
-            fn state(&self) -> &Self::State { &self.state }
-            fn num_buckets(&self) -> u32 { num_buckets_for_bits(self.state.num_bits) }
-            fn hash(&self, value: &Option<$t>) -> u32 {
-                debug_assert!(
-                    (1..=32).contains(&self.state.num_bits),
-                    r#""num_bits" must be [1, 32]"#
-                );
-                let mut buf = [0u8; 5];
-                let len = match value {
-                    None => { buf[0] = 0; 1 }
-                    Some(v) => {
-                        buf[0] = 1;
-                        let inner = XXH3Hasher::<$t>::from_state(self.state.inner);
-                        let hash = inner.hash(v);
-                        buf[1..5].copy_from_slice(&hash.to_le_bytes());
-                        5
-                    }
-                };
-                let hash_value = xxh3_64_with_seed(&buf[..len], self.state.seed);
-                extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
-            }
-        }
+        };
+        let hash_value = xxh3_64_with_seed(&buf[..len], self.state.seed);
+        extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
+    }
+}
 
-        impl XXH3Hasher<Option<$t>> {
+/// Generates the const methods of `XXH3Hasher<Option<T>>` for the specified types.
+///
+/// Each type is preceded by the generic parameters of the impl in square brackets.
+macro_rules! impl_option_xxh3_const {
+    ($([$($generics:tt)*] $t:ty),* $(,)?) => {
+        $(
+        impl<$($generics)*> XXH3Hasher<Option<$t>> {
             pub const fn make_state_const(seed: u64, num_buckets: u32) -> OptionState<$t> {
-                debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
                 let inner = XXH3Hasher::<$t>::make_state_const(seed, num_buckets);
-                let num_bits = num_bits_for_buckets(num_buckets);
-                debug_assert!(
-                    num_bits >= 1 && num_bits <= 32,
-                    r#""num_bits" must be [1, 32]"#,
-                );
+                let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
                 OptionState { seed: seed.wrapping_add(2000), inner, num_bits }
             }
             pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
@@ -151,205 +195,33 @@ macro_rules! impl_option_xxh3 {
     };
 }
 
-macro_rules! impl_option_xxh3_array {
+/// Generates the const methods of `XXH3Hasher<Option<T>>` for the integer types, the arrays of
+/// them and the same types wrapped into one more `Option`.
+macro_rules! impl_option_xxh3_int {
     ($($t:ty),*) => {
-        $(
-        impl<const N: usize> Hasher<Option<[$t; N]>> for XXH3Hasher<Option<[$t; N]>> {
-            type State = OptionState<[$t; N]>;
-
-            fn make_state(seed: u64, num_buckets: u32) -> Self::State {
-                OptionState::<[$t; N]>::from_seed(seed, num_buckets)
-            }
-            fn from_seed(seed: u64, num_buckets: u32) -> Self {
-                let state = OptionState::<[$t; N]>::from_seed(seed, num_buckets);
-                Self { state }
-            }
-            fn from_state(state: Self::State) -> Self { Self { state } }
-            fn state(&self) -> &Self::State { &self.state }
-            fn num_buckets(&self) -> u32 { num_buckets_for_bits(self.state.num_bits) }
-            fn hash(&self, value: &Option<[$t; N]>) -> u32 {
-                debug_assert!(
-                    (1..=32).contains(&self.state.num_bits),
-                    r#""num_bits" must be [1, 32]"#
-                );
-                let mut buf = [0u8; 5];
-                let len = match value {
-                    None => { buf[0] = 0; 1 }
-                    Some(v) => {
-                        buf[0] = 1;
-                        let inner = XXH3Hasher::<[$t; N]>::from_state(self.state.inner);
-                        let hash = inner.hash(v);
-                        buf[1..5].copy_from_slice(&hash.to_le_bytes());
-                        5
-                    }
-                };
-                let hash_value = xxh3_64_with_seed(&buf[..len], self.state.seed);
-                extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
-            }
-        }
-
-        impl<const N: usize> XXH3Hasher<Option<[$t; N]>> {
-            pub const fn make_state_const(seed: u64, num_buckets: u32) -> OptionState<[$t; N]> {
-                debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-                let inner = XXH3Hasher::<[$t; N]>::make_state_const(seed, num_buckets);
-                let num_bits = num_bits_for_buckets(num_buckets);
-                debug_assert!(
-                    num_bits >= 1 && num_bits <= 32,
-                    r#""num_bits" must be [1, 32]"#,
-                );
-                OptionState { seed: seed.wrapping_add(2000), inner, num_bits }
-            }
-            pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-                let state = Self::make_state_const(seed, num_buckets);
-                Self { state }
-            }
-            pub const fn from_state_const(
-                state: <Self as Hasher<Option<[$t; N]>>>::State,
-            ) -> Self {
-                Self { state }
-            }
-            pub const fn num_buckets_const(&self) -> u32 {
-                num_buckets_for_bits(self.state.num_bits)
-            }
-            pub const fn hash_const(&self, value: &Option<[$t; N]>) -> u32 {
-                debug_assert!(
-                    self.state.num_bits >= 1 && self.state.num_bits <= 32,
-                    r#""num_bits" must be [1, 32]"#
-                );
-                let mut buf = [0u8; 5];
-                let len = match value {
-                    None => { buf[0] = 0; 1 }
-                    Some(v) => {
-                        buf[0] = 1;
-                        let inner = XXH3Hasher::<[$t; N]>::from_state_const(self.state.inner);
-                        let hash = inner.hash_const(v);
-                        let hash_bytes = hash.to_le_bytes();
-                        buf[1] = hash_bytes[0];
-                        buf[2] = hash_bytes[1];
-                        buf[3] = hash_bytes[2];
-                        buf[4] = hash_bytes[3];
-                        5
-                    }
-                };
-                let hash_value = match len {
-                    1 => {
-                        let slice = core::slice::from_ref(&buf[0]);
-                        xxh3_64_with_seed_const(slice, self.state.seed)
-                    }
-                    _ => xxh3_64_with_seed_const(&buf, self.state.seed),
-                };
-                extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
-            }
-        }
-        )*
-    };
-}
-
-macro_rules! impl_option_xxh3_ref {
-    ($($t:ty),*) => {
-        $(
-        impl<'a> Hasher<Option<$t>> for XXH3Hasher<Option<$t>> {
-            type State = OptionState<$t>;
-
-            fn make_state(seed: u64, num_buckets: u32) -> Self::State {
-                OptionState::<$t>::from_seed(seed, num_buckets)
-            }
-            fn from_seed(seed: u64, num_buckets: u32) -> Self {
-                let state = OptionState::<$t>::from_seed(seed, num_buckets);
-                Self { state }
-            }
-            fn from_state(state: Self::State) -> Self { Self { state } }
-            fn state(&self) -> &Self::State { &self.state }
-            fn num_buckets(&self) -> u32 { num_buckets_for_bits(self.state.num_bits) }
-            fn hash(&self, value: &Option<$t>) -> u32 {
-                debug_assert!(
-                    (1..=32).contains(&self.state.num_bits),
-                    r#""num_bits" must be [1, 32]"#
-                );
-                let mut buf = [0u8; 5];
-                let len = match value {
-                    None => { buf[0] = 0; 1 }
-                    Some(v) => {
-                        buf[0] = 1;
-                        let inner = XXH3Hasher::<$t>::from_state(self.state.inner);
-                        let hash = inner.hash(v);
-                        buf[1..5].copy_from_slice(&hash.to_le_bytes());
-                        5
-                    }
-                };
-                let hash_value = xxh3_64_with_seed(&buf[..len], self.state.seed);
-                extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
-            }
-        }
-
-        impl<'a> XXH3Hasher<Option<$t>> {
-            pub const fn make_state_const(seed: u64, num_buckets: u32) -> OptionState<$t> {
-                debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-                let inner = XXH3Hasher::<$t>::make_state_const(seed, num_buckets);
-                let num_bits = num_bits_for_buckets(num_buckets);
-                debug_assert!(
-                    num_bits >= 1 && num_bits <= 32,
-                    r#""num_bits" must be [1, 32]"#,
-                );
-                OptionState { seed: seed.wrapping_add(2000), inner, num_bits }
-            }
-            pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-                let state = Self::make_state_const(seed, num_buckets);
-                Self { state }
-            }
-            pub const fn from_state_const(state: <Self as Hasher<Option<$t>>>::State) -> Self {
-                Self { state }
-            }
-            pub const fn num_buckets_const(&self) -> u32 {
-                num_buckets_for_bits(self.state.num_bits)
-            }
-            pub const fn hash_const(&self, value: &Option<$t>) -> u32 {
-                debug_assert!(
-                    self.state.num_bits >= 1 && self.state.num_bits <= 32,
-                    r#""num_bits" must be [1, 32]"#
-                );
-                let mut buf = [0u8; 5];
-                let len = match value {
-                    None => { buf[0] = 0; 1 }
-                    Some(v) => {
-                        buf[0] = 1;
-                        let inner = XXH3Hasher::<$t>::from_state_const(self.state.inner);
-                        let hash = inner.hash_const(v);
-                        let hash_bytes = hash.to_le_bytes();
-                        buf[1] = hash_bytes[0];
-                        buf[2] = hash_bytes[1];
-                        buf[3] = hash_bytes[2];
-                        buf[4] = hash_bytes[3];
-                        5
-                    }
-                };
-                let hash_value = match len {
-                    1 => {
-                        let slice = core::slice::from_ref(&buf[0]);
-                        xxh3_64_with_seed_const(slice, self.state.seed)
-                    }
-                    _ => xxh3_64_with_seed_const(&buf, self.state.seed),
-                };
-                extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
-            }
-        }
-        )*
+        impl_option_xxh3_const!(
+            $(
+                [] $t,
+                [const N: usize] [$t; N],
+                [] Option<$t>,
+                [const N: usize] Option<[$t; N]>,
+            )*
+        );
     };
 }
 
-impl_option_xxh3!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+impl_option_xxh3_int!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
 #[cfg(target_pointer_width = "64")]
-impl_option_xxh3!(usize, isize);
+impl_option_xxh3_int!(usize, isize);
 #[cfg(any(target_pointer_width = "32", target_pointer_width = "16"))]
-impl_option_xxh3!(usize, isize);
+impl_option_xxh3_int!(usize, isize);
 
-impl_option_xxh3_array!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
-#[cfg(target_pointer_width = "64")]
-impl_option_xxh3_array!(usize, isize);
-#[cfg(any(target_pointer_width = "32", target_pointer_width = "16"))]
-impl_option_xxh3_array!(usize, isize);
-
-impl_option_xxh3_ref!(&'a [u8], &'a str);
+impl_option_xxh3_const!(
+    ['a] &'a [u8],
+    ['a] &'a str,
+    ['a] Option<&'a [u8]>,
+    ['a] Option<&'a str>,
+);
 
 #[cfg(test)]
 mod tests {
@@ -424,6 +296,31 @@ mod tests {
         }
     );
 
+    generate_hasher_tests!(
+        XXH3Hasher<Option<Option<u32>>>,
+        Option<Option<u32>>,
+        |rng: &mut ChaCha20Rng| {
+            match rng.random::<u32>() % 10 {
+                0..2 => None,
+                2..4 => Some(None),
+                _ => Some(Some(rng.random::<u32>())),
+            }
+        }
+    );
+
+    generate_hasher_tests!(
+        XXH3Hasher<Option<Option<&'static str>>>,
+        Option<Option<&'static str>>,
+        |rng: &mut ChaCha20Rng| {
+            let options = ["alpha", "beta", "gamma", "delta"];
+            match rng.random::<u32>() % 10 {
+                0..2 => None,
+                2..4 => Some(None),
+                _ => Some(Some(options[(rng.next_u32() as usize) % options.len()])),
+            }
+        }
+    );
+
     generate_hasher_tests!(
         XXH3Hasher<Option<[u32; 32]>>,
         Option<[u32; 32]>,
@@ -436,4 +333,33 @@ mod tests {
             }
         }
     );
+
+    #[test]
+    fn test_nested_option_distinguishes_levels() {
+        let values = [None, Some(None), Some(Some(0)), Some(Some(1))];
+        for seed in 0..100 {
+            let hasher = XXH3Hasher::<Option<Option<u32>>>::from_seed(seed, u32::MAX);
+            let const_hasher = XXH3Hasher::<Option<Option<u32>>>::from_seed_const(seed, u32::MAX);
+            let hashes = values.map(|v| hasher.hash(&v));
+            let const_hashes = values.map(|v| const_hasher.hash_const(&v));
+            for i in 0..values.len() {
+                for j in i + 1..values.len() {
+                    assert_ne!(hashes[i], hashes[j], "Seed: {seed}, i: {i}, j: {j}");
+                    assert_ne!(
+                        const_hashes[i], const_hashes[j],
+                        "Seed: {seed}, i: {i}, j: {j}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_deeply_nested_option() {
+        let hasher = XXH3Hasher::<Option<Option<Option<u64>>>>::from_seed(42, 1 << 16);
+        let values = [None, Some(None), Some(Some(None)), Some(Some(Some(7)))];
+        for value in values {
+            assert!(hasher.hash(&value) < hasher.num_buckets());
+        }
+    }
 }
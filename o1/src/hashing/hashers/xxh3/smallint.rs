@@ -1,8 +1,11 @@
 //! Implements Hasher for 32-bit and smaller integers using the XXH3 hash function.
 
 use super::core::XXH3Hasher;
-use crate::hashing::common::{extract_bits_64, num_bits_for_buckets, num_buckets_for_bits};
-use o1_core::Hasher;
+use crate::hashing::common::{
+    clamp_num_bits, extract_bits_64, extract_bits_64_wide, num_bits_for_buckets,
+    num_buckets_for_bits,
+};
+use o1_core::{Hasher, WideHasher};
 use xxhash_rust::const_xxh3::xxh3_64_with_seed as xxh3_64_with_seed_const;
 use xxhash_rust::xxh3::xxh3_64_with_seed;
 
@@ -14,22 +17,12 @@ pub struct SmallIntState {
 
 impl SmallIntState {
     pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
         Self { num_bits, seed }
     }
 
     pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
         Self { num_bits, seed }
     }
 }
@@ -58,6 +51,22 @@ const fn hash_const(state: &SmallIntState, value: u32) -> u32 {
     extract_bits_64::<{ u64::BITS }>(hash_value, state.num_bits)
 }
 
+#[inline]
+fn hash_wide<const NUM_BITS: u32>(state: &SmallIntState, value: u32) -> u64 {
+    let bytes = &value.to_le_bytes();
+    let hash_value = xxh3_64_with_seed(bytes, state.seed);
+
+    extract_bits_64_wide::<{ u64::BITS }>(hash_value, NUM_BITS)
+}
+
+#[inline]
+const fn hash_wide_const<const NUM_BITS: u32>(state: &SmallIntState, value: u32) -> u64 {
+    let bytes = &value.to_le_bytes();
+    let hash_value = xxh3_64_with_seed_const(bytes, state.seed);
+
+    extract_bits_64_wide::<{ u64::BITS }>(hash_value, NUM_BITS)
+}
+
 impl Hasher<u32> for XXH3Hasher<u32> {
     type State = SmallIntState;
 
@@ -99,6 +108,15 @@ impl XXH3Hasher<u32> {
     pub const fn hash_const(&self, value: &u32) -> u32 {
         hash_const(&self.state, *value)
     }
+    pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &u32) -> u64 {
+        hash_wide_const::<NUM_BITS>(&self.state, *value)
+    }
+}
+
+impl WideHasher<u32> for XXH3Hasher<u32> {
+    fn hash_wide<const NUM_BITS: u32>(&self, value: &u32) -> u64 {
+        hash_wide::<NUM_BITS>(&self.state, *value)
+    }
 }
 
 /// Generates Hasher impls for other small integer types by upcasting to u32.
@@ -136,6 +154,15 @@ macro_rules! impl_xxh3_small_int {
                 pub const fn hash_const(&self, value: &$k) -> u32 {
                     hash_const(&self.state, (*value) as u32)
                 }
+                pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &$k) -> u64 {
+                    hash_wide_const::<NUM_BITS>(&self.state, (*value) as u32)
+                }
+            }
+
+            impl WideHasher<$k> for XXH3Hasher<$k> {
+                fn hash_wide<const NUM_BITS: u32>(&self, value: &$k) -> u64 {
+                    hash_wide::<NUM_BITS>(&self.state, (*value) as u32)
+                }
             }
         )*
     };
@@ -162,30 +189,35 @@ impl<const N: usize> Default for SmallArrayState<N> {
 
 impl<const N: usize> SmallArrayState<N> {
     fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
 
     const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         Self { num_bits, seed }
     }
 }
 
+/// Hashes the bytes of a fixed-size array, they may be a packed representation of a value.
+#[inline]
+pub(super) fn hash_bytes<const N: usize>(state: &SmallArrayState<N>, bytes: &[u8]) -> u32 {
+    let hash_value = xxh3_64_with_seed(bytes, state.seed);
+    extract_bits_64::<{ u64::BITS }>(hash_value, state.num_bits)
+}
+
+/// Const version of [`hash_bytes`].
+#[inline]
+pub(super) const fn hash_bytes_const<const N: usize>(
+    state: &SmallArrayState<N>,
+    bytes: &[u8],
+) -> u32 {
+    let hash_value = xxh3_64_with_seed_const(bytes, state.seed);
+    extract_bits_64::<{ u64::BITS }>(hash_value, state.num_bits)
+}
+
 macro_rules! impl_smallint_array_hasher {
     ($(($t:ty, $S:expr)),*) => {
         $(
@@ -208,9 +240,8 @@ macro_rules! impl_smallint_array_hasher {
                         r#""num_bits" must be [1, 32]"#
                     );
                     let bytes_len = N * $S;
-                    let bytes = unsafe { std::slice::from_raw_parts(value.as_ptr() as *const u8, bytes_len) };
-                    let hash_value = xxh3_64_with_seed(bytes, self.state.seed);
-                    extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
+                    let bytes = unsafe { core::slice::from_raw_parts(value.as_ptr() as *const u8, bytes_len) };
+                    hash_bytes(&self.state, bytes)
                 }
             }
 
@@ -236,8 +267,7 @@ macro_rules! impl_smallint_array_hasher {
                         i += 1;
                     }
                     let bytes = unsafe { core::slice::from_raw_parts(byte_array.as_ptr() as *const u8, N * $S) };
-                    let hash_value = xxh3_64_with_seed_const(bytes, self.state.seed);
-                    extract_bits_64::<{ u64::BITS }>(hash_value, self.state.num_bits)
+                    hash_bytes_const(&self.state, bytes)
                 }
             }
         )*
@@ -312,4 +342,18 @@ mod tests {
             *(&rng.random::<[i32; 32]>() as *const [i32; 32] as *const [isize; 32])
         }
     );
+
+    #[test]
+    fn test_hash_wide_u32() {
+        let hasher = XXH3Hasher::<u32>::from_seed(42, 1 << 16);
+        let mut max_hash = 0;
+        for i in 0..1000_u32 {
+            let value: u32 = i;
+            let hash = hasher.hash_wide::<40>(&value);
+            assert!(hash < 1 << 40);
+            assert_eq!(hash, hasher.hash_wide_const::<40>(&value));
+            max_hash = max_hash.max(hash);
+        }
+        assert!(max_hash > u32::MAX as u64);
+    }
 }
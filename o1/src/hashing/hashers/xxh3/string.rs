@@ -1,8 +1,15 @@
 //! Implements Hasher for unbounded strings and byte slices using the XXH3 hash function.
 
 use super::core::XXH3Hasher;
-use crate::hashing::common::{extract_bits_64, num_bits_for_buckets, num_buckets_for_bits};
-use o1_core::Hasher;
+use crate::hashing::common::{
+    clamp_num_bits, cow_bytes_as_slice, cow_str_as_bytes, extract_bits_64, extract_bits_64_wide,
+    num_bits_for_buckets, num_buckets_for_bits, CowBytes, CowStr,
+};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use o1_core::{Hasher, WideHasher};
 use xxhash_rust::const_xxh3::xxh3_64_with_seed as xxh3_64_with_seed_const;
 use xxhash_rust::xxh3::xxh3_64_with_seed;
 
@@ -14,27 +21,13 @@ pub struct StringState {
 
 impl StringState {
     pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            (1..=32).contains(&num_bits),
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         StringState { num_bits, seed }
     }
 
     pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
-        debug_assert!(num_buckets > 0, r#""num_buckets" must be greater than 0"#);
-
-        let num_bits = num_bits_for_buckets(num_buckets);
-
-        debug_assert!(
-            num_bits >= 1 && num_bits <= 32,
-            r#""num_bits" must be [1, 32]"#
-        );
+        let num_bits = clamp_num_bits(num_bits_for_buckets(num_buckets));
 
         StringState { num_bits, seed }
     }
@@ -62,6 +55,22 @@ const fn hash_const(state: &StringState, value: &[u8]) -> u32 {
     extract_bits_64::<{ u64::BITS }>(hash_value, state.num_bits)
 }
 
+#[inline]
+fn hash_wide<const NUM_BITS: u32>(state: &StringState, value: &[u8]) -> u64 {
+    let bytes = value;
+    let hash_value = xxh3_64_with_seed(bytes, state.seed);
+
+    extract_bits_64_wide::<{ u64::BITS }>(hash_value, NUM_BITS)
+}
+
+#[inline]
+const fn hash_wide_const<const NUM_BITS: u32>(state: &StringState, value: &[u8]) -> u64 {
+    let bytes = value;
+    let hash_value = xxh3_64_with_seed_const(bytes, state.seed);
+
+    extract_bits_64_wide::<{ u64::BITS }>(hash_value, NUM_BITS)
+}
+
 impl Hasher<&[u8]> for XXH3Hasher<&[u8]> {
     type State = StringState;
 
@@ -103,6 +112,9 @@ impl XXH3Hasher<&[u8]> {
     pub const fn hash_const(&self, value: &&[u8]) -> u32 {
         hash_const(&self.state, value)
     }
+    pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &&[u8]) -> u64 {
+        hash_wide_const::<NUM_BITS>(&self.state, value)
+    }
 }
 
 impl Hasher<String> for XXH3Hasher<String> {
@@ -129,6 +141,28 @@ impl Hasher<String> for XXH3Hasher<String> {
     }
 }
 
+impl XXH3Hasher<String> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+        StringState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<String>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &String) -> u32 {
+        hash_const(&self.state, value.as_bytes())
+    }
+    pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &String) -> u64 {
+        hash_wide_const::<NUM_BITS>(&self.state, value.as_bytes())
+    }
+}
+
 impl<'a> Hasher<&'a str> for XXH3Hasher<&'a str> {
     type State = StringState;
 
@@ -170,6 +204,237 @@ impl<'a> XXH3Hasher<&'a str> {
     pub const fn hash_const(&self, value: &&str) -> u32 {
         hash_const(&self.state, value.as_bytes())
     }
+    pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &&str) -> u64 {
+        hash_wide_const::<NUM_BITS>(&self.state, value.as_bytes())
+    }
+}
+
+impl Hasher<Vec<u8>> for XXH3Hasher<Vec<u8>> {
+    type State = StringState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        StringState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: StringState) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Vec<u8>) -> u32 {
+        hash(&self.state, value.as_slice())
+    }
+}
+
+impl XXH3Hasher<Vec<u8>> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+        StringState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Vec<u8>>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &Vec<u8>) -> u32 {
+        hash_const(&self.state, value.as_slice())
+    }
+    pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &Vec<u8>) -> u64 {
+        hash_wide_const::<NUM_BITS>(&self.state, value.as_slice())
+    }
+}
+
+impl Hasher<Box<[u8]>> for XXH3Hasher<Box<[u8]>> {
+    type State = StringState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        StringState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: StringState) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Box<[u8]>) -> u32 {
+        hash(&self.state, value)
+    }
+}
+
+impl XXH3Hasher<Box<[u8]>> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+        StringState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Box<[u8]>>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    #[allow(clippy::borrowed_box)]
+    pub const fn hash_const(&self, value: &Box<[u8]>) -> u32 {
+        hash_const(&self.state, value)
+    }
+    #[allow(clippy::borrowed_box)]
+    pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &Box<[u8]>) -> u64 {
+        hash_wide_const::<NUM_BITS>(&self.state, value)
+    }
+}
+
+impl<'a> Hasher<Cow<'a, str>> for XXH3Hasher<Cow<'a, str>> {
+    type State = StringState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        StringState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: StringState) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Cow<'a, str>) -> u32 {
+        hash(&self.state, cow_str_as_bytes(value))
+    }
+}
+
+impl<'a> XXH3Hasher<Cow<'a, str>> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+        StringState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Cow<'a, str>>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &CowStr<'a>) -> u32 {
+        hash_const(&self.state, cow_str_as_bytes(value))
+    }
+    pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &CowStr<'a>) -> u64 {
+        hash_wide_const::<NUM_BITS>(&self.state, cow_str_as_bytes(value))
+    }
+}
+
+impl<'a> Hasher<Cow<'a, [u8]>> for XXH3Hasher<Cow<'a, [u8]>> {
+    type State = StringState;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        StringState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: StringState) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Cow<'a, [u8]>) -> u32 {
+        hash(&self.state, cow_bytes_as_slice(value))
+    }
+}
+
+impl<'a> XXH3Hasher<Cow<'a, [u8]>> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> StringState {
+        StringState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = StringState::from_seed_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Cow<'a, [u8]>>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &CowBytes<'a>) -> u32 {
+        hash_const(&self.state, cow_bytes_as_slice(value))
+    }
+    pub const fn hash_wide_const<const NUM_BITS: u32>(&self, value: &CowBytes<'a>) -> u64 {
+        hash_wide_const::<NUM_BITS>(&self.state, cow_bytes_as_slice(value))
+    }
+}
+
+impl WideHasher<&[u8]> for XXH3Hasher<&[u8]> {
+    fn hash_wide<const NUM_BITS: u32>(&self, value: &&[u8]) -> u64 {
+        hash_wide::<NUM_BITS>(&self.state, value)
+    }
+}
+
+impl WideHasher<String> for XXH3Hasher<String> {
+    fn hash_wide<const NUM_BITS: u32>(&self, value: &String) -> u64 {
+        hash_wide::<NUM_BITS>(&self.state, value.as_bytes())
+    }
+}
+
+impl<'a> WideHasher<&'a str> for XXH3Hasher<&'a str> {
+    fn hash_wide<const NUM_BITS: u32>(&self, value: &&str) -> u64 {
+        hash_wide::<NUM_BITS>(&self.state, value.as_bytes())
+    }
+}
+
+impl WideHasher<Vec<u8>> for XXH3Hasher<Vec<u8>> {
+    fn hash_wide<const NUM_BITS: u32>(&self, value: &Vec<u8>) -> u64 {
+        hash_wide::<NUM_BITS>(&self.state, value.as_slice())
+    }
+}
+
+impl WideHasher<Box<[u8]>> for XXH3Hasher<Box<[u8]>> {
+    fn hash_wide<const NUM_BITS: u32>(&self, value: &Box<[u8]>) -> u64 {
+        hash_wide::<NUM_BITS>(&self.state, value)
+    }
+}
+
+impl<'a> WideHasher<Cow<'a, str>> for XXH3Hasher<Cow<'a, str>> {
+    fn hash_wide<const NUM_BITS: u32>(&self, value: &Cow<'a, str>) -> u64 {
+        hash_wide::<NUM_BITS>(&self.state, cow_str_as_bytes(value))
+    }
+}
+
+impl<'a> WideHasher<Cow<'a, [u8]>> for XXH3Hasher<Cow<'a, [u8]>> {
+    fn hash_wide<const NUM_BITS: u32>(&self, value: &Cow<'a, [u8]>) -> u64 {
+        hash_wide::<NUM_BITS>(&self.state, cow_bytes_as_slice(value))
+    }
 }
 
 #[cfg(test)]
@@ -186,6 +451,13 @@ mod tests {
         .leak()
     });
 
+    generate_hasher_tests!(XXH3Hasher<String>, String, |rng| {
+        String::generate(
+            rng,
+            &<String as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+    });
+
     generate_hasher_tests!(XXH3Hasher<&[u8]>, &'static [u8], |rng| {
         String::generate(
             rng,
@@ -194,4 +466,87 @@ mod tests {
         .into_bytes()
         .leak()
     });
+
+    generate_hasher_tests!(XXH3Hasher<Vec<u8>>, Vec<u8>, |rng| {
+        Vec::<u8>::generate(
+            rng,
+            &<Vec<u8> as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+    });
+
+    generate_hasher_tests!(XXH3Hasher<Box<[u8]>>, Box<[u8]>, |rng| {
+        Vec::<u8>::generate(
+            rng,
+            &<Vec<u8> as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        )
+        .into_boxed_slice()
+    });
+
+    // The borrowed values hash like the owned ones - see
+    // `test_cow_borrowed_and_owned_hash_equally`.
+    generate_hasher_tests!(XXH3Hasher<Cow<str>>, Cow<'static, str>, |rng| {
+        Cow::Owned(String::generate(
+            rng,
+            &<String as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        ))
+    });
+
+    // The borrowed values hash like the owned ones - see
+    // `test_cow_borrowed_and_owned_hash_equally`.
+    generate_hasher_tests!(XXH3Hasher<Cow<[u8]>>, Cow<'static, [u8]>, |rng| {
+        Cow::Owned(Vec::<u8>::generate(
+            rng,
+            &<Vec<u8> as Generate<ChaCha20Rng>>::GenerateParams::default(),
+        ))
+    });
+
+    #[test]
+    fn test_cow_borrowed_and_owned_hash_equally() {
+        let hasher = XXH3Hasher::<Cow<str>>::from_seed(42, 1 << 16);
+        let str_hasher = XXH3Hasher::<&str>::from_state(*hasher.state());
+        for value in [
+            "",
+            "x",
+            "short",
+            "a string longer than the vector hashing threshold",
+        ] {
+            let borrowed = Cow::Borrowed(value);
+            let owned: Cow<str> = Cow::Owned(String::from(value));
+            assert_eq!(
+                hasher.hash(&borrowed),
+                hasher.hash(&owned),
+                "Value: {value}"
+            );
+            assert_eq!(
+                hasher.hash(&borrowed),
+                str_hasher.hash(&value),
+                "Value: {value}"
+            );
+            assert_eq!(
+                hasher.hash_const(&owned),
+                hasher.hash(&owned),
+                "Value: {value}"
+            );
+        }
+
+        let hasher = XXH3Hasher::<Cow<[u8]>>::from_seed(42, 1 << 16);
+        let borrowed: Cow<[u8]> = Cow::Borrowed(b"x");
+        let owned: Cow<[u8]> = Cow::Owned(b"x".to_vec());
+        assert_eq!(hasher.hash(&borrowed), hasher.hash(&owned));
+        assert_eq!(hasher.hash_const(&borrowed), hasher.hash_const(&owned));
+    }
+
+    #[test]
+    fn test_hash_wide_slice() {
+        let hasher = XXH3Hasher::<&[u8]>::from_seed(42, 1 << 16);
+        let mut max_hash = 0;
+        for i in 0..1000_u32 {
+            let value: &[u8] = &i.to_le_bytes();
+            let hash = hasher.hash_wide::<40>(&value);
+            assert!(hash < 1 << 40);
+            assert_eq!(hash, hasher.hash_wide_const::<40>(&value));
+            max_hash = max_hash.max(hash);
+        }
+        assert!(max_hash > u32::MAX as u64);
+    }
 }
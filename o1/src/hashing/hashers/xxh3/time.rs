@@ -0,0 +1,75 @@
+//! Implements [`Hasher`] for [`Duration`] using XXH3.
+//!
+//! The `(as_secs(), subsec_nanos())` pair is packed into a single `u128` - seconds in the higher
+//! bits and nanoseconds in the lowest 32 bits - and hashed by delegating to
+//! [`XXH3Hasher<u128>`].
+//!
+//! # Notes
+//!
+//! `SystemTime` is not hashable portably since its representation is platform-specific - convert
+//! it to a [`Duration`] since `UNIX_EPOCH` first with `SystemTime::duration_since()`.
+
+use super::bigint::BigIntState;
+use super::core::XXH3Hasher;
+use crate::hashing::common::num_buckets_for_bits;
+use core::time::Duration;
+use o1_core::Hasher;
+
+/// Packs the seconds and the nanoseconds of the duration into a single `u128`.
+#[inline]
+const fn to_bits(value: &Duration) -> u128 {
+    ((value.as_secs() as u128) << u32::BITS) | value.subsec_nanos() as u128
+}
+
+impl Hasher<Duration> for XXH3Hasher<Duration> {
+    type State = BigIntState<u128>;
+
+    fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+        BigIntState::from_seed(seed, num_buckets)
+    }
+    fn from_seed(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state(seed, num_buckets);
+        Self { state }
+    }
+    fn from_state(state: Self::State) -> Self {
+        Self { state }
+    }
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+    fn num_buckets(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    fn hash(&self, value: &Duration) -> u32 {
+        XXH3Hasher::<u128>::from_state(self.state).hash(&to_bits(value))
+    }
+}
+
+impl XXH3Hasher<Duration> {
+    pub const fn make_state_const(seed: u64, num_buckets: u32) -> BigIntState<u128> {
+        BigIntState::from_seed_const(seed, num_buckets)
+    }
+    pub const fn from_seed_const(seed: u64, num_buckets: u32) -> Self {
+        let state = Self::make_state_const(seed, num_buckets);
+        Self { state }
+    }
+    pub const fn from_state_const(state: <Self as Hasher<Duration>>::State) -> Self {
+        Self { state }
+    }
+    pub const fn num_buckets_const(&self) -> u32 {
+        num_buckets_for_bits(self.state.num_bits)
+    }
+    pub const fn hash_const(&self, value: &Duration) -> u32 {
+        XXH3Hasher::<u128>::from_state_const(self.state).hash_const(&to_bits(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use o1_test::generate_hasher_tests;
+
+    generate_hasher_tests!(XXH3Hasher<Duration>, Duration, |rng: &mut ChaCha20Rng| {
+        Duration::new(rng.random::<u64>(), rng.random_range(0..1_000_000_000))
+    });
+}
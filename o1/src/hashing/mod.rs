@@ -1,6 +1,11 @@
-pub(crate) mod common;
+// Public only to be accessible from `new_fks_map_seeded!`.
+#[doc(hidden)]
+pub mod common;
 mod flawed;
 pub mod hashers;
 mod mod_prime;
 pub mod multiply_shift;
+pub mod murmur3;
 pub mod polynomial;
+pub mod tabulation;
+pub mod wyhash;
@@ -10,7 +10,11 @@
 //! [Thorup (2015)]: https://doi.org/10.48550/arXiv.1504.06804
 
 use crate::hashing::common::extract_bits_64;
-use std::ptr::copy_nonoverlapping;
+use alloc::vec::Vec;
+use core::ptr::copy_nonoverlapping;
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
 
 // TODO: Consider implementing the weakly-universal version of multiply-shift that returns u64.
 // TODO: Generally in the future 64-bit versions will probably be needed too.
@@ -104,6 +108,14 @@ pub const fn pair_multiply_shift_u128(value: u128, num_bits: u32, seed: &[u64; 5
 /// # Guarantees
 ///
 /// - Strong universality.
+///
+/// # Notes
+///
+/// - On `x86_64` uses AVX2 when it is available (detected at runtime with the `std` feature,
+///   at compile time otherwise). The hash values are identical to
+///   [`pair_multiply_shift_vector_u64_const`].
+/// - Other architectures, including `aarch64`, use the scalar version - NEON lacks a 64-bit lane
+///   multiplication as well, while the scalar 64-bit one is native there.
 #[inline]
 pub fn pair_multiply_shift_vector_u64(
     value: &[u64],
@@ -117,7 +129,22 @@ pub fn pair_multiply_shift_vector_u64(
         r#""value_seed" must be twice as long as the input "value""#,
     );
 
-    let mut sum = seed; // Initializing the sum with the seed value.
+    #[cfg(target_arch = "x86_64")]
+    if value.len() >= 4 && avx2::is_available() {
+        // SAFETY: AVX2 availability has just been checked.
+        let sum = seed.wrapping_add(unsafe { avx2::sum(value, value_seed) });
+        return extract_bits_64::<{ u64::BITS }>(sum, num_bits);
+    }
+
+    let sum = seed.wrapping_add(scalar_sum(value, value_seed));
+
+    extract_bits_64::<{ u64::BITS }>(sum, num_bits)
+}
+
+/// Computes the sum of the products of [`pair_multiply_shift_vector_u64`] one value at a time.
+#[inline]
+fn scalar_sum(value: &[u64], value_seed: &[u64]) -> u64 {
+    let mut sum: u64 = 0;
 
     for (i, &v) in value.iter().enumerate() {
         let s = &value_seed[i * 2..i * 2 + 2];
@@ -129,7 +156,7 @@ pub fn pair_multiply_shift_vector_u64(
         sum = sum.wrapping_add(s[0].wrapping_add(high).wrapping_mul(s[1].wrapping_add(low)));
     }
 
-    extract_bits_64::<{ u64::BITS }>(sum, num_bits)
+    sum
 }
 
 /// Hashes a vector of 64-bit unsigned integers to a 32-bit hash value.
@@ -236,19 +263,55 @@ pub fn pair_multiply_shift_vector_u8(
 
             pair_multiply_shift(value, num_bits, &seed_arr)
         }
-        _ => {
-            let c = value.len();
-            let d = (c + 7) >> 3;
-
-            // TODO: This could be optimized by using a pre-allocated buffer.
-            let mut x = vec![0_u64; d];
-            let x_bytes =
-                unsafe { std::slice::from_raw_parts_mut(x.as_mut_ptr() as *mut u8, d * 8) };
-            x_bytes[..c].copy_from_slice(value);
+        _ => pair_multiply_shift_vector_u8_with_scratch(
+            value,
+            num_bits,
+            seed,
+            value_seed,
+            &mut Vec::new(),
+        ),
+    }
+}
 
-            pair_multiply_shift_vector_u64(x.as_slice(), num_bits, seed, value_seed)
-        }
+/// Hashes a string (a vector of bytes) to a 32-bit hash value reusing the given `scratch`
+/// buffer instead of allocating one.
+///
+/// Equivalent to [`pair_multiply_shift_vector_u8`]. Inputs longer than 8 bytes are packed into
+/// `scratch`, which is grown only if it's shorter than `value.len().div_ceil(8)`, so reusing it
+/// across calls avoids allocations.
+///
+/// # Parameters
+///
+/// - `value`: The input vector with length up to `d`.
+/// - `num_bits`: Number of bits in the output hash. Hash range would be equal to `2 ** num_bits`.
+/// - `seed`: Random seed (constant part).
+/// - `value_seed`: Random seed (variable part dependent on input length). Must have length equal to `value.len().div_ceil(4)`.
+/// - `scratch`: The buffer the input is packed into. Its contents are overwritten.
+///
+/// # Guarantees
+///
+/// - Strong universality.
+#[inline]
+pub fn pair_multiply_shift_vector_u8_with_scratch(
+    value: &[u8],
+    num_bits: u32,
+    seed: u64,
+    value_seed: &[u64],
+    scratch: &mut Vec<u64>,
+) -> u32 {
+    let c = value.len();
+    if c <= 8 {
+        return pair_multiply_shift_vector_u8(value, num_bits, seed, value_seed);
     }
+    let d = (c + 7) >> 3;
+
+    scratch.clear();
+    scratch.resize(d, 0);
+    let x_bytes =
+        unsafe { core::slice::from_raw_parts_mut(scratch.as_mut_ptr() as *mut u8, d * 8) };
+    x_bytes[..c].copy_from_slice(value);
+
+    pair_multiply_shift_vector_u64(scratch.as_slice(), num_bits, seed, value_seed)
 }
 
 /// Hashes a string (a vector of bytes) to a 32-bit hash value.
@@ -505,6 +568,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_universality_test_builder() {
+        let test = UniversalityTest::new()
+            .num_buckets(4)
+            .num_trials(200)
+            .alpha(0.001)
+            .max_mi(0.1);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let result = test.run::<ChaCha20Rng, u32>(&mut rng, &|rng, num_buckets| {
+            let mut seed = [0_u64; 2];
+            seed[0] = rng.random_range(1..=u64::MAX);
+            seed[1] = rng.random_range(0..=u64::MAX);
+
+            let num_bits = num_bits_for_buckets(num_buckets as u32);
+            (
+                Box::new(move |value: &u32| multiply_shift(*value, num_bits, &seed) as usize),
+                num_buckets_for_bits(num_bits) as usize,
+            )
+        });
+        assert!(result.outcome, "{result:?}");
+
+        // Doesn't depend on the sampled seed at all, so it can't be universal.
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let result = test.run::<ChaCha20Rng, u32>(&mut rng, &|_, num_buckets| {
+            (
+                Box::new(move |value: &u32| *value as usize % num_buckets),
+                num_buckets,
+            )
+        });
+        assert!(!result.outcome, "{result:?}");
+    }
+
     #[test]
     #[cfg_attr(not(feature = "_slow-tests"), ignore)]
     fn test_pair_multiply_shift_strong_universality_guarantee() {
@@ -603,6 +699,55 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(not(feature = "_slow-tests"), ignore)]
+    fn test_multiply_shift_avalanche() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        avalanche::<ChaCha20Rng, u32>(
+            &mut rng,
+            &|rng, num_buckets| {
+                let mut seed = [0_u64; 2];
+                seed[0] = rng.random_range(1..=u64::MAX);
+                seed[1] = rng.random_range(0..=u64::MAX);
+
+                let num_bits = num_bits_for_buckets(num_buckets as u32);
+                (
+                    Box::new(move |value: &u32| multiply_shift(*value, num_bits, &seed) as usize),
+                    num_buckets_for_bits(num_bits) as usize,
+                )
+            },
+            1 << 16,
+            10000,
+            32,
+            0.02,
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "_slow-tests"), ignore)]
+    fn test_pair_multiply_shift_avalanche() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        avalanche::<ChaCha20Rng, u64>(
+            &mut rng,
+            &|rng, num_buckets| {
+                let seed: [u64; 3] = rng.random();
+                let num_bits = num_bits_for_buckets(num_buckets as u32);
+                (
+                    Box::new(move |value: &u64| {
+                        pair_multiply_shift(*value, num_bits, &seed) as usize
+                    }),
+                    num_buckets_for_bits(num_bits) as usize,
+                )
+            },
+            1 << 16,
+            10000,
+            32,
+            0.02,
+        );
+    }
+
     #[test]
     fn test_pair_multiply_shift_vector_u64_const_equivalence() {
         let mut rng = ChaCha20Rng::from_os_rng();
@@ -0,0 +1,107 @@
+//! AVX2 acceleration of [`pair_multiply_shift_vector_u64`](super::pair_multiply_shift_vector_u64).
+//!
+//! AVX2 lacks a 64-bit lane multiplication, so the products modulo `2 ** 64` are assembled from
+//! three 32-bit multiplications: `x * y = lo(x) * lo(y) + ((hi(x) * lo(y) + lo(x) * hi(y)) << 32)`.
+//! Since the wrapping additions are commutative, the result is bit-identical to the scalar
+//! version regardless of the order in which the products are summed.
+use core::arch::x86_64::*;
+
+/// Number of the input values processed by a single iteration.
+const LANES: usize = 4;
+
+/// Checks whether AVX2 is available on the current CPU.
+#[inline]
+pub(super) fn is_available() -> bool {
+    #[cfg(any(feature = "std", test))]
+    {
+        std::is_x86_feature_detected!("avx2")
+    }
+    #[cfg(not(any(feature = "std", test)))]
+    {
+        cfg!(target_feature = "avx2")
+    }
+}
+
+/// Multiplies 64-bit lanes of `x` and `y` modulo `2 ** 64`.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn mul_epi64(x: __m256i, y: __m256i) -> __m256i {
+    let low = _mm256_mul_epu32(x, y);
+    let cross = _mm256_add_epi64(
+        _mm256_mul_epu32(_mm256_srli_epi64::<32>(x), y),
+        _mm256_mul_epu32(x, _mm256_srli_epi64::<32>(y)),
+    );
+    _mm256_add_epi64(low, _mm256_slli_epi64::<32>(cross))
+}
+
+/// Computes the wrapping sum of `(value_seed[i * 2] + (v >> 32)) * (value_seed[i * 2 + 1] + v)`
+/// over all the values `v` of `value`.
+///
+/// # Safety
+///
+/// AVX2 must be available - see [`is_available`].
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn sum(value: &[u64], value_seed: &[u64]) -> u64 {
+    let mut acc = _mm256_setzero_si256();
+
+    let chunks = value.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    let seed_chunks = value_seed.chunks_exact(LANES * 2);
+
+    for (chunk, seed_chunk) in chunks.zip(seed_chunks) {
+        // SAFETY: `chunks_exact()` guarantees that there are 4 values and 8 seed values.
+        let (v, seed_first, seed_second) = unsafe {
+            (
+                _mm256_loadu_si256(chunk.as_ptr() as *const __m256i),
+                _mm256_loadu_si256(seed_chunk.as_ptr() as *const __m256i),
+                _mm256_loadu_si256(seed_chunk.as_ptr().add(LANES) as *const __m256i),
+            )
+        };
+        // Unpacking operates within 128-bit halves, so the seeds end up in the order of
+        // the values 0, 2, 1, 3 - the values are permuted to match.
+        let high_seed = _mm256_unpacklo_epi64(seed_first, seed_second);
+        let low_seed = _mm256_unpackhi_epi64(seed_first, seed_second);
+        let v = _mm256_permute4x64_epi64::<0b11_01_10_00>(v);
+
+        let x = _mm256_add_epi64(high_seed, _mm256_srli_epi64::<32>(v));
+        let y = _mm256_add_epi64(low_seed, v);
+        acc = _mm256_add_epi64(acc, mul_epi64(x, y));
+    }
+
+    let mut lanes = [0_u64; LANES];
+    // SAFETY: `lanes` is exactly 256 bits long.
+    unsafe { _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, acc) };
+    let sum = lanes
+        .iter()
+        .fold(0_u64, |acc, &lane| acc.wrapping_add(lane));
+
+    let offset = value.len() - remainder.len();
+    sum.wrapping_add(super::scalar_sum(remainder, &value_seed[offset * 2..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_sum_equivalent_to_scalar() {
+        if !is_available() {
+            return;
+        }
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        for _ in 0..1000 {
+            let len = rng.random_range(0..=300);
+            let value: Vec<u64> = (0..len).map(|_| rng.random()).collect();
+            let value_seed: Vec<u64> = (0..len * 2).map(|_| rng.random()).collect();
+
+            assert_eq!(
+                unsafe { sum(&value, &value_seed) },
+                super::super::scalar_sum(&value, &value_seed),
+                "Length: {len}"
+            );
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Implementation of the x86 32-bit variant of [MurmurHash3] by Austin Appleby.
+//!
+//! The implementation is bit-for-bit compatible with the reference `MurmurHash3_x86_32()`, so
+//! the produced hash values can be cross-checked against other tools using the same function.
+//!
+//! [MurmurHash3]: https://github.com/aappleby/smhasher/blob/master/src/MurmurHash3.cpp
+
+const C1: u32 = 0xcc9e_2d51;
+const C2: u32 = 0x1b87_3593;
+
+#[inline]
+const fn mix_k1(k1: u32) -> u32 {
+    k1.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2)
+}
+
+#[inline]
+const fn mix_h1(h1: u32, k1: u32) -> u32 {
+    (h1 ^ mix_k1(k1))
+        .rotate_left(13)
+        .wrapping_mul(5)
+        .wrapping_add(0xe654_6b64)
+}
+
+#[inline]
+const fn fmix32(mut h: u32) -> u32 {
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85eb_ca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2_ae35);
+    h ^= h >> 16;
+    h
+}
+
+/// Incremental MurmurHash3 x86_32 for inputs that arrive in pieces.
+///
+/// Feeding the input through several calls of [`Murmur3x86_32::write`] produces the same hash
+/// value as hashing the concatenated input at once with [`murmur3_32`].
+///
+/// All the methods are `const`.
+#[derive(Debug, Clone, Copy)]
+pub struct Murmur3x86_32 {
+    h1: u32,
+    tail: u32,
+    tail_len: u32,
+    len: u32,
+}
+
+impl Murmur3x86_32 {
+    /// Starts hashing with the given seed.
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            h1: seed,
+            tail: 0,
+            tail_len: 0,
+            len: 0,
+        }
+    }
+
+    /// Appends `bytes` to the hashed input.
+    #[inline]
+    pub const fn write(mut self, bytes: &[u8]) -> Self {
+        let mut i = 0;
+        // Complete the partial block left over from the previous write.
+        while self.tail_len != 0 && i < bytes.len() {
+            self = self.push_byte(bytes[i]);
+            i += 1;
+        }
+        while i + 4 <= bytes.len() {
+            let k1 = u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+            self.h1 = mix_h1(self.h1, k1);
+            i += 4;
+        }
+        while i < bytes.len() {
+            self = self.push_byte(bytes[i]);
+            i += 1;
+        }
+        self.len = self.len.wrapping_add(bytes.len() as u32);
+        self
+    }
+
+    /// Finishes hashing and returns the hash value.
+    #[inline]
+    pub const fn finish(self) -> u32 {
+        let mut h1 = self.h1;
+        if self.tail_len != 0 {
+            h1 ^= mix_k1(self.tail);
+        }
+        fmix32(h1 ^ self.len)
+    }
+
+    #[inline]
+    const fn push_byte(mut self, byte: u8) -> Self {
+        self.tail |= (byte as u32) << (8 * self.tail_len);
+        self.tail_len += 1;
+        if self.tail_len == 4 {
+            self.h1 = mix_h1(self.h1, self.tail);
+            self.tail = 0;
+            self.tail_len = 0;
+        }
+        self
+    }
+}
+
+/// Hashes a byte string with MurmurHash3 x86_32.
+///
+/// Can be evaluated at compile time.
+///
+/// # Parameters
+///
+/// - `bytes`: The input value.
+/// - `seed`: The seed of the hash function.
+#[inline]
+pub const fn murmur3_32(bytes: &[u8], seed: u32) -> u32 {
+    Murmur3x86_32::new(seed).write(bytes).finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference values produced by `MurmurHash3_x86_32()` from SMHasher.
+    const TEST_VECTORS: &[(&[u8], u32, u32)] = &[
+        (b"", 0, 0x0000_0000),
+        (b"", 1, 0x514e_28b7),
+        (b"", 0xffff_ffff, 0x81f1_6f39),
+        (b"\0\0\0\0", 0, 0x2362_f9de),
+        (b"\x21\x43\x65\x87", 0, 0xf55b_516b),
+        (b"\x21\x43\x65\x87", 0x5082_edee, 0x2362_f9de),
+        (b"\x21\x43\x65", 0, 0x7e4a_8634),
+        (b"\x21\x43", 0, 0xa0f7_b07a),
+        (b"\x21", 0, 0x7266_1cf4),
+        (b"aaaa", 0x9747_b28c, 0x5a97_808a),
+        (b"Hello, world!", 0x9747_b28c, 0x2488_4cba),
+        (
+            b"The quick brown fox jumps over the lazy dog",
+            0x9747_b28c,
+            0x2fa8_26cd,
+        ),
+    ];
+
+    #[test]
+    fn test_murmur3_32_test_vectors() {
+        for &(input, seed, expected) in TEST_VECTORS {
+            assert_eq!(
+                murmur3_32(input, seed),
+                expected,
+                "Input: {input:?}, seed: {seed:#x}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_murmur3_32_const() {
+        const HASH: u32 = murmur3_32(b"Hello, world!", 0x9747_b28c);
+        assert_eq!(HASH, 0x2488_4cba);
+    }
+
+    #[test]
+    fn test_murmur3_x86_32_incremental() {
+        let input = b"The quick brown fox jumps over the lazy dog";
+        let expected = murmur3_32(input, 42);
+        for split_a in 0..input.len() {
+            for split_b in split_a..input.len() {
+                let hash = Murmur3x86_32::new(42)
+                    .write(&input[..split_a])
+                    .write(&input[split_a..split_b])
+                    .write(&input[split_b..])
+                    .finish();
+                assert_eq!(hash, expected, "Splits: {split_a}, {split_b}");
+            }
+        }
+    }
+}
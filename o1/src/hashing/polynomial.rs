@@ -12,7 +12,8 @@ use crate::hashing::common::{extract_bits_128, extract_bits_64};
 use crate::hashing::multiply_shift::pair_multiply_shift_vector_u64;
 use crate::hashing::multiply_shift::pair_multiply_shift_vector_u64_const;
 use crate::utils::bit_hacks::mod_mersenne_prime;
-use std::ptr::copy_nonoverlapping;
+use core::ptr::copy_nonoverlapping;
+use rand::Rng;
 
 /// The type for the underlying seed value for [`PolynomialSeed`].
 pub type PolynomialSeedValue = [u64; 1 + 1 + 64 + 1 + 64 + 1];
@@ -60,6 +61,50 @@ impl PolynomialSeed {
         }
         PolynomialSeed(seed)
     }
+
+    /// Generates a random seed valid for [`polynomial`].
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from_rng_generic::<DEFAULT_P_E, R>(rng)
+    }
+
+    /// Generates a random seed valid for [`polynomial_generic`] with the Mersenne prime
+    /// `2 ** P_E - 1`.
+    ///
+    /// The first two seed values are drawn from `[1, 2 ** P_E - 1)` and `[0, 2 ** P_E - 1)`
+    /// respectively (capped at `u64::MAX`), the rest are the multiply-shift seeds hashing the
+    /// 256-byte chunks and keep all of their bits.
+    pub fn from_rng_generic<const P_E: u32, R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let max = max_coefficient::<P_E>();
+        let mut seed = [0_u64; 132];
+        seed[0] = rng.random_range(1..=max);
+        seed[1] = rng.random_range(0..=max);
+        seed[2..].fill_with(|| rng.random());
+        PolynomialSeed(seed)
+    }
+
+    /// Checks whether the seed satisfies the requirements of [`polynomial`].
+    pub const fn validate(&self) -> bool {
+        self.validate_generic::<DEFAULT_P_E>()
+    }
+
+    /// Checks whether the seed satisfies the requirements of [`polynomial_generic`] with the
+    /// Mersenne prime `2 ** P_E - 1` - the first seed value must be within `[1, 2 ** P_E - 1)`
+    /// and the second one within `[0, 2 ** P_E - 1)`.
+    pub const fn validate_generic<const P_E: u32>(&self) -> bool {
+        let max = max_coefficient::<P_E>();
+        self.0[0] > 0 && self.0[0] <= max && self.0[1] <= max
+    }
+}
+
+/// The largest value of the first two seed values for the Mersenne prime `2 ** P_E - 1` - the
+/// prime minus one, capped at `u64::MAX`.
+const fn max_coefficient<const P_E: u32>() -> u64 {
+    let max = (1_u128 << P_E) - 2;
+    if max > u64::MAX as u128 {
+        u64::MAX
+    } else {
+        max as u64
+    }
 }
 
 impl From<&[u64]> for PolynomialSeed {
@@ -76,17 +121,55 @@ impl Default for PolynomialSeed {
     }
 }
 
-/// Hashes a 32-bit unsigned integer using the multiply-shift hashing scheme.
+/// Exponent of the default Mersenne prime `2 ** 89 - 1` used by [`polynomial`] and
+/// [`polynomial_const`].
+pub const DEFAULT_P_E: u32 = 89;
+
+/// Calculates the number of bits in each of the two halves of a chunk hash.
+///
+/// Chunk hashes are the coefficients of the polynomial, so they must be smaller than the
+/// Mersenne prime `2 ** p_e - 1` - hence they are limited to `p_e - 1` bits (but no more than 64).
+#[inline]
+const fn chunk_hash_half_bits(p_e: u32) -> u32 {
+    let chunk_hash_bits = if p_e - 1 < 64 { p_e - 1 } else { 64 };
+    chunk_hash_bits >> 1
+}
+
+/// Hashes a byte-string using the polynomial hashing scheme with the default Mersenne prime
+/// `2 ** 89 - 1`.
+///
+/// See [`polynomial_generic`] for the details.
 ///
 /// # Parameters
 ///
 /// - `value`: The input bytes.
 /// - `num_bits`: Number of bits in the output hash. Hash range would be equal to `2 ** num_bits`.
-/// - `p`: Large Mersenne prime. `2 ** 89 − 1` could be a practical value.
-/// - `p_e`: Exponent of the Mersenne prime.
 /// - `seed`: Random seed values. It should have length of `1 + 1 + 64 + 1 + 64 + 1`,
-///           so `132` in total. All the seed values should be less than 2 ** 89 - 1. And the first
-///           seed value should be greater than `0`.
+///           so `132` in total. The first two seed values should be less than `2 ** 89 - 1`.
+///           And the first seed value should be greater than `0`.
+///
+/// # Guarantees
+///
+/// - Strongly universal.
+#[inline]
+pub fn polynomial(value: &[u8], num_bits: u32, seed: &PolynomialSeed) -> u32 {
+    polynomial_generic::<DEFAULT_P_E>(value, num_bits, seed)
+}
+
+/// Hashes a byte-string using the polynomial hashing scheme over the Mersenne prime
+/// `2 ** P_E - 1`.
+///
+/// Smaller primes (for example `2 ** 61 - 1`) reduce the cost of the `u128` arithmetic.
+///
+/// # Parameters
+///
+/// - `value`: The input bytes.
+/// - `num_bits`: Number of bits in the output hash. Hash range would be equal to `2 ** num_bits`.
+///               Must not exceed `P_E`.
+/// - `seed`: Random seed values. It should have length of `1 + 1 + 64 + 1 + 64 + 1`,
+///           so `132` in total. The first two seed values should be less than `2 ** P_E - 1`.
+///           And the first seed value should be greater than `0`.
+/// - `P_E`: Exponent of the Mersenne prime. `2 ** P_E - 1` must be prime.
 ///
 /// # Guarantees
 ///
@@ -94,30 +177,38 @@ impl Default for PolynomialSeed {
 ///
 /// # Notes
 ///
-/// - The implementation splits the input into 256-bit chunks and then applies polynomial hashing
-///   to hashes of the chunks.
+/// - The implementation splits the input into 256-byte chunks and then applies polynomial hashing
+///   to hashes of the chunks. Chunk hashes are limited to `min(P_E - 1, 64)` bits.
 #[inline]
-pub fn polynomial(value: &[u8], num_bits: u32, seed: &PolynomialSeed) -> u32 {
-    const P_E: u32 = 89;
-    const P: u128 = (1_u128 << P_E) - 1;
+pub fn polynomial_generic<const P_E: u32>(
+    value: &[u8],
+    num_bits: u32,
+    seed: &PolynomialSeed,
+) -> u32 {
+    let p: u128 = (1_u128 << P_E) - 1;
 
-    let seed = seed.0;
+    let seed_value = &seed.0;
 
-    let a = seed[0];
-    let b = seed[1];
-    let h1_seed = &seed[2..2 + (64 + 1)];
-    let h2_seed = &seed[2 + (64 + 1)..(2 + (64 + 1)) + 64 + 1];
+    let a = seed_value[0];
+    let b = seed_value[1];
+    let h1_seed = &seed_value[2..2 + (64 + 1)];
+    let h2_seed = &seed_value[2 + (64 + 1)..(2 + (64 + 1)) + 64 + 1];
 
+    debug_assert!(
+        P_E > 1 && P_E < 128,
+        r#""P_E" must be in the range [2, 127]"#
+    );
     debug_assert!(num_bits <= 32, r#""num_bits" must be <= 32"#);
+    debug_assert!(num_bits <= P_E, r#""num_bits" must be <= "P_E""#);
     debug_assert!(
-        a > 0 && (a as u128) < P,
+        a > 0 && (a as u128) < p,
         r#""seed[0]" must be in the range [1, {}-1]"#,
-        P,
+        p,
     );
     debug_assert!(
-        seed[1..].iter().all(|&x| (x as u128) < P),
-        r#""seed[1..]" must be in the range [0, {}-1]"#,
-        P,
+        (b as u128) < p,
+        r#""seed[1]" must be in the range [0, {}-1]"#,
+        p,
     );
 
     debug_assert_eq!(
@@ -137,12 +228,49 @@ pub fn polynomial(value: &[u8], num_bits: u32, seed: &PolynomialSeed) -> u32 {
         return extract_bits_64::<64>(b, num_bits);
     }
 
+    extract_bits_128::<P_E>(polynomial_digest_generic::<P_E>(value, seed), num_bits)
+}
+
+/// Computes the value of the polynomial of [`polynomial`] over the default Mersenne prime
+/// `2 ** 89 - 1` without reducing it to the hash value.
+///
+/// See [`polynomial_digest_generic`] for the details.
+#[inline]
+pub fn polynomial_digest(value: &[u8], seed: &PolynomialSeed) -> u128 {
+    polynomial_digest_generic::<DEFAULT_P_E>(value, seed)
+}
+
+/// Computes the value of the polynomial of [`polynomial_generic`] over the Mersenne prime
+/// `2 ** P_E - 1` without reducing it to the hash value.
+///
+/// The digest is less than `2 ** P_E - 1`. [`polynomial_generic`] takes the highest bits of the
+/// digest of a non-empty value, while a digest computed once can be reduced with several
+/// independent hash functions instead - e.g. with [`pair_multiply_shift_u128`].
+///
+/// Requirements for the seed are the same as for [`polynomial_generic`]. The digest of an empty
+/// value is the second seed value.
+///
+/// [`pair_multiply_shift_u128`]: crate::hashing::multiply_shift::pair_multiply_shift_u128
+#[inline]
+pub fn polynomial_digest_generic<const P_E: u32>(value: &[u8], seed: &PolynomialSeed) -> u128 {
+    let seed = &seed.0;
+
+    let a = seed[0];
+    let b = seed[1];
+    let h1_seed = &seed[2..2 + (64 + 1)];
+    let h2_seed = &seed[2 + (64 + 1)..(2 + (64 + 1)) + 64 + 1];
+
+    if value.is_empty() {
+        return b as u128;
+    }
+
     let num_chunks = value.len() >> 8;
     let remainder_len = value.len() & 0xFF;
 
     let mut buffer = [0_u64; 32];
-    let buffer_bytes =
-        unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8) };
+    let buffer_bytes = unsafe {
+        core::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8)
+    };
 
     let mut hash_value = b as u128;
 
@@ -150,14 +278,14 @@ pub fn polynomial(value: &[u8], num_bits: u32, seed: &PolynomialSeed) -> u32 {
         #[allow(clippy::identity_op)]
         let chunk = &value[0 << 8..(0 + 1) << 8];
         buffer_bytes[..].copy_from_slice(chunk);
-        hash_value += hash_chunk(&buffer, h1_seed, h2_seed) as u128;
+        hash_value += hash_chunk::<P_E>(&buffer, h1_seed, h2_seed) as u128;
 
         for i in 1..num_chunks {
             let chunk = &value[i << 8..(i + 1) << 8];
             buffer_bytes[..].copy_from_slice(chunk);
-            let chunk_hash = hash_chunk(&buffer, h1_seed, h2_seed);
+            let chunk_hash = hash_chunk::<P_E>(&buffer, h1_seed, h2_seed);
 
-            hash_value = mod_mersenne_prime::<P_E, P>(
+            hash_value = mod_mersenne_prime::<P_E>(
                 hash_value
                     .wrapping_mul(a as u128)
                     .wrapping_add(chunk_hash as u128),
@@ -169,24 +297,141 @@ pub fn polynomial(value: &[u8], num_bits: u32, seed: &PolynomialSeed) -> u32 {
         let remainder_chunk = &value[value.len() - remainder_len..];
         buffer_bytes[..remainder_len].copy_from_slice(remainder_chunk);
         buffer_bytes[remainder_len..].fill(0);
-        let chunk_hash = hash_chunk(&buffer, h1_seed, h2_seed);
-        hash_value = mod_mersenne_prime::<P_E, P>(
+        let chunk_hash = hash_chunk::<P_E>(&buffer, h1_seed, h2_seed);
+        hash_value = mod_mersenne_prime::<P_E>(
             hash_value
                 .wrapping_mul(a as u128)
                 .wrapping_add(chunk_hash as u128),
         );
     }
 
-    hash_value = mod_mersenne_prime::<P_E, P>(hash_value.wrapping_mul(a as u128));
+    mod_mersenne_prime::<P_E>(hash_value.wrapping_mul(a as u128))
+}
+
+/// Incremental version of [`polynomial_generic`] for inputs that arrive in pieces.
+///
+/// Maintains the running Horner accumulator and a buffer with a partial 256-byte chunk across
+/// [`PolynomialHasher::update`] calls, so that feeding the input piece by piece yields the same
+/// result as hashing the concatenation at once.
+///
+/// # Examples
+///
+/// ```
+/// use o1::hashing::polynomial::{polynomial, PolynomialHasher, PolynomialSeed};
+///
+/// let seed = PolynomialSeed::default();
+///
+/// let mut hasher: PolynomialHasher = PolynomialHasher::new(&seed);
+/// hasher.update(b"Hello, ");
+/// hasher.update(b"world!");
+///
+/// assert_eq!(hasher.finish(16), polynomial(b"Hello, world!", 16, &seed));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolynomialHasher<const P_E: u32 = DEFAULT_P_E> {
+    seed: PolynomialSeed,
+    /// The running value of the polynomial.
+    hash_value: u128,
+    /// Buffer with the current partial chunk.
+    buffer: [u64; 32],
+    /// Number of bytes in the current partial chunk.
+    buffer_len: usize,
+    /// Number of complete chunks that have been hashed so far.
+    num_chunks: usize,
+}
+
+impl<const P_E: u32> PolynomialHasher<P_E> {
+    /// Creates a new hasher with the given `seed`.
+    ///
+    /// Requirements for the seed are the same as for [`polynomial_generic`].
+    pub fn new(seed: &PolynomialSeed) -> Self {
+        Self {
+            seed: *seed,
+            hash_value: seed.0[1] as u128,
+            buffer: [0; 32],
+            buffer_len: 0,
+            num_chunks: 0,
+        }
+    }
+
+    /// Feeds the next piece of the input into the hasher.
+    pub fn update(&mut self, mut value: &[u8]) {
+        while !value.is_empty() {
+            let buffer_bytes = unsafe {
+                core::slice::from_raw_parts_mut(self.buffer.as_mut_ptr() as *mut u8, 256)
+            };
+            let num_bytes = value.len().min(256 - self.buffer_len);
+            buffer_bytes[self.buffer_len..self.buffer_len + num_bytes]
+                .copy_from_slice(&value[..num_bytes]);
+            self.buffer_len += num_bytes;
+            value = &value[num_bytes..];
+
+            if self.buffer_len == 256 {
+                let chunk_hash = self.hash_buffer();
+                // The first complete chunk is added to the initial value without multiplication -
+                // mirrors `polynomial_generic`.
+                self.hash_value = if self.num_chunks == 0 {
+                    self.hash_value + chunk_hash as u128
+                } else {
+                    self.accumulate(chunk_hash)
+                };
+                self.num_chunks += 1;
+                self.buffer_len = 0;
+            }
+        }
+    }
+
+    /// Finishes hashing and returns a hash of `num_bits` bits.
+    pub fn finish(mut self, num_bits: u32) -> u32 {
+        debug_assert!(num_bits <= 32, r#""num_bits" must be <= 32"#);
+
+        if self.num_chunks == 0 && self.buffer_len == 0 {
+            return extract_bits_64::<64>(self.seed.0[1], num_bits);
+        }
+
+        if self.buffer_len > 0 {
+            let buffer_bytes = unsafe {
+                core::slice::from_raw_parts_mut(self.buffer.as_mut_ptr() as *mut u8, 256)
+            };
+            buffer_bytes[self.buffer_len..].fill(0);
+            let chunk_hash = self.hash_buffer();
+            self.hash_value = self.accumulate(chunk_hash);
+        }
+
+        let hash_value =
+            mod_mersenne_prime::<P_E>(self.hash_value.wrapping_mul(self.seed.0[0] as u128));
+
+        extract_bits_128::<P_E>(hash_value, num_bits)
+    }
+
+    /// Hashes the content of the buffer as a chunk.
+    fn hash_buffer(&self) -> u64 {
+        let h1_seed = &self.seed.0[2..2 + (64 + 1)];
+        let h2_seed = &self.seed.0[2 + (64 + 1)..(2 + (64 + 1)) + 64 + 1];
+        hash_chunk::<P_E>(&self.buffer, h1_seed, h2_seed)
+    }
 
-    extract_bits_128::<{ P_E }>(hash_value, num_bits)
+    /// Performs a single step of Horner's rule with the given chunk hash.
+    fn accumulate(&self, chunk_hash: u64) -> u128 {
+        mod_mersenne_prime::<P_E>(
+            self.hash_value
+                .wrapping_mul(self.seed.0[0] as u128)
+                .wrapping_add(chunk_hash as u128),
+        )
+    }
 }
 
-/// Hashes a 256-long chunk into a 64-bit hash using concatenation of two 32-bit hashes.
-fn hash_chunk(chunk: &[u64], h1_seed: &[u64], h2_seed: &[u64]) -> u64 {
-    let chunk_hash_high = pair_multiply_shift_vector_u64(chunk, 32, h1_seed[0], &h1_seed[1..]);
-    let chunk_hash_low = pair_multiply_shift_vector_u64(chunk, 32, h2_seed[0], &h2_seed[1..]);
-    ((chunk_hash_high as u64) << 32) | (chunk_hash_low as u64)
+/// Hashes a 256-byte chunk into a hash of up to 64 bits using concatenation of two hashes
+/// produced by [`pair_multiply_shift_vector_u64`].
+///
+/// The width of the hash depends on `P_E` - see [`chunk_hash_half_bits`].
+fn hash_chunk<const P_E: u32>(chunk: &[u64], h1_seed: &[u64], h2_seed: &[u64]) -> u64 {
+    let half_bits = chunk_hash_half_bits(P_E);
+    let chunk_hash_high =
+        pair_multiply_shift_vector_u64(chunk, half_bits, h1_seed[0], &h1_seed[1..]);
+    let chunk_hash_low =
+        pair_multiply_shift_vector_u64(chunk, half_bits, h2_seed[0], &h2_seed[1..]);
+    ((chunk_hash_high as u64) << half_bits) | (chunk_hash_low as u64)
 }
 
 /// Const version of the polynomial hash function.
@@ -198,38 +443,64 @@ fn hash_chunk(chunk: &[u64], h1_seed: &[u64], h2_seed: &[u64]) -> u64 {
 /// - `value`: The input bytes.
 /// - `num_bits`: Number of bits in the output hash. Hash range would be equal to `2 ** num_bits`.
 /// - `seed`: Random seed values. It should have length of `1 + 1 + 64 + 1 + 64 + 1`,
-///           so `132` in total. All the seed values should be less than 2 ** 89 - 1. And the first
-///           seed value should be greater than `0`.
+///           so `132` in total. The first two seed values should be less than `2 ** 89 - 1`.
+///           And the first seed value should be greater than `0`.
 ///
 /// # Guarantees
 ///
 /// - Strongly universal.
 #[inline]
 pub const fn polynomial_const(value: &[u8], num_bits: u32, seed: &PolynomialSeed) -> u32 {
-    const P_E: u32 = 89;
-    const P: u128 = (1_u128 << P_E) - 1;
+    polynomial_generic_const::<DEFAULT_P_E>(value, num_bits, seed)
+}
+
+/// Const version of the polynomial hash function with a configurable Mersenne prime.
+///
+/// Compile-time equivalent of [`polynomial_generic`].
+///
+/// # Parameters
+///
+/// - `value`: The input bytes.
+/// - `num_bits`: Number of bits in the output hash. Hash range would be equal to `2 ** num_bits`.
+///               Must not exceed `P_E`.
+/// - `seed`: Random seed values. It should have length of `1 + 1 + 64 + 1 + 64 + 1`,
+///           so `132` in total. The first two seed values should be less than `2 ** P_E - 1`.
+///           And the first seed value should be greater than `0`.
+/// - `P_E`: Exponent of the Mersenne prime. `2 ** P_E - 1` must be prime.
+///
+/// # Guarantees
+///
+/// - Strongly universal.
+#[inline]
+pub const fn polynomial_generic_const<const P_E: u32>(
+    value: &[u8],
+    num_bits: u32,
+    seed: &PolynomialSeed,
+) -> u32 {
+    let p: u128 = (1_u128 << P_E) - 1;
 
     let seed = seed.0;
 
     let a = seed[0];
     let b = seed[1];
 
-    let h1_seed = unsafe { std::slice::from_raw_parts(seed.as_ptr().add(2), 65) };
-    let h2_seed = unsafe { std::slice::from_raw_parts(seed.as_ptr().add(2 + 65), 65) };
+    let h1_seed = unsafe { core::slice::from_raw_parts(seed.as_ptr().add(2), 65) };
+    let h2_seed = unsafe { core::slice::from_raw_parts(seed.as_ptr().add(2 + 65), 65) };
 
+    debug_assert!(
+        P_E > 1 && P_E < 128,
+        r#""P_E" must be in the range [2, 127]"#
+    );
     debug_assert!(num_bits <= 32, r#""num_bits" must be <= 32"#);
+    debug_assert!(num_bits <= P_E, r#""num_bits" must be <= "P_E""#);
     debug_assert!(
-        a > 0 && (a as u128) < P,
-        r#""seed[0]" must be in the range [1, 618970019642690137449562111-1]"#,
+        a > 0 && (a as u128) < p,
+        r#""seed[0]" must be in the range [1, 2 ** P_E - 1)"#,
+    );
+    debug_assert!(
+        (b as u128) < p,
+        r#""seed[1]" must be in the range [0, 2 ** P_E - 1)"#,
     );
-    let mut i = 1;
-    while i < seed.len() {
-        debug_assert!(
-            (seed[i] as u128) < P,
-            r#""seed[...]" must be in the range [0, 618970019642690137449562111-1]"#,
-        );
-        i += 1;
-    }
     debug_assert!(
         h1_seed.len() == 64 + 1,
         r#""seed[2..2 + (64 + 1)]" must have length 65"#,
@@ -261,7 +532,7 @@ pub const fn polynomial_const(value: &[u8], num_bits: u32, seed: &PolynomialSeed
             j += 1;
         }
 
-        hash_value += hash_chunk_const(&buffer, h1_seed, h2_seed) as u128;
+        hash_value += hash_chunk_const::<P_E>(&buffer, h1_seed, h2_seed) as u128;
 
         let mut i = 1;
         while i < num_chunks {
@@ -281,9 +552,9 @@ pub const fn polynomial_const(value: &[u8], num_bits: u32, seed: &PolynomialSeed
                 j += 1;
             }
 
-            let chunk_hash = hash_chunk_const(&buffer, h1_seed, h2_seed);
+            let chunk_hash = hash_chunk_const::<P_E>(&buffer, h1_seed, h2_seed);
 
-            hash_value = mod_mersenne_prime::<P_E, P>(
+            hash_value = mod_mersenne_prime::<P_E>(
                 hash_value
                     .wrapping_mul(a as u128)
                     .wrapping_add(chunk_hash as u128),
@@ -311,35 +582,37 @@ pub const fn polynomial_const(value: &[u8], num_bits: u32, seed: &PolynomialSeed
             j += 1;
         }
 
-        let chunk_hash = hash_chunk_const(&buffer, h1_seed, h2_seed);
-        hash_value = mod_mersenne_prime::<P_E, P>(
+        let chunk_hash = hash_chunk_const::<P_E>(&buffer, h1_seed, h2_seed);
+        hash_value = mod_mersenne_prime::<P_E>(
             hash_value
                 .wrapping_mul(a as u128)
                 .wrapping_add(chunk_hash as u128),
         );
     }
 
-    hash_value = mod_mersenne_prime::<P_E, P>(hash_value.wrapping_mul(a as u128));
+    hash_value = mod_mersenne_prime::<P_E>(hash_value.wrapping_mul(a as u128));
 
-    extract_bits_128::<{ P_E }>(hash_value, num_bits)
+    extract_bits_128::<P_E>(hash_value, num_bits)
 }
 
 /// Compile-time counterpart of [`hash_chunk`].
-const fn hash_chunk_const(chunk: &[u64], h1_seed: &[u64], h2_seed: &[u64]) -> u64 {
+const fn hash_chunk_const<const P_E: u32>(chunk: &[u64], h1_seed: &[u64], h2_seed: &[u64]) -> u64 {
+    let half_bits = chunk_hash_half_bits(P_E);
+
     // In const contexts, we can't use slice patterns like [1..], so we need to use raw pointers
     let h1_seed_value = h1_seed[0];
     let h1_seed_rest =
-        unsafe { std::slice::from_raw_parts(h1_seed.as_ptr().add(1), h1_seed.len() - 1) };
+        unsafe { core::slice::from_raw_parts(h1_seed.as_ptr().add(1), h1_seed.len() - 1) };
 
     let h2_seed_value = h2_seed[0];
     let h2_seed_rest =
-        unsafe { std::slice::from_raw_parts(h2_seed.as_ptr().add(1), h2_seed.len() - 1) };
+        unsafe { core::slice::from_raw_parts(h2_seed.as_ptr().add(1), h2_seed.len() - 1) };
 
     let chunk_hash_high =
-        pair_multiply_shift_vector_u64_const(chunk, 32, h1_seed_value, h1_seed_rest);
+        pair_multiply_shift_vector_u64_const(chunk, half_bits, h1_seed_value, h1_seed_rest);
     let chunk_hash_low =
-        pair_multiply_shift_vector_u64_const(chunk, 32, h2_seed_value, h2_seed_rest);
-    ((chunk_hash_high as u64) << 32) | (chunk_hash_low as u64)
+        pair_multiply_shift_vector_u64_const(chunk, half_bits, h2_seed_value, h2_seed_rest);
+    ((chunk_hash_high as u64) << half_bits) | (chunk_hash_low as u64)
 }
 
 #[cfg(test)]
@@ -374,6 +647,142 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_seed_from_rng_is_valid() {
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..10_000 {
+            assert!(PolynomialSeed::from_rng(&mut rng).validate());
+            let seed = PolynomialSeed::from_rng_generic::<31, _>(&mut rng);
+            assert!(seed.validate_generic::<31>());
+            assert!(seed.validate());
+        }
+        assert!(PolynomialSeed::default().validate());
+    }
+
+    #[test]
+    fn test_seed_validate_rejects_out_of_range() {
+        let p_31 = (1_u64 << 31) - 1;
+
+        let mut value = [0_u64; 132];
+        assert!(!PolynomialSeed::from(value).validate());
+
+        value[0] = p_31 - 1;
+        value[1] = p_31 - 1;
+        assert!(PolynomialSeed::from(value).validate_generic::<31>());
+
+        value[0] = p_31;
+        assert!(!PolynomialSeed::from(value).validate_generic::<31>());
+        assert!(PolynomialSeed::from(value).validate());
+
+        value[0] = 1;
+        value[1] = p_31;
+        assert!(!PolynomialSeed::from(value).validate_generic::<31>());
+    }
+
+    #[test]
+    fn test_polynomial_hasher_equivalence() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        for len in [0, 1, 255, 256, 257, 511, 512, 513, 1000, 4096] {
+            for _ in 0..99 {
+                let seed = PolynomialSeed::from(rng.random::<[u64; 132]>());
+                let value: Vec<u8> = (0..len).map(|_| rng.random()).collect();
+
+                let mut hasher = PolynomialHasher::<DEFAULT_P_E>::new(&seed);
+                let mut rest = value.as_slice();
+                while !rest.is_empty() {
+                    let piece_len = rng.random_range(0..=rest.len().min(600));
+                    hasher.update(&rest[..piece_len]);
+                    rest = &rest[piece_len..];
+                }
+
+                assert_eq!(
+                    hasher.finish(32),
+                    polynomial(&value, 32, &seed),
+                    "Length: {}",
+                    len,
+                );
+            }
+        }
+    }
+
+    /// Generates a valid seed for [`polynomial_generic`] with the Mersenne prime `2 ** 61 - 1`.
+    fn random_seed_61<R: Rng>(rng: &mut R) -> PolynomialSeed {
+        const P: u64 = (1 << 61) - 1;
+
+        let mut seed = [0_u64; 1 + 1 + 64 + 1 + 64 + 1];
+        seed.fill_with(|| rng.random());
+        seed[0] = rng.random_range(1..P);
+        seed[1] = rng.random_range(0..P);
+
+        PolynomialSeed::from(seed)
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "_slow-tests"), ignore)]
+    fn test_polynomial_generic_61_strong_universality_guarantee() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        strong_universality::<ChaCha20Rng, String>(
+            &mut rng,
+            &|rng, num_buckets| {
+                let seed = random_seed_61(rng);
+                let num_bits = num_bits_for_buckets(num_buckets as u32);
+                (
+                    Box::new(move |value: &String| {
+                        polynomial_generic::<61>(value.as_bytes(), num_bits, &seed) as usize
+                    }),
+                    num_buckets_for_bits(num_bits) as usize,
+                )
+            },
+            16,
+            15,
+            1000,
+            0.01,
+        );
+    }
+
+    #[test]
+    fn test_polynomial_generic_61_const_equivalence() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        for str_len in [0, 1, 8, 255, 256, 257, 1024] {
+            let non_const_family = |seed: u64, num_buckets: usize| {
+                let seed = random_seed_61(&mut ChaCha20Rng::seed_from_u64(seed));
+                let num_bits = num_bits_for_buckets(num_buckets as u32);
+                (
+                    Box::new(move |value: &String| {
+                        polynomial_generic::<61>(value.as_bytes(), num_bits, &seed) as usize
+                    }) as Box<dyn Fn(&String) -> usize>,
+                    num_buckets_for_bits(num_bits) as usize,
+                )
+            };
+
+            let const_family = |seed: u64, num_buckets: usize| {
+                let seed = random_seed_61(&mut ChaCha20Rng::seed_from_u64(seed));
+                let num_bits = num_bits_for_buckets(num_buckets as u32);
+                (
+                    Box::new(move |value: &String| {
+                        polynomial_generic_const::<61>(value.as_bytes(), num_bits, &seed) as usize
+                    }) as Box<dyn Fn(&String) -> usize>,
+                    num_buckets_for_bits(num_bits) as usize,
+                )
+            };
+
+            equivalence(
+                &mut rng,
+                &non_const_family,
+                &const_family,
+                &|rng: &mut ChaCha20Rng| {
+                    let bytes: Vec<u8> = (0..str_len).map(|_| rng.random::<u8>()).collect();
+                    String::from_utf8_lossy(&bytes).to_string()
+                },
+                1000,
+                99,
+            );
+        }
+    }
+
     #[test]
     fn test_polynomial_const_equivalence() {
         let mut rng = ChaCha20Rng::from_os_rng();
@@ -0,0 +1,102 @@
+//! Implementation of the simple tabulation hashing scheme from [Zobrist (1970)] and
+//! [Pătraşcu and Thorup (2012)].
+//!
+//! The key is split into characters (bytes), each character is mapped to a random value using
+//! its own precomputed table and the results are combined with XOR.
+//!
+//! [Zobrist (1970)]: https://research.cs.wisc.edu/techreports/1970/TR88.pdf
+//! [Pătraşcu and Thorup (2012)]: https://doi.org/10.1145/2220357.2220361
+
+/// Number of tables used to hash a 64-bit value - one per byte.
+pub const NUM_TABLES_U64: usize = 8;
+
+/// Precomputed random tables for hashing 64-bit values.
+pub type TablesU64 = [[u32; 256]; NUM_TABLES_U64];
+
+/// Hashes a 64-bit unsigned integer using the simple tabulation hashing scheme.
+///
+/// # Parameters
+///
+/// - `value`: The input value.
+/// - `tables`: Tables of random values, one per byte of the input.
+///
+/// # Guarantees
+///
+/// - 3-independence.
+#[inline]
+pub fn tabulation_u64(value: u64, tables: &TablesU64) -> u32 {
+    value
+        .to_le_bytes()
+        .iter()
+        .zip(tables.iter())
+        .fold(0, |hash, (byte, table)| hash ^ table[*byte as usize])
+}
+
+/// Hashes a 64-bit unsigned integer using the simple tabulation hashing scheme.
+///
+/// The same as [`tabulation_u64`] but can be evaluated at compile time.
+#[inline]
+pub const fn tabulation_u64_const(value: u64, tables: &TablesU64) -> u32 {
+    let bytes = value.to_le_bytes();
+    let mut hash = 0;
+    let mut i = 0;
+    while i < NUM_TABLES_U64 {
+        hash ^= tables[i][bytes[i] as usize];
+        i += 1;
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::common::{extract_bits_64, num_bits_for_buckets, num_buckets_for_bits};
+    use o1_test::*;
+    use rand::prelude::*;
+    use rand_chacha::ChaCha20Rng;
+
+    fn random_tables(rng: &mut ChaCha20Rng) -> Box<TablesU64> {
+        let mut tables = Box::new([[0_u32; 256]; NUM_TABLES_U64]);
+        tables.iter_mut().for_each(|table| rng.fill(table));
+        tables
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "_slow-tests"), ignore)]
+    fn test_tabulation_u64_strong_universality_guarantee() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+
+        strong_universality::<ChaCha20Rng, u64>(
+            &mut rng,
+            &|rng, num_buckets| {
+                let tables = random_tables(rng);
+                let num_bits = num_bits_for_buckets(num_buckets as u32);
+                (
+                    Box::new(move |value: &u64| {
+                        let hash = tabulation_u64(*value, &tables);
+                        extract_bits_64::<{ u32::BITS }>(hash as u64, num_bits) as usize
+                    }),
+                    num_buckets_for_bits(num_bits) as usize,
+                )
+            },
+            16,
+            15,
+            1000,
+            0.01,
+        );
+    }
+
+    #[test]
+    fn test_tabulation_u64_const_equivalence() {
+        let mut rng = ChaCha20Rng::from_os_rng();
+        let tables = random_tables(&mut rng);
+
+        for _ in 0..1000 {
+            let value = rng.random::<u64>();
+            assert_eq!(
+                tabulation_u64(value, &tables),
+                tabulation_u64_const(value, &tables)
+            );
+        }
+    }
+}
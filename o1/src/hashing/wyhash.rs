@@ -0,0 +1,176 @@
+//! Implementation of [wyhash] (final4) by Wang Yi.
+//!
+//! The implementation is bit-for-bit compatible with the reference `wyhash()` with the default
+//! secret, so the produced hash values can be cross-checked against other tools using the same
+//! function.
+//!
+//! [wyhash]: https://github.com/wangyi-fudan/wyhash
+
+/// The default secret of wyhash.
+pub const DEFAULT_SECRET: [u64; 4] = [
+    0xa076_1d64_78bd_642f,
+    0xe703_7ed1_a0b4_28db,
+    0x8ebc_6af0_9c88_c6e3,
+    0x5899_65cc_7537_4cc3,
+];
+
+/// Multiplies `a` by `b` and returns the low and the high halves of the 128-bit product.
+#[inline]
+const fn mum(a: u64, b: u64) -> (u64, u64) {
+    let product = a as u128 * b as u128;
+    (product as u64, (product >> 64) as u64)
+}
+
+#[inline]
+const fn mix(a: u64, b: u64) -> u64 {
+    let (lo, hi) = mum(a, b);
+    lo ^ hi
+}
+
+#[inline]
+const fn read_8(bytes: &[u8], i: usize) -> u64 {
+    u64::from_le_bytes([
+        bytes[i],
+        bytes[i + 1],
+        bytes[i + 2],
+        bytes[i + 3],
+        bytes[i + 4],
+        bytes[i + 5],
+        bytes[i + 6],
+        bytes[i + 7],
+    ])
+}
+
+#[inline]
+const fn read_4(bytes: &[u8], i: usize) -> u64 {
+    u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]) as u64
+}
+
+/// Reads the first, the middle and the last byte of a 1-3 bytes long input.
+#[inline]
+const fn read_3(bytes: &[u8]) -> u64 {
+    let len = bytes.len();
+    ((bytes[0] as u64) << 16) | ((bytes[len >> 1] as u64) << 8) | bytes[len - 1] as u64
+}
+
+/// Hashes a byte string with wyhash.
+///
+/// Can be evaluated at compile time.
+///
+/// # Parameters
+///
+/// - `bytes`: The input value.
+/// - `seed`: The seed of the hash function.
+/// - `secret`: The secret of the hash function - [`DEFAULT_SECRET`] unless there is a reason
+///   to use another one.
+pub const fn wyhash(bytes: &[u8], seed: u64, secret: &[u64; 4]) -> u64 {
+    let len = bytes.len();
+    let mut seed = seed ^ mix(seed ^ secret[0], secret[1]);
+    let (a, b);
+
+    if len <= 16 {
+        if len >= 4 {
+            let shift = (len >> 3) << 2;
+            a = (read_4(bytes, 0) << 32) | read_4(bytes, shift);
+            b = (read_4(bytes, len - 4) << 32) | read_4(bytes, len - 4 - shift);
+        } else if len > 0 {
+            a = read_3(bytes);
+            b = 0;
+        } else {
+            a = 0;
+            b = 0;
+        }
+    } else {
+        let mut i = len;
+        let mut p = 0;
+        if i > 48 {
+            let mut seed_1 = seed;
+            let mut seed_2 = seed;
+            while i > 48 {
+                seed = mix(read_8(bytes, p) ^ secret[1], read_8(bytes, p + 8) ^ seed);
+                seed_1 = mix(
+                    read_8(bytes, p + 16) ^ secret[2],
+                    read_8(bytes, p + 24) ^ seed_1,
+                );
+                seed_2 = mix(
+                    read_8(bytes, p + 32) ^ secret[3],
+                    read_8(bytes, p + 40) ^ seed_2,
+                );
+                p += 48;
+                i -= 48;
+            }
+            seed ^= seed_1 ^ seed_2;
+        }
+        while i > 16 {
+            seed = mix(read_8(bytes, p) ^ secret[1], read_8(bytes, p + 8) ^ seed);
+            p += 16;
+            i -= 16;
+        }
+        a = read_8(bytes, p + i - 16);
+        b = read_8(bytes, p + i - 8);
+    }
+
+    let (a, b) = mum(a ^ secret[1], b ^ seed);
+    mix(a ^ secret[0] ^ len as u64, b ^ secret[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference values produced by `wyhash()` final4 with the default secret.
+    const TEST_VECTORS: &[(&[u8], u64, u64)] = &[
+        (b"", 0, 0x0409_638e_e2bd_e459),
+        (b"a", 1, 0xa841_2d09_1b5f_e0a9),
+        (b"abc", 2, 0x32dd_92e4_b291_5153),
+        (b"message digest", 3, 0x8619_1240_89a3_a16b),
+        (b"abcdefghijklmnopqrstuvwxyz", 4, 0x7a43_afb6_1d7f_5f40),
+        (
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+            5,
+            0xff42_329b_90e5_0d58,
+        ),
+        (
+            b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+            6,
+            0xc39c_ab13_b115_aad3,
+        ),
+    ];
+
+    #[test]
+    fn test_wyhash_test_vectors() {
+        for &(input, seed, expected) in TEST_VECTORS {
+            assert_eq!(
+                wyhash(input, seed, &DEFAULT_SECRET),
+                expected,
+                "Input: {input:?}, seed: {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_wyhash_const() {
+        const HASH: u64 = wyhash(b"abc", 2, &DEFAULT_SECRET);
+        assert_eq!(HASH, 0x32dd_92e4_b291_5153);
+    }
+
+    /// The lengths around the boundaries of the 48-byte rounds, checked against the reference
+    /// `wyhash()`.
+    #[test]
+    fn test_wyhash_long_inputs() {
+        let bytes: [u8; 100] = core::array::from_fn(|i| (i * 31 + 7) as u8);
+        for (len, expected) in [
+            (48, 0x0a1a_a5be_b2d7_b6e3),
+            (49, 0xa440_2b30_2943_d399),
+            (96, 0xf5c9_5fe2_c3ac_4683),
+            (97, 0x9d9b_3594_c95a_aa20),
+            (100, 0xcd17_b579_1ef8_4d82),
+        ] {
+            assert_eq!(
+                wyhash(&bytes[..len], 42, &DEFAULT_SECRET),
+                expected,
+                "Length: {len}"
+            );
+        }
+    }
+}
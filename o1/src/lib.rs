@@ -1,3 +1,11 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub mod fks;
 pub mod hashing;
 pub mod utils;
+
+/// Derives [`Hasher`](o1_core::Hasher) of [`MSPHasher`](hashing::hashers::msp::MSPHasher) for enums.
+#[cfg(feature = "derive")]
+pub use o1_derive::O1Hash;
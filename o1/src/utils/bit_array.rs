@@ -2,6 +2,9 @@
 //!
 //! Use the convenient [`bit_array!`] macro as a factory - it allows to specify array's length in
 //! bits.
+//!
+//! [`BitVec`] is its runtime counterpart with the length known only at runtime.
+use alloc::vec::Vec;
 
 /// Marker trait for unsigned integer types used for storing bits.
 ///
@@ -73,6 +76,7 @@ impl BitStore for u128 {
 /// assert!(!bits.get(1).unwrap());
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
 pub struct Bits<T: BitStore> {
     value: T,
 }
@@ -105,6 +109,26 @@ macro_rules! impl_bits {
                 Self { value }
             }
 
+            /// Creates a new Bits with the bits at the specified indices set to 1.
+            ///
+            /// Out-of-range indices are ignored, the same as by [`Bits::set`].
+            pub fn from_indices<I: IntoIterator<Item = usize>>(indices: I) -> Self {
+                let mut bits = Self::new();
+                indices.into_iter().for_each(|index| bits.set(index));
+                bits
+            }
+
+            /// Const version of [`Bits::from_indices`].
+            pub const fn from_indices_const(indices: &[usize]) -> Self {
+                let mut bits = Self::new();
+                let mut i = 0;
+                while i < indices.len() {
+                    bits.set(indices[i]);
+                    i += 1;
+                }
+                bits
+            }
+
             /// Returns the raw underlying value.
             pub const fn value(&self) -> $type {
                 self.value
@@ -165,6 +189,86 @@ macro_rules! impl_bits {
                 self.value.count_ones() as usize
             }
 
+            /// Returns the index of the lowest bit set to 1 or `None` if no bits are set.
+            pub const fn first_one(&self) -> Option<usize> {
+                if self.value == 0 {
+                    return None;
+                }
+                Some(self.value.trailing_zeros() as usize)
+            }
+
+            /// Returns the index of the highest bit set to 1 or `None` if no bits are set.
+            pub const fn last_one(&self) -> Option<usize> {
+                if self.value == 0 {
+                    return None;
+                }
+                Some(<$type as BitStore>::BITS - 1 - self.value.leading_zeros() as usize)
+            }
+
+            /// Returns the number of consecutive bits set to 1 starting from the lowest index.
+            pub const fn trailing_ones(&self) -> usize {
+                self.value.trailing_ones() as usize
+            }
+
+            /// Returns the number of consecutive bits set to 1 starting from the highest index.
+            pub const fn leading_ones(&self) -> usize {
+                self.value.leading_ones() as usize
+            }
+
+            /// Returns [`first_one()`](Self::first_one), [`last_one()`](Self::last_one) and
+            /// [`count_ones()`](Self::count_ones) computed together.
+            pub const fn summary(&self) -> (Option<usize>, Option<usize>, usize) {
+                if self.value == 0 {
+                    return (None, None, 0);
+                }
+                (
+                    Some(self.value.trailing_zeros() as usize),
+                    Some(<$type as BitStore>::BITS - 1 - self.value.leading_zeros() as usize),
+                    self.value.count_ones() as usize,
+                )
+            }
+
+            /// Moves every bit `n` positions towards the higher indices.
+            ///
+            /// The vacated positions are filled with zeros and the bits moved past
+            /// [`len()`](Self::len) are dropped.
+            pub const fn shift_left(&self, n: usize) -> Self {
+                if n >= <$type as BitStore>::BITS {
+                    return Self::new();
+                }
+                Self::from_value(self.value << n as u32)
+            }
+
+            /// Moves every bit `n` positions towards the lower indices.
+            ///
+            /// The vacated positions are filled with zeros and the bits moved below zero are
+            /// dropped.
+            pub const fn shift_right(&self, n: usize) -> Self {
+                if n >= <$type as BitStore>::BITS {
+                    return Self::new();
+                }
+                Self::from_value(self.value >> n as u32)
+            }
+
+            /// Moves every bit `n` positions towards the higher indices, wrapping the bits moved
+            /// past [`len()`](Self::len) around to the lowest indices.
+            pub const fn rotate_left(&self, n: usize) -> Self {
+                let n = n % <$type as BitStore>::BITS;
+                Self::from_value(self.value.rotate_left(n as u32))
+            }
+
+            /// Moves every bit `n` positions towards the lower indices, wrapping the bits moved
+            /// below zero around to the highest indices.
+            pub const fn rotate_right(&self, n: usize) -> Self {
+                let n = n % <$type as BitStore>::BITS;
+                Self::from_value(self.value.rotate_right(n as u32))
+            }
+
+            /// Reverses the order of the bits - the bit `i` moves to `len() - 1 - i`.
+            pub const fn reverse(&self) -> Self {
+                Self::from_value(self.value.reverse_bits())
+            }
+
             /// Returns an iterator over the indices of all bits set to 1.
             pub fn iter_ones(&self) -> BitsOnesIter<$type> {
                 BitsOnesIter {
@@ -253,8 +357,8 @@ pub struct BitArrayOnesIter<'a, T: BitStore, const N: usize> {
     bucket_idx: usize,
     /// Iterator for the current bucket
     bucket_iter: Option<BitsOnesIter<T>>,
-    /// Maximum bucket index
-    max_idx: usize,
+    /// Exclusive upper bound of the yielded indices
+    end: usize,
 }
 
 /// Compile-time iterator over the indices of set bits
@@ -266,8 +370,8 @@ pub struct BitArrayOnesConstIter<T: BitStore, const N: usize> {
     bucket_idx: usize,
     /// Content of the current bucket with iterated ones being unset
     bucket: T,
-    /// Maximum bucket index
-    max_idx: usize,
+    /// Exclusive upper bound of the yielded indices
+    end: usize,
 }
 
 /// Generates a [`BitArray`] implementation for the specified type.
@@ -286,6 +390,66 @@ macro_rules! impl_bit_array {
                 Self { buckets: [Bits::<$type>::new(); N] }
             }
 
+            /// Creates a new BitArray with the bits at the specified indices set to 1.
+            ///
+            /// Out-of-range indices are ignored, the same as by [`BitArray::set`].
+            pub fn from_indices<I: IntoIterator<Item = usize>>(indices: I) -> Self {
+                let mut arr = Self::new();
+                indices.into_iter().for_each(|index| arr.set(index));
+                arr
+            }
+
+            /// Const version of [`BitArray::from_indices`].
+            pub const fn from_indices_const(indices: &[usize]) -> Self {
+                let mut arr = Self::new();
+                let mut i = 0;
+                while i < indices.len() {
+                    arr.set(indices[i]);
+                    i += 1;
+                }
+                arr
+            }
+
+            /// Creates a new BitArray from a `u128` - the bit `i` of `value` becomes the bit `i` of
+            /// the array.
+            ///
+            /// The bits of `value` beyond [`len()`](Self::len) are dropped.
+            pub const fn from_u128(value: u128) -> Self {
+                let mut arr = Self::new();
+                let mut i = 0;
+                while i < N && i * <$type as BitStore>::BITS < u128::BITS as usize {
+                    let word = (value >> (i * <$type as BitStore>::BITS)) as $type;
+                    arr.buckets[i] = Bits::<$type>::from_value(word);
+                    i += 1;
+                }
+                arr
+            }
+
+            /// Converts the BitArray to a `u128` - the bit `i` of the array becomes the bit `i` of
+            /// the result.
+            ///
+            /// Returns `None` if [`len()`](Self::len) exceeds 128.
+            pub const fn to_u128(&self) -> Option<u128> {
+                if self.len() > u128::BITS as usize {
+                    return None;
+                }
+
+                let mut value = 0;
+                let mut i = 0;
+                while i < N {
+                    value |= (self.buckets[i].value() as u128) << (i * <$type as BitStore>::BITS);
+                    i += 1;
+                }
+                Some(value)
+            }
+
+            /// Returns the underlying words, the bit `i` of the array is the bit `i % BITS` of the
+            /// word `i / BITS`.
+            pub const fn as_words(&self) -> &[$type] {
+                // SAFETY: `Bits` is a transparent wrapper over its storage type.
+                unsafe { core::slice::from_raw_parts(self.buckets.as_ptr().cast::<$type>(), N) }
+            }
+
             /// Returns the total number of bits in the BitArray.
             pub const fn len(&self) -> usize {
                 N * <$type as BitStore>::BITS
@@ -355,24 +519,240 @@ macro_rules! impl_bit_array {
                 count
             }
 
+            /// Returns the index of the lowest bit set to 1 or `None` if no bits are set.
+            pub const fn first_one(&self) -> Option<usize> {
+                let mut i = 0;
+                while i < N {
+                    if let Some(bit_idx) = self.buckets[i].first_one() {
+                        return Some(i * <$type as BitStore>::BITS + bit_idx);
+                    }
+                    i += 1;
+                }
+                None
+            }
+
+            /// Returns the index of the highest bit set to 1 or `None` if no bits are set.
+            pub const fn last_one(&self) -> Option<usize> {
+                let mut i = N;
+                while i > 0 {
+                    i -= 1;
+                    if let Some(bit_idx) = self.buckets[i].last_one() {
+                        return Some(i * <$type as BitStore>::BITS + bit_idx);
+                    }
+                }
+                None
+            }
+
+            /// Returns the number of consecutive bits set to 1 starting from the lowest index,
+            /// continuing across the bucket boundaries.
+            pub const fn trailing_ones(&self) -> usize {
+                let mut count = 0;
+                let mut i = 0;
+                while i < N {
+                    let bucket_count = self.buckets[i].trailing_ones();
+                    count += bucket_count;
+                    if bucket_count < <$type as BitStore>::BITS {
+                        break;
+                    }
+                    i += 1;
+                }
+                count
+            }
+
+            /// Returns the number of consecutive bits set to 1 starting from the highest index -
+            /// [`len()`](Self::len) minus one - continuing across the bucket boundaries.
+            pub const fn leading_ones(&self) -> usize {
+                let mut count = 0;
+                let mut i = N;
+                while i > 0 {
+                    i -= 1;
+                    let bucket_count = self.buckets[i].leading_ones();
+                    count += bucket_count;
+                    if bucket_count < <$type as BitStore>::BITS {
+                        break;
+                    }
+                }
+                count
+            }
+
+            /// Returns true if every bit set in `self` is also set in `other`.
+            pub const fn is_subset(&self, other: &Self) -> bool {
+                let mut i = 0;
+                while i < N {
+                    let value = self.buckets[i].value();
+                    if value & other.buckets[i].value() != value {
+                        return false;
+                    }
+                    i += 1;
+                }
+                true
+            }
+
+            /// Returns true if at least one bit is set in both `self` and `other`.
+            pub const fn intersects(&self, other: &Self) -> bool {
+                let mut i = 0;
+                while i < N {
+                    if self.buckets[i].value() & other.buckets[i].value() != 0 {
+                        return true;
+                    }
+                    i += 1;
+                }
+                false
+            }
+
+            /// Moves every bit `n` positions towards the higher indices, across the bucket
+            /// boundaries.
+            ///
+            /// The vacated positions are filled with zeros and the bits moved past
+            /// [`len()`](Self::len) are dropped.
+            pub const fn shift_left(&self, n: usize) -> Self {
+                let mut result = Self::new();
+                if n >= self.len() {
+                    return result;
+                }
+
+                let (bucket_shift, bit_shift) = self.index(n);
+                let mut i = bucket_shift;
+                while i < N {
+                    let src = i - bucket_shift;
+                    let mut value = self.buckets[src].value() << bit_shift as u32;
+                    if bit_shift > 0 && src > 0 {
+                        value |= self.buckets[src - 1].value()
+                            >> (<$type as BitStore>::BITS - bit_shift) as u32;
+                    }
+                    result.buckets[i] = Bits::<$type>::from_value(value);
+                    i += 1;
+                }
+                result
+            }
+
+            /// Moves every bit `n` positions towards the lower indices, across the bucket
+            /// boundaries.
+            ///
+            /// The vacated positions are filled with zeros and the bits moved below zero are
+            /// dropped.
+            pub const fn shift_right(&self, n: usize) -> Self {
+                let mut result = Self::new();
+                if n >= self.len() {
+                    return result;
+                }
+
+                let (bucket_shift, bit_shift) = self.index(n);
+                let mut i = 0;
+                while i + bucket_shift < N {
+                    let src = i + bucket_shift;
+                    let mut value = self.buckets[src].value() >> bit_shift as u32;
+                    if bit_shift > 0 && src + 1 < N {
+                        value |= self.buckets[src + 1].value()
+                            << (<$type as BitStore>::BITS - bit_shift) as u32;
+                    }
+                    result.buckets[i] = Bits::<$type>::from_value(value);
+                    i += 1;
+                }
+                result
+            }
+
+            /// Moves every bit `n` positions towards the higher indices, wrapping the bits moved
+            /// past [`len()`](Self::len) around to the lowest indices.
+            pub const fn rotate_left(&self, n: usize) -> Self {
+                if N == 0 {
+                    return *self;
+                }
+                let n = n % self.len();
+                self.shift_left(n).union(&self.shift_right(self.len() - n))
+            }
+
+            /// Moves every bit `n` positions towards the lower indices, wrapping the bits moved
+            /// below zero around to the highest indices.
+            pub const fn rotate_right(&self, n: usize) -> Self {
+                if N == 0 {
+                    return *self;
+                }
+                let n = n % self.len();
+                self.shift_right(n).union(&self.shift_left(self.len() - n))
+            }
+
+            /// Reverses the order of the bits across the bucket boundaries - the bit `i` moves to
+            /// `len() - 1 - i`.
+            ///
+            /// The length spans all buckets, so it reverses the order of the buckets and the
+            /// bits within each of them.
+            pub const fn reverse(&self) -> Self {
+                let mut result = Self::new();
+                let mut i = 0;
+                while i < N {
+                    result.buckets[i] = self.buckets[N - 1 - i].reverse();
+                    i += 1;
+                }
+                result
+            }
+
+            /// Returns a BitArray with the bits set in either `self` or `other`.
+            const fn union(&self, other: &Self) -> Self {
+                let mut result = Self::new();
+                let mut i = 0;
+                while i < N {
+                    result.buckets[i] = Bits::<$type>::from_value(
+                        self.buckets[i].value() | other.buckets[i].value(),
+                    );
+                    i += 1;
+                }
+                result
+            }
+
             /// Returns an iterator over the indices of all bits set to 1.
             pub fn iter_ones(&self) -> BitArrayOnesIter<'_, $type, N> {
+                self.iter_ones_range(0, self.len())
+            }
+
+            /// Returns an iterator over the indices of the bits set to 1 within `[start, end)`.
+            ///
+            /// `end` is clamped to the length of the array, an empty range yields nothing.
+            pub fn iter_ones_range(&self, start: usize, end: usize) -> BitArrayOnesIter<'_, $type, N> {
+                let (bucket_idx, bucket) = self.range_start(start, end);
                 BitArrayOnesIter {
                     bit_array: self,
-                    bucket_idx: 0,
-                    bucket_iter: if N > 0 { Some(self.buckets[0].iter_ones()) } else { None },
-                    max_idx: self.len(),
+                    bucket_idx,
+                    bucket_iter: Some(Bits::<$type>::from_value(bucket).iter_ones()),
+                    end: if end < self.len() { end } else { self.len() },
                 }
             }
 
             /// Returns a const iterator over the indices of all bits set to 1.
             pub const fn iter_ones_const(&self) -> BitArrayOnesConstIter<$type, N> {
+                self.iter_ones_range_const(0, self.len())
+            }
+
+            /// Returns a const iterator over the indices of the bits set to 1 within
+            /// `[start, end)`.
+            ///
+            /// `end` is clamped to the length of the array, an empty range yields nothing.
+            pub const fn iter_ones_range_const(
+                &self,
+                start: usize,
+                end: usize,
+            ) -> BitArrayOnesConstIter<$type, N> {
+                let (bucket_idx, bucket) = self.range_start(start, end);
                 BitArrayOnesConstIter {
                     bit_array: *self,
-                    bucket_idx: 0,
-                    bucket: if N > 0 { self.buckets[0].value() } else { 0 },
-                    max_idx: self.len(),
+                    bucket_idx,
+                    bucket,
+                    end: if end < self.len() { end } else { self.len() },
+                }
+            }
+
+            /// Returns the index of the bucket containing `start` and its value with the bits
+            /// below `start` cleared.
+            ///
+            /// For an empty range the returned index is `N` so that iteration yields nothing.
+            const fn range_start(&self, start: usize, end: usize) -> (usize, $type) {
+                if start >= end || start >= self.len() {
+                    return (N, 0);
                 }
+                let (bucket_idx, bit_idx) = self.index(start);
+                let mask = <$type>::MAX << bit_idx as u32;
+
+                (bucket_idx, self.buckets[bucket_idx].value() & mask)
             }
         }
 
@@ -390,6 +770,11 @@ macro_rules! impl_bit_array {
                     if let Some(ref mut iter) = self.bucket_iter {
                         if let Some(bit_idx) = iter.next() {
                             let index = self.bucket_idx * <$type as BitStore>::BITS + bit_idx;
+                            if index >= self.end {
+                                self.bucket_idx = N;
+                                self.bucket_iter = None;
+                                return None;
+                            }
                             return Some(index);
                         }
                     }
@@ -413,6 +798,10 @@ macro_rules! impl_bit_array {
                         let trailing_zeros = self.bucket.trailing_zeros() as usize;
 
                         let index = self.bucket_idx * <$type as BitStore>::BITS + trailing_zeros;
+                        if index >= self.end {
+                            self.bucket_idx = N;
+                            return None;
+                        }
 
                         self.bucket &= !(1 as $type << trailing_zeros as u32);
 
@@ -438,6 +827,253 @@ impl_bit_array!(u32);
 impl_bit_array!(u64);
 impl_bit_array!(u128);
 
+/// A bit vector with the length determined at runtime - the runtime counterpart of [`BitArray`].
+///
+/// The bits beyond [`len()`](Self::len) in the last bucket are always kept at 0.
+///
+/// # Examples
+///
+/// ```
+/// use o1::utils::bit_array::BitVec;
+///
+/// let mut vec = BitVec::<u64>::with_len(100);
+/// vec.set(0);
+/// vec.set(99);
+///
+/// assert_eq!(vec.iter_ones().collect::<Vec<_>>(), vec![0, 99]);
+/// assert_eq!(vec.get(100), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BitVec<T: BitStore> {
+    buckets: Vec<Bits<T>>,
+    /// The number of bits.
+    len: usize,
+}
+
+/// Iterator over the indices of set bits in a BitVec.
+pub struct BitVecOnesIter<'a, T: BitStore> {
+    bit_vec: &'a BitVec<T>,
+    /// Index of the current bucket
+    bucket_idx: usize,
+    /// Iterator for the current bucket
+    bucket_iter: Option<BitsOnesIter<T>>,
+}
+
+/// Generates a [`BitVec`] implementation for the specified type.
+macro_rules! impl_bit_vec {
+    ($type:ty) => {
+        impl BitVec<$type> {
+            const fn index(bit_idx: usize) -> (usize, usize) {
+                let bucket_idx = bit_idx >> <$type as BitStore>::BITS_LOG2;
+                let bit_idx = bit_idx & <$type as BitStore>::BITS_MASK;
+
+                (bucket_idx, bit_idx)
+            }
+
+            /// The number of buckets required for `len` bits.
+            const fn num_buckets(len: usize) -> usize {
+                len.div_ceil(<$type as BitStore>::BITS)
+            }
+
+            /// Creates a new empty BitVec.
+            pub const fn new() -> Self {
+                Self {
+                    buckets: Vec::new(),
+                    len: 0,
+                }
+            }
+
+            /// Creates a new BitVec of `len` bits set to 0.
+            pub fn with_len(len: usize) -> Self {
+                let mut vec = Self::new();
+                vec.resize(len);
+                vec
+            }
+
+            /// Creates a new empty BitVec able to grow up to `capacity` bits without
+            /// reallocating.
+            pub fn with_capacity(capacity: usize) -> Self {
+                Self {
+                    buckets: Vec::with_capacity(Self::num_buckets(capacity)),
+                    len: 0,
+                }
+            }
+
+            /// Changes the length to `len` bits.
+            ///
+            /// The added bits are set to 0, the removed bits are discarded.
+            pub fn resize(&mut self, len: usize) {
+                self.buckets
+                    .resize(Self::num_buckets(len), Bits::<$type>::new());
+                self.len = len;
+                self.clear_padding();
+            }
+
+            /// Appends a bit.
+            pub fn push(&mut self, bit: bool) {
+                self.resize(self.len + 1);
+                if bit {
+                    self.set(self.len - 1);
+                }
+            }
+
+            /// Clears the bits of the last bucket beyond the length.
+            fn clear_padding(&mut self) {
+                let (bucket_idx, bit_idx) = Self::index(self.len);
+                if bit_idx != 0 {
+                    let bucket = &mut self.buckets[bucket_idx];
+                    *bucket = Bits::<$type>::from_value(
+                        bucket.value() & !(<$type>::MAX << bit_idx as u32),
+                    );
+                }
+            }
+
+            /// Returns the total number of bits.
+            pub const fn len(&self) -> usize {
+                self.len
+            }
+
+            /// Returns true if the BitVec has no bits.
+            pub const fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            /// Gets the value of the bit at the specified index.
+            pub fn get(&self, index: usize) -> Option<bool> {
+                if index >= self.len {
+                    return None;
+                }
+
+                let (bucket_idx, bit_idx) = Self::index(index);
+                self.buckets[bucket_idx].get(bit_idx)
+            }
+
+            /// Sets the bit at the specified index to 1.
+            ///
+            /// Out-of-range indices are ignored, the same as by [`BitArray::set`].
+            pub fn set(&mut self, index: usize) {
+                if index >= self.len {
+                    return;
+                }
+
+                let (bucket_idx, bit_idx) = Self::index(index);
+                self.buckets[bucket_idx].set(bit_idx);
+            }
+
+            /// Clears the bit at the specified index (sets to 0).
+            pub fn clear(&mut self, index: usize) {
+                if index >= self.len {
+                    return;
+                }
+
+                let (bucket_idx, bit_idx) = Self::index(index);
+                self.buckets[bucket_idx].clear(bit_idx);
+            }
+
+            /// Sets all bits to 0.
+            pub fn clear_all(&mut self) {
+                self.buckets
+                    .iter_mut()
+                    .for_each(|bucket| bucket.clear_all());
+            }
+
+            /// Sets all bits to 1.
+            pub fn set_all(&mut self) {
+                self.buckets.iter_mut().for_each(|bucket| bucket.set_all());
+                self.clear_padding();
+            }
+
+            /// Counts the number of bits set to 1.
+            pub fn count_ones(&self) -> usize {
+                self.buckets.iter().map(|bucket| bucket.count_ones()).sum()
+            }
+
+            /// Returns the index of the lowest bit set to 1 or `None` if no bits are set.
+            pub fn first_one(&self) -> Option<usize> {
+                self.buckets.iter().enumerate().find_map(|(i, bucket)| {
+                    bucket
+                        .first_one()
+                        .map(|bit_idx| i * <$type as BitStore>::BITS + bit_idx)
+                })
+            }
+
+            /// Returns the index of the highest bit set to 1 or `None` if no bits are set.
+            pub fn last_one(&self) -> Option<usize> {
+                self.buckets
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .find_map(|(i, bucket)| {
+                        bucket
+                            .last_one()
+                            .map(|bit_idx| i * <$type as BitStore>::BITS + bit_idx)
+                    })
+            }
+
+            /// Reverses the order of the bits - the bit `i` moves to `len() - 1 - i`.
+            ///
+            /// Reverses the order of the buckets and the bits within each of them, which moves
+            /// the padding of the last bucket to the lowest indices, and then shifts the bits
+            /// down by the size of the padding.
+            pub fn reverse(&mut self) {
+                self.buckets.reverse();
+                self.buckets
+                    .iter_mut()
+                    .for_each(|bucket| *bucket = bucket.reverse());
+
+                let padding = self.buckets.len() * <$type as BitStore>::BITS - self.len;
+                if padding == 0 {
+                    return;
+                }
+                for i in 0..self.buckets.len() {
+                    let carried = self.buckets.get(i + 1).map_or(0, |bucket| {
+                        bucket.value() << (<$type as BitStore>::BITS - padding) as u32
+                    });
+                    self.buckets[i] = Bits::<$type>::from_value(
+                        (self.buckets[i].value() >> padding as u32) | carried,
+                    );
+                }
+            }
+
+            /// Returns an iterator over the indices of all bits set to 1.
+            pub fn iter_ones(&self) -> BitVecOnesIter<'_, $type> {
+                BitVecOnesIter {
+                    bit_vec: self,
+                    bucket_idx: 0,
+                    bucket_iter: self.buckets.first().map(|bucket| bucket.iter_ones()),
+                }
+            }
+        }
+
+        impl<'a> Iterator for BitVecOnesIter<'a, $type> {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                while let Some(ref mut iter) = self.bucket_iter {
+                    if let Some(bit_idx) = iter.next() {
+                        return Some(self.bucket_idx * <$type as BitStore>::BITS + bit_idx);
+                    }
+
+                    self.bucket_idx += 1;
+                    self.bucket_iter = self
+                        .bit_vec
+                        .buckets
+                        .get(self.bucket_idx)
+                        .map(|bucket| bucket.iter_ones());
+                }
+
+                None
+            }
+        }
+    };
+}
+
+impl_bit_vec!(u8);
+impl_bit_vec!(u16);
+impl_bit_vec!(u32);
+impl_bit_vec!(u64);
+impl_bit_vec!(u128);
+
 /// Creates a [`BitArray`].
 ///
 /// # Examples
@@ -490,7 +1126,7 @@ pub use bits;
 
 #[cfg(test)]
 mod bits_tests {
-    use super::*;
+    use crate::utils::bit_array::Bits;
 
     #[test]
     const fn test_set_and_get() {
@@ -506,6 +1142,23 @@ mod bits_tests {
         assert!(b.get(8).is_none());
     }
 
+    #[test]
+    fn test_from_indices() {
+        let b = Bits::<u16>::from_indices([0, 5, 15]);
+        assert_eq!(b.iter_ones().collect::<Vec<_>>(), vec![0, 5, 15]);
+        assert_eq!(b.value(), 0b1000_0000_0010_0001);
+
+        let b = Bits::<u8>::from_indices(vec![3, 8, 100, 3]);
+        assert_eq!(b.iter_ones().collect::<Vec<_>>(), vec![3]);
+        assert!(Bits::<u32>::from_indices([]).value() == 0);
+    }
+
+    #[test]
+    const fn test_from_indices_const() {
+        const B: Bits<u16> = Bits::<u16>::from_indices_const(&[0, 5, 15, 16]);
+        assert!(B.value() == 0b1000_0000_0010_0001);
+    }
+
     #[test]
     fn test_iter_ones() {
         let mut b = bits!(u16);
@@ -639,6 +1292,99 @@ mod bits_tests {
         assert!(b128.get(127).unwrap());
     }
 
+    #[test]
+    const fn test_first_one_last_one() {
+        let mut b = bits!(u8);
+        assert!(b.first_one().is_none());
+        assert!(b.last_one().is_none());
+
+        b.set(3);
+        assert!(matches!(b.first_one(), Some(3)));
+        assert!(matches!(b.last_one(), Some(3)));
+
+        b.set(1);
+        b.set(6);
+        assert!(matches!(b.first_one(), Some(1)));
+        assert!(matches!(b.last_one(), Some(6)));
+
+        let mut b = bits!(u128);
+        b.set_all();
+        assert!(matches!(b.first_one(), Some(0)));
+        assert!(matches!(b.last_one(), Some(127)));
+    }
+
+    #[test]
+    const fn test_trailing_ones_leading_ones() {
+        let mut b = bits!(u8);
+        assert!(b.trailing_ones() == 0);
+        assert!(b.leading_ones() == 0);
+
+        b = super::Bits::<u8>::from_value(0b1100_0111);
+        assert!(b.trailing_ones() == 3);
+        assert!(b.leading_ones() == 2);
+
+        b.set_all();
+        assert!(b.trailing_ones() == 8);
+        assert!(b.leading_ones() == 8);
+
+        let b = super::Bits::<u128>::from_value(u128::MAX >> 1);
+        assert!(b.trailing_ones() == 127);
+        assert!(b.leading_ones() == 0);
+    }
+
+    #[test]
+    fn test_summary() {
+        use super::Bits;
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..1000 {
+            // Sparse patterns are more likely to have distinct first and last ones.
+            let b8 = Bits::<u8>::from_value(rng.random::<u8>() & rng.random::<u8>());
+            assert_eq!(
+                b8.summary(),
+                (b8.first_one(), b8.last_one(), b8.count_ones())
+            );
+            let b64 = Bits::<u64>::from_value(rng.random::<u64>() & rng.random::<u64>());
+            assert_eq!(
+                b64.summary(),
+                (b64.first_one(), b64.last_one(), b64.count_ones())
+            );
+            let b128 = Bits::<u128>::from_value(1 << rng.random_range(0..128));
+            assert_eq!(
+                b128.summary(),
+                (b128.first_one(), b128.last_one(), b128.count_ones())
+            );
+        }
+        assert_eq!(Bits::<u16>::new().summary(), (None, None, 0));
+    }
+
+    #[test]
+    const fn test_shift_and_rotate() {
+        let mut b = bits!(u8);
+        b.set(0);
+        b.set(7);
+
+        assert!(b.shift_left(1).value() == 0b0000_0010);
+        assert!(b.shift_right(1).value() == 0b0100_0000);
+        assert!(b.shift_left(8).value() == 0);
+        assert!(b.shift_right(100).value() == 0);
+        assert!(b.rotate_left(1).value() == 0b0000_0011);
+        assert!(b.rotate_right(1).value() == 0b1100_0000);
+        assert!(b.rotate_left(9).value() == b.rotate_left(1).value());
+    }
+
+    #[test]
+    const fn test_reverse() {
+        let b = Bits::<u8>::from_value(0b0000_1101);
+
+        assert!(b.reverse().value() == 0b1011_0000);
+        assert!(b.reverse().reverse().value() == b.value());
+        assert!(Bits::<u128>::from_value(1).reverse().value() == 1 << 127);
+        assert!(Bits::<u16>::new().reverse().value() == 0);
+    }
+
     #[test]
     const fn test_clear_all_set_all_const() {
         let mut b = bits!(u8);
@@ -683,6 +1429,68 @@ mod bit_array_tests {
         assert!(arr.get(16).is_none());
     }
 
+    #[test]
+    fn test_from_indices() {
+        let arr = BitArray::<u8, 2>::from_indices([0, 5, 15]);
+        assert_eq!(arr.iter_ones().collect::<Vec<_>>(), vec![0, 5, 15]);
+        assert_eq!(arr.count_ones(), 3);
+
+        let arr = BitArray::<u64, 2>::from_indices((0..200).step_by(50));
+        assert_eq!(arr.iter_ones().collect::<Vec<_>>(), vec![0, 50, 100]);
+    }
+
+    #[test]
+    const fn test_from_indices_const() {
+        const ARR: BitArray<u8, 2> = BitArray::<u8, 2>::from_indices_const(&[0, 5, 15, 16]);
+        assert!(ARR.count_ones() == 3);
+        assert!(matches!(ARR.get(0), Some(true)));
+        assert!(matches!(ARR.get(5), Some(true)));
+        assert!(matches!(ARR.get(15), Some(true)));
+        assert!(ARR.get(16).is_none());
+    }
+
+    #[test]
+    fn test_u128_round_trip() {
+        for value in [
+            0,
+            1,
+            1 << 63,
+            1 << 64,
+            u128::MAX,
+            0x0123_4567_89ab_cdef_fedc_ba98_7654_3210,
+        ] {
+            let arr = BitArray::<u64, 2>::from_u128(value);
+            assert_eq!(arr.to_u128(), Some(value));
+            assert_eq!(BitArray::<u8, 16>::from_u128(value).to_u128(), Some(value));
+            assert_eq!(arr.as_words(), &[value as u64, (value >> 64) as u64]);
+        }
+    }
+
+    #[test]
+    fn test_u128_bit_positions() {
+        let arr = BitArray::<u64, 2>::from_indices([0, 5, 63, 64, 127]);
+        assert_eq!(
+            arr.to_u128(),
+            Some(1 | 1 << 5 | 1 << 63 | 1 << 64 | 1 << 127)
+        );
+
+        let arr = BitArray::<u64, 2>::from_u128(1 << 70 | 1 << 3);
+        assert_eq!(arr.iter_ones().collect::<Vec<_>>(), vec![3, 70]);
+        assert_eq!(arr.as_words(), &[1 << 3, 1 << 6]);
+    }
+
+    #[test]
+    const fn test_u128_length() {
+        assert!(BitArray::<u64, 3>::new().to_u128().is_none());
+        assert!(matches!(BitArray::<u32, 2>::new().to_u128(), Some(0)));
+
+        // The bits beyond the length are dropped.
+        const ARR: BitArray<u32, 1> = BitArray::<u32, 1>::from_u128(1 << 40 | 1);
+        assert!(matches!(ARR.to_u128(), Some(1)));
+        const WIDE: BitArray<u64, 3> = BitArray::<u64, 3>::from_u128(u128::MAX);
+        assert!(WIDE.count_ones() == 128);
+    }
+
     #[test]
     const fn test_clear() {
         let mut arr = bit_array!(16, u8);
@@ -750,6 +1558,59 @@ mod bit_array_tests {
         assert!(ones == vec![5, 15]);
     }
 
+    fn ranged_array() -> BitArray<u8, 4> {
+        let mut arr = bit_array!(32, u8);
+        for idx in [0, 3, 7, 8, 12, 13, 20, 23, 24, 31] {
+            arr.set(idx);
+        }
+        arr
+    }
+
+    const RANGES: [(usize, usize); 12] = [
+        (0, 32),
+        (0, 0),
+        (3, 4),
+        (1, 7),
+        (1, 8),
+        (4, 21),
+        (8, 16),
+        (9, 13),
+        (13, 31),
+        (20, 100),
+        (25, 10),
+        (32, 40),
+    ];
+
+    #[test]
+    fn test_iter_ones_range() {
+        let arr = ranged_array();
+        for (start, end) in RANGES {
+            let expected: Vec<usize> = arr
+                .iter_ones()
+                .filter(|idx| (start..end).contains(idx))
+                .collect();
+            let ones: Vec<usize> = arr.iter_ones_range(start, end).collect();
+            assert_eq!(ones, expected, "Range: [{start}, {end})");
+        }
+    }
+
+    #[test]
+    fn test_iter_ones_range_const() {
+        let arr = ranged_array();
+        for (start, end) in RANGES {
+            let expected: Vec<usize> = arr
+                .iter_ones()
+                .filter(|idx| (start..end).contains(idx))
+                .collect();
+            let mut ones = Vec::new();
+            let mut iter = arr.iter_ones_range_const(start, end);
+            while let Some(index) = iter.next() {
+                ones.push(index);
+            }
+            assert_eq!(ones, expected, "Range: [{start}, {end})");
+        }
+    }
+
     #[test]
     const fn test_iter_ones_const() {
         let mut arr = bit_array!(16, u8);
@@ -782,6 +1643,251 @@ mod bit_array_tests {
         assert!(ones[1] == 15);
     }
 
+    #[test]
+    const fn test_first_one_last_one() {
+        let mut arr = bit_array!(32, u8);
+        assert!(arr.first_one().is_none());
+        assert!(arr.last_one().is_none());
+
+        arr.set(13);
+        assert!(matches!(arr.first_one(), Some(13)));
+        assert!(matches!(arr.last_one(), Some(13)));
+
+        arr.set(2);
+        arr.set(25);
+        assert!(matches!(arr.first_one(), Some(2)));
+        assert!(matches!(arr.last_one(), Some(25)));
+
+        arr.set_all();
+        assert!(matches!(arr.first_one(), Some(0)));
+        assert!(matches!(arr.last_one(), Some(31)));
+    }
+
+    #[test]
+    const fn test_trailing_ones_leading_ones() {
+        let mut arr = BitArray::<u8, 3>::new();
+        assert!(arr.trailing_ones() == 0);
+        assert!(arr.leading_ones() == 0);
+
+        // A run crossing the boundary between the first and the second bucket.
+        let mut i = 0;
+        while i < 11 {
+            arr.set(i);
+            i += 1;
+        }
+        arr.set(13);
+        assert!(arr.trailing_ones() == 11);
+        assert!(arr.leading_ones() == 0);
+
+        // A run crossing the boundary between the last and the second bucket.
+        let mut i = 15;
+        while i < 24 {
+            arr.set(i);
+            i += 1;
+        }
+        assert!(arr.leading_ones() == 9);
+        assert!(arr.trailing_ones() == 11);
+
+        arr.set_all();
+        assert!(arr.trailing_ones() == arr.len());
+        assert!(arr.leading_ones() == arr.len());
+
+        // The storage is padded up to a whole number of buckets, which counts towards `len()`.
+        let mut arr = bit_array!(20, u8);
+        arr.set_all();
+        assert!(arr.len() == 24);
+        assert!(arr.trailing_ones() == arr.len());
+        assert!(arr.leading_ones() == arr.len());
+
+        let empty = BitArray::<u64, 0>::new();
+        assert!(empty.trailing_ones() == 0);
+        assert!(empty.leading_ones() == 0);
+    }
+
+    #[test]
+    fn test_trailing_ones_leading_ones_match_bits() {
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..1000 {
+            let mut arr = BitArray::<u16, 4>::new();
+            // Runs of ones at both ends followed by random bits.
+            let run = rng.random_range(0..=arr.len());
+            for i in 0..run {
+                arr.set(i);
+                arr.set(arr.len() - 1 - i);
+            }
+            for i in 0..arr.len() {
+                if rng.random_bool(0.1) {
+                    arr.clear(i);
+                }
+            }
+            let expected_trailing = (0..arr.len()).take_while(|&i| arr.get(i).unwrap()).count();
+            let expected_leading = (0..arr.len())
+                .rev()
+                .take_while(|&i| arr.get(i).unwrap())
+                .count();
+            assert_eq!(arr.trailing_ones(), expected_trailing);
+            assert_eq!(arr.leading_ones(), expected_leading);
+        }
+    }
+
+    #[test]
+    const fn test_first_one_last_one_in_last_bucket() {
+        let mut arr = BitArray::<u64, 4>::new();
+        arr.set(200);
+        assert!(matches!(arr.first_one(), Some(200)));
+        assert!(matches!(arr.last_one(), Some(200)));
+
+        let empty = BitArray::<u64, 0>::new();
+        assert!(empty.first_one().is_none());
+        assert!(empty.last_one().is_none());
+    }
+
+    #[test]
+    const fn test_is_subset() {
+        let empty = bit_array!(32, u8);
+        let mut arr = bit_array!(32, u8);
+        arr.set(1);
+        arr.set(20);
+
+        assert!(empty.is_subset(&empty));
+        assert!(empty.is_subset(&arr));
+        assert!(arr.is_subset(&arr));
+        assert!(!arr.is_subset(&empty));
+
+        let mut superset = arr;
+        superset.set(31);
+        assert!(arr.is_subset(&superset));
+        assert!(!superset.is_subset(&arr));
+    }
+
+    #[test]
+    const fn test_intersects() {
+        let empty = bit_array!(32, u8);
+        let mut left = bit_array!(32, u8);
+        left.set(1);
+        left.set(20);
+        let mut right = bit_array!(32, u8);
+        right.set(2);
+        right.set(31);
+
+        assert!(!empty.intersects(&empty));
+        assert!(!empty.intersects(&left));
+        assert!(!left.intersects(&right));
+        assert!(left.intersects(&left));
+
+        right.set(20);
+        assert!(left.intersects(&right));
+        assert!(right.intersects(&left));
+    }
+
+    #[test]
+    const fn test_shift_left_then_right_restores() {
+        let mut arr = BitArray::<u16, 3>::new();
+        arr.set(0);
+        arr.set(15);
+        arr.set(16);
+        arr.set(30);
+        arr.set(47);
+
+        let restored = arr.shift_left(1).shift_right(1);
+
+        let mut expected = arr;
+        expected.clear(47);
+        assert!(restored.count_ones() == 4);
+        assert!(restored.is_subset(&expected));
+        assert!(expected.is_subset(&restored));
+    }
+
+    #[test]
+    const fn test_shift_across_buckets() {
+        let mut arr = BitArray::<u8, 3>::new();
+        arr.set(0);
+        arr.set(6);
+        arr.set(13);
+        arr.set(23);
+
+        let left = arr.shift_left(5);
+        assert!(left.count_ones() == 3);
+        assert!(left.get(5).unwrap());
+        assert!(left.get(11).unwrap());
+        assert!(left.get(18).unwrap());
+
+        let right = arr.shift_right(10);
+        assert!(right.count_ones() == 2);
+        assert!(right.get(3).unwrap());
+        assert!(right.get(13).unwrap());
+
+        assert!(arr.shift_left(24).count_ones() == 0);
+        assert!(arr.shift_right(24).count_ones() == 0);
+        assert!(matches!(arr.shift_left(16).first_one(), Some(16)));
+        assert!(matches!(arr.shift_right(16).last_one(), Some(7)));
+    }
+
+    #[test]
+    const fn test_rotate() {
+        let mut arr = BitArray::<u8, 3>::new();
+        arr.set(1);
+        arr.set(22);
+
+        let left = arr.rotate_left(5);
+        assert!(left.count_ones() == 2);
+        assert!(left.get(6).unwrap());
+        assert!(left.get(3).unwrap());
+
+        let right = arr.rotate_right(3);
+        assert!(right.count_ones() == 2);
+        assert!(right.get(22).unwrap());
+        assert!(right.get(19).unwrap());
+
+        let full = arr.rotate_left(24);
+        assert!(full.is_subset(&arr) && arr.is_subset(&full));
+        let back = arr.rotate_left(13).rotate_right(13);
+        assert!(back.is_subset(&arr) && arr.is_subset(&back));
+
+        let empty = BitArray::<u8, 0>::new();
+        assert!(empty.rotate_left(3).is_empty());
+    }
+
+    #[test]
+    const fn test_reverse_across_buckets() {
+        let arr = BitArray::<u8, 3>::from_indices_const(&[0, 1, 9, 16, 23]);
+
+        let reversed = arr.reverse();
+        let expected = BitArray::<u8, 3>::from_indices_const(&[23, 22, 14, 7, 0]);
+        assert!(reversed.is_subset(&expected) && expected.is_subset(&reversed));
+
+        let restored = reversed.reverse();
+        assert!(restored.is_subset(&arr) && arr.is_subset(&restored));
+
+        let empty = BitArray::<u8, 0>::new();
+        assert!(empty.reverse().is_empty());
+    }
+
+    #[test]
+    fn test_reverse_twice_restores() {
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..100 {
+            let value = rng.random::<u128>();
+            let arr = BitArray::<u32, 4>::from_u128(value);
+            assert_eq!(arr.reverse().to_u128(), Some(value.reverse_bits()));
+            assert_eq!(arr.reverse().reverse(), arr);
+
+            let arr = BitArray::<u64, 3>::from_indices((0..192).filter(|_| rng.random::<bool>()));
+            let reversed = arr.reverse();
+            assert_eq!(reversed.count_ones(), arr.count_ones());
+            for i in arr.iter_ones() {
+                assert_eq!(reversed.get(arr.len() - 1 - i), Some(true), "Index: {i}");
+            }
+            assert_eq!(reversed.reverse(), arr);
+        }
+    }
+
     #[test]
     fn test_different_storage_types() {
         let mut arr_u8 = BitArray::<u8, 2>::new();
@@ -805,3 +1911,156 @@ mod bit_array_tests {
         assert!(arr_u128.get(200).unwrap());
     }
 }
+
+#[cfg(test)]
+mod bit_vec_tests {
+    use crate::utils::bit_array::BitVec;
+
+    #[test]
+    fn test_set_and_get() {
+        let mut vec = BitVec::<u8>::with_len(16);
+        vec.set(0);
+        vec.set(5);
+        vec.set(15);
+
+        assert!(vec.get(0).unwrap());
+        assert!(!vec.get(1).unwrap());
+        assert!(vec.get(5).unwrap());
+        assert!(vec.get(15).unwrap());
+        assert!(vec.get(16).is_none());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut vec = BitVec::<u8>::with_len(16);
+        vec.set(0);
+        vec.set(5);
+        vec.set(15);
+
+        vec.clear(0);
+        assert!(!vec.get(0).unwrap());
+        assert!(vec.get(5).unwrap());
+        vec.clear(15);
+        assert!(!vec.get(15).unwrap());
+        assert_eq!(vec.count_ones(), 1);
+    }
+
+    #[test]
+    fn test_clear_all_and_set_all() {
+        let mut vec = BitVec::<u32>::with_len(70);
+        vec.set_all();
+        assert_eq!(vec.count_ones(), 70);
+        assert_eq!(vec.first_one(), Some(0));
+        assert_eq!(vec.last_one(), Some(69));
+
+        vec.clear_all();
+        assert_eq!(vec.count_ones(), 0);
+        assert_eq!(vec.first_one(), None);
+        assert_eq!(vec.last_one(), None);
+    }
+
+    #[test]
+    fn test_iter_ones() {
+        let mut vec = BitVec::<u16>::with_len(100);
+        for i in [0, 15, 16, 47, 99] {
+            vec.set(i);
+        }
+        assert_eq!(vec.iter_ones().collect::<Vec<_>>(), vec![0, 15, 16, 47, 99]);
+        assert_eq!(BitVec::<u16>::new().iter_ones().next(), None);
+    }
+
+    /// The length isn't a multiple of the bucket size, so the last bucket is partially used.
+    #[test]
+    fn test_partial_last_bucket() {
+        let mut vec = BitVec::<u8>::with_len(10);
+        assert_eq!(vec.len(), 10);
+        vec.set(10);
+        vec.set(15);
+        assert_eq!(vec.get(10), None);
+        assert_eq!(vec.count_ones(), 0);
+
+        vec.set_all();
+        assert_eq!(vec.count_ones(), 10);
+        assert_eq!(vec.last_one(), Some(9));
+        assert_eq!(vec.iter_ones().last(), Some(9));
+    }
+
+    #[test]
+    fn test_growth() {
+        let mut vec = BitVec::<u64>::with_capacity(100);
+        assert!(vec.is_empty());
+        for i in 0..200 {
+            vec.push(i % 3 == 0);
+        }
+        assert_eq!(vec.len(), 200);
+        assert_eq!(vec.count_ones(), 67);
+        assert!(vec.iter_ones().all(|i| i % 3 == 0));
+
+        vec.resize(300);
+        assert_eq!(vec.count_ones(), 67);
+        assert_eq!(vec.get(250), Some(false));
+        vec.set(299);
+        assert_eq!(vec.last_one(), Some(299));
+    }
+
+    /// The length isn't a multiple of the bucket size, so the reversal has to drop the padding.
+    #[test]
+    fn test_reverse() {
+        let mut vec = BitVec::<u8>::with_len(13);
+        for i in [0, 1, 7, 8, 12] {
+            vec.set(i);
+        }
+        vec.reverse();
+        assert_eq!(vec.len(), 13);
+        assert_eq!(vec.iter_ones().collect::<Vec<_>>(), vec![0, 4, 5, 11, 12]);
+
+        vec.reverse();
+        assert_eq!(vec.iter_ones().collect::<Vec<_>>(), vec![0, 1, 7, 8, 12]);
+
+        let mut vec = BitVec::<u64>::with_len(128);
+        vec.set(3);
+        vec.reverse();
+        assert_eq!(vec.iter_ones().collect::<Vec<_>>(), vec![124]);
+
+        let mut empty = BitVec::<u32>::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    /// Shrinking discards the removed bits, so they don't reappear after growing back.
+    #[test]
+    fn test_shrink() {
+        let mut vec = BitVec::<u8>::with_len(20);
+        vec.set_all();
+        vec.resize(5);
+        assert_eq!(vec.count_ones(), 5);
+
+        vec.resize(20);
+        assert_eq!(vec.iter_ones().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        vec.resize(0);
+        assert!(vec.is_empty());
+        assert_eq!(vec.first_one(), None);
+    }
+
+    #[test]
+    fn test_matches_bit_array() {
+        use crate::utils::bit_array::BitArray;
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..100 {
+            let mut arr = BitArray::<u16, 4>::new();
+            let mut vec = BitVec::<u16>::with_len(arr.len());
+            for _ in 0..20 {
+                let index = rng.random_range(0..arr.len());
+                arr.set(index);
+                vec.set(index);
+            }
+            assert!(arr.iter_ones().eq(vec.iter_ones()));
+            assert_eq!(arr.count_ones(), vec.count_ones());
+            assert_eq!(arr.first_one(), vec.first_one());
+            assert_eq!(arr.last_one(), vec.last_one());
+        }
+    }
+}
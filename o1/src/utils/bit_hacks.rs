@@ -1,16 +1,82 @@
 /// Performs a modulo operation by a Mersenne prime.
 ///
-/// Faster equivalent of the operation: `x % p`, where `p == 2 ** n`.
+/// Faster equivalent of the operation: `x % p`, where `p == 2 ** P_E - 1`.
+///
+/// # Notes
+///
+/// - Relies on the identity `2 ** P_E ≡ 1 (mod p)` - the high bits are repeatedly folded onto the
+///   low bits until the value fits. For small exponents more than one fold might be necessary.
+/// - Any `u128` input is valid: a fold can't overflow since `P_E <= 127`, so the sum of the
+///   folded parts is at most `2 ** 127`.
+/// - Tested for the Mersenne exponents 31, 61, 89, 107 and 127.
 #[inline]
-pub const fn mod_mersenne_prime<const P_E: u32, const P: u128>(x: u128) -> u128 {
+pub const fn mod_mersenne_prime<const P_E: u32>(x: u128) -> u128 {
     debug_assert!(
-        P == (2_u128.pow(P_E) - 1),
-        r#""p" must be a Mersenne prime, so "p == 2 ** s - 1" constraint should stand."#
+        P_E > 1 && P_E < 128,
+        r#""P_E" must be in the range [2, 127]"#
     );
-    let result = (x & P) + (x >> P_E);
-    if result >= P {
-        result - P
+    let p: u128 = (1_u128 << P_E) - 1;
+    let mut result = x;
+    while result > p {
+        result = (result & p) + (result >> P_E);
+    }
+    debug_assert!(result <= p);
+    if result == p {
+        0
     } else {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    /// Compares [`mod_mersenne_prime`] with the naive modulo on the edge cases around the
+    /// multiples of the prime and on random inputs of various magnitudes.
+    fn check<const P_E: u32>() {
+        let p: u128 = (1_u128 << P_E) - 1;
+        let mut values = vec![0, 1, u128::MAX, u128::MAX - 1, 1 << P_E, 1 << 127];
+        for k in [1, 2, 3, p - 1, p, p + 1, u128::MAX / p] {
+            if let Some(multiple) = k.checked_mul(p) {
+                values.extend([multiple - 1, multiple, multiple.saturating_add(1)]);
+            }
+        }
+        let mut rng = ChaCha20Rng::seed_from_u64(u64::from(P_E));
+        for _ in 0..10_000 {
+            let value: u128 = rng.random();
+            // Full range, up to the product of two residues and up to `2 * p + 1`.
+            values.extend([
+                value,
+                value >> 128_u32.saturating_sub(2 * P_E),
+                (2 * p).checked_add(2).map_or(value, |m| value % m),
+            ]);
+        }
+
+        for value in values {
+            assert_eq!(
+                mod_mersenne_prime::<P_E>(value),
+                value % p,
+                "P_E: {P_E}, value: {value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_mod_mersenne_prime() {
+        check::<31>();
+        check::<61>();
+        check::<89>();
+        check::<107>();
+        check::<127>();
+    }
+
+    #[test]
+    fn test_mod_mersenne_prime_const() {
+        const P: u128 = (1 << 89) - 1;
+        const RESULT: u128 = mod_mersenne_prime::<89>(2 * P + 5);
+        assert_eq!(RESULT, 5);
+    }
+}
@@ -17,3 +17,13 @@ pub const fn div_ceil_f32(a: f32, b: f32) -> i32 {
         result
     }
 }
+
+/// Calculates the ceiling of a non-negative `f32` number without relying on `std`.
+pub const fn ceil_f32(value: f32) -> f32 {
+    let truncated = value as u64 as f32;
+    if truncated < value {
+        truncated + 1.0
+    } else {
+        truncated
+    }
+}
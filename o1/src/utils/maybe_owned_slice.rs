@@ -1,5 +1,7 @@
-use std::fmt::Debug;
-use std::ops::{Deref, DerefMut};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::ops::{Deref, DerefMut};
 
 /// A smart pointer that holds onto either an owned heap-allocated slice or a borrowed slice.
 ///
@@ -9,8 +11,11 @@ use std::ops::{Deref, DerefMut};
 ///   that has an option to borrow the slice as mutable (currently utilized in `src/fks/drop.rs`).
 ///   There is no use-case where `Borrowed` variant is borrowed as mutable, but it has been done for
 ///   consistency.
+/// - `Shared` variant holds onto an immutable slice, which allows to reference the memory of
+///   `const` items. It can't be borrowed as mutable - an attempt to do it panics.
 pub enum MaybeOwnedSliceMut<'a, T> {
     Borrowed(&'a mut [T]),
+    Shared(&'a [T]),
     Owned(Box<[T]>),
 }
 
@@ -19,6 +24,7 @@ impl<T> Deref for MaybeOwnedSliceMut<'_, T> {
     fn deref(&self) -> &[T] {
         match self {
             MaybeOwnedSliceMut::Borrowed(ref slice) => slice,
+            MaybeOwnedSliceMut::Shared(slice) => slice,
             MaybeOwnedSliceMut::Owned(boxed) => boxed,
         }
     }
@@ -26,10 +32,7 @@ impl<T> Deref for MaybeOwnedSliceMut<'_, T> {
 
 impl<T> DerefMut for MaybeOwnedSliceMut<'_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        match self {
-            MaybeOwnedSliceMut::Borrowed(ref mut slice) => slice,
-            MaybeOwnedSliceMut::Owned(ref mut boxed) => boxed,
-        }
+        self.as_mut_slice()
     }
 }
 
@@ -37,9 +40,10 @@ impl<T> Debug for MaybeOwnedSliceMut<'_, T>
 where
     T: Debug,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             MaybeOwnedSliceMut::Borrowed(slice) => f.debug_tuple("Borrowed").field(slice).finish(),
+            MaybeOwnedSliceMut::Shared(slice) => f.debug_tuple("Shared").field(slice).finish(),
             MaybeOwnedSliceMut::Owned(boxed) => f.debug_tuple("Owned").field(boxed).finish(),
         }
     }
@@ -52,7 +56,7 @@ impl<'a, T> MaybeOwnedSliceMut<'a, T> {
     pub fn owned_into_vec(self) -> Vec<T> {
         match self {
             MaybeOwnedSliceMut::Owned(boxed) => boxed.into_vec(),
-            MaybeOwnedSliceMut::Borrowed(_) => {
+            MaybeOwnedSliceMut::Borrowed(_) | MaybeOwnedSliceMut::Shared(_) => {
                 panic!("Cannot convert borrowed slice to Vec. Use `as_slice()` method instead.",)
             }
         }
@@ -63,23 +67,38 @@ impl<'a, T> MaybeOwnedSliceMut<'a, T> {
     pub const fn from_slice(s: &'a mut [T]) -> Self {
         MaybeOwnedSliceMut::Borrowed(s)
     }
+    pub const fn from_shared_slice(s: &'a [T]) -> Self {
+        MaybeOwnedSliceMut::Shared(s)
+    }
     pub const fn as_slice(&self) -> &[T] {
         match self {
             MaybeOwnedSliceMut::Borrowed(slice) => slice,
+            MaybeOwnedSliceMut::Shared(slice) => slice,
             MaybeOwnedSliceMut::Owned(boxed) => boxed,
         }
     }
+    /// # Panics
+    ///
+    /// - If the slice is `Shared`.
     pub fn as_mut_slice(&mut self) -> &mut [T] {
         match self {
             MaybeOwnedSliceMut::Borrowed(ref mut slice) => slice,
+            MaybeOwnedSliceMut::Shared(_) => panic!("Cannot borrow a shared slice as mutable."),
             MaybeOwnedSliceMut::Owned(ref mut boxed) => &mut *boxed,
         }
     }
     pub const fn is_owned(&self) -> bool {
         matches!(self, MaybeOwnedSliceMut::Owned(_))
     }
+    /// Whether the slice is borrowed - either as mutable or as shared.
     pub const fn is_borrowed(&self) -> bool {
-        matches!(self, MaybeOwnedSliceMut::Borrowed(_))
+        matches!(
+            self,
+            MaybeOwnedSliceMut::Borrowed(_) | MaybeOwnedSliceMut::Shared(_)
+        )
+    }
+    pub const fn is_shared(&self) -> bool {
+        matches!(self, MaybeOwnedSliceMut::Shared(_))
     }
 }
 
@@ -101,12 +120,21 @@ impl<'a, T> From<&'a mut [T]> for MaybeOwnedSliceMut<'a, T> {
     }
 }
 
+impl<'a, T> From<&'a [T]> for MaybeOwnedSliceMut<'a, T> {
+    fn from(slice: &'a [T]) -> Self {
+        MaybeOwnedSliceMut::from_shared_slice(slice)
+    }
+}
+
 impl<T: Clone> Clone for MaybeOwnedSliceMut<'_, T> {
     fn clone(&self) -> Self {
         match self {
             MaybeOwnedSliceMut::Borrowed(slice) => {
                 MaybeOwnedSliceMut::Owned(slice.to_vec().into_boxed_slice())
             }
+            MaybeOwnedSliceMut::Shared(slice) => {
+                MaybeOwnedSliceMut::Owned(slice.to_vec().into_boxed_slice())
+            }
             MaybeOwnedSliceMut::Owned(boxed) => MaybeOwnedSliceMut::Owned(boxed.clone()),
         }
     }
@@ -120,10 +148,6 @@ impl<'b, T: PartialEq> PartialEq<MaybeOwnedSliceMut<'b, T>> for MaybeOwnedSliceM
 
 impl<T: Eq> Eq for MaybeOwnedSliceMut<'_, T> {}
 
-unsafe impl<T: Send> Send for MaybeOwnedSliceMut<'_, T> {}
-
-unsafe impl<T: Sync> Sync for MaybeOwnedSliceMut<'_, T> {}
-
 impl<T: Unpin> Unpin for MaybeOwnedSliceMut<'_, T> {}
 
 impl<T> AsRef<[T]> for MaybeOwnedSliceMut<'_, T> {
@@ -137,3 +161,78 @@ impl<T> AsMut<[T]> for MaybeOwnedSliceMut<'_, T> {
         self.as_mut_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MaybeOwnedSliceMut;
+    use alloc::boxed::Box;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_from_box() {
+        let boxed: Box<[u32]> = vec![1, 2, 3].into_boxed_slice();
+        let slice: MaybeOwnedSliceMut<'_, u32> = boxed.into();
+
+        assert!(slice.is_owned());
+        assert!(!slice.is_borrowed());
+        assert_eq!(slice[1], 2);
+        assert_eq!(slice.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_from_mut_slice() {
+        let mut data = [1u32, 2, 3];
+        let slice: MaybeOwnedSliceMut<'_, u32> = data.as_mut_slice().into();
+
+        assert!(slice.is_borrowed());
+        assert!(!slice.is_owned());
+        assert_eq!(slice.len(), 3);
+        assert_eq!(slice[2], 3);
+        assert_eq!(slice.iter().sum::<u32>(), 6);
+    }
+
+    #[test]
+    fn test_deref_mut_owned() {
+        let mut slice = MaybeOwnedSliceMut::from_vec(vec![1u32, 2, 3]);
+        slice[0] = 10;
+        for value in slice.iter_mut() {
+            *value += 1;
+        }
+
+        assert_eq!(slice.as_slice(), &[11, 3, 4]);
+    }
+
+    #[test]
+    fn test_deref_mut_borrowed() {
+        let mut data = [1u32, 2, 3];
+        {
+            let mut slice = MaybeOwnedSliceMut::from(data.as_mut_slice());
+            slice[1] = 20;
+            slice.reverse();
+            assert_eq!(slice.as_slice(), &[3, 20, 1]);
+        }
+
+        assert_eq!(data, [3, 20, 1]);
+    }
+
+    #[test]
+    fn test_from_shared_slice() {
+        let data = [1u32, 2, 3];
+        let slice: MaybeOwnedSliceMut<'_, u32> = data.as_slice().into();
+
+        assert!(slice.is_borrowed());
+        assert!(slice.is_shared());
+        assert!(!slice.is_owned());
+        assert_eq!(slice.as_slice(), &[1, 2, 3]);
+        assert!(slice.clone().is_owned());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot borrow a shared slice as mutable.")]
+    fn test_deref_mut_shared() {
+        let data = [1u32, 2, 3];
+        let mut slice = MaybeOwnedSliceMut::from_shared_slice(&data);
+        slice[0] = 10;
+    }
+}
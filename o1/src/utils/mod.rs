@@ -4,4 +4,5 @@ pub mod bit_array;
 pub mod bit_hacks;
 pub mod const_hacks;
 pub mod maybe_owned_slice;
+pub mod prefetch;
 pub mod xorshift;
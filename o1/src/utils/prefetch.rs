@@ -0,0 +1,15 @@
+//! Software prefetching hints.
+//!
+//! On unsupported architectures the hints compile to nothing.
+
+/// Hint the CPU to fetch the cache line containing `ptr` for a subsequent read.
+#[inline(always)]
+pub fn prefetch_read<T>(ptr: *const T) {
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+        _mm_prefetch::<_MM_HINT_T0>(ptr as *const i8);
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = ptr;
+}
@@ -45,6 +45,9 @@ impl XorShift<u32> {
 }
 
 impl XorShift<u64> {
+    /// Binary logarithm of the number of steps performed by [`jump()`](Self::jump).
+    pub const JUMP_STEPS_LOG2: u32 = 32;
+
     pub const fn new(seed: u64) -> Self {
         debug_assert!(seed != 0, r#""seed" must be non-zero"#);
 
@@ -59,8 +62,73 @@ impl XorShift<u64> {
         self.state = x;
         x
     }
+
+    /// Advance the state by `2 ** JUMP_STEPS_LOG2` steps.
+    ///
+    /// Equivalent to calling [`next()`](Self::next) that many times, but takes constant time.
+    pub const fn jump(&mut self) {
+        self.state = apply_matrix_64(&JUMP_MATRIX_64, self.state);
+    }
+
+    /// Derive a sub-generator for the given `stream` from the current state.
+    ///
+    /// Different `stream` values always yield different initial states of the sub-generator
+    /// and the parent generator is left untouched.
+    pub const fn split(&self, stream: u64) -> XorShift<u64> {
+        let mut z = self.state ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        if z == 0 {
+            z = 0x9E37_79B9_7F4A_7C15;
+        }
+
+        XorShift { state: z }
+    }
 }
 
+/// Transition matrix of [`XorShift<u64>`] over GF(2), stored by columns: `matrix[i]` is the
+/// state that follows the state `1 << i`.
+type Matrix64 = [u64; 64];
+
+const fn apply_matrix_64(matrix: &Matrix64, value: u64) -> u64 {
+    let mut result = 0;
+    let mut i = 0;
+    while i < 64 {
+        if (value >> i) & 1 == 1 {
+            result ^= matrix[i];
+        }
+        i += 1;
+    }
+    result
+}
+
+/// Compute the transition matrix that advances [`XorShift<u64>`] by `2 ** steps_log2` steps.
+const fn jump_matrix_64(steps_log2: u32) -> Matrix64 {
+    let mut matrix = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        let mut rng = XorShift { state: 1u64 << i };
+        matrix[i] = rng.next();
+        i += 1;
+    }
+
+    let mut k = 0;
+    while k < steps_log2 {
+        let mut squared = [0; 64];
+        let mut i = 0;
+        while i < 64 {
+            squared[i] = apply_matrix_64(&matrix, matrix[i]);
+            i += 1;
+        }
+        matrix = squared;
+        k += 1;
+    }
+    matrix
+}
+
+const JUMP_MATRIX_64: Matrix64 = jump_matrix_64(XorShift::<u64>::JUMP_STEPS_LOG2);
+
 macro_rules! generate_random {
     ($ty:ty, $seed:expr) => {{
         use crate::utils::xorshift::XorShift;
@@ -86,3 +154,49 @@ macro_rules! generate_random_array {
     }};
 }
 pub(crate) use generate_random_array;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jump_matrix_matches_stepping() {
+        let matrix = jump_matrix_64(10);
+        let mut rng = XorShift::<u64>::new(42);
+        let jumped = apply_matrix_64(&matrix, rng.state);
+        for _ in 0..1 << 10 {
+            rng.next();
+        }
+        assert_eq!(jumped, rng.state);
+    }
+
+    #[test]
+    fn test_jump_is_deterministic() {
+        let mut first = XorShift::<u64>::new(42);
+        let mut second = XorShift::<u64>::new(42);
+        first.jump();
+        second.jump();
+        for _ in 0..100 {
+            assert_eq!(first.next(), second.next());
+        }
+
+        let mut not_jumped = XorShift::<u64>::new(42);
+        let mut jumped = XorShift::<u64>::new(42);
+        jumped.jump();
+        assert_ne!(not_jumped.next(), jumped.next());
+    }
+
+    #[test]
+    fn test_split_streams_differ() {
+        let rng = XorShift::<u64>::new(42);
+        let mut first = rng.split(0);
+        let mut second = rng.split(1);
+        let first_seq: Vec<u64> = (0..100).map(|_| first.next()).collect();
+        let second_seq: Vec<u64> = (0..100).map(|_| second.next()).collect();
+        assert_ne!(first_seq, second_seq);
+
+        let mut again = rng.split(0);
+        let again_seq: Vec<u64> = (0..100).map(|_| again.next()).collect();
+        assert_eq!(first_seq, again_seq);
+    }
+}
@@ -0,0 +1,122 @@
+//! Checks the [`Hasher`] implementations generated by the `O1Hash` derive-macro.
+#![cfg(feature = "derive")]
+
+use o1::fks::FKSMap;
+use o1::hashing::hashers::msp::MSPHasher;
+use o1::O1Hash;
+use o1_core::{HashMap, Hasher};
+use o1_test::generate_hasher_tests;
+use rand::Rng;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, O1Hash)]
+enum Command {
+    Stop,
+    Pause,
+    Move(i32, i32),
+    Speed { value: u16 },
+    Say(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, O1Hash)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, O1Hash)]
+#[o1(no_const)]
+enum Label {
+    Missing,
+    Named(String),
+}
+
+fn make_commands() -> Vec<Command> {
+    let mut commands = vec![Command::Stop, Command::Pause, Command::Say("hello")];
+    for i in 0..20 {
+        for j in 0..20 {
+            commands.push(Command::Move(i, -j));
+        }
+        commands.push(Command::Speed { value: i as u16 });
+    }
+    commands
+}
+
+// Checks the range of the hash values and the effect of the seed, which the combining of the
+// variant and the fields in `EnumCombiner` is responsible for.
+generate_hasher_tests!(
+    runtime: MSPHasher<Command>,
+    Command,
+    |rng: &mut ChaCha20Rng| match rng.random_range(0..4) {
+        0 => Command::Stop,
+        1 => Command::Pause,
+        2 => Command::Move(rng.random(), rng.random()),
+        _ => Command::Speed {
+            value: rng.random(),
+        },
+    }
+);
+
+#[test]
+fn test_map_keyed_by_enum() {
+    let commands = make_commands();
+    let data: Vec<(Command, usize)> = commands.iter().copied().zip(0..).collect();
+    let map = FKSMap::<Command, usize, MSPHasher<Command>>::new(data.into(), 42, 0.75).unwrap();
+
+    assert!(map.verify());
+    for (i, command) in commands.iter().enumerate() {
+        assert_eq!(map.get(command), Some(&i), "Key: {:?}", command);
+    }
+    assert_eq!(map.get(&Command::Move(0, 1)), None);
+    assert_eq!(map.get(&Command::Speed { value: 100 }), None);
+    assert_eq!(map.get(&Command::Say("bye")), None);
+}
+
+#[test]
+fn test_fieldless_enum() {
+    let data = [(Color::Red, 0), (Color::Green, 1), (Color::Blue, 2)];
+    let map = FKSMap::<Color, u32, MSPHasher<Color>>::new(data.into(), 42, 0.75).unwrap();
+
+    for (color, value) in &data {
+        assert_eq!(map.get(color), Some(value));
+    }
+}
+
+#[test]
+fn test_hash_const_matches_hash() {
+    for num_buckets in [1, 7, 1 << 10, u32::MAX] {
+        let state = CommandHashState::make_state_const(42, num_buckets);
+        let hasher = MSPHasher::<Command>::from_state(state);
+        assert_eq!(state.num_buckets_const(), hasher.num_buckets());
+        for command in make_commands() {
+            let hash = hasher.hash(&command);
+            assert_eq!(state.hash_const(&command), hash);
+            assert!(hash < hasher.num_buckets());
+        }
+    }
+}
+
+#[test]
+fn test_variants_with_equal_fields_differ() {
+    let hasher = MSPHasher::<Command>::from_seed(42, u32::MAX);
+    let differ = (0..100).any(|i| {
+        hasher.hash(&Command::Move(i, 0)) != hasher.hash(&Command::Move(0, i))
+            || hasher.hash(&Command::Speed { value: i as u16 }) != hasher.hash(&Command::Move(i, 0))
+    });
+    assert!(differ);
+}
+
+#[test]
+fn test_no_const_enum() {
+    let data = [
+        (Label::Missing, 0),
+        (Label::Named("a".to_string()), 1),
+        (Label::Named("b".to_string()), 2),
+    ];
+    let map = FKSMap::<Label, u32, MSPHasher<Label>>::new(data.clone().into(), 42, 0.75).unwrap();
+
+    for (label, value) in &data {
+        assert_eq!(map.get(label), Some(value));
+    }
+    assert_eq!(map.get(&Label::Named("c".to_string())), None);
+}
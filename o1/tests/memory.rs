@@ -0,0 +1,92 @@
+//! Checks the memory usage of [`FKSMap`] with a counting global allocator.
+//!
+//! Kept in a separate binary whose tests run one at a time holding [`LOCK`], so that no other
+//! allocations interfere with the measurements.
+use o1::fks::FKSMap;
+use o1::hashing::hashers::msp::MSPHasher;
+use o1_core::HashMap;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::mem::size_of;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_new_streaming_peak_memory_is_linear() {
+    let _lock = LOCK.lock().unwrap();
+    const LEN: usize = 200_000;
+    /// [`FKSMap::new`] would take `LEN * LEN / 8` bytes - 5 GB - just for the bit-masks of the
+    /// keys of the buckets.
+    const MAX_BYTES_PER_ENTRY: usize = 256;
+
+    let data: Box<[(u64, u64)]> = (0..LEN as u64).map(|i| (i * 7919, i)).collect();
+    let keys: Vec<u64> = data.iter().map(|(key, _)| *key).collect();
+
+    let before = ALLOCATED.load(Ordering::SeqCst);
+    PEAK.store(before, Ordering::SeqCst);
+    let map = FKSMap::<u64, u64, MSPHasher<u64>>::new_streaming(data, 42, 0.75).unwrap();
+    let peak = PEAK.load(Ordering::SeqCst) - before;
+
+    assert!(
+        peak <= LEN * MAX_BYTES_PER_ENTRY,
+        "Peak: {peak} bytes, {} per entry",
+        peak / LEN
+    );
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(map.get(key), Some(&(i as u64)));
+    }
+}
+
+/// [`Clone`] allocates exactly the bucket and the slot arrays of the copy, so the allocated bytes
+/// are the heap part of [`FKSMap::total_memory_bytes`].
+#[test]
+fn test_total_memory_bytes_matches_allocations() {
+    let _lock = LOCK.lock().unwrap();
+    type Map = FKSMap<'static, String, u64, MSPHasher<String>>;
+
+    let data: Box<[(String, u64)]> = (0..10_000_u64).map(|i| (format!("{i:x}"), i)).collect();
+    let mut map = Map::new(data, 42, 0.75).unwrap();
+
+    for is_compact in [false, true] {
+        if is_compact {
+            map.compact();
+        }
+        let before = ALLOCATED.load(Ordering::SeqCst);
+        let clone = map.clone();
+        // The keys are cloned as well - their contents aren't accounted for.
+        let keys_bytes: usize = clone.iter().map(|(key, _)| key.capacity()).sum();
+        let allocated = ALLOCATED.load(Ordering::SeqCst) - before - keys_bytes;
+
+        assert_eq!(
+            clone.total_memory_bytes(),
+            size_of::<Map>() + allocated,
+            "Compact: {is_compact}"
+        );
+        assert_eq!(clone.total_memory_bytes(), map.total_memory_bytes());
+    }
+}
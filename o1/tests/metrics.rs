@@ -0,0 +1,32 @@
+//! Checks the counter of the bytes hashed by the string hashers.
+//!
+//! Kept in a separate binary with a single test, so that no other hashing interferes with the
+//! counter.
+#![cfg(feature = "metrics")]
+use o1::hashing::hashers::msp::{bytes_hashed, reset_bytes_hashed, MSPHasher};
+use o1_core::Hasher;
+
+#[test]
+fn test_bytes_hashed() {
+    let short = "abc";
+    let medium = "a".repeat(100);
+    let long = "b".repeat(1000);
+    let bytes: &[u8] = b"0123456789";
+
+    reset_bytes_hashed();
+
+    let str_hasher = <MSPHasher<&str> as Hasher<&str>>::from_seed(42, 1000);
+    str_hasher.hash(&short);
+    str_hasher.hash(&medium.as_str());
+    str_hasher.hash(&long.as_str());
+    <MSPHasher<String> as Hasher<String>>::from_seed(42, 1000).hash(&long);
+    <MSPHasher<&[u8]> as Hasher<&[u8]>>::from_seed(42, 1000).hash(&bytes);
+    let mut scratch = Vec::new();
+    MSPHasher::<String>::from_seed(42, 1000).hash_with_scratch(&medium, &mut scratch);
+
+    let expected =
+        short.len() + medium.len() + long.len() + long.len() + bytes.len() + medium.len();
+    assert_eq!(bytes_hashed(), expected);
+    assert_eq!(reset_bytes_hashed(), expected);
+    assert_eq!(bytes_hashed(), 0);
+}
@@ -0,0 +1,79 @@
+//! Property-based round-trip tests of [`FKSMap`] over arbitrary data and seeds.
+//!
+//! Enabled by the internal `_proptest` feature, failing cases are shrunk to the minimal data and
+//! seed.
+#![cfg(feature = "_proptest")]
+
+use o1::fks::FKSMap;
+use o1::hashing::hashers::msp::MSPHasher;
+use o1_core::{HashMap, Hasher};
+use proptest::prelude::*;
+use std::collections::HashMap as StdHashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Builds a map of `data` and checks that every key is found and every absent key misses.
+fn check_round_trip<K, V, H>(
+    data: StdHashMap<K, V>,
+    absent_keys: Vec<K>,
+    seed: u64,
+) -> Result<(), TestCaseError>
+where
+    K: Eq + Hash + Clone + Debug,
+    V: PartialEq + Clone + Debug,
+    H: Hasher<K>,
+{
+    let entries: Box<[(K, V)]> = data.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    let map = FKSMap::<K, V, H>::new(entries, seed, 0.75);
+    prop_assert!(map.is_ok(), "Construction failed: {:?}", map.err());
+    let map = map.unwrap();
+
+    for (key, value) in data.iter() {
+        prop_assert_eq!(map.get(key), Some(value), "Key: {:?}", key);
+    }
+    for key in absent_keys.iter().filter(|key| !data.contains_key(key)) {
+        prop_assert_eq!(map.get(key), None, "Key: {:?}", key);
+    }
+    Ok(())
+}
+
+/// Strings up to 300 characters long cover all the paths of the string hasher.
+const STRING_KEY: &str = "\\PC{0,300}";
+
+proptest! {
+    #[test]
+    fn test_round_trip_u64(
+        data in prop::collection::hash_map(any::<u64>(), any::<u32>(), 0..1000),
+        absent_keys in prop::collection::vec(any::<u64>(), 0..100),
+        seed in any::<u64>(),
+    ) {
+        check_round_trip::<_, _, MSPHasher<u64>>(data, absent_keys, seed)?;
+    }
+
+    #[test]
+    fn test_round_trip_u8(
+        data in prop::collection::hash_map(any::<u8>(), any::<u32>(), 0..=256),
+        absent_keys in prop::collection::vec(any::<u8>(), 0..100),
+        seed in any::<u64>(),
+    ) {
+        check_round_trip::<_, _, MSPHasher<u8>>(data, absent_keys, seed)?;
+    }
+
+    #[test]
+    fn test_round_trip_i128(
+        data in prop::collection::hash_map(any::<i128>(), any::<u32>(), 0..1000),
+        absent_keys in prop::collection::vec(any::<i128>(), 0..100),
+        seed in any::<u64>(),
+    ) {
+        check_round_trip::<_, _, MSPHasher<i128>>(data, absent_keys, seed)?;
+    }
+
+    #[test]
+    fn test_round_trip_string(
+        data in prop::collection::hash_map(STRING_KEY, any::<u32>(), 0..300),
+        absent_keys in prop::collection::vec(STRING_KEY, 0..100),
+        seed in any::<u64>(),
+    ) {
+        check_round_trip::<_, _, MSPHasher<String>>(data, absent_keys, seed)?;
+    }
+}
@@ -0,0 +1,70 @@
+//! Checks the [`Hasher`] implementations generated by `impl_transparent_hasher!`.
+use o1::fks::FKSMap;
+use o1::hashing::hashers::msp::MSPHasher;
+use o1::impl_transparent_hasher;
+use o1_core::{HashMap, Hasher};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+struct UserId(u64);
+
+impl_transparent_hasher!(UserId, u64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+struct Username(String);
+
+impl Username {
+    fn inner(&self) -> &String {
+        &self.0
+    }
+}
+
+impl_transparent_hasher!(Username, String, inner);
+
+#[test]
+fn test_transparent_hasher_forwards_to_inner() {
+    let hasher = <MSPHasher<UserId> as Hasher<UserId>>::from_seed(42, 1000);
+    let inner_hasher = <MSPHasher<u64> as Hasher<u64>>::from_seed(42, 1000);
+    assert_eq!(hasher.num_buckets(), inner_hasher.num_buckets());
+    for value in 0..1000 {
+        assert_eq!(hasher.hash(&UserId(value)), inner_hasher.hash(&value));
+    }
+}
+
+#[test]
+fn test_transparent_hasher_map() {
+    let data: Box<[(UserId, u32)]> = (0..1000).map(|i| (UserId(i * 7919), i as u32)).collect();
+    let map = FKSMap::<UserId, u32, MSPHasher<UserId>>::new(data.clone(), 42, 0.75).unwrap();
+    for (key, value) in data.iter() {
+        assert_eq!(map.get(key), Some(value), "Key: {:?}", key);
+    }
+    assert_eq!(map.get(&UserId(1)), None);
+}
+
+#[test]
+fn test_transparent_hasher_map_inner_method() {
+    let data: Box<[(Username, u32)]> = ["alice", "bob", "carol"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| (Username(name.to_string()), i as u32))
+        .collect();
+    let map = FKSMap::<Username, u32, MSPHasher<Username>>::new(data.clone(), 42, 0.75).unwrap();
+    for (key, value) in data.iter() {
+        assert_eq!(map.get(key), Some(value), "Key: {:?}", key);
+    }
+    assert_eq!(map.get(&Username("dave".to_string())), None);
+}
+
+#[cfg(feature = "xxh3")]
+#[test]
+fn test_transparent_hasher_map_xxh3() {
+    use o1::hashing::hashers::xxh3::XXH3Hasher;
+
+    let data: Box<[(UserId, u32)]> = (0..1000).map(|i| (UserId(i * 7919), i as u32)).collect();
+    let map = FKSMap::<UserId, u32, XXH3Hasher<UserId>>::new(data.clone(), 42, 0.75).unwrap();
+    for (key, value) in data.iter() {
+        assert_eq!(map.get(key), Some(value), "Key: {:?}", key);
+    }
+    assert_eq!(map.get(&UserId(1)), None);
+}
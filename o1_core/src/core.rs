@@ -46,10 +46,54 @@ where
     ///
     /// # Notes
     ///
-    /// - Currently only `u32` is supported due to lack of need for larger hash values.
+    /// - Only `u32` hash values are supported here, see [`WideHasher`] for wider ones.
     fn hash(&self, value: &T) -> u32;
 }
 
+/// Hasher capable of producing hash values wider than 32 bits.
+///
+/// The output width is chosen at the call site with the `NUM_BITS` const parameter and is
+/// independent from the number of buckets the hasher was created with - so the same instance
+/// can serve both [`Hasher::hash`] and larger-range purposes.
+///
+/// Implementations should also provide an equivalent `hash_wide_const()` method.
+pub trait WideHasher<T>: Hasher<T>
+where
+    T: Eq,
+{
+    /// Hash the given `value` into a `NUM_BITS`-bit hash value.
+    ///
+    /// `NUM_BITS` must be within `[1, 64]`.
+    fn hash_wide<const NUM_BITS: u32>(&self, value: &T) -> u64;
+}
+
+/// Hasher computing its hash values in two steps - a wide digest of the value followed by a
+/// cheap reduction of the digest to the hash value.
+///
+/// Hashers sharing the digest function can hash a value once with [`DigestHasher::digest`] and
+/// reduce the digest with each of them - e.g. the L1 and the L2 hashers of a two-level hash table.
+pub trait DigestHasher<T>: Hasher<T>
+where
+    T: Eq,
+{
+    /// The intermediate hash value.
+    type Digest: Copy;
+
+    /// Compute the digest of the given `value`.
+    ///
+    /// Depends only on the digest function of the hasher, not on its number of buckets.
+    fn digest(&self, value: &T) -> Self::Digest;
+
+    /// Reduce the `digest` to the hash value.
+    ///
+    /// `hash_digest(digest(value))` must be equal to `hash(value)`.
+    fn hash_digest(&self, digest: Self::Digest) -> u32;
+
+    /// Create a new hasher with the same digest function as this one and with the reduction
+    /// seeded with the given `seed` for `num_buckets` number of buckets.
+    fn with_shared_digest(&self, seed: u64, num_buckets: u32) -> Self;
+}
+
 // TODO: I'm not sure about the design choice of including `Hasher` as a generic parameter.
 //       It prevents designing Maps that rely on some specific "internal" hasher or hashers that
 //       require other inputs than just seed for initialization - for example count of keys
@@ -64,12 +108,44 @@ pub trait HashMap<K: Eq, V, H: Hasher<K>> {
     /// Get the value associated with the given `key`.
     fn get(&self, key: &K) -> Option<&V>;
 
+    /// Get the value associated with the given `key` or `default` if the key is absent.
+    fn get_or<'a>(&'a self, key: &K, default: &'a V) -> &'a V {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Get a copy of the value associated with the given `key` or the default value of `V` if
+    /// the key is absent.
+    fn get_or_default(&self, key: &K) -> V
+    where
+        V: Default + Clone,
+    {
+        self.get(key).cloned().unwrap_or_default()
+    }
+
+    /// Get the stored key and the value associated with the given `key`.
+    fn get_key_value(&self, key: &K) -> Option<(&K, &V)>;
+
     /// Get the number of elements in the map.
     fn len(&self) -> usize;
 
     /// Check if the map is empty.
     fn is_empty(&self) -> bool;
 
+    /// Get the number of entries the map can hold without being rebuilt.
+    ///
+    /// The maps are static, so it's the size of the storage they have allocated rather than a
+    /// limit of growth. Equal to [`len()`](HashMap::len) unless the implementation allocates
+    /// places for entries that stay unoccupied.
+    fn capacity(&self) -> usize {
+        self.len()
+    }
+
+    /// Get the number of the allocated places for entries that aren't occupied -
+    /// [`capacity()`](HashMap::capacity) minus [`len()`](HashMap::len).
+    fn slack(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
     /// Get the load factor of the map.
     fn load_factor(&self) -> f64;
 
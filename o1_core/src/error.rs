@@ -8,4 +8,29 @@ pub enum O1Error {
     /// that resolves in the context determined by the hashing scheme.
     #[error("Unable to find hash function suitable for resolving collisions.")]
     UnableToFindHashFunction,
+    /// Occurs when a hash table is constructed with parameters it could never be constructed
+    /// with, regardless of the seed.
+    #[error("Invalid configuration: {reason}")]
+    InvalidConfig {
+        /// The name of the offending parameter.
+        parameter: &'static str,
+        /// The description of the violated requirement.
+        reason: &'static str,
+    },
+    /// Occurs when a hash table is constructed with more entries than its hash functions can
+    /// address without truncating the bucket indices.
+    #[error("Too large: {len} entries exceed the maximum of {max}")]
+    TooLarge {
+        /// The number of entries passed to the constructor.
+        len: usize,
+        /// The maximum number of entries under the given configuration.
+        max: usize,
+    },
+    /// Occurs when resolving the hash functions of a hash table takes more trials than the
+    /// budget given to the constructor allows.
+    #[error("Budget exhausted: resolving the hash functions took more than {max_trials} trials")]
+    BudgetExhausted {
+        /// The maximum total number of trials.
+        max_trials: usize,
+    },
 }
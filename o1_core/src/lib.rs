@@ -1,3 +1,4 @@
+#![no_std]
 pub mod core;
 pub use core::*;
 pub mod error;
@@ -0,0 +1,282 @@
+//! Derive-macros generating the hashers of the `o1` crate.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident};
+
+/// Derives `Hasher<T>` for `MSPHasher<T>` where `T` is an enum.
+///
+/// The hash value of a fieldless variant is the hash of the index of the variant in the order of
+/// declaration. The hash values of the fields of a data-carrying variant are computed by the
+/// `MSPHasher` of their types and combined with the hash of the index by
+/// `o1::hashing::hashers::msp::EnumCombiner`.
+///
+/// The macro generates the state of the hasher - a struct named `<Enum>HashState` with the same
+/// visibility as the enum. Besides `from_seed`, the state has the const methods
+/// `make_state_const`, `num_buckets_const` and `hash_const` - the counterparts of the const methods
+/// of the built-in hashers. They require every field type to have the const methods as well, so
+/// they aren't generated when the enum is annotated with `#[o1(no_const)]`.
+///
+/// # Notes
+///
+/// - Generic enums and enums without variants aren't supported.
+/// - The state must be `Copy`, so every field type must have a `Copy` state.
+/// - `MSPHasher<Enum>` has no inherent const methods - inherent methods of `MSPHasher` can only be
+///   defined in the `o1` crate, and const trait methods aren't stable yet. So the compile-time
+///   constructors of the maps can't be used with it, only the const methods of the state can
+///   hash the values at compile time.
+///
+/// # Examples
+///
+/// ```rust
+/// use o1::fks::FKSMap;
+/// use o1::hashing::hashers::msp::MSPHasher;
+/// use o1::O1Hash;
+/// use o1_core::{HashMap, Hasher};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, O1Hash)]
+/// enum Shape {
+///     Point,
+///     Circle(u32),
+///     Rect { width: u32, height: u32 },
+/// }
+///
+/// let data = [(Shape::Point, 0), (Shape::Circle(3), 1), (Shape::Rect { width: 2, height: 5 }, 2)];
+/// let map = FKSMap::<Shape, u32, MSPHasher<Shape>>::new(data.into(), 42, 0.75).unwrap();
+/// assert_eq!(map.get(&Shape::Circle(3)), Some(&1));
+///
+/// // The state hashes the values at compile time.
+/// const STATE: ShapeHashState = ShapeHashState::make_state_const(42, 16);
+/// const HASH: u32 = STATE.hash_const(&Shape::Circle(3));
+/// assert_eq!(MSPHasher::<Shape>::from_state(STATE).hash(&Shape::Circle(3)), HASH);
+/// ```
+#[proc_macro_derive(O1Hash, attributes(o1))]
+pub fn derive_o1_hash(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A field of a variant of the enum.
+struct Field {
+    /// The identifier the field is bound to in the `match` arms.
+    binding: Ident,
+    /// The field of the generated state holding the state of the field's hasher.
+    state: Ident,
+    ty: syn::Type,
+}
+
+/// A variant of the enum.
+struct Variant {
+    /// The pattern matching the variant and binding its fields.
+    pattern: TokenStream2,
+    fields: Vec<Field>,
+}
+
+/// Checks whether the enum is annotated with `#[o1(no_const)]`.
+fn parse_no_const(input: &DeriveInput) -> syn::Result<bool> {
+    let mut no_const = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("o1")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("no_const") {
+                no_const = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported \"o1\" attribute"))
+            }
+        })?;
+    }
+    Ok(no_const)
+}
+
+fn parse_variants(input: &DeriveInput) -> syn::Result<Vec<Variant>> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "\"O1Hash\" can only be derived for enums",
+        ));
+    };
+    if data.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "\"O1Hash\" can't be derived for enums without variants",
+        ));
+    }
+    let enum_ident = &input.ident;
+    let mut field_idx = 0_usize;
+    let mut variants = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        let variant_ident = &variant.ident;
+        let mut fields = Vec::with_capacity(variant.fields.len());
+        for field in &variant.fields {
+            fields.push(Field {
+                binding: format_ident!("f{}", fields.len()),
+                state: format_ident!("field_{}", field_idx),
+                ty: field.ty.clone(),
+            });
+            field_idx += 1;
+        }
+        let bindings = fields.iter().map(|field| &field.binding);
+        let pattern = match &variant.fields {
+            Fields::Unit => quote!(#enum_ident::#variant_ident),
+            Fields::Unnamed(_) => quote!(#enum_ident::#variant_ident(#(#bindings),*)),
+            Fields::Named(named) => {
+                let names = named.named.iter().map(|field| &field.ident);
+                quote!(#enum_ident::#variant_ident { #(#names: #bindings),* })
+            }
+        };
+        variants.push(Variant { pattern, fields });
+    }
+    Ok(variants)
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "\"O1Hash\" doesn't support generic enums",
+        ));
+    }
+    let no_const = parse_no_const(&input)?;
+    let variants = parse_variants(&input)?;
+
+    let enum_ident = &input.ident;
+    let vis = &input.vis;
+    let state_ident = format_ident!("{}HashState", enum_ident);
+    let max_num_fields = variants.iter().map(|v| v.fields.len()).max().unwrap_or(0);
+
+    let msp = quote!(::o1::hashing::hashers::msp);
+    let combiner_ty = quote!(#msp::EnumCombiner<#max_num_fields>);
+    let fields: Vec<&Field> = variants.iter().flat_map(|v| &v.fields).collect();
+    let state_fields = fields.iter().map(|field| &field.state);
+    let state_tys = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote!(<#msp::MSPHasher<#ty> as ::o1_core::Hasher<#ty>>::State)
+    });
+    let field_seeds = (0..fields.len()).map(|idx| quote!(<#combiner_ty>::field_seed(seed, #idx)));
+
+    let make_states = fields.iter().zip(field_seeds.clone()).map(|(field, seed)| {
+        let (state, ty) = (&field.state, &field.ty);
+        quote! {
+            #state: <#msp::MSPHasher<#ty> as ::o1_core::Hasher<#ty>>::make_state(#seed, u32::MAX)
+        }
+    });
+    let arms = hash_arms(&variants, |field| {
+        let (state, ty, binding) = (&field.state, &field.ty, &field.binding);
+        quote! {
+            <#msp::MSPHasher<#ty> as ::o1_core::Hasher<#ty>>::hash(
+                &<#msp::MSPHasher<#ty> as ::o1_core::Hasher<#ty>>::from_state(
+                    self.state().#state.clone(),
+                ),
+                #binding,
+            )
+        }
+    });
+
+    let const_methods = if no_const {
+        quote!()
+    } else {
+        let make_states_const = fields.iter().zip(field_seeds).map(|(field, seed)| {
+            let (state, ty) = (&field.state, &field.ty);
+            quote!(#state: #msp::MSPHasher::<#ty>::make_state_const(#seed, u32::MAX))
+        });
+        let arms_const = hash_arms(&variants, |field| {
+            let (state, ty, binding) = (&field.state, &field.ty, &field.binding);
+            quote!(#msp::MSPHasher::<#ty>::from_state_const(self.#state).hash_const(#binding))
+        });
+        quote! {
+            impl #state_ident {
+                pub const fn make_state_const(seed: u64, num_buckets: u32) -> Self {
+                    Self {
+                        combiner: <#combiner_ty>::from_seed(seed, num_buckets),
+                        #(#make_states_const,)*
+                    }
+                }
+                pub const fn num_buckets_const(&self) -> u32 {
+                    self.combiner.num_buckets()
+                }
+                pub const fn hash_const(&self, value: &#enum_ident) -> u32 {
+                    let combiner = &self.combiner;
+                    match value {
+                        #(#arms_const)*
+                    }
+                }
+            }
+        }
+    };
+
+    let state_doc = format!("State of `MSPHasher<{enum_ident}>` generated by `O1Hash`.");
+    Ok(quote! {
+        #[doc = #state_doc]
+        #[derive(Debug, Clone, Copy, Default)]
+        #vis struct #state_ident {
+            combiner: #combiner_ty,
+            #(#state_fields: #state_tys,)*
+        }
+
+        impl #state_ident {
+            pub fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                Self {
+                    combiner: <#combiner_ty>::from_seed(seed, num_buckets),
+                    #(#make_states,)*
+                }
+            }
+        }
+
+        #const_methods
+
+        impl ::o1_core::Hasher<#enum_ident> for #msp::MSPHasher<#enum_ident> {
+            type State = #state_ident;
+
+            fn make_state(seed: u64, num_buckets: u32) -> Self::State {
+                #state_ident::from_seed(seed, num_buckets)
+            }
+            fn from_seed(seed: u64, num_buckets: u32) -> Self {
+                Self::from_state(#state_ident::from_seed(seed, num_buckets))
+            }
+            fn from_state(state: Self::State) -> Self {
+                Self::__from_state(state)
+            }
+            fn state(&self) -> &Self::State {
+                self.__state()
+            }
+            fn num_buckets(&self) -> u32 {
+                self.state().combiner.num_buckets()
+            }
+            fn hash(&self, value: &#enum_ident) -> u32 {
+                let combiner = &self.state().combiner;
+                match value {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// Generates the `match` arms hashing the variants, `hash_field` generates the expression hashing
+/// a field.
+fn hash_arms(
+    variants: &[Variant],
+    hash_field: impl Fn(&Field) -> TokenStream2,
+) -> Vec<TokenStream2> {
+    variants
+        .iter()
+        .enumerate()
+        .map(|(variant_idx, variant)| {
+            let variant_idx = variant_idx as u32;
+            let pattern = &variant.pattern;
+            let combines = variant.fields.iter().enumerate().map(|(field_idx, field)| {
+                let field_hash = hash_field(field);
+                quote!(let acc = combiner.combine(acc, #field_idx, #field_hash);)
+            });
+            quote! {
+                #pattern => {
+                    let acc = combiner.start(#variant_idx);
+                    #(#combines)*
+                    combiner.finish(acc)
+                }
+            }
+        })
+        .collect()
+}
@@ -3,6 +3,8 @@ use rand::distr::Alphanumeric;
 use rand::Rng;
 use std::collections::HashSet;
 use std::hash::Hash;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
 
 /// Provides capabilities to generate random values of the implementer-type.
 pub trait Generate<R: Rng>: Sized {
@@ -107,6 +109,77 @@ impl<R: Rng> Generate<R> for String {
     }
 }
 
+impl<R: Rng> Generate<R> for Vec<u8> {
+    type GenerateParams = StringParams;
+
+    fn generate(rng: &mut R, params: &Self::GenerateParams) -> Self {
+        let length = rng.random_range(params.min_length..=params.max_length);
+        (0..length).map(|_| rng.random()).collect()
+    }
+}
+
+impl Default for NumParams<Duration> {
+    fn default() -> Self {
+        Self {
+            min: Duration::ZERO,
+            max: Duration::MAX,
+        }
+    }
+}
+
+impl<R: Rng> Generate<R> for Duration {
+    type GenerateParams = NumParams<Duration>;
+
+    fn generate(rng: &mut R, params: &Self::GenerateParams) -> Self {
+        rng.random_range(params.min..=params.max)
+    }
+}
+
+/// Generates addresses from the range of their integer representations.
+impl<R: Rng> Generate<R> for Ipv4Addr {
+    type GenerateParams = NumParams<u32>;
+
+    fn generate(rng: &mut R, params: &Self::GenerateParams) -> Self {
+        Ipv4Addr::from_bits(u32::generate(rng, params))
+    }
+}
+
+/// Generates addresses from the range of their integer representations.
+impl<R: Rng> Generate<R> for Ipv6Addr {
+    type GenerateParams = NumParams<u128>;
+
+    fn generate(rng: &mut R, params: &Self::GenerateParams) -> Self {
+        Ipv6Addr::from_bits(u128::generate(rng, params))
+    }
+}
+
+/// Provides capabilities to generate random values close to a given one.
+///
+/// Useful for generating clustered values - e.g. IDs sharing the high-order bits.
+pub trait GenerateNear<R: Rng>: Sized {
+    /// Generates a random value that differs from `center` only in the `num_low_bits` low-order
+    /// bits.
+    fn generate_near(rng: &mut R, center: &Self, num_low_bits: u32) -> Self;
+}
+
+macro_rules! impl_generate_near_num {
+    ($($type:ty),*) => {
+        $(
+            impl<R: Rng> GenerateNear<R> for $type {
+                fn generate_near(rng: &mut R, center: &Self, num_low_bits: u32) -> Self {
+                    // Wrapping, because `1 << (BITS - 1)` is the minimum of the signed types.
+                    let mask: $type = (1 as $type)
+                        .checked_shl(num_low_bits)
+                        .map_or(!0, |bit| bit.wrapping_sub(1));
+                    (*center & !mask) | (rng.random::<$type>() & mask)
+                }
+            }
+        )*
+    };
+}
+
+impl_generate_near_num!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+
 /// Provides capabilities to derive new random values by introducing minimal random changes.
 ///
 /// Useful for generating random values that are very similar with each other.
@@ -150,6 +223,42 @@ macro_rules! impl_jitter_num {
 
 impl_jitter_num!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
 
+/// Flips a random bit of either the seconds or the nanoseconds, the latter are retried until they
+/// stay below one second.
+impl<R: Rng> Jitter<R> for Duration {
+    fn jitter(&self, rng: &mut R) -> Option<Self> {
+        const NANOS_BITS: u32 = 30;
+
+        loop {
+            let bit_idx = rng.random_range(0..u64::BITS + NANOS_BITS);
+            if bit_idx < u64::BITS {
+                return Some(Duration::new(
+                    self.as_secs() ^ (1 << bit_idx),
+                    self.subsec_nanos(),
+                ));
+            }
+            let nanos = self.subsec_nanos() ^ (1 << (bit_idx - u64::BITS));
+            if nanos < 1_000_000_000 {
+                return Some(Duration::new(self.as_secs(), nanos));
+            }
+        }
+    }
+}
+
+/// Flips a random bit of the integer representation of the address.
+impl<R: Rng> Jitter<R> for Ipv4Addr {
+    fn jitter(&self, rng: &mut R) -> Option<Self> {
+        self.to_bits().jitter(rng).map(Ipv4Addr::from_bits)
+    }
+}
+
+/// Flips a random bit of the integer representation of the address.
+impl<R: Rng> Jitter<R> for Ipv6Addr {
+    fn jitter(&self, rng: &mut R) -> Option<Self> {
+        self.to_bits().jitter(rng).map(Ipv6Addr::from_bits)
+    }
+}
+
 impl<R: Rng> Jitter<R> for String {
     fn jitter(&self, rng: &mut R) -> Option<Self> {
         if self.is_empty() {
@@ -194,3 +303,77 @@ impl<R: Rng> Jitter<R> for String {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    /// Generates many values and checks that jittering changes every one of them.
+    fn check_jitter<T>()
+    where
+        T: Generate<ChaCha20Rng> + Jitter<ChaCha20Rng> + Hash + Eq + Copy + std::fmt::Debug,
+    {
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let params = T::GenerateParams::default();
+        let values = T::generate_many(&mut rng, &params, 1000);
+        assert_eq!(values.len(), 1000);
+        for value in values {
+            let jittered = value.jitter(&mut rng).unwrap();
+            assert_ne!(jittered, value);
+        }
+    }
+
+    #[test]
+    fn test_duration_jitter() {
+        check_jitter::<Duration>();
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let max = Duration::new(u64::MAX, 999_999_999);
+        for _ in 0..1000 {
+            assert!(max.jitter(&mut rng).unwrap().subsec_nanos() < 1_000_000_000);
+        }
+    }
+
+    #[test]
+    fn test_ipv4_jitter() {
+        check_jitter::<Ipv4Addr>();
+    }
+
+    #[test]
+    fn test_ipv6_jitter() {
+        check_jitter::<Ipv6Addr>();
+    }
+
+    #[test]
+    fn test_generate_near() {
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let center = u64::generate(&mut rng, &NumParams::default());
+        for _ in 0..1000 {
+            let value = u64::generate_near(&mut rng, &center, 10);
+            assert_eq!(value >> 10, center >> 10);
+        }
+        let values: HashSet<u64> = (0..1000)
+            .map(|_| u64::generate_near(&mut rng, &center, u64::BITS))
+            .collect();
+        assert!(values.len() > 990);
+    }
+
+    #[test]
+    fn test_generate_near_signed() {
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let center = i8::MIN;
+        let values: HashSet<i8> = (0..1000)
+            .map(|_| i8::generate_near(&mut rng, &center, i8::BITS - 1))
+            .collect();
+        assert!(values.iter().all(|value| *value < 0), "{values:?}");
+        assert_eq!(values.len(), 128);
+
+        let center = i64::generate(&mut rng, &NumParams::default());
+        for _ in 0..1000 {
+            let value = i64::generate_near(&mut rng, &center, i64::BITS - 1);
+            assert_eq!(value < 0, center < 0);
+        }
+    }
+}
@@ -1,8 +1,15 @@
+use o1_core::Hasher;
+use rand::Rng;
+use std::fmt::Debug;
+
 /// Generates standard test cases for hashers.
 ///
 /// This macro generates test functions that verify:
 /// - Equivalence between runtime and const-time methods
 ///
+/// Hashers without a const implementation are tested with the `runtime:` prefix instead, which
+/// verifies the invariants of the runtime methods - see [`check_runtime_hasher`].
+///
 /// # Parameters
 ///
 /// - `test_name`: The name of the test function
@@ -18,9 +25,32 @@
 ///     u32,
 ///     |rng| rng.random::<u32>()
 /// );
+/// generate_hasher_tests!(
+///     runtime: HighwayHasher<u32>,
+///     u32,
+///     |rng| rng.random::<u32>()
+/// );
 /// ```
 #[macro_export]
 macro_rules! generate_hasher_tests {
+    (runtime: $hasher_type:ty, $key_type:ty, $generate_key:expr$(,)?) => {
+        compose_idents::compose_idents!(
+            test_fn = concat(test_hasher_runtime_hashing_, normalize($key_type)),
+            {
+                #[test]
+                fn test_fn() {
+                    use rand::SeedableRng;
+                    use rand_chacha::ChaCha20Rng;
+
+                    $crate::check_runtime_hasher::<$hasher_type, $key_type, _>(
+                        &mut ChaCha20Rng::from_os_rng(),
+                        &$generate_key,
+                        50,
+                    );
+                }
+            }
+        );
+    };
     ($hasher_type:ty, $key_type:ty, $generate_key:expr$(,)?) => {
         compose_idents::compose_idents!(
             test_fn = concat(test_hasher_const_hashing_equivalence_, normalize($key_type)),
@@ -44,3 +74,51 @@ macro_rules! generate_hasher_tests {
     };
 }
 pub use generate_hasher_tests;
+
+/// Verifies the invariants of the runtime methods of the hasher `H`.
+///
+/// # Parameters
+///
+/// - `rng`: A random number generator used to create test keys and seeds.
+/// - `gen_key`: Key generator function.
+/// - `num_trials`: Number of different (seed, key) pairs to test.
+///
+/// # Panics
+///
+/// - If a hash value is out of the range of the number of buckets of the hasher.
+/// - If hashers created from the same seed or from the same state produce different hash values.
+/// - If changing the seed doesn't change any of the hash values.
+pub fn check_runtime_hasher<H, K, R>(rng: &mut R, gen_key: &dyn Fn(&mut R) -> K, num_trials: usize)
+where
+    H: Hasher<K>,
+    K: Eq + Debug,
+    R: Rng,
+{
+    let keys: Vec<K> = (0..num_trials).map(|_| gen_key(rng)).collect();
+
+    for num_buckets in [0, 1, 2, 7, 1 << 10, 1 << 16, u32::MAX] {
+        let seed = rng.next_u64();
+        let hasher = H::from_seed(seed, num_buckets);
+        let same_seed = H::from_seed(seed, num_buckets);
+        let same_state = H::from_state(H::make_state(seed, num_buckets));
+        assert!(hasher.num_buckets() >= num_buckets);
+
+        for key in keys.iter() {
+            let hash = hasher.hash(key);
+            assert!(
+                hash < hasher.num_buckets(),
+                "Hash value {hash} of key {key:?} is out of {} buckets",
+                hasher.num_buckets()
+            );
+            assert_eq!(hash, same_seed.hash(key), "Seed: {seed}, key: {key:?}");
+            assert_eq!(hash, same_state.hash(key), "Seed: {seed}, key: {key:?}");
+        }
+    }
+
+    let hasher = H::from_seed(1, u32::MAX);
+    let other = H::from_seed(2, u32::MAX);
+    assert!(
+        keys.iter().any(|key| hasher.hash(key) != other.hash(key)),
+        "Changing the seed doesn't change the hash values"
+    );
+}
@@ -1,5 +1,5 @@
 //! Utilities for testing map implementations.
-use crate::Generate;
+use crate::{Generate, GenerateNear};
 use o1_core::HashMap;
 use o1_core::Hasher;
 use rand::Rng;
@@ -22,6 +22,64 @@ pub fn generate_map_data<R: Rng, K: Eq + Hash + Generate<R>, V: Eq + Hash + Gene
         .into_boxed_slice()
 }
 
+/// Distribution of the generated keys.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum KeyDistribution {
+    /// The keys are spread uniformly over the whole range of the type.
+    #[default]
+    Uniform,
+    /// The keys are grouped into `num_clusters` clusters, the keys of a cluster share all but the
+    /// `num_low_bits` low-order bits - like the keys from real ID spaces.
+    Clustered {
+        num_clusters: usize,
+        num_low_bits: u32,
+    },
+}
+
+/// Generates data suitable for passing to a map constructor with keys following the given
+/// distribution.
+///
+/// # Panics
+///
+/// - If the clusters can't fit `size` distinct keys.
+pub fn generate_map_data_distributed<
+    R: Rng,
+    K: Eq + Hash + Generate<R> + GenerateNear<R>,
+    V: Eq + Hash + Generate<R>,
+>(
+    rng: &mut R,
+    size: usize,
+    distribution: KeyDistribution,
+    key_params: &K::GenerateParams,
+    val_params: &V::GenerateParams,
+) -> Box<[(K, V)]> {
+    let (num_clusters, num_low_bits) = match distribution {
+        KeyDistribution::Uniform => {
+            return generate_map_data(rng, size, key_params, val_params);
+        }
+        KeyDistribution::Clustered {
+            num_clusters,
+            num_low_bits,
+        } => (num_clusters, num_low_bits),
+    };
+    assert!(
+        num_clusters > 0 && (num_clusters << num_low_bits.min(63)) >= size,
+        "The clusters can't fit {size} keys"
+    );
+
+    let centers = K::generate_many(rng, key_params, num_clusters);
+    let mut keys = HashSet::new();
+    while keys.len() < size {
+        let center = &centers[rng.random_range(0..num_clusters)];
+        keys.insert(K::generate_near(rng, center, num_low_bits));
+    }
+    let vals = V::generate_many(rng, val_params, size).into_vec();
+    keys.into_iter()
+        .zip(vals)
+        .collect::<Vec<_>>()
+        .into_boxed_slice()
+}
+
 /// Tests key retrieval for a given map.
 pub fn test_get<
     R: Rng,
@@ -106,6 +164,46 @@ macro_rules! generate_map_int_tests {
     };
 }
 
+/// Generates tests for a map type for clustered integer keys.
+///
+/// The keys of a cluster differ only in the low-order bits, which stresses the hashers that
+/// depend mostly on the high-order bits of the product.
+#[macro_export]
+macro_rules! generate_map_int_clustered_tests {
+    ($Map:tt, $Hasher:tt, $cons: expr, $($type:ty),*) => {
+        $(
+            compose_idents!(test_fn = concat(test_build_get_map_clustered_, $type), {
+                #[test]
+                fn test_fn() {
+                    use $crate::*;
+
+                    use rand::rngs::ThreadRng;
+                    let mut rng = rand::rng();
+
+                    let data = generate_map_data_distributed::<_, $type, u128>(
+                        &mut rng,
+                        999,
+                        KeyDistribution::Clustered {
+                            num_clusters: 4,
+                            num_low_bits: 10,
+                        },
+                        &<$type as Generate<ThreadRng>>::GenerateParams::default(),
+                        &<u128 as Generate<ThreadRng>>::GenerateParams::default(),
+                    );
+                    let map = test_build::<
+                        $type,
+                        u128,
+                        $Hasher<$type>,
+                        $Map<$type, u128, $Hasher<$type>>,
+                        _,
+                    >($cons, data.to_vec().into_boxed_slice());
+                    test_get(&mut rng, map, &data);
+                }
+            });
+        )*
+    };
+}
+
 /// Generates tests of special cases for a map type for integer keys.
 #[macro_export]
 macro_rules! generate_map_int_special_tests {
@@ -216,6 +314,7 @@ macro_rules! generate_map_str_special_tests {
 macro_rules! generate_map_tests {
     ($Map:tt, $Hasher:tt, $factory:expr) => {
         use compose_idents::compose_idents;
+        use $crate::generate_map_int_clustered_tests;
         use $crate::generate_map_int_special_tests;
         use $crate::generate_map_int_tests;
         use $crate::generate_map_str_special_tests;
@@ -234,6 +333,7 @@ macro_rules! generate_map_tests {
         generate_map_int_special_tests!(
             $Map, $Hasher, $factory, u8, i8, u16, i16, u32, i32, u64, i64, u128, i128
         );
+        generate_map_int_clustered_tests!($Map, $Hasher, $factory, u32, i32, u64, i64, u128, i128);
         generate_map_str_tests!($Map, $Hasher, $factory);
         generate_map_str_special_tests!($Map, $Hasher, $factory);
     };
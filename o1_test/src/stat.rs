@@ -248,9 +248,155 @@ where
     }
 }
 
-type HashFunctionFamily<R, K> = dyn Fn(&mut R, usize) -> (Box<dyn Fn(&K) -> usize>, usize);
+pub type HashFunctionFamily<R, K> = dyn Fn(&mut R, usize) -> (Box<dyn Fn(&K) -> usize>, usize);
+
+/// A result of a strong universality test.
+#[derive(Debug)]
+pub struct UniversalityResult {
+    pub outcome: bool,
+    pub independence: PValueAggregation,
+    pub uniformity: PValueAggregation,
+    pub max_mi: f64,
+    pub max_mi_threshold: f64,
+}
+
+/// A configurable test of a hash function family for strong universality.
+///
+/// Unlike [`strong_universality`] doesn't assert anything and returns a [`UniversalityResult`]
+/// instead, which allows testing hash functions that are expected to fail.
+#[derive(Debug, Clone, Copy)]
+pub struct UniversalityTest {
+    num_buckets: usize,
+    num_samples_per_bucket: u32,
+    num_trials: u32,
+    alpha: f64,
+    max_mi: f64,
+}
+
+impl Default for UniversalityTest {
+    fn default() -> Self {
+        Self {
+            num_buckets: 16,
+            num_samples_per_bucket: 15,
+            num_trials: 1000,
+            alpha: 0.01,
+            // Currently this is more of a sanity check, 0.09 threshold has been chosen based on
+            // practice as a guard against hash functions that have serious flaws.
+            // TODO: Stricter threshold should be applied.
+            max_mi: 0.09,
+        }
+    }
+}
+
+impl UniversalityTest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the requested number of buckets - the family may round it.
+    pub fn num_buckets(mut self, num_buckets: usize) -> Self {
+        self.num_buckets = num_buckets;
+        self
+    }
+
+    /// Sets the number of sampled hash functions per a pair of buckets in each trial.
+    pub fn num_samples_per_bucket(mut self, num_samples_per_bucket: u32) -> Self {
+        self.num_samples_per_bucket = num_samples_per_bucket;
+        self
+    }
+
+    /// Sets the number of trials - each one tests a different pair of keys.
+    pub fn num_trials(mut self, num_trials: u32) -> Self {
+        self.num_trials = num_trials;
+        self
+    }
+
+    /// Sets the significance level of the chi-square tests.
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Sets the maximum allowed bias-corrected mutual information between the hash values.
+    pub fn max_mi(mut self, max_mi: f64) -> Self {
+        self.max_mi = max_mi;
+        self
+    }
+
+    /// Runs the test against the given hash function family.
+    pub fn run<R, K>(&self, rng: &mut R, family: &HashFunctionFamily<R, K>) -> UniversalityResult
+    where
+        R: Rng,
+        K: PartialEq + Default + Clone + Generate<R> + Jitter<R> + Debug,
+    {
+        let (_, num_buckets) = family(rng, self.num_buckets);
+        let num_possible_pairs = num_buckets.pow(2);
+
+        let mut independence_statistics = Vec::new();
+        let mut uniformity_statistics = Vec::new();
+        let mut max_mi = 0.0;
+
+        let mut x = K::generate(rng, &<K as Generate<R>>::GenerateParams::default());
+        let mut y: K;
+
+        for _ in 0..self.num_trials {
+            let num_samples = self.num_samples_per_bucket as usize * num_possible_pairs;
+            (x, y) = loop {
+                let new_x = x.clone().jitter(rng).unwrap();
+                let new_y = x.clone().jitter(rng).unwrap();
+                if new_x != new_y {
+                    break (new_x, new_y);
+                }
+            };
+            let mut hxs = Array1::zeros(num_samples);
+            let mut hys = Array1::zeros(num_samples);
+
+            for i in 0..num_samples {
+                let (hash_function, _) = family(rng, num_buckets);
+                hxs[i] = hash_function(&x);
+                hys[i] = hash_function(&y);
+            }
+            let contingency: Array2<f64> = make_contingency_matrix(&hxs, &hys, num_buckets);
+            independence_statistics.push(chi2_independence(&contingency));
+            uniformity_statistics.push(chi2_uniformity(
+                contingency
+                    .view()
+                    .into_shape_with_order((contingency.len(),))
+                    .unwrap(),
+            ));
+
+            let mi_statistic = mutual_information(&contingency);
+            max_mi = max_mi.max(mi_statistic.bias_corrected_mi);
+        }
+
+        let independence_p_values = Array1::from_shape_vec(
+            independence_statistics.len(),
+            independence_statistics.iter().map(|s| s.p_value).collect(),
+        )
+        .unwrap();
+        let uniformity_p_values = Array1::from_shape_vec(
+            uniformity_statistics.len(),
+            uniformity_statistics.iter().map(|s| s.p_value).collect(),
+        )
+        .unwrap();
+
+        let independence = aggregate_p_values(&independence_p_values, self.alpha);
+        let uniformity = aggregate_p_values(&uniformity_p_values, self.alpha);
+        let outcome = independence.outcome && uniformity.outcome && max_mi < self.max_mi;
+
+        UniversalityResult {
+            outcome,
+            independence,
+            uniformity,
+            max_mi,
+            max_mi_threshold: self.max_mi,
+        }
+    }
+}
 
 /// Tests a hash function family for strong universality.
+///
+/// A shortcut for [`UniversalityTest`] that panics if the test fails.
 pub fn strong_universality<R, K>(
     rng: &mut R,
     family: &HashFunctionFamily<R, K>,
@@ -261,77 +407,125 @@ pub fn strong_universality<R, K>(
 ) where
     R: Rng,
     K: PartialEq + Default + Clone + Generate<R> + Jitter<R> + Debug,
+{
+    let result = UniversalityTest::new()
+        .num_buckets(raw_num_buckets)
+        .num_samples_per_bucket(num_samples_per_bucket)
+        .num_trials(num_trials)
+        .alpha(alpha)
+        .run(rng, family);
+
+    assert!(
+        result.independence.outcome,
+        "Pairwise independence test has failed:\n{:?}",
+        result.independence,
+    );
+    assert!(
+        result.uniformity.outcome,
+        "Pairwise uniformity test has failed:\n{:?}",
+        result.uniformity,
+    );
+    assert!(
+        result.max_mi < result.max_mi_threshold,
+        "Max MI is too high: {}",
+        result.max_mi
+    );
+}
+
+/// A result of an avalanche test.
+#[derive(Debug)]
+pub struct AvalancheStatistic {
+    /// Empirical probability of each output bit to flip when a single input bit is flipped.
+    pub flip_probabilities: Vec<f64>,
+    /// Mean Hamming distance between the hashes of the original and the altered keys.
+    pub mean_hamming_distance: f64,
+    /// Number of the hashed pairs of keys.
+    pub num_samples: usize,
+}
+
+/// Tests a hash function family for the avalanche property.
+///
+/// For every key a hash function is drawn from the family and the key is altered
+/// `num_flips_per_key` times with [`Jitter`] which for integers flips exactly one random bit.
+/// Then each output bit is expected to flip with probability close to 0.5.
+///
+/// # Parameters
+///
+/// - `rng`: A random number generator.
+/// - `family`: A hash function family. The number of buckets it produces must be a power of two.
+/// - `raw_num_buckets`: The desired number of buckets.
+/// - `num_keys`: The number of the generated keys.
+/// - `num_flips_per_key`: The number of the altered copies of each key.
+/// - `tolerance`: The maximum allowed deviation of the flip probability of an output bit from 0.5.
+///
+/// # Notes
+///
+/// The standard deviation of the empirical flip probability of an ideal hash function is
+/// `0.5 / sqrt(num_keys * num_flips_per_key)`. The tolerance should be several times larger than
+/// that, otherwise the test would be flaky - e.g. for 1000 keys and 32 flips per key the standard
+/// deviation is about 0.003 and tolerance of 0.02 makes it a 7-sigma event for a good hash
+/// function to fail.
+pub fn avalanche<R, K>(
+    rng: &mut R,
+    family: &HashFunctionFamily<R, K>,
+    raw_num_buckets: usize,
+    num_keys: u32,
+    num_flips_per_key: u32,
+    tolerance: f64,
+) -> AvalancheStatistic
+where
+    R: Rng,
+    K: PartialEq + Generate<R> + Jitter<R> + Debug,
 {
     let (_, num_buckets) = family(rng, raw_num_buckets);
-    let num_possible_pairs = num_buckets.pow(2);
-
-    let mut independence_statistics = Vec::new();
-    let mut uniformity_statistics = Vec::new();
-    let mut max_mi = 0.0;
-
-    let mut x = K::generate(rng, &<K as Generate<R>>::GenerateParams::default());
-    let mut y: K;
-
-    for _ in 0..num_trials {
-        let num_trials = num_samples_per_bucket as usize * num_possible_pairs;
-        (x, y) = loop {
-            let new_x = x.clone().jitter(rng).unwrap();
-            let new_y = x.clone().jitter(rng).unwrap();
-            if new_x != new_y {
-                break (new_x, new_y);
+    assert!(
+        num_buckets.is_power_of_two() && num_buckets > 1,
+        r#""num_buckets" must be a power of two greater than 1"#
+    );
+    let num_output_bits = num_buckets.ilog2() as usize;
+
+    let mut num_flips = vec![0_usize; num_output_bits];
+    let mut num_samples = 0;
+    let params = <K as Generate<R>>::GenerateParams::default();
+
+    for _ in 0..num_keys {
+        let (hash_function, _) = family(rng, num_buckets);
+        let key = K::generate(rng, &params);
+        let hash = hash_function(&key);
+
+        for _ in 0..num_flips_per_key {
+            let altered_key = match key.jitter(rng) {
+                Some(altered_key) if altered_key != key => altered_key,
+                _ => continue,
+            };
+            let diff = hash ^ hash_function(&altered_key);
+            for (bit_idx, count) in num_flips.iter_mut().enumerate() {
+                *count += (diff >> bit_idx) & 1;
             }
-        };
-        let mut hxs = Array1::zeros(num_trials);
-        let mut hys = Array1::zeros(num_trials);
-
-        for i in 0..num_trials {
-            let (hash_function, _) = family(rng, num_buckets);
-            let hx = hash_function(&x);
-            let hy = hash_function(&y);
-            hxs[i] = hx;
-            hys[i] = hy;
+            num_samples += 1;
         }
-        let contingency: Array2<f64> = make_contingency_matrix(&hxs, &hys, num_buckets);
-        let independence_statistic = chi2_independence(&contingency);
-        independence_statistics.push(independence_statistic);
-        let uniformity_statistic = chi2_uniformity(
-            contingency
-                .view()
-                .into_shape_with_order((contingency.len(),))
-                .unwrap(),
-        );
-        uniformity_statistics.push(uniformity_statistic);
-
-        let mi_statistic = mutual_information(&contingency);
-        max_mi = max_mi.max(mi_statistic.bias_corrected_mi);
     }
+    assert!(num_samples > 0, "No keys could have been altered");
 
-    let independence_p_values = Array1::from_shape_vec(
-        independence_statistics.len(),
-        independence_statistics.iter().map(|s| s.p_value).collect(),
-    )
-    .unwrap();
-    let uniformity_p_values = Array1::from_shape_vec(
-        uniformity_statistics.len(),
-        uniformity_statistics.iter().map(|s| s.p_value).collect(),
-    )
-    .unwrap();
-
-    let independence_result = aggregate_p_values(&independence_p_values, alpha);
-    let uniformity_result = aggregate_p_values(&uniformity_p_values, alpha);
+    let flip_probabilities: Vec<f64> = num_flips
+        .iter()
+        .map(|&count| count as f64 / num_samples as f64)
+        .collect();
+    let mean_hamming_distance = flip_probabilities.iter().sum();
+    let statistic = AvalancheStatistic {
+        flip_probabilities,
+        mean_hamming_distance,
+        num_samples,
+    };
 
     assert!(
-        independence_result.outcome,
-        "Pairwise independence test has failed:\n{:?}",
-        independence_result,
+        statistic
+            .flip_probabilities
+            .iter()
+            .all(|p| (p - 0.5).abs() <= tolerance),
+        "Avalanche test has failed:\n{:?}",
+        statistic,
     );
-    assert!(
-        uniformity_result.outcome,
-        "Pairwise uniformity test has failed:\n{:?}",
-        uniformity_result,
-    );
-    // Currently this is more of a sanity check, 0.09 threshold has been chosen based on practice
-    // as a guard against hash functions that have serious flaws.
-    // TODO: Stricter threshold should be applied.
-    assert!(max_mi < 0.09, "Max MI is too high: {}", max_mi);
+
+    statistic
 }